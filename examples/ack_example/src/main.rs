@@ -0,0 +1,109 @@
+use rule_rs::types::{AckHandle, AckOutcome, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn, Level};
+
+/// 模拟消息队列中的一条消息
+#[derive(Debug, Clone)]
+struct Delivery {
+    payload: serde_json::Value,
+    attempt: usize,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 模拟不稳定的下游: 订单 2 第一次写入失败
+    let failures = Arc::new(AtomicUsize::new(0));
+    let counter = failures.clone();
+    engine
+        .register_fn(
+            "fn/store",
+            Arc::new(move |msg: Message| {
+                if msg.data["order"] == 2 && counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Err(RuleError::NodeExecutionError("写入失败".to_string()));
+                }
+                Ok(msg)
+            }),
+        )
+        .await;
+
+    // 起始 -> 写入 -> 日志
+    let mut builder = RuleChainBuilder::new("消息确认示例");
+    let start = builder.add_node("start", json!({}));
+    let store = builder.add_node("fn/store", json!({}));
+    let log = builder.add_node(
+        "log",
+        json!({ "template": "订单 ${msg.data.order} 已写入" }),
+    );
+    builder
+        .connect(start, store, "success")
+        .connect(store, log, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    // 消息源: 规则链处理成功后才确认,失败时重新投递
+    let mut queue: VecDeque<Delivery> = (1..=3)
+        .map(|order| Delivery {
+            payload: json!({ "order": order }),
+            attempt: 1,
+        })
+        .collect();
+    let mut acked = Vec::new();
+    while let Some(delivery) = queue.pop_front() {
+        let (ack, outcome) = AckHandle::channel();
+        let msg = Message::new("order", delivery.payload.clone()).with_ack(ack);
+        // 处理结果同时通过返回值和确认句柄给出,消息源只需要关心确认句柄
+        let _ = engine.process_msg(chain_id, msg).await;
+        // 句柄未确认就被丢弃时按处理失败对待
+        let outcome = outcome
+            .await
+            .unwrap_or_else(|_| AckOutcome::Nack("确认句柄已丢弃".to_string()));
+        match outcome {
+            AckOutcome::Ack => {
+                info!(
+                    "确认消息 {} (第 {} 次投递)",
+                    delivery.payload, delivery.attempt
+                );
+                acked.push((delivery.payload["order"].clone(), delivery.attempt));
+            }
+            AckOutcome::Nack(reason) if delivery.attempt < 3 => {
+                warn!("消息 {} 处理失败, 重新投递: {}", delivery.payload, reason);
+                queue.push_back(Delivery {
+                    attempt: delivery.attempt + 1,
+                    ..delivery
+                });
+            }
+            _ => warn!("消息 {} 多次处理失败, 丢弃", delivery.payload),
+        }
+    }
+    assert_eq!(
+        acked,
+        vec![(json!(1), 1), (json!(3), 1), (json!(2), 2)],
+        "订单 2 重新投递后确认"
+    );
+
+    // 回调形式的确认句柄: 规则链不存在时同样回报失败
+    let outcomes = Arc::new(Mutex::new(Vec::new()));
+    let recorded = outcomes.clone();
+    let ack = AckHandle::new(move |outcome| recorded.lock().unwrap().push(outcome));
+    let missing = engine
+        .process_msg(
+            uuid::Uuid::new_v4(),
+            Message::new("order", json!({})).with_ack(ack.clone()),
+        )
+        .await;
+    assert!(matches!(missing, Err(RuleError::ChainNotFound(_))));
+    assert!(ack.is_resolved());
+    assert!(!ack.ack(), "只有第一次确认生效");
+    assert!(!outcomes.lock().unwrap()[0].is_ack());
+
+    info!("消息确认示例验证通过");
+    Ok(())
+}
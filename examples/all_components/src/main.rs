@@ -98,15 +98,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     "method": "POST",
                     "headers": {
                         "Content-Type": "application/json",
-                        "Authorization": "Bearer ${msg.token}"
+                        "Authorization": "Bearer ${msg.data.token}"
                     },
                     "params": {
                         "id": "${msg.id}",
                         "type": "${msg.type}"
                     },
-                    "body": {
-                        "data": "${msg}",
-                        "timestamp": "${Date.now()}"
+                    "body_template": {
+                        "id": "${msg.id}",
+                        "data": "${msg.data}"
                     },
                     "timeout": 5000,
                     "retry": {
@@ -68,7 +68,7 @@ const RULE_CHAIN: &str = r#"{
             "type_name": "success"
         },
         {
-            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3305",
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3304",
             "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3306",
             "type_name": "success"
         }
@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use rule_rs::engine::rule::DEFAULT_BATCH_CONCURRENCY;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 慢节点,记录同时执行的最大数量,msg.data.fail 为 true 时返回错误
+#[derive(Debug, Default)]
+struct SlowNode {
+    running: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+#[async_trait]
+impl NodeHandler for SlowNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(running, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        self.running.fetch_sub(1, Ordering::SeqCst);
+
+        if msg.data["fail"] == true {
+            return Err(RuleError::NodeExecutionError(format!(
+                "消息 {} 处理失败",
+                msg.data["seq"]
+            )));
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/slow".to_string(),
+            name: "慢节点".to_string(),
+            description: "模拟耗时的处理".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+fn batch(count: usize) -> Vec<Message> {
+    (0..count)
+        .map(|seq| Message::new("order", json!({ "seq": seq, "fail": seq == 3 })))
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    let slow = Arc::new(SlowNode::default());
+    let node = slow.clone();
+    engine
+        .register_node_type(
+            "custom/slow",
+            Arc::new(move |_| Ok(node.clone() as Arc<dyn NodeHandler>)),
+        )
+        .await;
+
+    // 起始 -> 慢节点 -> 转换
+    let mut builder = RuleChainBuilder::new("批量处理示例");
+    let start = builder.add_node("start", json!({}));
+    let node = builder.add_node("custom/slow", json!({}));
+    let transform = builder.add_node(
+        "transform",
+        json!({ "template": { "seq": "${msg.seq}", "done": true } }),
+    );
+    let chain = builder
+        .connect(start, node, "success")
+        .connect(node, transform, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    // 10 条消息最多同时处理 5 条,结果按输入顺序返回,失败的消息不影响其他消息
+    let begin = Instant::now();
+    let results = engine
+        .process_batch_with_concurrency(chain_id, batch(10), 5)
+        .await;
+    let elapsed = begin.elapsed();
+    info!("并发 5 处理 10 条消息耗时 {:?}", elapsed);
+    assert_eq!(results.len(), 10);
+    for (seq, result) in results.iter().enumerate() {
+        match result {
+            Ok(msg) => assert_eq!(msg.data, json!({ "seq": seq.to_string(), "done": true })),
+            Err(e) => {
+                info!("消息 {} 失败: {}", seq, e);
+                assert_eq!(seq, 3);
+            }
+        }
+    }
+    assert_eq!(slow.peak.load(Ordering::SeqCst), 5);
+    assert!(elapsed < Duration::from_millis(500));
+
+    // 默认并发数
+    slow.peak.store(0, Ordering::SeqCst);
+    let results = engine.process_batch(chain_id, batch(40)).await;
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 39);
+    assert_eq!(slow.peak.load(Ordering::SeqCst), DEFAULT_BATCH_CONCURRENCY);
+
+    // 并发数为 0 时逐条处理
+    slow.peak.store(0, Ordering::SeqCst);
+    let results = engine
+        .process_batch_with_concurrency(chain_id, batch(3), 0)
+        .await;
+    assert_eq!(results.len(), 3);
+    assert_eq!(slow.peak.load(Ordering::SeqCst), 1);
+
+    // 规则链不存在时每条消息都返回错误
+    let missing = Uuid::new_v4();
+    let results = engine.process_batch(missing, batch(3)).await;
+    assert_eq!(results.len(), 3);
+    assert!(results
+        .iter()
+        .all(|r| matches!(r, Err(RuleError::ChainNotFound(id)) if *id == missing)));
+
+    info!("批量处理验证通过");
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+
+// 带默认连接的规则链: 路由节点按 msg.data.branch 选择分支
+const CHAIN_WITH_DEFAULT: &str = r#"{
+    "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+    "name": "带默认分支的路由",
+    "root": true,
+    "nodes": [
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3300",
+            "type_name": "custom/router",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "type_name": "custom/record",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": { "name": "high" },
+            "layout": { "x": 200, "y": 50 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3303",
+            "type_name": "custom/record",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": { "name": "default" },
+            "layout": { "x": 200, "y": 150 }
+        }
+    ],
+    "connections": [
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3300",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "type_name": "high"
+        },
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3300",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3303",
+            "type_name": "default"
+        }
+    ],
+    "metadata": {
+        "version": 1,
+        "created_at": 1679800000,
+        "updated_at": 1679800000
+    }
+}"#;
+
+// 没有默认连接的规则链
+const CHAIN_WITHOUT_DEFAULT: &str = r#"{
+    "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3401",
+    "name": "不带默认分支的路由",
+    "root": true,
+    "nodes": [
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3400",
+            "type_name": "custom/router",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3401",
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3402",
+            "type_name": "custom/record",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3401",
+            "config": { "name": "high" },
+            "layout": { "x": 200, "y": 100 }
+        }
+    ],
+    "connections": [
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3400",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3402",
+            "type_name": "high"
+        }
+    ],
+    "metadata": {
+        "version": 1,
+        "created_at": 1679800000,
+        "updated_at": 1679800000
+    }
+}"#;
+
+/// 路由节点,把 msg.data.branch 设置为分支名称
+#[derive(Debug)]
+struct RouterNode;
+
+#[async_trait]
+impl NodeHandler for RouterNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        if let Some(branch) = msg.data["branch"].as_str() {
+            msg.set_meta("branch_name", branch.to_string());
+        }
+        ctx.send_next(msg.clone()).await?;
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/router".to_string(),
+            name: "路由节点".to_string(),
+            description: "按消息数据设置分支".to_string(),
+            node_type: NodeType::Head,
+            config_schema: None,
+        }
+    }
+}
+
+/// 记录各分支收到的消息
+type Records = Arc<Mutex<Vec<String>>>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RecordConfig {
+    name: String,
+}
+
+/// 记录节点,保存收到消息的分支名称
+#[derive(Debug)]
+struct RecordNode {
+    config: RecordConfig,
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        info!("{} 分支收到消息: {}", self.config.name, msg.data);
+        self.records.lock().unwrap().push(self.config.name.clone());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册路由节点和记录节点
+    engine
+        .register_node_type(
+            "custom/router",
+            Arc::new(|_| Ok(Arc::new(RouterNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |config| {
+                let config: RecordConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(RecordNode {
+                    config,
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    let with_default = engine.load_chain(CHAIN_WITH_DEFAULT).await?;
+    let without_default = engine.load_chain(CHAIN_WITHOUT_DEFAULT).await?;
+
+    // 分支匹配: 走同名连接
+    engine
+        .process_msg(
+            with_default,
+            Message::new("test", json!({ "branch": "high" })),
+        )
+        .await?;
+    assert_eq!(*records.lock().unwrap(), vec!["high"]);
+
+    // 分支不匹配但有默认连接: 走 default 连接
+    engine
+        .process_msg(
+            with_default,
+            Message::new("test", json!({ "branch": "low" })),
+        )
+        .await?;
+    assert_eq!(*records.lock().unwrap(), vec!["high", "default"]);
+
+    // 分支不匹配且没有默认连接: 返回配置错误,不会落到第一个连接
+    let result = engine
+        .process_msg(
+            without_default,
+            Message::new("test", json!({ "branch": "low" })),
+        )
+        .await;
+    match result {
+        Err(RuleError::ConfigError(e)) if e.contains("low") => info!("未匹配的分支: {}", e),
+        other => panic!("期望配置错误, 实际: {:?}", other),
+    }
+    assert_eq!(*records.lock().unwrap(), vec!["high", "default"]);
+
+    info!("分支路由验证通过");
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+/// 创建 起始 -> 算术表达式 的规则链,处理 `data` 并返回计算后的 msg.data
+async fn calc(engine: &RuleEngine, expressions: Value, data: Value) -> Result<Value, RuleError> {
+    let mut builder = RuleChainBuilder::new("算术表达式示例");
+    let start = builder.add_node("start", json!({}));
+    let calc = builder.add_node("calc", json!({ "expressions": expressions }));
+    builder.connect(start, calc, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("metric", data))
+        .await;
+    engine.remove_chain(chain_id).await?;
+    Ok(result?.data)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 四则运算,嵌套字段以点分隔,所有表达式都基于输入数据计算
+    let data = calc(
+        &engine,
+        json!({
+            "value": "value * 2 + offset",
+            "order.total": "order.price * order.count",
+            "ratio": "value / 4.0",
+            "alarm": "value > 20 && order.count >= 3",
+            "root": "math::sqrt(order.price)",
+        }),
+        json!({ "value": 21, "offset": 1, "order": { "price": 2.25, "count": 4 } }),
+    )
+    .await?;
+    info!("计算结果: {}", data);
+    assert_eq!(data["value"], 43);
+    assert_eq!(data["order"]["total"], 9.0);
+    assert_eq!(data["ratio"], 5.25);
+    assert_eq!(data["alarm"], true);
+    assert_eq!(data["root"], 1.5);
+
+    // 整数之间的运算结果仍为整数
+    let data = calc(
+        &engine,
+        json!({ "half": "value / 2" }),
+        json!({ "value": 7 }),
+    )
+    .await?;
+    assert_eq!(data["half"], 3);
+
+    // 除零以及不存在的变量返回组件错误
+    for (expr, data) in [
+        ("value / 0", json!({ "value": 7 })),
+        ("value / 0.0", json!({ "value": 7 })),
+        ("missing * 2", json!({ "value": 7 })),
+    ] {
+        let result = calc(&engine, json!({ "result": expr }), data).await;
+        info!("{} -> {:?}", expr, result);
+        assert!(matches!(result, Err(RuleError::ComponentError(_))));
+    }
+
+    // 表达式语法错误在加载时返回配置错误
+    let result = calc(&engine, json!({ "result": "value * (2" }), json!({})).await;
+    assert!(matches!(result, Err(RuleError::ConfigError(_))));
+
+    info!("算术表达式节点示例验证通过");
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{CancellationToken, NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, Level};
+
+/// 慢节点,按 msg.data.sleep_ms 模拟长时间 IO,并在后台监听取消令牌
+#[derive(Debug)]
+struct SlowNode {
+    /// 开始执行的次数
+    started: Arc<AtomicUsize>,
+    /// 后台任务观察到取消的次数
+    cancelled: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl NodeHandler for SlowNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        self.started.fetch_add(1, Ordering::SeqCst);
+
+        // 节点启动的后台任务不会随节点中止,需要自行监听令牌
+        let token = ctx.cancel_token().clone();
+        let cancelled = self.cancelled.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = token.cancelled() => {
+                    cancelled.fetch_add(1, Ordering::SeqCst);
+                }
+                _ = tokio::time::sleep(Duration::from_secs(5)) => {}
+            }
+        });
+
+        let sleep_ms = msg.data["sleep_ms"].as_u64().unwrap_or_default();
+        tokio::time::sleep(Duration::from_millis(sleep_ms)).await;
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/slow".to_string(),
+            name: "慢节点".to_string(),
+            description: "模拟长时间执行的节点".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+fn slow_msg(sleep_ms: u64) -> Message {
+    Message::new("test", json!({ "sleep_ms": sleep_ms }))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = Arc::new(RuleEngine::new().await);
+    let started = Arc::new(AtomicUsize::new(0));
+    let cancelled = Arc::new(AtomicUsize::new(0));
+    let (node_started, node_cancelled) = (started.clone(), cancelled.clone());
+    engine
+        .register_node_type(
+            "custom/slow",
+            Arc::new(move |_| {
+                Ok(Arc::new(SlowNode {
+                    started: node_started.clone(),
+                    cancelled: node_cancelled.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 起始 -> 慢节点 -> 转换
+    let mut builder = RuleChainBuilder::new("取消示例");
+    let start = builder.add_node("start", json!({}));
+    let slow = builder.add_node("custom/slow", json!({}));
+    let transform = builder.add_node("transform", json!({ "template": { "done": true } }));
+    let chain = builder
+        .connect(start, slow, "success")
+        .connect(slow, transform, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    // 未取消时正常完成
+    let result = engine.process_msg(chain_id, slow_msg(50)).await?;
+    assert_eq!(result.data, json!({ "done": true }));
+
+    // 同一规则链的两条消息并发执行,只取消其中一条
+    let token = CancellationToken::new();
+    let first = tokio::spawn({
+        let engine = engine.clone();
+        let token = token.clone();
+        async move {
+            let begin = Instant::now();
+            let result = engine
+                .process_msg_with_token(chain_id, slow_msg(2000), token)
+                .await;
+            (result, begin.elapsed())
+        }
+    });
+    let second = tokio::spawn({
+        let engine = engine.clone();
+        async move {
+            engine
+                .process_msg_with_token(chain_id, slow_msg(300), CancellationToken::new())
+                .await
+        }
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    token.cancel();
+
+    // 正在执行的慢节点被中止,不必等待其完成
+    let (result, elapsed) = first.await?;
+    info!("被取消的执行: {:?}, 耗时 {:?}", result, elapsed);
+    assert!(matches!(result, Err(RuleError::Cancelled)));
+    assert!(elapsed < Duration::from_millis(1000));
+
+    let result = second.await??;
+    assert_eq!(result.data, json!({ "done": true }));
+    info!("另一条消息正常完成");
+
+    // 节点的后台任务通过令牌感知取消
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(cancelled.load(Ordering::SeqCst), 1);
+
+    // 已取消的令牌不会执行任何节点
+    let before = started.load(Ordering::SeqCst);
+    let result = engine
+        .process_msg_with_token(chain_id, slow_msg(0), token)
+        .await;
+    assert!(matches!(result, Err(RuleError::Cancelled)));
+    assert_eq!(started.load(Ordering::SeqCst), before);
+
+    info!("消息取消验证通过");
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use rule_rs::types::{RuleChainBuilder, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::json;
+use tracing::{info, Level};
+use uuid::Uuid;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 使用构建器构造规则链: 起始 -> 转换 -> 日志
+    let mut builder = RuleChainBuilder::new("构建器示例");
+    let start = builder.add_node("start", json!({}));
+    let transform = builder.add_node(
+        "transform",
+        json!({ "template": { "greeting": "hello ${msg.name}" } }),
+    );
+    let log = builder.add_node("log", json!({ "template": "${msg.data.greeting}" }));
+    let chain = builder
+        .connect(start, transform, "success")
+        .connect(transform, log, "success")
+        .root(true)
+        .build();
+
+    // 节点ID自动生成,节点所属的规则链ID自动设置
+    assert_eq!(chain.nodes[0].id, start);
+    assert!(chain.nodes.iter().all(|node| node.chain_id == chain.id));
+
+    // 直接加载规则链,无需序列化为字符串
+    let chain_id = engine.load_rule_chain(chain).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("greet", json!({ "name": "rule" })))
+        .await?;
+    info!("处理结果: {}", result.data);
+    assert_eq!(result.data, json!({ "greeting": "hello rule" }));
+
+    // 已持有JSON值时无需转换为字符串
+    let mut value = serde_json::to_value(&*engine.get_chain(chain_id).await.unwrap())?;
+    value["name"] = json!("构建器示例(已更新)");
+    let version = engine.update_chain_value(chain_id, value).await?;
+    let updated = engine.get_chain(chain_id).await.unwrap();
+    assert_eq!(updated.metadata.version, version);
+    assert_eq!(updated.name, "构建器示例(已更新)");
+
+    let mut builder = RuleChainBuilder::new("JSON值");
+    let start = builder.add_node("start", json!({}));
+    let log = builder.add_node("log", json!({ "template": "${msg.data}" }));
+    let value = serde_json::to_value(builder.connect(start, log, "success").build())?;
+    engine.load_chain_value(value).await?;
+
+    // 指定规则链ID,已添加的节点同步更新
+    let fixed_id = Uuid::parse_str("3f2504e0-4f89-11d3-9a0c-0305e82c6001")?;
+    let mut builder = RuleChainBuilder::new("指定ID");
+    let start = builder.add_node("start", json!({}));
+    let chain = builder.id(fixed_id).build();
+    assert_eq!(chain.id, fixed_id);
+    assert_eq!(chain.nodes[0].chain_id, fixed_id);
+    assert!(!chain.root);
+
+    // 构建的规则链同样经过加载校验
+    let chain = builder.connect(start, Uuid::new_v4(), "success").build();
+    match engine.load_rule_chain(chain).await {
+        Err(RuleError::ConfigError(e)) => {
+            info!("加载失败: {}", e);
+            assert!(e.contains("不存在的节点"));
+        }
+        other => panic!("期望配置错误, 实际: {:?}", other),
+    }
+
+    info!("规则链构建器验证通过");
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleChainBuilder, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{info, Level};
+
+/// 把订单消息转换为账单消息
+#[derive(Debug)]
+struct InvoiceNode;
+
+#[async_trait]
+impl NodeHandler for InvoiceNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.msg_type = "invoice".to_string();
+        msg.data = json!({ "order": msg.data["order"], "amount": msg.data["amount"] });
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/invoice".to_string(),
+            name: "账单节点".to_string(),
+            description: "把订单消息转换为账单消息".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    engine
+        .register_node_type(
+            "custom/invoice",
+            Arc::new(|_| Ok(Arc::new(InvoiceNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+
+    // 接收 order,输出 invoice
+    let mut builder = RuleChainBuilder::new("开票");
+    builder.input_type("order").output_type("invoice");
+    let start = builder.add_node("start", json!({}));
+    let invoice = builder.add_node("custom/invoice", json!({}));
+    builder.connect(start, invoice, "success");
+    let chain = builder.root(true).build();
+
+    // 声明随规则链 JSON 一起保存
+    let definition = serde_json::to_value(&chain)?;
+    info!("规则链元数据: {}", definition["metadata"]);
+    assert_eq!(definition["metadata"]["input_type"], "order");
+    assert_eq!(definition["metadata"]["output_type"], "invoice");
+    let chain_id = engine.load_chain_value(definition).await?;
+
+    let result = engine
+        .process_msg(
+            chain_id,
+            Message::new("order", json!({ "order": "A-1", "amount": 99 })),
+        )
+        .await?;
+    info!("账单: {}", result.data);
+    assert_eq!(result.msg_type, "invoice");
+
+    // 其它类型的消息在执行前被拒绝
+    let result = engine
+        .process_msg(chain_id, Message::new("refund", json!({ "order": "A-1" })))
+        .await;
+    info!("退款消息: {:?}", result);
+    assert!(matches!(result, Err(RuleError::ConfigError(_))));
+
+    // 输出类型不一致时只记录警告,结果照常返回
+    let mut builder = RuleChainBuilder::new("错误的接线");
+    builder.output_type("invoice");
+    builder.add_node("start", json!({}));
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({ "order": "A-2" })))
+        .await?;
+    assert_eq!(result.msg_type, "order");
+
+    // 未声明时不检查
+    let mut builder = RuleChainBuilder::new("无声明");
+    builder.add_node("start", json!({}));
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+    engine
+        .process_msg(chain_id, Message::new("anything", json!({})))
+        .await?;
+
+    info!("规则链输入输出类型验证通过");
+    Ok(())
+}
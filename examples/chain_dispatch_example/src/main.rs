@@ -0,0 +1,278 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+use uuid::Uuid;
+
+const ROUTER_CHAIN_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c8001";
+const UPPER_CHAIN_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c8101";
+const LOWER_CHAIN_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c8201";
+const LOOP_CHAIN_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c8301";
+
+/// 生成规则链,节点按顺序依次连接
+fn chain(chain_id: &str, root: bool, nodes: Vec<(&str, Value)>) -> String {
+    let node_id = |i: usize| format!("{}{:02}", &chain_id[..chain_id.len() - 2], 10 + i);
+
+    let nodes: Vec<Value> = nodes
+        .into_iter()
+        .enumerate()
+        .map(|(i, (type_name, config))| {
+            json!({
+                "id": node_id(i),
+                "type_name": type_name,
+                "chain_id": chain_id,
+                "config": config,
+                "layout": { "x": 50 + 150 * i, "y": 100 }
+            })
+        })
+        .collect();
+    let connections: Vec<Value> = (1..nodes.len())
+        .map(|i| {
+            json!({
+                "from_id": node_id(i - 1),
+                "to_id": node_id(i),
+                "type_name": "success"
+            })
+        })
+        .collect();
+
+    json!({
+        "id": chain_id,
+        "name": format!("规则链 {}", chain_id),
+        "root": root,
+        "nodes": nodes,
+        "connections": connections,
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+    .to_string()
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DispatchConfig {
+    /// 固定的目标规则链,未配置时使用 msg.data.target
+    target: Option<Uuid>,
+}
+
+/// 分发节点,运行时选择目标规则链执行,并把其输出发送到下一个节点
+#[derive(Debug)]
+struct DispatchNode {
+    config: DispatchConfig,
+}
+
+#[async_trait]
+impl NodeHandler for DispatchNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let target = match self.config.target {
+            Some(target) => target,
+            None => msg.data["target"]
+                .as_str()
+                .and_then(|s| Uuid::parse_str(s).ok())
+                .ok_or_else(|| RuleError::ConfigError("缺少目标规则链".to_string()))?,
+        };
+
+        let result = ctx.dispatch_to_chain(target, msg).await?;
+        ctx.send_next(result.clone()).await?;
+        Ok(result)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/dispatch".to_string(),
+            name: "分发节点".to_string(),
+            description: "按消息内容执行其他规则链".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TagConfig {
+    name: String,
+}
+
+/// 标记节点,在消息数据中写入处理者名称
+#[derive(Debug)]
+struct TagNode {
+    config: TagConfig,
+}
+
+#[async_trait]
+impl NodeHandler for TagNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.data["handled_by"] = json!(self.config.name);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/tag".to_string(),
+            name: "标记节点".to_string(),
+            description: "标记消息的处理者".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+/// 记录路由规则链收到的最终消息
+type Records = Arc<Mutex<Vec<Value>>>;
+
+#[derive(Debug)]
+struct RecordNode {
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        info!("路由结果: {}", msg.data);
+        self.records.lock().unwrap().push(msg.data.clone());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册自定义节点
+    engine
+        .register_node_type(
+            "custom/dispatch",
+            Arc::new(|config| {
+                let config: DispatchConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(DispatchNode { config }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+    engine
+        .register_node_type(
+            "custom/tag",
+            Arc::new(|config| {
+                let config: TagConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(TagNode { config }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |_| {
+                Ok(Arc::new(RecordNode {
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 路由规则链: 起始 -> 分发 -> 记录
+    let router = engine
+        .load_chain(&chain(
+            ROUTER_CHAIN_ID,
+            true,
+            vec![
+                ("start", json!({})),
+                ("custom/dispatch", json!({})),
+                ("custom/record", json!({})),
+            ],
+        ))
+        .await?;
+    // 可复用的根规则链
+    engine
+        .load_chain(&chain(
+            UPPER_CHAIN_ID,
+            true,
+            vec![
+                ("start", json!({})),
+                ("custom/tag", json!({ "name": "upper" })),
+            ],
+        ))
+        .await?;
+    // 非根规则链不能被分发执行
+    engine
+        .load_chain(&chain(
+            LOWER_CHAIN_ID,
+            false,
+            vec![
+                ("start", json!({})),
+                ("custom/tag", json!({ "name": "lower" })),
+            ],
+        ))
+        .await?;
+    // 分发回路由规则链,形成运行时循环调用
+    engine
+        .load_chain(&chain(
+            LOOP_CHAIN_ID,
+            true,
+            vec![
+                ("start", json!({})),
+                ("custom/dispatch", json!({ "target": ROUTER_CHAIN_ID })),
+            ],
+        ))
+        .await?;
+
+    let request = |target: &str| Message::new("request", json!({ "target": target }));
+
+    // 分发到根规则链,其输出继续发送到路由规则链的下一个节点
+    let result = engine.process_msg(router, request(UPPER_CHAIN_ID)).await?;
+    assert_eq!(result.data["handled_by"], "upper");
+    assert_eq!(records.lock().unwrap().len(), 1);
+
+    // 非根规则链与 process_msg 一致返回配置错误
+    match engine.process_msg(router, request(LOWER_CHAIN_ID)).await {
+        Err(RuleError::ConfigError(e)) if e.contains("not a root chain") => {
+            info!("非根规则链: {}", e)
+        }
+        other => panic!("期望配置错误, 实际: {:?}", other),
+    }
+
+    // 路由 -> 循环 -> 路由: 返回循环依赖错误
+    match engine.process_msg(router, request(LOOP_CHAIN_ID)).await {
+        Err(RuleError::CircularDependency(e)) => info!("循环调用: {}", e),
+        other => panic!("期望循环依赖错误, 实际: {:?}", other),
+    }
+    assert_eq!(records.lock().unwrap().len(), 1);
+
+    info!("规则链分发验证通过");
+    Ok(())
+}
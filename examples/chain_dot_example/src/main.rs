@@ -0,0 +1,87 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde_json::json;
+use tracing::{info, Level};
+use uuid::Uuid;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 起始 -> 条件分支 -> 高温告警/转换 -> 日志
+    let mut builder = RuleChainBuilder::new("温度\"告警\"");
+    let start = builder.add_node("start", json!({}));
+    let switch = builder.add_node(
+        "switch",
+        json!({
+            "cases": [{
+                "name": "high_temp",
+                "condition": "msg.data.temperature > 30",
+                "description": "高温"
+            }],
+            "default_next": "normal"
+        }),
+    );
+    let alert = builder.add_node(
+        "log",
+        json!({ "template": "高温: ${msg.data.temperature}" }),
+    );
+    let transform = builder.add_node("transform", json!({ "template": { "status": "ok" } }));
+    let log = builder.add_node("log", json!({ "template": "正常: ${msg.data.status}" }));
+    let chain = builder
+        .connect(start, switch, "success")
+        .connect(switch, alert, "high_temp")
+        .connect(switch, transform, "normal")
+        .connect(transform, log, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    // Graphviz DOT
+    let dot = engine.export_chain_dot(chain_id).await.unwrap();
+    info!("规则链 DOT:\n{}", dot);
+
+    // 图名为规则链ID,名称中的引号被转义
+    assert!(dot.starts_with(&format!("digraph \"{}\" {{", chain_id)));
+    assert!(dot.contains(r#"label="温度\"告警\"";"#));
+
+    // 节点以类型名称标注,按节点类型使用不同形状
+    assert!(dot.contains(&format!("\"{}\" [label=\"start\", shape=ellipse];", start)));
+    assert!(dot.contains(&format!("\"{}\" [label=\"switch\", shape=box];", switch)));
+    assert!(dot.contains(&format!(
+        "\"{}\" [label=\"log\", shape=doubleoctagon];",
+        alert
+    )));
+
+    // 连接以分支名称标注
+    assert!(dot.contains(&format!(
+        "\"{}\" -> \"{}\" [label=\"high_temp\"];",
+        switch, alert
+    )));
+    assert!(dot.contains(&format!(
+        "\"{}\" -> \"{}\" [label=\"normal\"];",
+        switch, transform
+    )));
+    assert_eq!(dot.matches(" -> ").count(), 4);
+
+    // Mermaid 流程图: 节点按顺序编号,标题保留规则链名称
+    let mermaid = engine.export_chain_mermaid(chain_id).await.unwrap();
+    info!("规则链 Mermaid:\n{}", mermaid);
+    assert!(mermaid.starts_with("---\ntitle: \"温度\\\"告警\\\"\"\n---\nflowchart LR\n"));
+    assert!(mermaid.contains("    n0([\"start\"])\n"));
+    assert!(mermaid.contains("    n1[\"switch\"]\n"));
+    assert!(mermaid.contains("    n2(((\"log\")))\n"));
+    assert!(mermaid.contains("    n1 -->|\"high_temp\"| n2\n"));
+    assert!(mermaid.contains("    n1 -->|\"normal\"| n3\n"));
+    assert_eq!(mermaid.matches(" -->").count(), 4);
+
+    // 未加载的规则链返回 None
+    assert!(engine.export_chain_dot(Uuid::new_v4()).await.is_none());
+    assert!(engine.export_chain_mermaid(Uuid::new_v4()).await.is_none());
+
+    info!("DOT 和 Mermaid 导出验证通过");
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use rule_rs::engine::ChainGuardPolicy;
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, Level};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 模拟下游接口: fail 为 true 的消息调用失败,记录实际调用次数
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    engine
+        .register_fn(
+            "fn/notify",
+            Arc::new(move |msg: Message| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                if msg.data["fail"] == true {
+                    return Err(RuleError::NodeExecutionError("通知接口不可用".to_string()));
+                }
+                Ok(msg)
+            }),
+        )
+        .await;
+
+    // 1 秒内至少执行 4 次且错误率超过 50% 时禁用规则链
+    let mut builder = RuleChainBuilder::new("自动禁用示例");
+    let start = builder.add_node("start", json!({}));
+    let notify = builder.add_node("fn/notify", json!({}));
+    builder.connect(start, notify, "success");
+    builder.error_guard(ChainGuardPolicy::new(0.5, Duration::from_secs(1)).with_min_executions(4));
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    // 2 次成功、2 次失败: 错误率 50% 未超过上限
+    for fail in [false, false, true, true] {
+        let _ = engine
+            .process_msg(chain_id, Message::new("alert", json!({ "fail": fail })))
+            .await;
+    }
+    assert!(!engine.is_chain_disabled(chain_id).await);
+
+    // 第 3 次失败后错误率 60%, 规则链被禁用
+    let result = engine
+        .process_msg(chain_id, Message::new("alert", json!({ "fail": true })))
+        .await;
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(_))));
+    assert!(engine.is_chain_disabled(chain_id).await);
+
+    // 禁用后消息不再到达下游
+    let result = engine
+        .process_msg(chain_id, Message::new("alert", json!({ "fail": false })))
+        .await;
+    assert!(matches!(result, Err(RuleError::ChainDisabled(id)) if id == chain_id));
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+    info!(
+        "规则链已禁用, 下游共调用 {} 次",
+        calls.load(Ordering::SeqCst)
+    );
+
+    // 修复下游后重新启用
+    engine.enable_chain(chain_id).await?;
+    engine
+        .process_msg(chain_id, Message::new("alert", json!({ "fail": false })))
+        .await?;
+    assert_eq!(calls.load(Ordering::SeqCst), 6);
+    info!("规则链已重新启用");
+
+    // 通过 set_chain_guard 收紧策略: 只要出现失败就禁用
+    engine
+        .set_chain_guard(
+            chain_id,
+            Some(ChainGuardPolicy::new(0.0, Duration::from_secs(1)).with_min_executions(1)),
+        )
+        .await?;
+    let _ = engine
+        .process_msg(chain_id, Message::new("alert", json!({ "fail": true })))
+        .await;
+    assert!(engine.is_chain_disabled(chain_id).await);
+
+    // 无效的策略被拒绝
+    let invalid = engine
+        .set_chain_guard(
+            chain_id,
+            Some(ChainGuardPolicy::new(1.5, Duration::from_secs(1))),
+        )
+        .await;
+    assert!(matches!(invalid, Err(RuleError::ConfigError(_))));
+
+    // 规则链元数据中的策略在加载时校验
+    let mut chain = builder.build();
+    chain.metadata.max_error_rate = Some(2.0);
+    let invalid = engine.load_rule_chain(chain).await;
+    assert!(matches!(invalid, Err(RuleError::ConfigError(e)) if e.contains("max_error_rate")));
+
+    info!("自动禁用示例验证通过");
+    Ok(())
+}
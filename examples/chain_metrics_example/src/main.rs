@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, Level};
+
+/// 耗时 10ms 的节点,msg.data.fail 为 true 时执行失败
+#[derive(Debug)]
+struct WorkNode;
+
+#[async_trait]
+impl NodeHandler for WorkNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        if msg.data["fail"].as_bool().unwrap_or_default() {
+            return Err(RuleError::NodeExecutionError("处理失败".to_string()));
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/work".to_string(),
+            name: "耗时节点".to_string(),
+            description: "模拟耗时处理".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    engine
+        .register_node_type(
+            "custom/work",
+            Arc::new(|_| Ok(Arc::new(WorkNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+
+    // 子规则链: 起始 -> 耗时节点
+    let mut builder = RuleChainBuilder::new("统计子规则链");
+    let start = builder.add_node("start", json!({}));
+    let work = builder.add_node("custom/work", json!({}));
+    let subchain = builder.connect(start, work, "success").build();
+    let subchain_id = engine.load_rule_chain(subchain).await?;
+
+    // 根规则链: 起始 -> 子规则链
+    let mut builder = RuleChainBuilder::new("统计示例");
+    let start = builder.add_node("start", json!({}));
+    let call = builder.add_node("subchain", json!({ "chain_id": subchain_id }));
+    let chain = builder.connect(start, call, "success").root(true).build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    // 加载后尚未执行
+    let metrics = engine.chain_metrics(chain_id).await.unwrap();
+    assert_eq!(metrics.executions, 0);
+    assert_eq!(metrics.avg_latency_ms, 0.0);
+
+    for fail in [false, false, false, true] {
+        let _ = engine
+            .process_msg(chain_id, Message::new("test", json!({ "fail": fail })))
+            .await;
+    }
+
+    let metrics = engine.chain_metrics(chain_id).await.unwrap();
+    info!("根规则链统计: {:?}", metrics);
+    assert_eq!(metrics.executions, 4);
+    assert_eq!(metrics.successes, 3);
+    assert_eq!(metrics.failures, 1);
+    assert!(metrics.avg_latency_ms >= 10.0);
+
+    // 作为子规则链的执行同样计入统计
+    let metrics = engine.chain_metrics(subchain_id).await.unwrap();
+    info!("子规则链统计: {:?}", metrics);
+    assert_eq!((metrics.executions, metrics.failures), (4, 1));
+
+    // 删除规则链后统计一并清除
+    engine.remove_chain(chain_id).await?;
+    assert!(engine.chain_metrics(chain_id).await.is_none());
+
+    info!("规则链执行统计验证通过");
+    Ok(())
+}
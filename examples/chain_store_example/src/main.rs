@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use rule_rs::engine::{ChainStore, FileChainStore};
+use rule_rs::types::{RuleChain, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 只读存储,写入时总是失败
+#[derive(Debug)]
+struct ReadOnlyStore;
+
+#[async_trait]
+impl ChainStore for ReadOnlyStore {
+    async fn save(&self, chain: &RuleChain) -> Result<(), RuleError> {
+        Err(RuleError::ConfigError(format!(
+            "存储只读, 无法保存规则链 {}",
+            chain.id
+        )))
+    }
+
+    async fn load_all(&self) -> Result<Vec<RuleChain>, RuleError> {
+        Ok(Vec::new())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), RuleError> {
+        Err(RuleError::ConfigError(format!(
+            "存储只读, 无法删除规则链 {}",
+            id
+        )))
+    }
+}
+
+/// 起始 -> 转换 规则链,转换结果为给定的数据
+fn chain(id: Uuid, output: Value) -> RuleChain {
+    let mut builder = RuleChainBuilder::new("持久化规则链");
+    builder.id(id);
+    let start = builder.add_node("start", json!({}));
+    let transform = builder.add_node("transform", json!({ "template": output }));
+    builder
+        .connect(start, transform, "success")
+        .root(true)
+        .build()
+}
+
+async fn output(engine: &RuleEngine, id: Uuid) -> Value {
+    engine
+        .process_msg(id, Message::new("test", json!({})))
+        .await
+        .unwrap()
+        .data
+}
+
+fn stored(dir: &Path, id: Uuid) -> Option<String> {
+    std::fs::read_to_string(dir.join(format!("{}.json", id))).ok()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    std::env::set_var("STORE_EXAMPLE_TOKEN", "secret-token");
+    let dir = std::env::temp_dir().join(format!("rule_rs_store_{}", Uuid::new_v4()));
+    let store = Arc::new(FileChainStore::new(&dir)?);
+
+    let engine = RuleEngine::new().await.with_store(store.clone()).await?;
+    assert!(engine.get_loaded_chains().await.is_empty());
+
+    // 加载时写入存储,保存的是占位符替换前的定义
+    let first = Uuid::new_v4();
+    engine
+        .load_rule_chain(chain(
+            first,
+            json!({ "v": 1, "token": "${env:STORE_EXAMPLE_TOKEN}" }),
+        ))
+        .await?;
+    assert_eq!(output(&engine, first).await["token"], "secret-token");
+    let content = stored(&dir, first).unwrap();
+    assert!(content.contains("${env:STORE_EXAMPLE_TOKEN}"));
+    assert!(!content.contains("secret-token"));
+
+    // 更新和回滚同步写入存储
+    engine
+        .update_chain_value(
+            first,
+            serde_json::to_value(chain(first, json!({ "v": 2 })))?,
+        )
+        .await?;
+    assert!(stored(&dir, first).unwrap().contains("\"v\": 2"));
+    let (version, _) = engine.get_chain_versions(first).await[0];
+    engine.rollback_chain(first, version).await?;
+    let content = stored(&dir, first).unwrap();
+    assert!(content.contains("\"v\": 1"));
+    info!("回滚后存储的规则链:\n{}", content);
+
+    // 删除时从存储中删除
+    let second = Uuid::new_v4();
+    engine
+        .load_rule_chain(chain(second, json!({ "v": 1 })))
+        .await?;
+    assert!(stored(&dir, second).is_some());
+    engine.remove_chain(second).await?;
+    assert!(stored(&dir, second).is_none());
+
+    // 新的引擎实例从存储恢复规则链,占位符重新解析
+    let restarted = RuleEngine::new().await.with_store(store).await?;
+    assert_eq!(restarted.get_loaded_chains().await.len(), 1);
+    assert_eq!(
+        output(&restarted, first).await,
+        json!({ "v": 1, "token": "secret-token" })
+    );
+    info!("重启后恢复规则链 {}", first);
+
+    // 写入存储失败时操作返回错误,规则链不会被加载
+    let readonly = RuleEngine::new()
+        .await
+        .with_store(Arc::new(ReadOnlyStore))
+        .await?;
+    let result = readonly
+        .load_rule_chain(chain(Uuid::new_v4(), json!({ "v": 1 })))
+        .await;
+    info!("只读存储加载结果: {:?}", result);
+    assert!(matches!(result, Err(RuleError::ConfigError(_))));
+    assert!(readonly.get_loaded_chains().await.is_empty());
+
+    // 未启用存储时不写入任何文件
+    let memory = RuleEngine::new().await;
+    let third = Uuid::new_v4();
+    memory
+        .load_rule_chain(chain(third, json!({ "v": 1 })))
+        .await?;
+    assert!(stored(&dir, third).is_none());
+
+    std::fs::remove_dir_all(&dir)?;
+    info!("规则链持久化验证通过");
+    Ok(())
+}
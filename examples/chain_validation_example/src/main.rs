@@ -0,0 +1,303 @@
+use rule_rs::types::{RuleChain, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+const CHAIN_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c9701";
+const START_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c9700";
+const TRANSFORM_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c9702";
+const LOG_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c9703";
+
+fn node(id: &str, type_name: &str, config: Value) -> Value {
+    json!({
+        "id": id,
+        "type_name": type_name,
+        "chain_id": CHAIN_ID,
+        "config": config,
+        "layout": { "x": 100, "y": 100 }
+    })
+}
+
+fn connection(from_id: &str, to_id: &str) -> Value {
+    json!({
+        "from_id": from_id,
+        "to_id": to_id,
+        "type_name": "success"
+    })
+}
+
+fn chain(nodes: Vec<Value>, connections: Vec<Value>) -> String {
+    json!({
+        "id": CHAIN_ID,
+        "name": "结构校验示例",
+        "root": true,
+        "nodes": nodes,
+        "connections": connections,
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+    .to_string()
+}
+
+/// 校验并加载规则链,期望两者都返回包含所有指定内容的配置错误
+async fn expect_config_error(engine: &RuleEngine, content: &str, expected: &[&str]) {
+    let validated = engine.validate_chain(content).await;
+    match engine.load_chain(content).await {
+        Err(RuleError::ConfigError(e)) => {
+            info!("加载失败: {}", e);
+            for s in expected {
+                assert!(e.contains(s), "错误信息 {} 缺少 {}", e, s);
+            }
+            // 只校验时得到与加载相同的错误
+            assert_eq!(
+                validated.unwrap_err().to_string(),
+                format!("配置错误: {}", e)
+            );
+        }
+        other => panic!("期望配置错误, 实际: {:?}", other),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    let start = || node(START_ID, "start", json!({}));
+    let transform = || node(TRANSFORM_ID, "transform", json!({ "template": {} }));
+    let log = || node(LOG_ID, "log", json!({ "template": "${msg.data}" }));
+
+    let missing_a = "3f2504e0-4f89-11d3-9a0c-0305e82c97aa";
+    let missing_b = "3f2504e0-4f89-11d3-9a0c-0305e82c97bb";
+
+    // 重复的节点ID
+    expect_config_error(
+        &engine,
+        &chain(
+            vec![start(), transform(), node(TRANSFORM_ID, "log", json!({}))],
+            vec![connection(START_ID, TRANSFORM_ID)],
+        ),
+        &["重复的节点ID", TRANSFORM_ID],
+    )
+    .await;
+
+    // 连接引用了不存在的节点,列出全部悬空的ID
+    expect_config_error(
+        &engine,
+        &chain(
+            vec![start(), transform(), log()],
+            vec![
+                connection(START_ID, missing_a),
+                connection(missing_b, LOG_ID),
+            ],
+        ),
+        &["不存在的节点", missing_a, missing_b],
+    )
+    .await;
+
+    // 重复ID和悬空连接同时存在时一起报告
+    expect_config_error(
+        &engine,
+        &chain(
+            vec![start(), start(), log()],
+            vec![connection(START_ID, missing_a)],
+        ),
+        &[START_ID, missing_a],
+    )
+    .await;
+
+    // 多个头节点各自作为入口,汇入同一处理流程
+    let second_start = "3f2504e0-4f89-11d3-9a0c-0305e82c9704";
+    let multi_head = chain(
+        vec![start(), log(), node(second_start, "start", json!({}))],
+        vec![
+            connection(START_ID, LOG_ID),
+            connection(second_start, LOG_ID),
+        ],
+    );
+    let analysis = engine.analyze_chain(&serde_json::from_str::<RuleChain>(&multi_head)?);
+    assert!(analysis.unreachable_nodes.is_empty());
+    engine.validate_chain(&multi_head).await?;
+
+    // 节点配置无法构造处理器时返回配置错误,而不是使进程崩溃
+    for (type_name, config, expected) in [
+        (
+            "schedule",
+            json!({ "cron": "every minute" }),
+            "无效的Cron表达式",
+        ),
+        (
+            "rest_client",
+            json!({ "url": "http://localhost", "method": "GE T" }),
+            "无效的HTTP方法",
+        ),
+    ] {
+        expect_config_error(
+            &engine,
+            &chain(
+                vec![start(), node(TRANSFORM_ID, type_name, config)],
+                vec![connection(START_ID, TRANSFORM_ID)],
+            ),
+            &[expected, TRANSFORM_ID],
+        )
+        .await;
+    }
+
+    // 定义无法解析
+    expect_config_error(&engine, "{\"id\": 1}", &["invalid type"]).await;
+
+    // 未注册的节点类型
+    expect_config_error(
+        &engine,
+        &chain(
+            vec![start(), node(TRANSFORM_ID, "custom/unknown", json!({}))],
+            vec![connection(START_ID, TRANSFORM_ID)],
+        ),
+        &["未找到节点类型", "custom/unknown"],
+    )
+    .await;
+
+    // 头节点被其他节点指向
+    expect_config_error(
+        &engine,
+        &chain(
+            vec![start(), transform()],
+            vec![
+                connection(START_ID, TRANSFORM_ID),
+                connection(TRANSFORM_ID, START_ID),
+            ],
+        ),
+        &["不能被其他节点指向"],
+    )
+    .await;
+
+    // 尾节点指向其他节点
+    expect_config_error(
+        &engine,
+        &chain(
+            vec![start(), log(), transform()],
+            vec![
+                connection(START_ID, LOG_ID),
+                connection(LOG_ID, TRANSFORM_ID),
+            ],
+        ),
+        &["尾节点 log 不能指向其他节点"],
+    )
+    .await;
+
+    // 节点之间的循环依赖
+    let second_transform = "3f2504e0-4f89-11d3-9a0c-0305e82c9706";
+    let circular = chain(
+        vec![
+            start(),
+            transform(),
+            node(second_transform, "transform", json!({ "template": {} })),
+        ],
+        vec![
+            connection(START_ID, TRANSFORM_ID),
+            connection(TRANSFORM_ID, second_transform),
+            connection(second_transform, TRANSFORM_ID),
+        ],
+    );
+    for result in [
+        engine.validate_chain(&circular).await,
+        engine.load_chain(&circular).await.map(|_| ()),
+    ] {
+        match result {
+            Err(RuleError::CircularDependency(e)) => info!("循环依赖: {}", e),
+            other => panic!("期望循环依赖错误, 实际: {:?}", other),
+        }
+    }
+
+    // 校验失败的规则链不会被加载
+    assert!(engine.get_loaded_chains().await.is_empty());
+
+    // 合法的规则链只校验时不会被加载,也不会创建新版本
+    let version = engine.get_current_version().await;
+    engine
+        .validate_chain(&chain(
+            vec![start(), transform(), log()],
+            vec![
+                connection(START_ID, TRANSFORM_ID),
+                connection(TRANSFORM_ID, LOG_ID),
+            ],
+        ))
+        .await?;
+    assert!(engine.get_loaded_chains().await.is_empty());
+    assert_eq!(engine.get_current_version().await, version);
+
+    // 合法的规则链正常加载
+    engine
+        .load_chain(&chain(
+            vec![start(), transform(), log()],
+            vec![
+                connection(START_ID, TRANSFORM_ID),
+                connection(TRANSFORM_ID, LOG_ID),
+            ],
+        ))
+        .await?;
+
+    // 节点省略 chain_id 时,加载后使用所属规则链的ID,消息正常路由
+    let without_chain_id = |mut node: Value| {
+        node.as_object_mut().unwrap().remove("chain_id");
+        node
+    };
+    let chain_id = engine
+        .load_chain(&chain(
+            vec![
+                without_chain_id(start()),
+                without_chain_id(node(
+                    TRANSFORM_ID,
+                    "transform",
+                    json!({ "template": { "routed": true } }),
+                )),
+                without_chain_id(log()),
+            ],
+            vec![
+                connection(START_ID, TRANSFORM_ID),
+                connection(TRANSFORM_ID, LOG_ID),
+            ],
+        ))
+        .await?;
+    let loaded = engine.get_chain(chain_id).await.unwrap();
+    assert!(loaded.nodes.iter().all(|n| n.chain_id == chain_id));
+    let result = engine
+        .process_msg(chain_id, Message::new("test", json!({})))
+        .await?;
+    assert_eq!(result.data, json!({ "routed": true }));
+
+    // 分析孤立节点: 不加载规则链即可得到不可达节点和终止节点
+    let orphan = "3f2504e0-4f89-11d3-9a0c-0305e82c9705";
+    let orphan_chain = chain(
+        vec![
+            start(),
+            transform(),
+            log(),
+            node(orphan, "log", json!({ "template": "${msg.data}" })),
+        ],
+        vec![
+            connection(START_ID, TRANSFORM_ID),
+            connection(TRANSFORM_ID, LOG_ID),
+        ],
+    );
+    let analysis = engine.analyze_chain(&serde_json::from_str::<RuleChain>(&orphan_chain)?);
+    let ids = |ids: &[_]| ids.iter().map(ToString::to_string).collect::<Vec<String>>();
+    assert_eq!(ids(&analysis.unreachable_nodes), vec![orphan]);
+    assert_eq!(ids(&analysis.terminal_nodes), vec![LOG_ID, orphan]);
+
+    // 默认只记录警告,规则链正常加载
+    engine.load_chain(&orphan_chain).await?;
+
+    // 严格模式下拒绝包含不可达节点的规则链
+    let strict = RuleEngine::new().await.with_reject_unreachable(true);
+    expect_config_error(&strict, &orphan_chain, &["不可达的节点", orphan]).await;
+
+    info!("规则链结构校验通过");
+    Ok(())
+}
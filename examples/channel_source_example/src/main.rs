@@ -0,0 +1,94 @@
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use rule_rs::types::{AckHandle, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{info, Level};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 模拟较慢的下游,记录同时处理的最大消息数
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let processed = Arc::new(AtomicUsize::new(0));
+    let (running_fn, peak_fn, processed_fn) = (running.clone(), peak.clone(), processed.clone());
+    engine
+        .register_async_fn(
+            "fn/slow",
+            Arc::new(
+                move |msg: Message| -> BoxFuture<'static, Result<Message, RuleError>> {
+                    let (running, peak, processed) =
+                        (running_fn.clone(), peak_fn.clone(), processed_fn.clone());
+                    async move {
+                        let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(current, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        processed.fetch_add(1, Ordering::SeqCst);
+                        Ok(msg)
+                    }
+                    .boxed()
+                },
+            ),
+        )
+        .await;
+
+    // 起始 -> 慢节点
+    let mut builder = RuleChainBuilder::new("通道消息源示例");
+    let start = builder.add_node("start", json!({}));
+    let slow = builder.add_node("fn/slow", json!({}));
+    builder.connect(start, slow, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    // 最多同时处理 3 条消息,通道容量为 2,生产者发送 10 条消息
+    let (tx, rx) = mpsc::channel(2);
+    let source = engine.attach_source(chain_id, rx, 3);
+    let (ack, outcome) = AckHandle::channel();
+    let producer = tokio::spawn(async move {
+        for i in 0..10 {
+            let mut msg = Message::new("order", json!({ "order": i }));
+            if i == 9 {
+                msg = msg.with_ack(ack.clone());
+            }
+            tx.send(msg).await.expect("消息源已停止");
+        }
+        // 发送方丢弃后通道关闭,消息源处理完剩余消息后结束
+    });
+
+    producer.await?;
+    source.join().await;
+    assert!(outcome.await?.is_ack(), "确认句柄收到处理结果");
+    assert_eq!(processed.load(Ordering::SeqCst), 10);
+    assert_eq!(peak.load(Ordering::SeqCst), 3, "同时处理的消息数不超过上限");
+    info!("10 条消息处理完成, 最多同时处理 3 条");
+
+    // 停止消息源: 正在处理的消息继续完成,未读取的消息留在通道中
+    processed.store(0, Ordering::SeqCst);
+    let (tx, rx) = mpsc::channel(10);
+    for i in 0..5 {
+        tx.send(Message::new("order", json!({ "order": i }))).await?;
+    }
+    let source = engine.attach_source(chain_id, rx, 2);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(source.in_flight(), 2);
+    let mut rx = source.stop().await;
+    assert_eq!(processed.load(Ordering::SeqCst), 2);
+    let mut remaining = 0;
+    while rx.try_recv().is_ok() {
+        remaining += 1;
+    }
+    assert_eq!(remaining, 3);
+    info!("消息源已停止, 通道中保留 {} 条未处理的消息", remaining);
+
+    info!("通道消息源示例验证通过");
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::{info, Level};
+
+/// 未提供配置 Schema 的自定义节点
+#[derive(Debug)]
+struct EchoNode;
+
+#[async_trait]
+impl NodeHandler for EchoNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/echo".to_string(),
+            name: "回显节点".to_string(),
+            description: "原样返回消息".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+/// 按组件描述符中的 Schema 校验节点配置,模拟编辑器在创建规则链前的检查
+async fn check_config(engine: &RuleEngine, type_name: &str, config: &Value) -> Vec<String> {
+    let Some(schema) = engine
+        .get_component_descriptor(type_name)
+        .await
+        .and_then(|d| d.config_schema)
+    else {
+        return Vec::new();
+    };
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    validator
+        .iter_errors(config)
+        .map(|e| format!("{}: {}", e.instance_path, e))
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    engine
+        .register_node_type(
+            "custom/echo",
+            Arc::new(|_| Ok(Arc::new(EchoNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+
+    // 内置节点都提供可编译的 Schema,自定义节点可以不提供
+    for desc in engine.get_registered_components().await {
+        match &desc.config_schema {
+            Some(schema) => {
+                assert!(
+                    jsonschema::validator_for(schema).is_ok(),
+                    "{} 的配置 Schema 无效",
+                    desc.type_name
+                );
+            }
+            None => {
+                info!("{} 未提供配置 Schema", desc.type_name);
+                assert_eq!(desc.type_name, "custom/echo");
+            }
+        }
+    }
+
+    // 组件列表接口返回的描述符包含 Schema
+    let desc = engine
+        .get_component_descriptor("rest_client")
+        .await
+        .unwrap();
+    let value = serde_json::to_value(&desc)?;
+    info!("rest_client 配置 Schema: {}", value["config_schema"]);
+    assert_eq!(value["config_schema"]["required"], json!(["url", "method"]));
+
+    // 合法的配置通过校验
+    let config = json!({
+        "url": "http://localhost:8080/api/${msg.data.id}",
+        "method": "POST",
+        "retry": { "max_attempts": 5 }
+    });
+    assert!(check_config(&engine, "rest_client", &config)
+        .await
+        .is_empty());
+    let config = json!({ "size": 10, "aggregate": "avg", "field": "temperature" });
+    assert!(check_config(&engine, "window", &config).await.is_empty());
+
+    // 缺少必填字段、枚举值或类型错误时给出具体位置
+    let errors = check_config(&engine, "rest_client", &json!({ "method": "FETCH" })).await;
+    info!("rest_client 配置错误: {:?}", errors);
+    assert_eq!(errors.len(), 2);
+    let errors = check_config(
+        &engine,
+        "window",
+        &json!({ "size": -1, "aggregate": "median" }),
+    )
+    .await;
+    info!("window 配置错误: {:?}", errors);
+    assert!(errors.iter().any(|e| e.starts_with("/size")));
+    assert!(errors.iter().any(|e| e.starts_with("/aggregate")));
+
+    // 没有 Schema 的节点不做检查
+    assert!(check_config(&engine, "custom/echo", &json!({ "any": 1 }))
+        .await
+        .is_empty());
+
+    info!("组件配置 Schema 验证通过");
+    Ok(())
+}
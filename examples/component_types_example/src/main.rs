@@ -0,0 +1,87 @@
+use rule_rs::types::{NodeType, RuleChain};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde_json::json;
+use std::collections::HashMap;
+use tracing::{info, Level};
+
+/// 只依据导出的节点类型检查规则链,不需要运行中的引擎
+fn lint(chain: &RuleChain, types: &HashMap<String, NodeType>) -> Vec<String> {
+    let mut problems = Vec::new();
+    for node in &chain.nodes {
+        let Some(node_type) = types.get(&node.type_name) else {
+            problems.push(format!("未知的节点类型: {}", node.type_name));
+            continue;
+        };
+        let incoming = chain.connections.iter().any(|conn| conn.to_id == node.id);
+        let outgoing = chain.connections.iter().any(|conn| conn.from_id == node.id);
+        if *node_type == NodeType::Head && incoming {
+            problems.push(format!("头节点 {} 不能被其他节点指向", node.type_name));
+        }
+        if *node_type == NodeType::Tail && outgoing {
+            problems.push(format!("尾节点 {} 不能指向其他节点", node.type_name));
+        }
+    }
+    problems
+}
+
+fn chain(types: [&str; 3]) -> RuleChain {
+    let ids = [
+        "3f2504e0-4f89-11d3-9a0c-0305e82c3310",
+        "3f2504e0-4f89-11d3-9a0c-0305e82c3311",
+        "3f2504e0-4f89-11d3-9a0c-0305e82c3312",
+    ];
+    serde_json::from_value(json!({
+        "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+        "name": "离线检查",
+        "root": true,
+        "nodes": ids.iter().zip(types).map(|(id, type_name)| json!({
+            "id": id,
+            "type_name": type_name,
+            "config": {},
+            "layout": { "x": 0, "y": 0 }
+        })).collect::<Vec<_>>(),
+        "connections": [
+            { "from_id": ids[0], "to_id": ids[1], "type_name": "success" },
+            { "from_id": ids[1], "to_id": ids[2], "type_name": "success" }
+        ],
+        "metadata": { "version": 1, "created_at": 0, "updated_at": 0 }
+    }))
+    .unwrap()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 从引擎导出节点类型,保存为 JSON 供离线工具使用
+    let engine = RuleEngine::new().await;
+    let types = engine.component_types().await;
+    assert_eq!(types.len(), engine.get_registered_components().await.len());
+    assert_eq!(types["start"], NodeType::Head);
+    assert_eq!(types["transform"], NodeType::Middle);
+    assert_eq!(types["log"], NodeType::Tail);
+    let exported = serde_json::to_string(&types)?;
+    info!("导出的节点类型: {}", exported);
+
+    // 离线工具只读取导出的 JSON
+    let types: HashMap<String, NodeType> = serde_json::from_str(&exported)?;
+    assert!(lint(&chain(["start", "transform", "log"]), &types).is_empty());
+
+    let problems = lint(&chain(["transform", "log", "start"]), &types);
+    info!("检查结果: {:?}", problems);
+    assert_eq!(
+        problems,
+        vec![
+            "尾节点 log 不能指向其他节点".to_string(),
+            "头节点 start 不能被其他节点指向".to_string(),
+        ]
+    );
+    assert_eq!(
+        lint(&chain(["start", "custom/unknown", "log"]), &types),
+        vec!["未知的节点类型: custom/unknown".to_string()]
+    );
+
+    info!("节点类型导出验证通过");
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 模拟调用外部接口的节点,记录同时执行的数量
+#[derive(Debug, Default)]
+struct ApiCounter {
+    running: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct ApiNode {
+    counter: Arc<ApiCounter>,
+}
+
+#[async_trait]
+impl NodeHandler for ApiNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let running = self.counter.running.fetch_add(1, Ordering::SeqCst) + 1;
+        self.counter.peak.fetch_max(running, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.counter.running.fetch_sub(1, Ordering::SeqCst);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/api".to_string(),
+            name: "外部接口".to_string(),
+            description: "模拟耗时的外部接口调用".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+/// 起始 -> 外部接口 规则链
+async fn load_chain(engine: &RuleEngine, config: Value) -> Result<Uuid, RuleError> {
+    let mut builder = RuleChainBuilder::new("并发限制示例");
+    let start = builder.add_node("start", json!({}));
+    let api = builder.add_node("custom/api", config);
+    let chain = builder.connect(start, api, "success").root(true).build();
+    engine.load_rule_chain(chain).await
+}
+
+/// 并发处理 50 条消息,返回节点同时执行的最大数量
+async fn run_concurrently(engine: &Arc<RuleEngine>, chain_id: Uuid, counter: &ApiCounter) -> usize {
+    counter.peak.store(0, Ordering::SeqCst);
+    let tasks: Vec<_> = (0..50)
+        .map(|i| {
+            let engine = engine.clone();
+            tokio::spawn(async move {
+                let msg = Message::new("request", json!({ "index": i }));
+                engine.process_msg(chain_id, msg).await
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+    counter.peak.load(Ordering::SeqCst)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = Arc::new(RuleEngine::new().await);
+    let counter = Arc::new(ApiCounter::default());
+    let node_counter = counter.clone();
+    engine
+        .register_node_type(
+            "custom/api",
+            Arc::new(move |_| {
+                Ok(Arc::new(ApiNode {
+                    counter: node_counter.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 未限制时所有消息同时调用接口
+    let unlimited = load_chain(&engine, json!({})).await?;
+    let peak = run_concurrently(&engine, unlimited, &counter).await;
+    info!("未限制并发时最多同时执行 {} 次", peak);
+    assert!(peak > 3);
+
+    // 限制后同时执行的数量不超过上限
+    let limited = load_chain(&engine, json!({ "max_concurrency": 3 })).await?;
+    let peak = run_concurrently(&engine, limited, &counter).await;
+    info!("限制并发为 3 时最多同时执行 {} 次", peak);
+    assert_eq!(peak, 3);
+
+    // 并发上限必须大于 0
+    let result = load_chain(&engine, json!({ "max_concurrency": 0 })).await;
+    assert!(matches!(result, Err(RuleError::ConfigError(_))));
+
+    info!("节点并发限制验证通过");
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::json;
+use tracing::{info, Level};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 起始 -> 暂存令牌 -> 转换(只保留订单) -> 取回令牌到请求头 -> 取回整个登录响应
+    let chain = json!({
+        "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+        "name": "共享数据节点示例",
+        "root": true,
+        "nodes": [
+            {
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3310",
+                "type_name": "start",
+                "config": {},
+                "layout": { "x": 0, "y": 0 }
+            },
+            {
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3311",
+                "type_name": "context",
+                "config": { "operation": "set", "key": "token", "value_path": "auth.token" },
+                "layout": { "x": 100, "y": 0 }
+            },
+            {
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3312",
+                "type_name": "context",
+                "config": { "operation": "set", "key": "login" },
+                "layout": { "x": 200, "y": 0 }
+            },
+            {
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3313",
+                "type_name": "transform",
+                "config": { "template": { "order": "${msg.order}" } },
+                "layout": { "x": 300, "y": 0 }
+            },
+            {
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3314",
+                "type_name": "context",
+                "config": { "operation": "get", "key": "token", "value_path": "headers.authorization" },
+                "layout": { "x": 400, "y": 0 }
+            },
+            {
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3315",
+                "type_name": "context",
+                "config": { "operation": "get", "key": "login", "value_path": "login" },
+                "layout": { "x": 500, "y": 0 }
+            }
+        ],
+        "connections": [
+            {
+                "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3310",
+                "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3311",
+                "type_name": "success"
+            },
+            {
+                "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3311",
+                "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3312",
+                "type_name": "success"
+            },
+            {
+                "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3312",
+                "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3313",
+                "type_name": "success"
+            },
+            {
+                "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3313",
+                "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3314",
+                "type_name": "success"
+            },
+            {
+                "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3314",
+                "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3315",
+                "type_name": "success"
+            }
+        ],
+        "metadata": { "version": 1, "created_at": 0, "updated_at": 0 }
+    });
+    let chain_id = engine.load_chain_value(chain).await?;
+
+    // 转换节点丢弃了令牌,取回后写入请求头,后续的 rest_client 可以引用 ${msg.data.headers.authorization}
+    let login = json!({ "auth": { "token": "Bearer abc" }, "order": 42 });
+    let result = engine
+        .process_msg(chain_id, Message::new("order", login.clone()))
+        .await?;
+    info!("处理结果: {}", result.data);
+    assert_eq!(
+        result.data,
+        json!({
+            "order": "42",
+            "headers": { "authorization": "Bearer abc" },
+            "login": login
+        })
+    );
+
+    // 暂存的字段不存在时返回错误
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({ "order": 1 })))
+        .await;
+    info!("缺少令牌: {:?}", result);
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(_))));
+
+    // 键名不能为空
+    let descriptor = engine.get_component_descriptor("context").await.unwrap();
+    assert_eq!(descriptor.name, "共享数据节点");
+    let invalid = engine
+        .validate_chain(
+            &json!({
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+                "name": "无效配置",
+                "root": true,
+                "nodes": [
+                    {
+                        "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3320",
+                        "type_name": "start",
+                        "config": {},
+                        "layout": { "x": 0, "y": 0 }
+                    },
+                    {
+                        "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3321",
+                        "type_name": "context",
+                        "config": { "operation": "get", "key": "" },
+                        "layout": { "x": 100, "y": 0 }
+                    }
+                ],
+                "connections": [
+                    {
+                        "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3320",
+                        "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3321",
+                        "type_name": "success"
+                    }
+                ],
+                "metadata": { "version": 1, "created_at": 0, "updated_at": 0 }
+            })
+            .to_string(),
+        )
+        .await;
+    assert!(matches!(invalid, Err(RuleError::ConfigError(_))));
+
+    info!("共享数据节点验证通过");
+    Ok(())
+}
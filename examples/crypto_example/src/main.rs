@@ -0,0 +1,140 @@
+use rule_rs::components::CryptoConfig;
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 起始 -> 签名节点;校验操作的 valid/invalid 分支各接一个日志节点
+async fn load(engine: &RuleEngine, config: Value) -> Result<Uuid, RuleError> {
+    let verify = config["operation"]
+        .as_str()
+        .is_some_and(|op| op.ends_with("verify"));
+    let mut builder = RuleChainBuilder::new("签名示例");
+    let start = builder.add_node("start", json!({}));
+    let crypto = builder.add_node("crypto", config);
+    builder.connect(start, crypto, "success");
+    if verify {
+        let valid = builder.add_node("log", json!({ "template": "校验通过" }));
+        let invalid = builder.add_node(
+            "log",
+            json!({ "template": "校验失败: ${msg.metadata.error}" }),
+        );
+        builder.connect(crypto, valid, "valid");
+        builder.connect(crypto, invalid, "invalid");
+    }
+    engine.load_rule_chain(builder.root(true).build()).await
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 密钥通过 ${env:...} 占位符在加载规则链时读取
+    std::env::set_var("WEBHOOK_SECRET", "s3cr3t");
+    let engine = RuleEngine::new().await;
+    let body = r#"{"event":"push","ref":"main"}"#;
+
+    // HMAC 签名: 载荷为字符串时按原始内容计算
+    let sign = load(
+        &engine,
+        json!({ "operation": "hmac_sign", "secret": "${env:WEBHOOK_SECRET}" }),
+    )
+    .await?;
+    let signed = engine
+        .process_msg(sign, Message::new("webhook", json!(body)))
+        .await?;
+    let signature = signed.get_meta_str("signature").unwrap().to_string();
+    info!("签名: {}", signature);
+    assert_eq!(signature.len(), 64);
+
+    // 校验 webhook: 签名来自请求头,兼容 sha256= 前缀
+    let verify = load(
+        &engine,
+        json!({
+            "operation": "hmac_verify",
+            "secret": "${env:WEBHOOK_SECRET}",
+            "field": "x-hub-signature-256"
+        }),
+    )
+    .await?;
+    let mut msg = Message::new("webhook", json!(body));
+    msg.set_meta("x-hub-signature-256", format!("sha256={}", signature));
+    let result = engine.process_msg(verify, msg).await?;
+    assert!(result.get_meta("error").is_none());
+
+    // 篡改后的请求体走 invalid 分支
+    let mut msg = Message::new("webhook", json!(body.replace("main", "dev")));
+    msg.set_meta("x-hub-signature-256", format!("sha256={}", signature));
+    let result = engine.process_msg(verify, msg).await?;
+    info!("篡改后: {:?}", result.get_meta_str("error"));
+    assert_eq!(result.get_meta_str("error"), Some("签名不匹配"));
+
+    // 缺少签名同样走 invalid 分支
+    let result = engine
+        .process_msg(verify, Message::new("webhook", json!(body)))
+        .await?;
+    assert!(result.get_meta_str("error").unwrap().contains("不存在"));
+
+    // JWT: 使用 msg.data 中的声明签发,有效期一小时
+    let jwt_sign = load(
+        &engine,
+        json!({
+            "operation": "jwt_sign",
+            "secret": "${env:WEBHOOK_SECRET}",
+            "algorithm": "sha512",
+            "expires_in_secs": 3600
+        }),
+    )
+    .await?;
+    let issued = engine
+        .process_msg(jwt_sign, Message::new("login", json!({ "sub": "alice" })))
+        .await?;
+    let token = issued.get_meta_str("token").unwrap().to_string();
+    info!("JWT: {}", token);
+
+    let jwt_verify = load(
+        &engine,
+        json!({
+            "operation": "jwt_verify",
+            "secret": "${env:WEBHOOK_SECRET}",
+            "algorithm": "sha512",
+            "field": "authorization"
+        }),
+    )
+    .await?;
+    let mut msg = Message::new("request", json!({}));
+    msg.set_meta("authorization", format!("Bearer {}", token));
+    let result = engine.process_msg(jwt_verify, msg).await?;
+    let claims = result.get_meta("claims").unwrap();
+    info!("声明: {}", claims);
+    assert_eq!(claims["sub"], "alice");
+    assert!(claims["exp"].is_i64());
+
+    // 过期的 JWT 走 invalid 分支
+    let expired = load(
+        &engine,
+        json!({ "operation": "jwt_sign", "secret": "${env:WEBHOOK_SECRET}", "algorithm": "sha512" }),
+    )
+    .await?;
+    let issued = engine
+        .process_msg(
+            expired,
+            Message::new("login", json!({ "sub": "alice", "exp": 1_000_000_000 })),
+        )
+        .await?;
+    let mut msg = Message::new("request", json!({}));
+    msg.set_meta("authorization", issued.get_meta_str("token").unwrap());
+    let result = engine.process_msg(jwt_verify, msg).await?;
+    info!("过期: {:?}", result.get_meta_str("error"));
+    assert!(result.get_meta("claims").is_none());
+    assert!(result.get_meta_str("error").unwrap().contains("JWT 无效"));
+
+    // 配置的调试输出不包含密钥
+    let config: CryptoConfig = serde_json::from_value(json!({ "secret": "s3cr3t" }))?;
+    assert!(!format!("{:?}", config).contains("s3cr3t"));
+
+    info!("签名节点示例验证通过");
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use axum::http::header::CONTENT_TYPE;
+use axum::routing::get;
+use axum::Router;
+use rule_rs::types::{RuleChainBuilder, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+const EXPORT: &str = "id,name,city\n1,Alice,\"Shanghai, CN\"\n2,Bob,Beijing\n";
+
+/// 加载 起始 -> CSV转换 规则链,处理一条消息
+async fn convert(engine: &RuleEngine, config: Value, data: Value) -> Result<Message, RuleError> {
+    let mut builder = RuleChainBuilder::new("CSV转换");
+    let start = builder.add_node("start", json!({}));
+    let csv = builder.add_node("csv", config);
+    let chain = builder.connect(start, csv, "success").root(true).build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+    engine
+        .process_msg(chain_id, Message::new("csv", data))
+        .await
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 启动本地服务,提供 CSV 导出文件
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}/export.csv", listener.local_addr()?);
+    let app = Router::new().route(
+        "/export.csv",
+        get(|| async { ([(CONTENT_TYPE, "text/csv")], EXPORT) }),
+    );
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let engine = RuleEngine::new().await;
+
+    // HTTP请求获取导出文件 -> 解析为对象数组
+    let mut builder = RuleChainBuilder::new("CSV导入");
+    let start = builder.add_node("start", json!({}));
+    let fetch = builder.add_node("rest_client", json!({ "url": url, "method": "GET" }));
+    let parse = builder.add_node("csv", json!({ "direction": "parse" }));
+    let chain = builder
+        .connect(start, fetch, "success")
+        .connect(fetch, parse, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("import", json!({})))
+        .await?;
+    info!("解析结果: {}", result.data);
+    let rows = json!([
+        { "id": "1", "name": "Alice", "city": "Shanghai, CN" },
+        { "id": "2", "name": "Bob", "city": "Beijing" }
+    ]);
+    assert_eq!(result.data, rows);
+
+    // 没有表头时每行输出为字符串数组
+    let result = convert(
+        &engine,
+        json!({ "has_headers": false, "delimiter": ";" }),
+        json!("a;b\nc;d"),
+    )
+    .await?;
+    assert_eq!(result.data, json!([["a", "b"], ["c", "d"]]));
+
+    // 对象数组序列化为 CSV,缺少的字段输出为空
+    let result = convert(
+        &engine,
+        json!({ "direction": "serialize" }),
+        json!([
+            { "name": "Alice", "score": 90 },
+            { "name": "Bob, Jr.", "passed": true }
+        ]),
+    )
+    .await?;
+    info!("序列化结果:\n{}", result.data.as_str().unwrap());
+    assert_eq!(
+        result.data,
+        json!("name,score,passed\nAlice,90,\n\"Bob, Jr.\",,true\n")
+    );
+
+    // 数据格式错误时返回组件错误
+    match convert(&engine, json!({ "direction": "serialize" }), json!({})).await {
+        Err(RuleError::ComponentError(e)) => info!("序列化失败: {}", e),
+        other => panic!("期望组件错误, 实际: {:?}", other),
+    }
+
+    info!("CSV转换验证通过");
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, Level};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RememberConfig {
+    /// 共享数据的键名
+    key: String,
+    /// 记录的消息字段
+    field: String,
+}
+
+/// 把消息中的字段记录到本次执行的共享数据中,字段不存在时不记录
+#[derive(Debug)]
+struct RememberNode {
+    config: RememberConfig,
+}
+
+#[async_trait]
+impl NodeHandler for RememberNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        if let Some(value) = msg.data.get(&self.config.field) {
+            ctx.ctx_set(&self.config.key, value.clone()).await;
+        }
+        // 让并发执行的消息交错运行
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/remember".to_string(),
+            name: "记录节点".to_string(),
+            description: "把消息字段写入共享数据".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RecallConfig {
+    key: String,
+}
+
+/// 读取共享数据作为输出
+#[derive(Debug)]
+struct RecallNode {
+    config: RecallConfig,
+}
+
+#[async_trait]
+impl NodeHandler for RecallNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let value = ctx.ctx_get(&self.config.key).await.unwrap_or(Value::Null);
+        let mut out = msg;
+        out.data = json!({ "recalled": value, "before": out.data });
+        Ok(out)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/recall".to_string(),
+            name: "读取节点".to_string(),
+            description: "读取共享数据".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = Arc::new(RuleEngine::new().await);
+    engine
+        .register_node_type(
+            "custom/remember",
+            Arc::new(|config| {
+                let config: RememberConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(RememberNode { config }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+    engine
+        .register_node_type(
+            "custom/recall",
+            Arc::new(|config| {
+                let config: RecallConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(RecallNode { config }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 起始 -> 记录金额 -> 转换(丢弃金额) -> 读取金额
+    let mut builder = RuleChainBuilder::new("共享数据示例");
+    let start = builder.add_node("start", json!({}));
+    let remember = builder.add_node(
+        "custom/remember",
+        json!({ "key": "amount", "field": "amount" }),
+    );
+    let transform = builder.add_node(
+        "transform",
+        json!({ "template": { "step": "transformed" } }),
+    );
+    let recall = builder.add_node("custom/recall", json!({ "key": "amount" }));
+    let chain = builder
+        .connect(start, remember, "success")
+        .connect(remember, transform, "success")
+        .connect(transform, recall, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    // 前面节点记录的数据在后面的节点仍可读取,即使 msg.data 已被替换
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({ "amount": 42 })))
+        .await?;
+    info!("处理结果: {}", result.data);
+    assert_eq!(
+        result.data,
+        json!({ "recalled": 42, "before": { "step": "transformed" } })
+    );
+
+    // 并发处理的消息各自拥有独立的共享数据
+    let runs: Vec<_> = (0..5)
+        .map(|i| {
+            let engine = engine.clone();
+            tokio::spawn(async move {
+                let msg = Message::new("order", json!({ "amount": i }));
+                let result = engine.process_msg(chain_id, msg).await.unwrap();
+                (i, result.data["recalled"].clone())
+            })
+        })
+        .collect();
+    for run in runs {
+        let (i, recalled) = run.await?;
+        assert_eq!(recalled, json!(i));
+    }
+    info!("并发执行互不影响");
+
+    // 每次处理从空的共享数据开始
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({})))
+        .await?;
+    assert_eq!(result.data["recalled"], Value::Null);
+
+    info!("共享数据验证通过");
+    Ok(())
+}
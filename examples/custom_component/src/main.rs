@@ -101,6 +101,7 @@ impl NodeHandler for UpperNode {
             name: "大写转换节点".to_string(),
             description: "将文本转换为大写".to_string(),
             node_type: NodeType::Middle,
+            config_schema: None,
         }
     }
 }
@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+
+/// 生成规则链,第一个节点为指定类型的入口节点
+fn chain(index: u8, entry_type: &str, connections: &[(u8, u8)]) -> String {
+    let chain_id = format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}01", 70 + index);
+    let node_id = |n: u8| format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}{:02}", 70 + index, n);
+
+    json!({
+        "id": chain_id,
+        "name": format!("自定义入口示例 {}", index),
+        "root": true,
+        "nodes": [
+            {
+                "id": node_id(0),
+                "type_name": entry_type,
+                "chain_id": chain_id,
+                "config": {},
+                "layout": { "x": 50, "y": 100 }
+            },
+            {
+                "id": node_id(2),
+                "type_name": "custom/upper",
+                "chain_id": chain_id,
+                "config": {},
+                "layout": { "x": 200, "y": 100 }
+            },
+            {
+                "id": node_id(3),
+                "type_name": "custom/record",
+                "chain_id": chain_id,
+                "config": {},
+                "layout": { "x": 350, "y": 100 }
+            }
+        ],
+        "connections": connections
+            .iter()
+            .map(|(from, to)| json!({
+                "from_id": node_id(*from),
+                "to_id": node_id(*to),
+                "type_name": "success"
+            }))
+            .collect::<Vec<_>>(),
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+    .to_string()
+}
+
+/// 消息源节点,模拟从消息队列接收消息的自定义头节点
+#[derive(Debug)]
+struct QueueSourceNode;
+
+#[async_trait]
+impl NodeHandler for QueueSourceNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.data["source"] = json!("queue");
+        ctx.send_next(msg.clone()).await?;
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/queue_source".to_string(),
+            name: "队列消息源".to_string(),
+            description: "从消息队列接收消息".to_string(),
+            node_type: NodeType::Head,
+            config_schema: None,
+        }
+    }
+}
+
+/// 大写转换节点,普通的中间节点
+#[derive(Debug)]
+struct UpperNode;
+
+#[async_trait]
+impl NodeHandler for UpperNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        if let Some(text) = msg.data["text"].as_str() {
+            msg.data["text"] = json!(text.to_uppercase());
+        }
+        ctx.send_next(msg.clone()).await?;
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/upper".to_string(),
+            name: "大写转换".to_string(),
+            description: "把文本转换为大写".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+/// 记录下游节点收到的消息数据
+type Records = Arc<Mutex<Vec<Value>>>;
+
+#[derive(Debug)]
+struct RecordNode {
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        self.records.lock().unwrap().push(msg.data.clone());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册自定义组件
+    engine
+        .register_node_type(
+            "custom/queue_source",
+            Arc::new(|_| Ok(Arc::new(QueueSourceNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+    engine
+        .register_node_type(
+            "custom/upper",
+            Arc::new(|_| Ok(Arc::new(UpperNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |_| {
+                Ok(Arc::new(RecordNode {
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 自定义头节点作为规则链入口: 队列消息源 -> 大写转换 -> 记录
+    let source_chain = engine
+        .load_chain(&chain(1, "custom/queue_source", &[(0, 2), (2, 3)]))
+        .await?;
+    engine
+        .process_msg(
+            source_chain,
+            Message::new("event", json!({ "text": "hello" })),
+        )
+        .await?;
+
+    // 没有入向连接的中间节点同样可以作为入口: 大写转换 -> 大写转换 -> 记录
+    let middle_chain = engine
+        .load_chain(&chain(2, "custom/upper", &[(0, 2), (2, 3)]))
+        .await?;
+    engine
+        .process_msg(
+            middle_chain,
+            Message::new("event", json!({ "text": "world" })),
+        )
+        .await?;
+
+    assert_eq!(
+        *records.lock().unwrap(),
+        vec![
+            json!({ "text": "HELLO", "source": "queue" }),
+            json!({ "text": "WORLD" })
+        ]
+    );
+
+    // 起始节点被其他节点指向时拒绝加载
+    match engine
+        .load_chain(&chain(3, "custom/upper", &[(0, 2), (2, 0), (2, 3)]))
+        .await
+    {
+        Err(RuleError::ConfigError(e)) => {
+            info!("加载失败: {}", e);
+            assert!(e.contains("起始节点"));
+        }
+        other => panic!("期望配置错误, 实际: {:?}", other),
+    }
+
+    info!("自定义入口节点验证通过");
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+/// 创建 起始 -> 时间处理 的规则链,处理 `{ "time": input }` 并返回处理后的 time 字段
+async fn run(engine: &RuleEngine, config: Value, input: Value) -> Result<Value, RuleError> {
+    let mut builder = RuleChainBuilder::new("时间处理示例");
+    let start = builder.add_node("start", json!({}));
+    let datetime = builder.add_node("datetime", config);
+    builder.connect(start, datetime, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("report", json!({ "time": input })))
+        .await;
+    engine.remove_chain(chain_id).await?;
+    Ok(result?.data["time"].clone())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // now: 写入当前毫秒时间戳
+    let now = run(&engine, json!({ "field": "time" }), Value::Null).await?;
+    info!("当前时间戳: {}", now);
+    assert!(now.as_i64().unwrap() > 1_700_000_000_000);
+
+    // parse: 按格式解析上海本地时间
+    let parsed = run(
+        &engine,
+        json!({
+            "field": "time",
+            "operation": "parse",
+            "input_format": "%Y-%m-%d %H:%M:%S",
+            "from_tz": "Asia/Shanghai",
+        }),
+        json!("2024-01-02 08:00:00"),
+    )
+    .await?;
+    assert_eq!(parsed, json!(1_704_153_600_000_i64));
+
+    // format: 毫秒时间戳格式化为纽约本地时间
+    let formatted = run(
+        &engine,
+        json!({
+            "field": "time",
+            "operation": "format",
+            "output_format": "%Y/%m/%d %H:%M %Z",
+            "to_tz": "America/New_York",
+        }),
+        json!(1_704_153_600_000_i64),
+    )
+    .await?;
+    assert_eq!(formatted, json!("2024/01/01 19:00 EST"));
+
+    // convert_tz: 纽约本地时间转换为 UTC,跨过 2024-03-10 02:00 的夏令时开始
+    let to_utc = json!({
+        "field": "time",
+        "operation": "convert_tz",
+        "input_format": "%Y-%m-%d %H:%M:%S",
+        "from_tz": "America/New_York",
+    });
+    for (local, utc) in [
+        ("2024-03-10 01:59:59", "2024-03-10T06:59:59+00:00"),
+        ("2024-03-10 03:00:00", "2024-03-10T07:00:00+00:00"),
+        // 夏令时结束时 01:30 出现两次,取较早的 EDT
+        ("2024-11-03 01:30:00", "2024-11-03T05:30:00+00:00"),
+        ("2024-11-03 02:30:00", "2024-11-03T07:30:00+00:00"),
+    ] {
+        let converted = run(&engine, to_utc.clone(), json!(local)).await?;
+        info!("{} America/New_York -> {}", local, converted);
+        assert_eq!(converted, json!(utc));
+    }
+
+    // 夏令时开始时 02:30 不存在
+    let skipped = run(&engine, to_utc.clone(), json!("2024-03-10 02:30:00")).await;
+    assert!(matches!(skipped, Err(RuleError::ComponentError(_))));
+
+    // convert_tz: UTC 时间转换为纽约时间,偏移随夏令时变化
+    let to_new_york = json!({
+        "field": "time",
+        "operation": "convert_tz",
+        "to_tz": "America/New_York",
+    });
+    for (utc, local) in [
+        ("2024-03-10T06:59:59Z", "2024-03-10T01:59:59-05:00"),
+        ("2024-03-10T07:00:00Z", "2024-03-10T03:00:00-04:00"),
+        ("2024-11-03T05:30:00Z", "2024-11-03T01:30:00-04:00"),
+        ("2024-11-03T06:30:00Z", "2024-11-03T01:30:00-05:00"),
+    ] {
+        let converted = run(&engine, to_new_york.clone(), json!(utc)).await?;
+        assert_eq!(converted, json!(local));
+    }
+
+    // 输入与格式不符时返回组件错误
+    let invalid = run(&engine, to_utc, json!("03/10/2024")).await;
+    assert!(matches!(invalid, Err(RuleError::ComponentError(_))));
+
+    // 无效的格式和时区在加载时返回配置错误
+    for config in [
+        json!({ "field": "time", "operation": "format", "output_format": "%Q" }),
+        json!({ "field": "time", "operation": "format" }),
+        json!({ "field": "time", "operation": "convert_tz", "to_tz": "Mars/Base" }),
+    ] {
+        let result = run(&engine, config, json!(0)).await;
+        assert!(matches!(result, Err(RuleError::ConfigError(_))));
+    }
+
+    info!("时间处理节点示例验证通过");
+    Ok(())
+}
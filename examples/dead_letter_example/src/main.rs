@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use rule_rs::engine::{DeadLetter, NodeHandler};
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// msg.data.amount 为负数时执行失败的节点
+#[derive(Debug)]
+struct ChargeNode;
+
+#[async_trait]
+impl NodeHandler for ChargeNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        if msg.data["amount"].as_i64().unwrap_or_default() < 0 {
+            return Err(RuleError::NodeExecutionError("金额不能为负数".to_string()));
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/charge".to_string(),
+            name: "扣款节点".to_string(),
+            description: "金额为负数时失败".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 死信处理函数只负责收集,实际使用时可以写入队列或数据库
+    let letters: Arc<Mutex<Vec<DeadLetter>>> = Arc::default();
+    let collected = letters.clone();
+    let engine = RuleEngine::new()
+        .await
+        .with_dead_letter_handler(Arc::new(move |letter| {
+            collected.lock().unwrap().push(letter);
+        }));
+    engine
+        .register_node_type(
+            "custom/charge",
+            Arc::new(|_| Ok(Arc::new(ChargeNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+
+    // 子规则链: 起始 -> 扣款
+    let mut builder = RuleChainBuilder::new("扣款子规则链");
+    let sub_start = builder.add_node("start", json!({}));
+    let charge = builder.add_node("custom/charge", json!({}));
+    let subchain = builder.connect(sub_start, charge, "success").build();
+    let subchain_id = engine.load_rule_chain(subchain).await?;
+
+    // 根规则链: 起始 -> 暂存金额 -> 子规则链
+    let mut builder = RuleChainBuilder::new("死信示例");
+    let start = builder.add_node("start", json!({}));
+    let stash = builder.add_node(
+        "context",
+        json!({ "operation": "set", "key": "amount", "value_path": "amount" }),
+    );
+    let call = builder.add_node("subchain", json!({ "chain_id": subchain_id }));
+    let chain = builder
+        .connect(start, stash, "success")
+        .connect(stash, call, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    // 成功处理的消息不产生死信
+    engine
+        .process_msg(chain_id, Message::new("order", json!({ "amount": 10 })))
+        .await?;
+    assert!(letters.lock().unwrap().is_empty());
+
+    // 处理失败时调用方收到错误,死信记录原始消息和子规则链中失败的节点
+    let msg = Message::new("order", json!({ "amount": -5 }));
+    let result = engine.process_msg(chain_id, msg.clone()).await;
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(_))));
+    let letter = letters.lock().unwrap().pop().unwrap();
+    info!(
+        "死信: 规则链 {}, 节点 {:?}, 错误: {}",
+        letter.chain_id, letter.node_id, letter.error
+    );
+    assert_eq!(letter.chain_id, chain_id);
+    assert_eq!(letter.node_id, Some(charge));
+    assert_eq!(letter.msg.id, msg.id);
+    assert_eq!(letter.msg.data, json!({ "amount": -5 }));
+    assert!(matches!(letter.error, RuleError::NodeExecutionError(_)));
+
+    // 修正数据后重放死信
+    let mut replay = letter.msg;
+    replay.data["amount"] = json!(5);
+    engine.process_msg(chain_id, replay).await?;
+    assert!(letters.lock().unwrap().is_empty());
+
+    // 被 error_branch 处理的错误不产生死信
+    let mut builder = RuleChainBuilder::new("错误分支");
+    let start = builder.add_node("start", json!({}));
+    let charge = builder.add_node("custom/charge", json!({ "error_branch": "failure" }));
+    let fallback = builder.add_node("transform", json!({ "template": { "refund": true } }));
+    let chain = builder
+        .connect(start, charge, "success")
+        .connect(charge, fallback, "failure")
+        .root(true)
+        .build();
+    let handled_id = engine.load_rule_chain(chain).await?;
+    engine
+        .process_msg(handled_id, Message::new("order", json!({ "amount": -1 })))
+        .await?;
+    assert!(letters.lock().unwrap().is_empty());
+
+    // 规则链开始执行前的错误不产生死信
+    let result = engine
+        .process_msg(Uuid::new_v4(), Message::new("order", json!({})))
+        .await;
+    assert!(matches!(result, Err(RuleError::ChainNotFound(_))));
+    assert!(letters.lock().unwrap().is_empty());
+
+    info!("死信处理验证通过");
+    Ok(())
+}
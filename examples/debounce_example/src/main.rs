@@ -0,0 +1,85 @@
+use rule_rs::testing::register_mock;
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+use tracing::{info, Level};
+
+fn reading(sensor: &str, value: i64) -> Message {
+    Message::new(
+        "reading",
+        json!({ "sensor": { "id": sensor }, "value": value }),
+    )
+}
+
+// 使用暂停的时钟,sleep 会立即推进虚拟时间
+#[tokio::main(flavor = "current_thread", start_paused = true)]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    let sink = register_mock(&engine, "mock/sink").await;
+
+    // 起始 -> 防抖 -> 记录
+    let mut builder = RuleChainBuilder::new("防抖示例");
+    let start = builder.add_node("start", json!({}));
+    let debounce = builder.add_node(
+        "debounce",
+        json!({ "key_path": "sensor.id", "quiet_period_ms": 500 }),
+    );
+    let mock = builder.add_node("mock/sink", json!({}));
+    let chain = builder
+        .connect(start, debounce, "success")
+        .connect(debounce, mock, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    // 每 100ms 一条的连续抖动只在最后一条之后 500ms 转发一次
+    let begin = Instant::now();
+    for value in 1..=5 {
+        engine.process_msg(chain_id, reading("a", value)).await?;
+        sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(sink.count(), 0);
+    sleep(Duration::from_millis(300)).await;
+    assert_eq!(sink.count(), 0);
+    sleep(Duration::from_millis(150)).await;
+    info!("虚拟时间经过 {:?}", begin.elapsed());
+    assert_eq!(sink.count(), 1);
+    assert_eq!(sink.received()[0].data["value"], 5);
+
+    // 不同的键分别计时
+    sink.clear();
+    engine.process_msg(chain_id, reading("a", 1)).await?;
+    engine.process_msg(chain_id, reading("b", 1)).await?;
+    sleep(Duration::from_millis(300)).await;
+    engine.process_msg(chain_id, reading("b", 2)).await?;
+    sleep(Duration::from_millis(250)).await;
+    let received: Vec<_> = sink.received().into_iter().map(|msg| msg.data).collect();
+    assert_eq!(
+        received,
+        vec![json!({ "sensor": { "id": "a" }, "value": 1 })]
+    );
+    sleep(Duration::from_millis(300)).await;
+    assert_eq!(sink.count(), 2);
+    assert_eq!(sink.received()[1].data["value"], 2);
+
+    // 缺少防抖字段时返回错误
+    let result = engine
+        .process_msg(chain_id, Message::new("reading", json!({ "value": 1 })))
+        .await;
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(_))));
+
+    // 删除规则链后等待中的消息不再转发
+    sink.clear();
+    engine.process_msg(chain_id, reading("a", 9)).await?;
+    engine.remove_chain(chain_id).await?;
+    sleep(Duration::from_secs(1)).await;
+    assert_eq!(sink.count(), 0);
+
+    info!("防抖节点验证通过");
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, Level};
+
+const RULE_CHAIN: &str = r#"{
+    "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+    "name": "去重示例",
+    "root": true,
+    "nodes": [
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3300",
+            "type_name": "start",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "type_name": "dedup",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": {
+                "key_path": "sensor.id",
+                "window_ms": 500
+            },
+            "layout": { "x": 200, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3303",
+            "type_name": "custom/record",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": { "name": "success" },
+            "layout": { "x": 350, "y": 50 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3304",
+            "type_name": "custom/record",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": { "name": "duplicate" },
+            "layout": { "x": 350, "y": 150 }
+        }
+    ],
+    "connections": [
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3300",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "type_name": "success"
+        },
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3303",
+            "type_name": "success"
+        },
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3304",
+            "type_name": "duplicate"
+        }
+    ],
+    "metadata": {
+        "version": 1,
+        "created_at": 1679800000,
+        "updated_at": 1679800000
+    }
+}"#;
+
+/// 记录各分支收到的消息
+type Records = Arc<Mutex<Vec<String>>>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RecordConfig {
+    name: String,
+}
+
+/// 记录节点,保存收到消息的分支名称
+#[derive(Debug)]
+struct RecordNode {
+    config: RecordConfig,
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        info!("{} 分支收到消息: {}", self.config.name, msg.data);
+        self.records.lock().unwrap().push(self.config.name.clone());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册记录节点
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |config| {
+                let config: RecordConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(RecordNode {
+                    config,
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 加载规则链
+    let chain_id = engine.load_chain(RULE_CHAIN).await?;
+    info!("规则链加载成功: {}", chain_id);
+
+    let event = || Message::new("sensor", json!({ "sensor": { "id": "s-1" }, "value": 21 }));
+
+    // 窗口期内的两条相同消息,第二条走 duplicate 分支
+    engine.process_msg(chain_id, event()).await?;
+    engine.process_msg(chain_id, event()).await?;
+    assert_eq!(*records.lock().unwrap(), vec!["success", "duplicate"]);
+
+    // 窗口期过后相同消息重新放行
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    engine.process_msg(chain_id, event()).await?;
+    assert_eq!(
+        *records.lock().unwrap(),
+        vec!["success", "duplicate", "success"]
+    );
+    info!("去重验证通过: {:?}", records.lock().unwrap());
+
+    // 删除规则链时清理去重状态
+    engine.remove_chain(chain_id).await?;
+
+    Ok(())
+}
@@ -1,5 +1,10 @@
-use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
 use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tokio::time::{Duration, Instant};
 use tracing::{info, Level};
 
 const RULE_CHAIN: &str = r#"{
@@ -23,14 +28,9 @@ const RULE_CHAIN: &str = r#"{
         },
         {
             "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3303",
-            "type_name": "log",
+            "type_name": "custom/record",
             "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
-            "config": {
-                "template": "延迟消息: ${msg.data.value}",
-                "common": {
-                    "node_type": "Tail"
-                }
-            },
+            "config": {},
             "layout": { "x": 300, "y": 100 }
         }
     ],
@@ -48,7 +48,47 @@ const RULE_CHAIN: &str = r#"{
     }
 }"#;
 
-#[tokio::main]
+/// 记录下游节点收到消息的次序和时间
+type Records = Arc<Mutex<Vec<(u64, Instant)>>>;
+
+/// 记录节点,保存每次收到的 iteration 元数据和到达时间
+#[derive(Debug)]
+struct RecordNode {
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let iteration = msg
+            .get_meta("iteration")
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default();
+        info!("延迟消息: {}, 第 {} 次", msg.data["value"], iteration);
+        self.records
+            .lock()
+            .unwrap()
+            .push((iteration, Instant::now()));
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+// 暂停 tokio 时间,延迟会被自动快进,无需真实等待
+#[tokio::main(flavor = "current_thread", start_paused = true)]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 初始化日志系统
     tracing_subscriber::fmt()
@@ -58,6 +98,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 创建引擎实例并等待组件注册完成
     let engine = RuleEngine::new().await;
 
+    // 注册记录节点
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |_| {
+                Ok(Arc::new(RecordNode {
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
     // 加载规则链
     let chain_id = engine.load_chain(RULE_CHAIN).await?;
     info!(
@@ -73,14 +127,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }),
     );
 
-    // 处理消息
+    // 处理消息,周期模式下处理完成时三次发送均已完成
+    let start = Instant::now();
     match engine.process_msg(chain_id, msg).await {
         Ok(result) => info!("处理结果: {:?}", result),
         Err(e) => info!("处理失败: {:?}", e),
     }
 
-    // 等待一段时间以便观察周期性消息
-    tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+    // 下游节点每隔2秒收到一次消息,共3次
+    let records = records.lock().unwrap();
+    assert_eq!(records.len(), 3);
+    for (i, (iteration, at)) in records.iter().enumerate() {
+        assert_eq!(*iteration, i as u64 + 1);
+        assert_eq!(
+            at.duration_since(start),
+            Duration::from_millis(2000 * (i as u64 + 1))
+        );
+    }
+    info!("周期延迟验证通过: {:?}", records);
 
     Ok(())
 }
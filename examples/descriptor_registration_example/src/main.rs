@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use rule_rs::engine::{NodeFactory, NodeHandler};
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleChainBuilder, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{info, Level};
+
+/// 没有默认值的配置,空配置 `{}` 无法反序列化
+#[derive(Debug, Deserialize)]
+struct TagConfig {
+    tag: String,
+}
+
+/// 给消息数据加上标签
+#[derive(Debug)]
+struct TagNode {
+    config: TagConfig,
+}
+
+#[async_trait]
+impl NodeHandler for TagNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.data["tag"] = json!(self.config.tag);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        descriptor()
+    }
+}
+
+fn descriptor() -> NodeDescriptor {
+    NodeDescriptor {
+        type_name: "custom/tag".to_string(),
+        name: "标签节点".to_string(),
+        description: "给消息数据加上标签".to_string(),
+        node_type: NodeType::Middle,
+        config_schema: Some(json!({
+            "type": "object",
+            "required": ["tag"],
+            "properties": { "tag": { "type": "string" } }
+        })),
+    }
+}
+
+fn factory() -> NodeFactory {
+    Arc::new(|config| {
+        let config: TagConfig = serde_json::from_value(config)?;
+        Ok(Arc::new(TagNode { config }) as Arc<dyn NodeHandler>)
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // register_node_type 需要用空配置构造节点来获取描述符,没有默认配置的节点注册失败
+    engine.register_node_type("custom/tag", factory()).await;
+    assert!(engine
+        .get_component_descriptor("custom/tag")
+        .await
+        .is_none());
+
+    // 直接提供描述符,不构造节点
+    engine
+        .register_node_type_with_descriptor("custom/tag", descriptor(), factory())
+        .await;
+    let registered = engine.get_component_descriptor("custom/tag").await.unwrap();
+    info!(
+        "已注册: {} - {}",
+        registered.type_name, registered.description
+    );
+    assert_eq!(registered.node_type, NodeType::Middle);
+    assert!(engine
+        .get_registered_components()
+        .await
+        .iter()
+        .any(|d| d.type_name == "custom/tag"));
+    assert_eq!(
+        engine.component_types().await.get("custom/tag"),
+        Some(&NodeType::Middle)
+    );
+
+    // 节点在加载规则链时使用实际配置构造
+    let mut builder = RuleChainBuilder::new("标签示例");
+    let start = builder.add_node("start", json!({}));
+    let tag = builder.add_node("custom/tag", json!({ "tag": "vip" }));
+    builder.connect(start, tag, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("user", json!({ "name": "张三" })))
+        .await?;
+    info!("处理结果: {}", result.data);
+    assert_eq!(result.data, json!({ "name": "张三", "tag": "vip" }));
+
+    // 缺少必填配置时加载失败
+    let mut builder = RuleChainBuilder::new("缺少配置");
+    let start = builder.add_node("start", json!({}));
+    let tag = builder.add_node("custom/tag", json!({}));
+    builder.connect(start, tag, "success");
+    let result = engine.load_rule_chain(builder.root(true).build()).await;
+    info!("缺少配置: {:?}", result);
+    assert!(matches!(result, Err(RuleError::ConfigError(_))));
+
+    info!("描述符注册验证通过");
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 极简的 SMTP 服务,把收到的邮件内容发送到通道
+async fn handle_smtp(
+    stream: TcpStream,
+    mails: mpsc::UnboundedSender<String>,
+) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    writer.write_all(b"220 localhost ESMTP\r\n").await?;
+
+    while let Some(line) = lines.next_line().await? {
+        let command = line.to_uppercase();
+        if command.starts_with("DATA") {
+            writer
+                .write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n")
+                .await?;
+            let mut mail = String::new();
+            while let Some(line) = lines.next_line().await? {
+                if line == "." {
+                    break;
+                }
+                mail.push_str(&line);
+                mail.push('\n');
+            }
+            let _ = mails.send(mail);
+            writer.write_all(b"250 OK\r\n").await?;
+        } else if command.starts_with("QUIT") {
+            writer.write_all(b"221 Bye\r\n").await?;
+            break;
+        } else {
+            writer.write_all(b"250 OK\r\n").await?;
+        }
+    }
+    Ok(())
+}
+
+/// 加载 起始 -> 邮件 -> 成功/失败 规则链
+async fn load_chain(engine: &RuleEngine, port: u16) -> Uuid {
+    let mut builder = RuleChainBuilder::new("邮件告警示例");
+    let start = builder.add_node("start", json!({}));
+    let email = builder.add_node(
+        "email",
+        json!({
+            "smtp_host": "127.0.0.1",
+            "port": port,
+            "tls": "none",
+            "from": "Alerts <alerts@example.com>",
+            "to_template": "${msg.data.owner}",
+            "subject_template": "温度过高: ${msg.data.value}",
+            "body_template": "设备 ${msg.data.device} 当前温度 ${msg.data.value}",
+            "timeout_ms": 2000
+        }),
+    );
+    let sent = builder.add_node("transform", json!({ "template": { "status": "sent" } }));
+    let failed = builder.add_node("transform", json!({ "template": { "status": "failed" } }));
+    let chain = builder
+        .connect(start, email, "success")
+        .connect(email, sent, "success")
+        .connect(email, failed, "error")
+        .root(true)
+        .build();
+    engine.load_rule_chain(chain).await.unwrap()
+}
+
+fn alert(owner: &str) -> Message {
+    Message::new(
+        "alert",
+        json!({ "owner": owner, "device": "sensor-1", "value": 42.5 }),
+    )
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 启动本地 SMTP 服务
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let port = listener.local_addr()?.port();
+    let (tx, mut mails) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(handle_smtp(stream, tx.clone()));
+        }
+    });
+
+    let engine = RuleEngine::new().await;
+    let chain_id = load_chain(&engine, port).await;
+
+    // 发送成功走 success 分支,收件人和内容由消息渲染
+    let result = engine
+        .process_msg(chain_id, alert("ops@example.com"))
+        .await?;
+    assert_eq!(result.data["status"], "sent");
+    let mail = mails.recv().await.unwrap();
+    info!("收到邮件:\n{}", mail);
+    assert!(mail.contains("To: ops@example.com"));
+    assert!(mail.contains("From: Alerts <alerts@example.com>"));
+
+    // 收件人地址无效时走 error 分支,错误写入元数据
+    let result = engine.process_msg(chain_id, alert("not an email")).await?;
+    info!("发送失败: {}", result.metadata["error"]);
+    assert_eq!(result.data["status"], "failed");
+    assert!(result.metadata["error"]
+        .as_str()
+        .unwrap()
+        .contains("收件人地址无效"));
+
+    // SMTP 服务不可用时同样走 error 分支
+    let closed_port = TcpListener::bind("127.0.0.1:0").await?.local_addr()?.port();
+    let chain_id = load_chain(&engine, closed_port).await;
+    let result = engine
+        .process_msg(chain_id, alert("ops@example.com"))
+        .await?;
+    info!("发送失败: {}", result.metadata["error"]);
+    assert_eq!(result.data["status"], "failed");
+
+    info!("邮件发送验证通过");
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::TryRecvError;
+use tracing::{info, Level};
+
+const RULE_CHAIN: &str = r#"{
+    "id": "3f2504e0-4f89-11d3-9a0c-0305e82c9601",
+    "name": "广播示例",
+    "root": true,
+    "nodes": [
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c9600",
+            "type_name": "start",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9601",
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c9602",
+            "type_name": "emit",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9601",
+            "config": { "topic": "alerts" },
+            "layout": { "x": 200, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c9603",
+            "type_name": "custom/record",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9601",
+            "config": {},
+            "layout": { "x": 350, "y": 100 }
+        }
+    ],
+    "connections": [
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9600",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9602",
+            "type_name": "success"
+        },
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9602",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9603",
+            "type_name": "success"
+        }
+    ],
+    "metadata": {
+        "version": 1,
+        "created_at": 1679800000,
+        "updated_at": 1679800000
+    }
+}"#;
+
+/// 记录下游节点收到的消息数据
+type Records = Arc<Mutex<Vec<Value>>>;
+
+#[derive(Debug)]
+struct RecordNode {
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        self.records.lock().unwrap().push(msg.data.clone());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册记录节点
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |_| {
+                Ok(Arc::new(RecordNode {
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    let chain_id = engine.load_chain(RULE_CHAIN).await?;
+
+    // 两个订阅者分别消费 alerts 主题,另一个订阅者订阅其他主题
+    let mut alerts_a = engine.subscribe("alerts").await;
+    let mut alerts_b = engine.subscribe("alerts").await;
+    let mut metrics = engine.subscribe("metrics").await;
+
+    // 后台任务实时消费广播消息
+    let consumer = tokio::spawn(async move {
+        let mut received = Vec::new();
+        while received.len() < 3 {
+            let msg = alerts_a.recv().await.expect("接收广播消息失败");
+            info!("订阅者收到消息: {}", msg.data);
+            received.push(msg.data);
+        }
+        received
+    });
+
+    let expected: Vec<Value> = (1..=3).map(|level| json!({ "level": level })).collect();
+    for data in &expected {
+        let result = engine
+            .process_msg(chain_id, Message::new("alert", data.clone()))
+            .await?;
+        // 发布节点原样转发消息
+        assert_eq!(&result.data, data);
+    }
+
+    // 每个订阅者都收到全部消息,下游节点同样收到消息
+    assert_eq!(consumer.await?, expected);
+    for data in &expected {
+        assert_eq!(&alerts_b.recv().await?.data, data);
+    }
+    assert_eq!(*records.lock().unwrap(), expected);
+
+    // 其他主题收不到消息
+    assert!(matches!(metrics.try_recv(), Err(TryRecvError::Empty)));
+
+    info!("广播发布验证通过");
+    Ok(())
+}
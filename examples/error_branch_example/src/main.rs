@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{info, Level};
+
+/// msg.data.fail 为 true 时执行失败的节点
+#[derive(Debug)]
+struct FlakyNode;
+
+#[async_trait]
+impl NodeHandler for FlakyNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        if msg.data["fail"].as_bool().unwrap_or_default() {
+            return Err(RuleError::NodeExecutionError("下游服务不可用".to_string()));
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/flaky".to_string(),
+            name: "不稳定节点".to_string(),
+            description: "按消息内容决定是否失败".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    engine
+        .register_node_type(
+            "custom/flaky",
+            Arc::new(|_| Ok(Arc::new(FlakyNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+
+    // 起始 -> 不稳定节点 -success-> 成功处理
+    //                    -failure-> 失败处理
+    let mut builder = RuleChainBuilder::new("错误分支示例");
+    let start = builder.add_node("start", json!({}));
+    let flaky = builder.add_node("custom/flaky", json!({ "error_branch": "failure" }));
+    let success = builder.add_node("transform", json!({ "template": { "status": "ok" } }));
+    let failure = builder.add_node("transform", json!({ "template": { "status": "fallback" } }));
+    let chain = builder
+        .connect(start, flaky, "success")
+        .connect(flaky, success, "success")
+        .connect(flaky, failure, "failure")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    // 执行成功时走正常分支
+    let result = engine
+        .process_msg(chain_id, Message::new("test", json!({ "fail": false })))
+        .await?;
+    assert_eq!(result.data, json!({ "status": "ok" }));
+    assert!(result.get_meta_str("error").is_none());
+
+    // 执行失败时路由到错误分支,错误信息写入 metadata.error
+    let result = engine
+        .process_msg(chain_id, Message::new("test", json!({ "fail": true })))
+        .await?;
+    info!(
+        "失败后的结果: {}, 错误: {:?}",
+        result.data,
+        result.get_meta_str("error")
+    );
+    assert_eq!(result.data, json!({ "status": "fallback" }));
+    assert!(result
+        .get_meta_str("error")
+        .unwrap()
+        .contains("下游服务不可用"));
+
+    // 未配置错误分支时错误照常传播
+    let mut builder = RuleChainBuilder::new("无错误分支");
+    let start = builder.add_node("start", json!({}));
+    let flaky = builder.add_node("custom/flaky", json!({}));
+    let failure = builder.add_node("transform", json!({ "template": { "status": "fallback" } }));
+    let chain = builder
+        .connect(start, flaky, "success")
+        .connect(flaky, failure, "failure")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("test", json!({ "fail": true })))
+        .await;
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(_))));
+
+    // 配置了错误分支但没有对应的连接时同样传播错误
+    let mut builder = RuleChainBuilder::new("缺少错误连接");
+    let start = builder.add_node("start", json!({}));
+    let flaky = builder.add_node("custom/flaky", json!({ "error_branch": "failure" }));
+    let chain = builder.connect(start, flaky, "success").root(true).build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("test", json!({ "fail": true })))
+        .await;
+    info!("缺少错误连接: {:?}", result);
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(_))));
+
+    info!("错误分支路由验证通过");
+    Ok(())
+}
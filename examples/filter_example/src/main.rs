@@ -1,5 +1,7 @@
-use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
 use serde_json::json;
+use std::sync::{Arc, Mutex};
 use tracing::{info, Level};
 
 const RULE_CHAIN: &str = r#"{
@@ -85,5 +87,103 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // 检查必需字段: 通过的消息发送到 success 分支,缺少字段或不满足条件的消息发送到 false 分支
+    let routed = Arc::new(Mutex::new(Vec::new()));
+    for branch in ["passed", "rejected"] {
+        let routed = routed.clone();
+        engine
+            .register_fn(
+                &format!("fn/{}", branch),
+                Arc::new(move |msg: Message| {
+                    routed
+                        .lock()
+                        .unwrap()
+                        .push((branch, msg.data["id"].clone()));
+                    Ok(msg)
+                }),
+            )
+            .await;
+    }
+    let mut builder = RuleChainBuilder::new("必需字段过滤示例");
+    let start = builder.add_node("start", json!({}));
+    let filter = builder.add_node(
+        "filter",
+        json!({
+            "condition": "value < 10",
+            "checkFields": ["value", "device.type"],
+            "strict": false
+        }),
+    );
+    let passed = builder.add_node("fn/passed", json!({}));
+    let rejected = builder.add_node("fn/rejected", json!({}));
+    builder
+        .connect(start, filter, "success")
+        .connect(filter, passed, "success")
+        .connect(filter, rejected, "false");
+    let check_chain = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    let messages = [
+        json!({ "id": 1, "value": 5, "device": { "type": "sensor" } }),
+        json!({ "id": 2, "value": 5 }),
+        json!({ "id": 3, "value": 20, "device": { "type": "sensor" } }),
+    ];
+    for data in messages {
+        engine
+            .process_msg(check_chain, Message::new("test", data))
+            .await?;
+    }
+    assert_eq!(
+        *routed.lock().unwrap(),
+        vec![
+            ("passed", json!(1)),
+            ("rejected", json!(2)),
+            ("rejected", json!(3))
+        ]
+    );
+
+    // 严格模式: 缺少字段时返回校验错误
+    let mut builder = RuleChainBuilder::new("严格字段过滤示例");
+    let start = builder.add_node("start", json!({}));
+    let filter = builder.add_node(
+        "filter",
+        json!({ "condition": "true", "check_fields": ["value", "type"], "strict": true }),
+    );
+    let log = builder.add_node("log", json!({ "template": "字段完整: ${msg.data.type}" }));
+    builder
+        .connect(start, filter, "success")
+        .connect(filter, log, "success");
+    let strict_chain = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    let result = engine
+        .process_msg(strict_chain, Message::new("test", json!({ "value": 1 })))
+        .await;
+    assert!(matches!(result, Err(RuleError::ValidationError(e)) if e.contains("type")));
+    engine
+        .process_msg(
+            strict_chain,
+            Message::new("test", json!({ "value": 1, "type": "example" })),
+        )
+        .await?;
+
+    // 没有 false 分支时未通过过滤的消息被拒绝
+    let mut builder = RuleChainBuilder::new("无 false 分支过滤示例");
+    let start = builder.add_node("start", json!({}));
+    let filter = builder.add_node(
+        "filter",
+        json!({ "condition": "value < 10", "checkFields": ["value"] }),
+    );
+    let log = builder.add_node("log", json!({ "template": "通过: ${msg.data.value}" }));
+    builder
+        .connect(start, filter, "success")
+        .connect(filter, log, "success");
+    let reject_chain = engine.load_rule_chain(builder.root(true).build()).await?;
+    for data in [json!({ "value": 20 }), json!({ "other": 1 })] {
+        let result = engine
+            .process_msg(reject_chain, Message::new("test", data))
+            .await;
+        assert!(matches!(result, Err(RuleError::FilterReject)));
+    }
+
+    info!("过滤器示例验证通过");
     Ok(())
 }
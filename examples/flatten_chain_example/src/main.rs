@@ -0,0 +1,101 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 计税子规则链: 起始 -> 脚本
+fn tax_chain() -> rule_rs::types::RuleChain {
+    let mut builder = RuleChainBuilder::new("计税");
+    let start = builder.add_node("start", json!({}));
+    let tax = builder.add_node(
+        "script",
+        json!({ "script": "return { ...msg.data, tax: msg.data.amount / 10 };" }),
+    );
+    builder.connect(start, tax, "success");
+    builder.build()
+}
+
+/// 分级子规则链: 起始 -> 按会员等级选择 vip/normal 分支,分支由父规则链继续路由
+fn level_chain() -> rule_rs::types::RuleChain {
+    let mut builder = RuleChainBuilder::new("分级");
+    let start = builder.add_node("start", json!({}));
+    let switch = builder.add_node(
+        "switch",
+        json!({
+            "cases": [{ "name": "vip", "field": "level", "equals": "vip", "description": "会员" }],
+            "default_next": "normal"
+        }),
+    );
+    builder.connect(start, switch, "success");
+    builder.build()
+}
+
+async fn run(engine: &RuleEngine, chain_id: Uuid, level: &str) -> Value {
+    let msg = Message::new("order", json!({ "level": level, "amount": 100 }));
+    engine.process_msg(chain_id, msg).await.unwrap().data
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    let tax_id = engine.load_rule_chain(tax_chain()).await?;
+    let level_id = engine.load_rule_chain(level_chain()).await?;
+
+    // 订单规则链: 分级 -vip-> 折扣 -> 计税; 分级 -normal-> 计税
+    let mut builder = RuleChainBuilder::new("订单");
+    let start = builder.add_node("start", json!({}));
+    let level = builder.add_node("subchain", json!({ "chain_id": level_id }));
+    let discount = builder.add_node(
+        "script",
+        json!({ "script": "return { ...msg.data, amount: msg.data.amount * 0.8 };" }),
+    );
+    let vip_tax = builder.add_node("subchain", json!({ "chain_id": tax_id }));
+    let normal_tax = builder.add_node("subchain", json!({ "chain_id": tax_id }));
+    builder
+        .connect(start, level, "success")
+        .connect(level, discount, "vip")
+        .connect(level, normal_tax, "normal")
+        .connect(discount, vip_tax, "success");
+    let order_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    let vip = run(&engine, order_id, "vip").await;
+    let normal = run(&engine, order_id, "normal").await;
+    info!("展开前: vip {}, normal {}", vip, normal);
+    assert_eq!(vip["tax"], 8.0);
+    assert_eq!(normal["tax"], 10);
+
+    // 展开后不再包含子规则链节点,同一子规则链的两次引用使用不同的节点ID
+    let flat = engine.flatten_chain(order_id).await?;
+    let types: Vec<_> = flat.nodes.iter().map(|n| n.type_name.as_str()).collect();
+    info!("展开后的节点: {:?}", types);
+    assert!(!types.contains(&"subchain"));
+    assert_eq!(types.iter().filter(|t| **t == "script").count(), 3);
+    let ids: HashSet<_> = flat.nodes.iter().map(|n| n.id).collect();
+    assert_eq!(ids.len(), flat.nodes.len());
+    assert_eq!(flat.id, order_id);
+
+    // 加载展开后的规则链替换原规则链,结果不变
+    engine.load_rule_chain(flat).await?;
+    assert_eq!(run(&engine, order_id, "vip").await, vip);
+    assert_eq!(run(&engine, order_id, "normal").await, normal);
+
+    // 子规则链引用自身时拒绝展开
+    let loop_id = Uuid::new_v4();
+    let mut builder = RuleChainBuilder::new("循环");
+    builder.id(loop_id);
+    let start = builder.add_node("start", json!({}));
+    let inner = builder.add_node("subchain", json!({ "chain_id": loop_id }));
+    builder.connect(start, inner, "success");
+    engine.load_rule_chain(builder.root(true).build()).await?;
+    let result = engine.flatten_chain(loop_id).await;
+    info!("循环引用: {:?}", result.as_ref().err());
+    assert!(matches!(result, Err(RuleError::CircularDependency(_))));
+
+    info!("规则链展开示例验证通过");
+    Ok(())
+}
@@ -49,7 +49,8 @@ async fn main() {
                 "config": {
                     "template": {
                         "value": "${msg.data.value} * 2"
-                    }
+                    },
+                    "evaluate": true
                 },
                 "layout": { "x": 350, "y": 50 }
             },
@@ -60,7 +61,8 @@ async fn main() {
                 "config": {
                     "template": {
                         "value": "${msg.data.value} + 100"
-                    }
+                    },
+                    "evaluate": true
                 },
                 "layout": { "x": 350, "y": 150 }
             },
@@ -118,7 +120,8 @@ async fn main() {
     // 加载规则链
     match engine.load_chain(RULE_CHAIN).await {
         Ok(_) => {
-            // 创建测试消息
+            // 创建测试消息,两个分支分别计算 0 * 2 和 0 + 100,
+            // 日志节点输出 {"branches":[{"data":{"value":0}},{"data":{"value":100}}]}
             let msg = Message::new("test", json!({"value": 0}));
 
             // 执行规则链
@@ -0,0 +1,102 @@
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::post;
+use axum::{Json, Router};
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+const QUERY: &str = "query($id: ID!) { user(id: $id) { id name } }";
+
+/// 模拟 GraphQL 服务: 用户不存在时返回 200 和 errors,id 为负数时返回 500
+async fn graphql(Json(request): Json<Value>) -> impl IntoResponse {
+    assert_eq!(request["query"], QUERY);
+    let id = request["variables"]["id"].as_i64().unwrap_or_default();
+    if id < 0 {
+        return (StatusCode::INTERNAL_SERVER_ERROR, "internal error").into_response();
+    }
+    if id == 1 {
+        return Json(json!({ "data": { "user": { "id": 1, "name": "Alice" } } })).into_response();
+    }
+    Json(json!({
+        "data": { "user": null },
+        "errors": [{ "message": format!("user {} not found", id), "path": ["user"] }]
+    }))
+    .into_response()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 启动本地 GraphQL 服务
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}/graphql", listener.local_addr()?);
+    let app = Router::new().route("/graphql", post(graphql));
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    // 起始 -> GraphQL查询 -> 成功/失败分支分别转换
+    let mut builder = RuleChainBuilder::new("GraphQL示例");
+    let start = builder.add_node("start", json!({}));
+    let query = builder.add_node(
+        "graphql",
+        json!({
+            "endpoint": endpoint,
+            "query": QUERY,
+            "variables_template": { "id": "${msg.data.user_id}" }
+        }),
+    );
+    let found = builder.add_node(
+        "transform",
+        json!({ "template": { "branch": "success", "name": "${msg.user.name}" } }),
+    );
+    let failed = builder.add_node("transform", json!({ "template": { "branch": "error" } }));
+    let chain = builder
+        .connect(start, query, "success")
+        .connect(query, found, "success")
+        .connect(query, failed, "error")
+        .root(true)
+        .build();
+
+    let engine = RuleEngine::new().await;
+    let chain_id = engine.load_rule_chain(chain).await?;
+    let run = |user_id: i64| {
+        let engine = engine.clone();
+        async move {
+            engine
+                .process_msg(
+                    chain_id,
+                    Message::new("query", json!({ "user_id": user_id })),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    // 查询成功,响应的 data 写入消息数据
+    let result = run(1).await;
+    info!("查询成功: {}", result.data);
+    assert_eq!(result.data, json!({ "branch": "success", "name": "Alice" }));
+
+    // HTTP 状态码为 200 但包含 errors 时走失败分支,错误列表写入元数据
+    let result = run(2).await;
+    info!("查询失败: {:?}", result.metadata);
+    assert_eq!(result.data["branch"], "error");
+    assert_eq!(result.metadata["error"], "user 2 not found");
+    assert_eq!(
+        result.metadata["graphql_errors"],
+        json!([{ "message": "user 2 not found", "path": ["user"] }])
+    );
+
+    // HTTP 错误同样走失败分支
+    let result = run(-1).await;
+    info!("请求失败: {:?}", result.metadata);
+    assert_eq!(result.data["branch"], "error");
+    assert!(result.metadata["error"].as_str().unwrap().contains("500"));
+    assert!(!result.metadata.contains_key("graphql_errors"));
+
+    info!("GraphQL查询验证通过");
+    Ok(())
+}
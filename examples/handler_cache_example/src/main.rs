@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use axum::routing::get;
+use axum::{Json, Router};
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::Level;
+
+/// 标签节点的构造次数
+static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TagConfig {
+    label: String,
+}
+
+/// 标签节点,把配置中的标签写入消息
+#[derive(Debug)]
+struct TagNode {
+    config: TagConfig,
+}
+
+#[async_trait]
+impl NodeHandler for TagNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.data["label"] = json!(self.config.label);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/tag".to_string(),
+            name: "标签节点".to_string(),
+            description: "写入配置中的标签".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统,批量处理时节点日志过多,只输出警告
+    tracing_subscriber::fmt().with_max_level(Level::WARN).init();
+
+    let engine = RuleEngine::new().await;
+    engine
+        .register_node_type(
+            "custom/tag",
+            Arc::new(|config| {
+                CREATED.fetch_add(1, Ordering::SeqCst);
+                let config: TagConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(TagNode { config }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    let mut builder = RuleChainBuilder::new("处理器缓存示例");
+    let start = builder.add_node("start", json!({}));
+    let tag = builder.add_node("custom/tag", json!({ "label": "v1" }));
+    let chain = builder.connect(start, tag, "success").root(true).build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    // 处理器在加载时构造,处理消息时不再调用工厂函数
+    let created = CREATED.load(Ordering::SeqCst);
+    for i in 0..1000 {
+        let result = engine
+            .process_msg(chain_id, Message::new("test", json!({ "i": i })))
+            .await?;
+        assert_eq!(result.data["label"], "v1");
+    }
+    let after = CREATED.load(Ordering::SeqCst);
+    println!(
+        "加载后构造 {} 次, 处理1000条消息后构造 {} 次",
+        created, after
+    );
+    assert_eq!(after, created);
+
+    // 更新规则链后使用按新配置构造的处理器
+    let mut chain = (*engine.get_chain(chain_id).await.unwrap()).clone();
+    chain.nodes.iter_mut().find(|n| n.id == tag).unwrap().config = json!({ "label": "v2" });
+    engine
+        .update_chain_value(chain_id, serde_json::to_value(chain)?)
+        .await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("test", json!({})))
+        .await?;
+    assert_eq!(result.data["label"], "v2");
+
+    // 删除规则链后缓存一并清理
+    engine.remove_chain(chain_id).await?;
+    assert!(engine
+        .process_msg(chain_id, Message::new("test", json!({})))
+        .await
+        .is_err());
+
+    // HTTP请求节点复用加载时创建的客户端和连接池
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}/ping", listener.local_addr()?);
+    let app = Router::new().route("/ping", get(|| async { Json(json!({ "pong": true })) }));
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let mut builder = RuleChainBuilder::new("HTTP请求吞吐");
+    let start = builder.add_node("start", json!({}));
+    let rest = builder.add_node("rest_client", json!({ "url": url, "method": "GET" }));
+    let chain = builder.connect(start, rest, "success").root(true).build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    let started = Instant::now();
+    for _ in 0..500 {
+        let result = engine
+            .process_msg(chain_id, Message::new("request", json!({})))
+            .await?;
+        assert_eq!(result.data["body"], json!({ "pong": true }));
+    }
+    println!("500次HTTP请求耗时 {:?}", started.elapsed());
+
+    println!("处理器缓存验证通过");
+    Ok(())
+}
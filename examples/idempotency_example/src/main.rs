@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration};
+use tracing::{info, Level};
+
+/// 生成规则链: 起始 -> 处理
+fn process_chain(index: u8, idempotent: bool) -> String {
+    let chain_id = format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}01", 90 + index);
+    let node_id = |n: u8| format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}{:02}", 90 + index, n);
+
+    json!({
+        "id": chain_id,
+        "name": format!("幂等示例 {}", index),
+        "root": true,
+        "idempotent": idempotent,
+        "nodes": [
+            {
+                "id": node_id(0),
+                "type_name": "start",
+                "chain_id": chain_id,
+                "config": {},
+                "layout": { "x": 50, "y": 100 }
+            },
+            {
+                "id": node_id(2),
+                "type_name": "custom/process",
+                "chain_id": chain_id,
+                "config": {},
+                "layout": { "x": 200, "y": 100 }
+            }
+        ],
+        "connections": [
+            {
+                "from_id": node_id(0),
+                "to_id": node_id(2),
+                "type_name": "success"
+            }
+        ],
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+    .to_string()
+}
+
+/// 处理次数
+type Counter = Arc<Mutex<u64>>;
+
+/// 处理节点,模拟耗时的副作用操作并记录处理次数
+#[derive(Debug)]
+struct ProcessNode {
+    counter: Counter,
+}
+
+#[async_trait]
+impl NodeHandler for ProcessNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        sleep(Duration::from_millis(100)).await;
+        let count = {
+            let mut counter = self.counter.lock().unwrap();
+            *counter += 1;
+            *counter
+        };
+        info!("处理消息 {}, 第 {} 次", msg.id, count);
+
+        let mut msg = msg;
+        msg.data["count"] = json!(count);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/process".to_string(),
+            name: "处理节点".to_string(),
+            description: "记录消息的处理次数".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+// 暂停 tokio 时间,等待会被自动快进,无需真实等待
+#[tokio::main(flavor = "current_thread", start_paused = true)]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并启用幂等检查,已处理的消息ID保留60秒
+    let engine = RuleEngine::new()
+        .await
+        .with_idempotency(Duration::from_secs(60));
+
+    // 注册处理节点
+    let counter: Counter = Arc::new(Mutex::new(0));
+    let node_counter = counter.clone();
+    engine
+        .register_node_type(
+            "custom/process",
+            Arc::new(move |_| {
+                Ok(Arc::new(ProcessNode {
+                    counter: node_counter.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    let idempotent = engine.load_chain(&process_chain(1, true)).await?;
+    let non_idempotent = engine.load_chain(&process_chain(2, false)).await?;
+
+    let msg = Message::new("order", json!({ "order_id": 1 }));
+    let count = |counter: &Counter| *counter.lock().unwrap();
+
+    // 重复处理同一消息: 只执行一次,返回首次处理的结果
+    let first = engine.process_msg(idempotent, msg.clone()).await?;
+    let second = engine.process_msg(idempotent, msg.clone()).await?;
+    assert_eq!(count(&counter), 1);
+    assert_eq!(first.data, second.data);
+
+    // 并发处理同一消息: 首次处理未完成时返回重复消息错误
+    let concurrent = Message::new("order", json!({ "order_id": 2 }));
+    let (a, b) = tokio::join!(
+        engine.process_msg(idempotent, concurrent.clone()),
+        engine.process_msg(idempotent, concurrent.clone())
+    );
+    assert!(a.is_ok());
+    match b {
+        Err(RuleError::DuplicateMessage(id)) => {
+            assert_eq!(id, concurrent.id);
+            info!("重复消息: {}", id);
+        }
+        other => panic!("期望重复消息错误, 实际: {:?}", other),
+    }
+    assert_eq!(count(&counter), 2);
+
+    // 超过保留时长后重新处理
+    sleep(Duration::from_secs(61)).await;
+    engine.process_msg(idempotent, msg.clone()).await?;
+    assert_eq!(count(&counter), 3);
+
+    // 关闭幂等检查的规则链每次都会处理
+    engine.process_msg(non_idempotent, msg.clone()).await?;
+    engine.process_msg(non_idempotent, msg.clone()).await?;
+    assert_eq!(count(&counter), 5);
+
+    info!("幂等处理验证通过");
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{info, Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+/// 记录 JS console 产生的事件: (级别, 字段)
+type Captured = Arc<Mutex<Vec<(Level, HashMap<String, String>)>>>;
+
+#[derive(Default)]
+struct FieldVisitor(HashMap<String, String>);
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// 收集带有 node.id 字段的事件
+struct CaptureLayer(Captured);
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        if visitor.0.contains_key("node.id") {
+            self.0
+                .lock()
+                .unwrap()
+                .push((*event.metadata().level(), visitor.0));
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统,同时收集 JS 日志
+    let captured = Captured::default();
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(CaptureLayer(captured.clone()))
+        .init();
+
+    let engine = RuleEngine::new().await;
+
+    // 起始 -> 脚本 -> JS转换 -> JS函数,每个节点都输出日志
+    let mut builder = RuleChainBuilder::new("JS日志示例");
+    let start = builder.add_node("start", json!({}));
+    let script = builder.add_node(
+        "script",
+        json!({ "script": "console.log('收到订单', msg.data.order); return msg.data;" }),
+    );
+    let transform = builder.add_node(
+        "transform_js",
+        json!({ "script": "console.warn('金额过大', { amount: msg.amount }); return msg;" }),
+    );
+    let function = builder.add_node(
+        "js_function",
+        json!({
+            "functions": { "main": "console.error('库存不足'); return msg.data;" },
+            "main": "main"
+        }),
+    );
+    let chain = builder
+        .connect(start, script, "success")
+        .connect(script, transform, "success")
+        .connect(transform, function, "success")
+        .root(true)
+        .build();
+    let node_ids: Vec<_> = chain.nodes.iter().map(|node| node.id.to_string()).collect();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    let msg = Message::new("order", json!({ "order": 42, "amount": 1000 }));
+    let msg_id = msg.id.to_string();
+    let result = engine.process_msg(chain_id, msg).await?;
+    assert_eq!(result.data, json!({ "order": 42, "amount": 1000 }));
+
+    // JS 日志转为对应级别的 tracing 事件,带有节点和消息信息
+    let events = captured.lock().unwrap().clone();
+    info!("收集到的 JS 日志: {:?}", events);
+    let expected = [
+        (Level::INFO, 1, "script", "收到订单 42"),
+        (Level::WARN, 2, "transform_js", "金额过大 {\"amount\":1000}"),
+        (Level::ERROR, 3, "js_function", "库存不足"),
+    ];
+    assert_eq!(events.len(), expected.len());
+    for ((level, fields), (expected_level, index, type_name, text)) in events.iter().zip(expected) {
+        assert_eq!(*level, expected_level);
+        assert_eq!(fields["node.id"], node_ids[index]);
+        assert_eq!(fields["node.type_name"], type_name);
+        assert_eq!(fields["msg.id"], msg_id);
+        assert_eq!(fields["message"], text);
+    }
+
+    info!("JS日志验证通过");
+    Ok(())
+}
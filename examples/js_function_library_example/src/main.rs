@@ -0,0 +1,79 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+/// 起始 -> JS函数 规则链
+async fn load(engine: &RuleEngine, config: Value) -> Result<uuid::Uuid, RuleError> {
+    let mut builder = RuleChainBuilder::new("JS函数库示例");
+    let start = builder.add_node("start", json!({}));
+    let js = builder.add_node("js_function", config);
+    let chain = builder.connect(start, js, "success").root(true).build();
+    engine.load_rule_chain(chain).await
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 函数库只执行一次,计数器在消息间保留;字符串中出现的函数名保持原样
+    let chain_id = load(
+        &engine,
+        json!({
+            "library": "let count = 0; function round2(x) { return Math.round(x * 100) / 100; }",
+            "functions": {
+                "main": "count += 1; return { seq: count, total: round2(total(msg)), note: note() };",
+                "total": "return msg.data.price * msg.data.qty;",
+                "note": "return 'total(msg) uses round2(x)'; // total(msg)"
+            },
+            "main": "main"
+        }),
+    )
+    .await?;
+
+    for seq in 1..=3 {
+        let result = engine
+            .process_msg(
+                chain_id,
+                Message::new("order", json!({ "price": 1.115, "qty": 3 })),
+            )
+            .await?;
+        info!("处理结果: {}", result.data);
+        assert_eq!(
+            result.data,
+            json!({ "seq": seq, "total": 3.35, "note": "total(msg) uses round2(x)" })
+        );
+    }
+
+    // 函数抛出的异常作为执行错误返回
+    let chain_id = load(
+        &engine,
+        json!({
+            "functions": { "main": "throw new Error('价格缺失');" },
+            "main": "main"
+        }),
+    )
+    .await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({})))
+        .await;
+    info!("抛出异常: {:?}", result);
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(e)) if e.contains("价格缺失")));
+
+    // 主函数未定义、函数名非法或函数库语法错误时加载失败
+    for config in [
+        json!({ "functions": { "helper": "return 1;" }, "main": "main" }),
+        json!({ "functions": { "main": "return 1;", "bad name": "return 2;" }, "main": "main" }),
+        json!({ "library": "let = ;", "functions": { "main": "return 1;" }, "main": "main" }),
+    ] {
+        let result = load(&engine, config).await;
+        info!("无效配置: {:?}", result);
+        assert!(matches!(result, Err(RuleError::ConfigError(_))));
+    }
+
+    info!("JS函数库验证通过");
+    Ok(())
+}
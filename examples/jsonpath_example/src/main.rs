@@ -0,0 +1,109 @@
+use rule_rs::testing::{assert_not_received, register_mock};
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 起始 -> JSONPath,可选连接 no_match 分支
+async fn load(engine: &RuleEngine, config: Value, no_match: bool) -> Result<Uuid, RuleError> {
+    let mut builder = RuleChainBuilder::new("JSONPath示例");
+    let start = builder.add_node("start", json!({}));
+    let extract = builder.add_node("jsonpath", config);
+    let matched = builder.add_node("mock/matched", json!({}));
+    builder
+        .connect(start, extract, "success")
+        .connect(extract, matched, "success");
+    if no_match {
+        let missing = builder.add_node("mock/missing", json!({}));
+        builder.connect(extract, missing, "no_match");
+    }
+    engine.load_rule_chain(builder.root(true).build()).await
+}
+
+fn order() -> Message {
+    Message::new(
+        "order",
+        json!({
+            "customer": { "name": "张三" },
+            "items": [
+                { "sku": "A1", "qty": 2 },
+                { "sku": "B2", "qty": 1 }
+            ]
+        }),
+    )
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    let matched = register_mock(&engine, "mock/matched").await;
+    let missing = register_mock(&engine, "mock/missing").await;
+
+    // 单个匹配值提升到根
+    let chain_id = load(
+        &engine,
+        json!({ "expr": "$.customer.name", "first": true }),
+        true,
+    )
+    .await?;
+    let result = engine.process_msg(chain_id, order()).await?;
+    assert_eq!(result.data, json!("张三"));
+
+    // 多个匹配值写入 output_field,保留原有数据
+    let chain_id = load(
+        &engine,
+        json!({ "expr": "$.items[*].sku", "output_field": "skus" }),
+        true,
+    )
+    .await?;
+    let result = engine.process_msg(chain_id, order()).await?;
+    info!("多个匹配值: {}", result.data);
+    assert_eq!(result.data["skus"], json!(["A1", "B2"]));
+    assert_eq!(result.data["customer"]["name"], "张三");
+
+    // 过滤表达式
+    let chain_id = load(&engine, json!({ "expr": "$.items[?(@.qty > 1)]" }), true).await?;
+    let result = engine.process_msg(chain_id, order()).await?;
+    assert_eq!(result.data, json!([{ "sku": "A1", "qty": 2 }]));
+
+    // JSON Pointer
+    let chain_id = load(
+        &engine,
+        json!({ "expr": "/items/1/qty", "output_field": "second_qty", "first": true }),
+        true,
+    )
+    .await?;
+    let result = engine.process_msg(chain_id, order()).await?;
+    assert_eq!(result.data["second_qty"], 1);
+
+    // 没有匹配值时走 no_match 分支
+    matched.clear();
+    let chain_id = load(&engine, json!({ "expr": "$.coupon.code" }), true).await?;
+    engine.process_msg(chain_id, order()).await?;
+    assert_not_received(&matched);
+    assert_eq!(missing.count(), 1);
+    assert_eq!(missing.received()[0].data, json!([]));
+
+    // 未配置 no_match 连接时继续走 success
+    let chain_id = load(
+        &engine,
+        json!({ "expr": "$.coupon.code", "output_field": "coupon", "first": true }),
+        false,
+    )
+    .await?;
+    let result = engine.process_msg(chain_id, order()).await?;
+    assert_eq!(result.data["coupon"], Value::Null);
+    assert_eq!(matched.count(), 1);
+
+    // 无效的表达式在加载时报错
+    let result = load(&engine, json!({ "expr": "$.items[" }), false).await;
+    info!("无效表达式: {:?}", result);
+    assert!(matches!(result, Err(RuleError::ConfigError(_))));
+
+    info!("JSONPath节点验证通过");
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{info, Event, Level, Subscriber};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+/// 记录日志节点的输出: (级别, 目标, 内容)
+type Captured = Arc<Mutex<Vec<(Level, String, String)>>>;
+
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+/// 收集日志节点输出的日志,配置了目标的日志经 `tracing_log` 转换,需要还原其目标
+struct CaptureLayer(Captured);
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let Some(text) = visitor.0.strip_prefix("log组件输出: ") else {
+            return;
+        };
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+        self.0.lock().unwrap().push((
+            *metadata.level(),
+            metadata.target().to_string(),
+            text.to_string(),
+        ));
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统,同时收集日志节点的输出
+    let captured = Captured::default();
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(CaptureLayer(captured.clone()))
+        .init();
+
+    let engine = RuleEngine::new().await;
+
+    // 起始 -> 分叉 -> 追踪日志(debug)
+    //             -> 告警日志(warn, 目标 alerts)
+    //             -> 默认日志(info)
+    let mut builder = RuleChainBuilder::new("日志级别示例");
+    let start = builder.add_node("start", json!({}));
+    let fork = builder.add_node("fork", json!({}));
+    let trace_log = builder.add_node(
+        "log",
+        json!({ "template": "追踪 ${msg.data.order}", "level": "debug" }),
+    );
+    let alert_log = builder.add_node(
+        "log",
+        json!({
+            "template": "订单 ${msg.data.order} 金额过大",
+            "level": "warn",
+            "target": "alerts",
+        }),
+    );
+    let default_log = builder.add_node("log", json!({ "template": "处理完成" }));
+    builder
+        .connect(start, fork, "success")
+        .connect(fork, trace_log, "success")
+        .connect(fork, alert_log, "success")
+        .connect(fork, default_log, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    engine
+        .process_msg(chain_id, Message::new("order", json!({ "order": 7 })))
+        .await?;
+
+    // 分支并行执行,按级别排序后比较
+    let mut captured = captured.lock().unwrap().clone();
+    captured.sort_by_key(|(level, _, _)| *level);
+    for (level, target, text) in &captured {
+        info!("{} [{}] {}", level, target, text);
+    }
+    assert_eq!(
+        captured,
+        vec![
+            (
+                Level::WARN,
+                "alerts".to_string(),
+                "订单 7 金额过大".to_string()
+            ),
+            (
+                Level::INFO,
+                "rule_rs::components::log".to_string(),
+                "处理完成".to_string()
+            ),
+            (
+                Level::DEBUG,
+                "rule_rs::components::log".to_string(),
+                "追踪 7".to_string()
+            ),
+        ]
+    );
+
+    info!("日志级别示例验证通过");
+    Ok(())
+}
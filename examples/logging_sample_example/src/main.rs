@@ -0,0 +1,137 @@
+use rule_rs::aop::LoggingInterceptor;
+use rule_rs::testing::register_mock;
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{info, Event, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+use uuid::Uuid;
+
+/// 记录节点开始执行的日志: (消息ID, 节点类型)
+type Captured = Arc<Mutex<Vec<(Uuid, String)>>>;
+
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+/// 收集日志拦截器输出的 "开始执行节点" 日志
+struct CaptureLayer(Captured);
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let text = visitor.0;
+        if !text.starts_with("开始执行节点") {
+            return;
+        }
+        // 格式: 开始执行节点 [节点ID], 类型: 节点类型, 输入消息: Message { id: 消息ID, ...
+        let type_name = text
+            .split("类型: ")
+            .nth(1)
+            .and_then(|rest| rest.split(',').next())
+            .unwrap_or_default()
+            .to_string();
+        let msg_id = text
+            .split("Message { id: ")
+            .nth(1)
+            .and_then(|rest| rest.split(',').next())
+            .and_then(|id| Uuid::parse_str(id).ok());
+        if let Some(msg_id) = msg_id {
+            self.0.lock().unwrap().push((msg_id, type_name));
+        }
+    }
+}
+
+/// 起始 -> mock/a -> mock/b
+async fn load_chain(engine: &RuleEngine) -> Result<Uuid, Box<dyn std::error::Error>> {
+    register_mock(engine, "mock/a").await;
+    register_mock(engine, "mock/b").await;
+
+    let mut builder = RuleChainBuilder::new("日志采样示例");
+    let start = builder.add_node("start", json!({}));
+    let a = builder.add_node("mock/a", json!({}));
+    let b = builder.add_node("mock/b", json!({}));
+    builder
+        .connect(start, a, "success")
+        .connect(a, b, "success");
+    Ok(engine.load_rule_chain(builder.root(true).build()).await?)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统,同时收集节点日志
+    let captured = Captured::default();
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(CaptureLayer(captured.clone()))
+        .init();
+
+    // 每 4 条消息记录 1 条,并且不记录起始节点
+    let interceptor = LoggingInterceptor::builder()
+        .sample_rate(4)
+        .deny_node_types(["start"])
+        .build();
+    let engine = RuleEngine::new()
+        .await
+        .with_logging_interceptor(interceptor)
+        .await;
+    let chain_id = load_chain(&engine).await?;
+
+    let total = 400;
+    for seq in 0..total {
+        engine
+            .process_msg(chain_id, Message::new("event", json!({ "seq": seq })))
+            .await?;
+    }
+
+    let events = std::mem::take(&mut *captured.lock().unwrap());
+    let mut by_msg: HashMap<Uuid, HashSet<String>> = HashMap::new();
+    for (msg_id, type_name) in events {
+        by_msg.entry(msg_id).or_default().insert(type_name);
+    }
+    info!("{} 条消息中有 {} 条被记录", total, by_msg.len());
+    // 采样约为 1/4
+    assert!((60..=140).contains(&by_msg.len()));
+    // 同一条消息要么全部节点都记录,要么都不记录;起始节点被排除
+    for types in by_msg.values() {
+        assert_eq!(
+            types,
+            &HashSet::from(["mock/a".to_string(), "mock/b".to_string()])
+        );
+    }
+
+    // 只记录 mock/b 节点
+    let engine = RuleEngine::new()
+        .await
+        .with_logging_interceptor(
+            LoggingInterceptor::builder()
+                .allow_node_types(["mock/b"])
+                .build(),
+        )
+        .await;
+    let chain_id = load_chain(&engine).await?;
+    for seq in 0..10 {
+        engine
+            .process_msg(chain_id, Message::new("event", json!({ "seq": seq })))
+            .await?;
+    }
+    let events = std::mem::take(&mut *captured.lock().unwrap());
+    assert_eq!(events.len(), 10);
+    assert!(events.iter().all(|(_, type_name)| type_name == "mock/b"));
+
+    info!("日志采样验证通过");
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::Level;
+
+/// 规则链中加一节点的数量
+const NODE_COUNT: usize = 5000;
+
+/// 数值加一节点
+#[derive(Debug)]
+struct IncrementNode;
+
+#[async_trait]
+impl NodeHandler for IncrementNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.data["value"] = json!(msg.data["value"].as_u64().unwrap_or_default() + 1);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/increment".to_string(),
+            name: "加一节点".to_string(),
+            description: "数值加一".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统,节点日志过多,只输出警告
+    tracing_subscriber::fmt().with_max_level(Level::WARN).init();
+
+    let engine = RuleEngine::new().await;
+    engine
+        .register_node_type(
+            "custom/increment",
+            Arc::new(|_| Ok(Arc::new(IncrementNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+
+    // 起始 -> 加一 x NODE_COUNT 的线性规则链
+    let mut builder = RuleChainBuilder::new("长规则链示例");
+    let mut prev = builder.add_node("start", json!({}));
+    for _ in 0..NODE_COUNT {
+        let next = builder.add_node("custom/increment", json!({}));
+        builder.connect(prev, next, "success");
+        prev = next;
+    }
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    // 在默认栈大小的工作线程中执行,线性链路不会逐层加深调用栈
+    let started = Instant::now();
+    let result = tokio::spawn(async move {
+        engine
+            .process_msg(chain_id, Message::new("count", json!({ "value": 0 })))
+            .await
+    })
+    .await??;
+    println!(
+        "{} 个节点执行完成, 耗时 {:?}",
+        NODE_COUNT,
+        started.elapsed()
+    );
+    assert_eq!(result.data, json!({ "value": NODE_COUNT }));
+
+    println!("长规则链验证通过");
+    Ok(())
+}
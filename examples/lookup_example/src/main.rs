@@ -0,0 +1,134 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 起始 -> 查找 -> 日志,可选把 not_found 分支接到标记未知设备的转换节点
+async fn load(engine: &RuleEngine, config: Value, not_found: bool) -> Result<Uuid, RuleError> {
+    let mut builder = RuleChainBuilder::new("查找示例");
+    let start = builder.add_node("start", json!({}));
+    let lookup = builder.add_node("lookup", config);
+    let log = builder.add_node("log", json!({ "template": "设备 ${msg.data.device_id}" }));
+    builder.connect(start, lookup, "success");
+    builder.connect(lookup, log, "success");
+    if not_found {
+        let unknown = builder.add_node(
+            "transform",
+            json!({ "template": { "device_id": "${msg.data.device_id}", "status": "unknown" } }),
+        );
+        builder.connect(lookup, unknown, "not_found");
+    }
+    engine.load_rule_chain(builder.root(true).build()).await
+}
+
+async fn reading(engine: &RuleEngine, chain_id: Uuid, device_id: &str) -> Value {
+    let msg = Message::new("reading", json!({ "device_id": device_id, "temp": 21.5 }));
+    engine.process_msg(chain_id, msg).await.unwrap().data
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 内联表: 记录字段合并到 msg.data 顶层
+    let inline = json!({
+        "source": "inline",
+        "key_path": "device_id",
+        "table": {
+            "d-1": { "site": "上海", "model": "T100" },
+            "d-2": { "site": "北京", "model": "T200" }
+        }
+    });
+    let chain_id = load(&engine, inline.clone(), true).await?;
+    let data = reading(&engine, chain_id, "d-1").await;
+    info!("补充后: {}", data);
+    assert_eq!(
+        data,
+        json!({ "device_id": "d-1", "temp": 21.5, "site": "上海", "model": "T100" })
+    );
+
+    // 未找到记录时走 not_found 分支
+    let data = reading(&engine, chain_id, "d-9").await;
+    info!("未知设备: {}", data);
+    assert_eq!(data["status"], "unknown");
+
+    // 没有 not_found 连接时消息原样继续
+    let chain_id = load(&engine, inline, false).await?;
+    let data = reading(&engine, chain_id, "d-9").await;
+    assert_eq!(data, json!({ "device_id": "d-9", "temp": 21.5 }));
+
+    // 文件表: 写入 target 字段,缓存过期后重新加载
+    let path = std::env::temp_dir().join(format!("devices-{}.json", Uuid::new_v4()));
+    std::fs::write(&path, json!({ "d-1": { "site": "上海" } }).to_string())?;
+    let chain_id = load(
+        &engine,
+        json!({
+            "source": "file",
+            "key_path": "device_id",
+            "table": path.to_str().unwrap(),
+            "cache_ttl_ms": 200,
+            "target": "device"
+        }),
+        true,
+    )
+    .await?;
+    let data = reading(&engine, chain_id, "d-1").await;
+    assert_eq!(data["device"], json!({ "site": "上海" }));
+
+    // 缓存期内文件的修改不可见
+    std::fs::write(&path, json!({ "d-1": { "site": "深圳" } }).to_string())?;
+    let data = reading(&engine, chain_id, "d-1").await;
+    assert_eq!(data["device"]["site"], "上海");
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let data = reading(&engine, chain_id, "d-1").await;
+    info!("重新加载后: {}", data);
+    assert_eq!(data["device"]["site"], "深圳");
+
+    // 文件不存在时返回组件错误
+    std::fs::remove_file(&path)?;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let result = engine
+        .process_msg(
+            chain_id,
+            Message::new("reading", json!({ "device_id": "d-1" })),
+        )
+        .await;
+    assert!(matches!(result, Err(RuleError::ComponentError(_))));
+
+    // 以下步骤需要运行中的 Redis 服务,例如 docker run -p 6379:6379 redis
+    let Ok(url) = std::env::var("REDIS_URL") else {
+        info!("未设置 REDIS_URL,跳过 Redis 查找表示例");
+        return Ok(());
+    };
+    let client = redis::Client::open(url.as_str())?;
+    let mut conn = client.get_multiplexed_async_connection().await?;
+    redis::cmd("HSET")
+        .arg("lookup_example:devices")
+        .arg("d-1")
+        .arg(json!({ "site": "广州" }).to_string())
+        .exec_async(&mut conn)
+        .await?;
+    let chain_id = load(
+        &engine,
+        json!({
+            "source": "redis",
+            "url": url,
+            "key_path": "device_id",
+            "table": "lookup_example:devices"
+        }),
+        true,
+    )
+    .await?;
+    let data = reading(&engine, chain_id, "d-1").await;
+    info!("Redis 查找结果: {}", data);
+    assert_eq!(data["site"], "广州");
+
+    info!("查找节点示例验证通过");
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+/// 起始 -> 字段映射,返回映射后的 msg.data
+async fn map(engine: &RuleEngine, config: Value, data: Value) -> Result<Value, RuleError> {
+    let mut builder = RuleChainBuilder::new("字段映射示例");
+    let start = builder.add_node("start", json!({}));
+    let mapper = builder.add_node("mapper", config);
+    builder.connect(start, mapper, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("user", data))
+        .await?;
+    Ok(result.data)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 依次执行 rename -> remove -> copy -> set,支持嵌套路径
+    let config = json!({
+        "rename": { "uid": "user.id", "profile.nick": "user.name" },
+        "remove": ["debug", "profile"],
+        "copy": { "user.id": "key" },
+        "set": { "source": "api", "user.tags": ["new"] }
+    });
+    let data = json!({
+        "uid": 42,
+        "profile": { "nick": "小明", "avatar": "a.png" },
+        "debug": true
+    });
+    let result = map(&engine, config, data).await?;
+    info!("映射结果: {}", result);
+    assert_eq!(
+        result,
+        json!({
+            "user": { "id": 42, "name": "小明", "tags": ["new"] },
+            "key": 42,
+            "source": "api"
+        })
+    );
+
+    // 删除不存在的字段、重命名不存在的字段都不做任何修改
+    let config = json!({
+        "rename": { "missing": "other" },
+        "remove": ["a.b.c", "nothing", "list.5"]
+    });
+    let data = json!({ "a": { "b": 1 }, "list": [1, 2] });
+    assert_eq!(map(&engine, config, data.clone()).await?, data);
+
+    // 先读取所有源字段再写入,两个字段可以互换
+    let config = json!({ "rename": { "a": "b", "b": "a" } });
+    let result = map(&engine, config, json!({ "a": 1, "b": 2 })).await?;
+    assert_eq!(result, json!({ "a": 2, "b": 1 }));
+
+    // 重命名的目标字段已存在时被覆盖
+    let config = json!({ "rename": { "new_name": "name" } });
+    let result = map(&engine, config, json!({ "name": "旧", "new_name": "新" })).await?;
+    assert_eq!(result, json!({ "name": "新" }));
+
+    // 两个字段重命名为同一个目标在加载时报错
+    let config = json!({ "rename": { "a": "c", "b": "c" } });
+    let result = map(&engine, config, json!({})).await;
+    info!("重命名冲突: {:?}", result);
+    assert!(matches!(result, Err(RuleError::ConfigError(_))));
+
+    // 目标路径经过非对象字段时执行失败
+    let config = json!({ "set": { "name.first": "张" } });
+    let result = map(&engine, config, json!({ "name": "张三" })).await;
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(_))));
+
+    info!("字段映射验证通过");
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::Level;
+use uuid::Uuid;
+
+/// 反弹节点,在运行时把消息再次发送给自己,规则链定义中没有环
+#[derive(Debug)]
+struct BounceNode;
+
+#[async_trait]
+impl NodeHandler for BounceNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        ctx.send_to_node(&ctx.node.id, msg).await
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/bounce".to_string(),
+            name: "反弹节点".to_string(),
+            description: "把消息发送给自己".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+/// 加载 起始 -> 反弹 规则链,返回规则链ID和反弹节点ID
+async fn load_bounce_chain(engine: &RuleEngine) -> (Uuid, Uuid) {
+    engine
+        .register_node_type(
+            "custom/bounce",
+            Arc::new(|_| Ok(Arc::new(BounceNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+
+    let mut builder = RuleChainBuilder::new("运行时循环示例");
+    let start = builder.add_node("start", json!({}));
+    let bounce = builder.add_node("custom/bounce", json!({}));
+    let chain = builder.connect(start, bounce, "success").root(true).build();
+    (engine.load_rule_chain(chain).await.unwrap(), bounce)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统,循环中的节点日志过多,只输出警告
+    tracing_subscriber::fmt().with_max_level(Level::WARN).init();
+
+    // 通过静态校验的规则链在运行时无限循环,超过执行上限后返回错误
+    let engine = RuleEngine::new().await.with_max_hops(100);
+    let (chain_id, bounce) = load_bounce_chain(&engine).await;
+    match engine
+        .process_msg(chain_id, Message::new("ping", json!({})))
+        .await
+    {
+        Err(RuleError::MaxHopsExceeded { max, recent }) => {
+            println!("执行超限: 上限 {}, 最近节点 {:?}", max, recent);
+            assert_eq!(max, 100);
+            assert_eq!(recent.len(), 5);
+            assert!(recent.iter().all(|id| *id == bounce));
+        }
+        other => panic!("期望超出执行上限, 实际: {:?}", other),
+    }
+
+    // 正常规则链不受影响
+    let mut builder = RuleChainBuilder::new("正常规则链");
+    let start = builder.add_node("start", json!({}));
+    let transform = builder.add_node("transform", json!({ "template": { "ok": true } }));
+    let chain = builder
+        .connect(start, transform, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("ping", json!({})))
+        .await?;
+    assert_eq!(result.data, json!({ "ok": true }));
+
+    // 默认上限同样生效
+    let engine = RuleEngine::new().await;
+    let (chain_id, _) = load_bounce_chain(&engine).await;
+    let result = engine
+        .process_msg(chain_id, Message::new("ping", json!({})))
+        .await;
+    assert!(matches!(
+        result,
+        Err(RuleError::MaxHopsExceeded { max: 10_000, .. })
+    ));
+
+    println!("执行上限验证通过");
+    Ok(())
+}
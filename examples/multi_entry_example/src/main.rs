@@ -0,0 +1,82 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, Level};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 统计处理流程收到的消息数
+    let processed = Arc::new(AtomicUsize::new(0));
+    let counter = processed.clone();
+    engine
+        .register_fn(
+            "fn/count",
+            Arc::new(move |mut msg: Message| {
+                let count = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                msg.data["count"] = json!(count);
+                Ok(msg)
+            }),
+        )
+        .await;
+
+    // 两个头节点汇入同一处理流程:
+    // 起始 ----------------> 计数 -> 日志
+    // 定时(每秒, 共两次) --/
+    let mut builder = RuleChainBuilder::new("多入口示例");
+    let start = builder.add_node("start", json!({}));
+    let schedule = builder.add_node(
+        "schedule",
+        json!({ "cron": "*/1 * * * * *", "max_runs": 2 }),
+    );
+    let count = builder.add_node("fn/count", json!({}));
+    let log = builder.add_node("log", json!({ "template": "来自 ${msg.data.source}" }));
+    let chain = builder
+        .connect(start, count, "success")
+        .connect(schedule, count, "success")
+        .connect(count, log, "success")
+        .root(true)
+        .build();
+
+    // 两个入口出发都能到达所有节点
+    let analysis = engine.analyze_chain(&chain);
+    assert!(analysis.unreachable_nodes.is_empty());
+    let chain_id = engine.load_rule_chain(chain).await?;
+    let loaded = engine.get_chain(chain_id).await.unwrap();
+    let entries: Vec<_> = loaded.entry_nodes().iter().map(|node| node.id).collect();
+    assert_eq!(entries, vec![start, schedule]);
+
+    // process_msg 从第一个节点开始
+    let result = engine
+        .process_msg(chain_id, Message::new("event", json!({ "source": "api" })))
+        .await?;
+    assert_eq!(result.data["count"], 1);
+
+    // 从定时节点开始: 定时节点从自身出发向下游发送每次触发的消息
+    engine
+        .process_msg_from(
+            chain_id,
+            schedule,
+            Message::new("tick", json!({ "source": "schedule" })),
+        )
+        .await?;
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    info!("处理的消息数: {}", processed.load(Ordering::SeqCst));
+    assert_eq!(processed.load(Ordering::SeqCst), 3);
+
+    // 有入向连接的节点不是入口
+    let result = engine
+        .process_msg_from(chain_id, count, Message::new("event", json!({})))
+        .await;
+    assert!(matches!(result, Err(RuleError::ConfigError(_))));
+
+    info!("多入口规则链示例验证通过");
+    Ok(())
+}
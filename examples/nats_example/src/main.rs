@@ -0,0 +1,91 @@
+use futures::StreamExt;
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 起始 -> NATS,可选连接 error 分支到日志节点
+async fn load(engine: &RuleEngine, config: Value, error_branch: bool) -> Result<Uuid, RuleError> {
+    let mut builder = RuleChainBuilder::new("NATS示例");
+    let start = builder.add_node("start", json!({}));
+    let nats = builder.add_node("nats", config);
+    builder.connect(start, nats, "success");
+    if error_branch {
+        let log = builder.add_node(
+            "log",
+            json!({ "template": "NATS失败: ${msg.metadata.error}" }),
+        );
+        builder.connect(nats, log, "error");
+    }
+    engine.load_rule_chain(builder.root(true).build()).await
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 连接失败时走 error 分支,由日志节点输出 metadata.error
+    let unreachable = json!({ "url": "nats://127.0.0.1:1", "subject": "orders" });
+    let chain_id = load(&engine, unreachable.clone(), true).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({ "id": 1 })))
+        .await?;
+    info!("error 分支: {:?}", result.get_meta_str("error"));
+    assert!(result.get_meta_str("error").unwrap().contains("NATS连接"));
+
+    // 没有 error 连接时返回 ComponentError
+    let chain_id = load(&engine, unreachable, false).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({ "id": 1 })))
+        .await;
+    assert!(matches!(result, Err(RuleError::ComponentError(_))));
+
+    // 以下步骤需要运行中的 NATS 服务,例如 docker run -p 4222:4222 nats
+    let Ok(url) = std::env::var("NATS_URL") else {
+        info!("未设置 NATS_URL,跳过发布和请求示例");
+        return Ok(());
+    };
+
+    // 订阅者: 记录发布的订单,并回复价格查询
+    let client = async_nats::connect(&url).await?;
+    let mut orders = client.subscribe("orders").await?;
+    let mut prices = client.subscribe("prices").await?;
+    let responder = client.clone();
+    tokio::spawn(async move {
+        while let Some(request) = prices.next().await {
+            let query: Value = serde_json::from_slice(&request.payload).unwrap();
+            let reply = json!({ "sku": query["sku"], "price": 42 });
+            if let Some(reply_to) = request.reply {
+                responder
+                    .publish(reply_to, serde_json::to_vec(&reply).unwrap().into())
+                    .await
+                    .unwrap();
+            }
+        }
+    });
+    client.flush().await?;
+
+    let chain_id = load(&engine, json!({ "url": url, "subject": "orders" }), false).await?;
+    engine
+        .process_msg(chain_id, Message::new("order", json!({ "id": 2 })))
+        .await?;
+    let published = orders.next().await.unwrap();
+    let data: Value = serde_json::from_slice(&published.payload)?;
+    info!("收到发布的订单: {}", data);
+    assert_eq!(data, json!({ "id": 2 }));
+
+    let config = json!({ "url": url, "subject": "prices", "operation": "request" });
+    let chain_id = load(&engine, config, false).await?;
+    let result = engine
+        .process_msg(chain_id, Message::new("query", json!({ "sku": "A1" })))
+        .await?;
+    info!("价格回复: {}", result.data);
+    assert_eq!(result.data, json!({ "sku": "A1", "price": 42 }));
+
+    info!("NATS节点验证通过");
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use rule_rs::aop::NodeInterceptor;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+
+/// 记录每个执行节点的类型和输入数据
+#[derive(Debug, Default)]
+struct TraceInterceptor {
+    executed: Mutex<Vec<(String, Value)>>,
+}
+
+#[async_trait]
+impl NodeInterceptor for TraceInterceptor {
+    async fn before<'a>(&self, ctx: &NodeContext<'a>, msg: &Message) -> Result<(), RuleError> {
+        self.executed
+            .lock()
+            .unwrap()
+            .push((ctx.node.type_name.clone(), msg.data.clone()));
+        Ok(())
+    }
+
+    async fn after<'a>(&self, _ctx: &NodeContext<'a>, _msg: &Message) -> Result<(), RuleError> {
+        Ok(())
+    }
+
+    async fn error<'a>(&self, _ctx: &NodeContext<'a>, _error: &RuleError) -> Result<(), RuleError> {
+        Ok(())
+    }
+}
+
+/// 数值加一节点,只返回输出,由引擎转发
+#[derive(Debug)]
+struct IncrementNode;
+
+#[async_trait]
+impl NodeHandler for IncrementNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.data["value"] = json!(msg.data["value"].as_i64().unwrap_or_default() + 1);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/increment".to_string(),
+            name: "加一节点".to_string(),
+            description: "数值加一".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct GateConfig {
+    min: i64,
+}
+
+/// 门限节点,数值小于下限时丢弃消息
+#[derive(Debug)]
+struct GateNode {
+    config: GateConfig,
+}
+
+#[async_trait]
+impl NodeHandler for GateNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        if msg.data["value"].as_i64().unwrap_or_default() < self.config.min {
+            // 标记已路由,引擎不再转发
+            ctx.mark_routed();
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/gate".to_string(),
+            name: "门限节点".to_string(),
+            description: "丢弃小于下限的数值".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    let trace = Arc::new(TraceInterceptor::default());
+    engine.add_node_interceptor(trace.clone()).await;
+
+    // 注册自定义节点
+    engine
+        .register_node_type(
+            "custom/increment",
+            Arc::new(|_| Ok(Arc::new(IncrementNode) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+    engine
+        .register_node_type(
+            "custom/gate",
+            Arc::new(|config| {
+                let config: GateConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(GateNode { config }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 起始 -> 脚本 -> 加一 -> 门限 -> JS转换 -> 日志
+    let mut builder = RuleChainBuilder::new("节点转发示例");
+    let start = builder.add_node("start", json!({}));
+    let script = builder.add_node(
+        "script",
+        json!({ "script": "return { value: msg.data.value * 2 };" }),
+    );
+    let increment = builder.add_node("custom/increment", json!({}));
+    let gate = builder.add_node("custom/gate", json!({ "min": 10 }));
+    let transform = builder.add_node(
+        "transform_js",
+        json!({ "script": "return { value: msg.value, passed: true };" }),
+    );
+    let log = builder.add_node("log", json!({ "template": "结果: ${msg.data.value}" }));
+    let chain = builder
+        .connect(start, script, "success")
+        .connect(script, increment, "success")
+        .connect(increment, gate, "success")
+        .connect(gate, transform, "success")
+        .connect(transform, log, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+    let take = || std::mem::take(&mut *trace.executed.lock().unwrap());
+
+    // 节点返回后由引擎转发,无需调用 send_next
+    let result = engine
+        .process_msg(chain_id, Message::new("number", json!({ "value": 10 })))
+        .await?;
+    info!("处理结果: {}", result.data);
+    assert_eq!(result.data, json!({ "value": 21, "passed": true }));
+    let executed = take();
+    let types: Vec<&str> = executed.iter().map(|(t, _)| t.as_str()).collect();
+    assert_eq!(
+        types,
+        [
+            "start",
+            "script",
+            "custom/increment",
+            "custom/gate",
+            "transform_js",
+            "log"
+        ]
+    );
+    assert_eq!(executed[3].1, json!({ "value": 21 }));
+
+    // 门限节点标记已路由后规则链在此结束
+    let result = engine
+        .process_msg(chain_id, Message::new("number", json!({ "value": 2 })))
+        .await?;
+    info!("处理结果: {}", result.data);
+    assert_eq!(result.data, json!({ "value": 5 }));
+    let types: Vec<String> = take().into_iter().map(|(t, _)| t).collect();
+    assert_eq!(types.last().unwrap(), "custom/gate");
+
+    info!("节点转发验证通过");
+    Ok(())
+}
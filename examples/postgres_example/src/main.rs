@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+
+/// 生成规则链: 建表 -> 插入 -> 查询 -> 记录
+fn postgres_chain(dsn: &str) -> String {
+    let chain_id = "3f2504e0-4f89-11d3-9a0c-0305e82c3501";
+    let node_id = |n: u8| format!("3f2504e0-4f89-11d3-9a0c-0305e82c35{:02}", n);
+
+    let nodes = vec![
+        json!({
+            "id": node_id(0),
+            "type_name": "start",
+            "chain_id": chain_id,
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        }),
+        json!({
+            "id": node_id(2),
+            "type_name": "postgres",
+            "chain_id": chain_id,
+            "config": {
+                "dsn": dsn,
+                "operation": "execute",
+                "sql": "CREATE TABLE IF NOT EXISTS rule_rs_users (name TEXT PRIMARY KEY, age INT4, tags JSONB)"
+            },
+            "layout": { "x": 200, "y": 100 }
+        }),
+        json!({
+            "id": node_id(3),
+            "type_name": "postgres",
+            "chain_id": chain_id,
+            "config": {
+                "dsn": dsn,
+                "operation": "execute",
+                "sql": "INSERT INTO rule_rs_users (name, age, tags) VALUES ($1, $2, $3) ON CONFLICT (name) DO UPDATE SET age = EXCLUDED.age, tags = EXCLUDED.tags",
+                "params": ["user.name", "user.age", "user.tags"]
+            },
+            "layout": { "x": 350, "y": 100 }
+        }),
+        json!({
+            "id": node_id(4),
+            "type_name": "postgres",
+            "chain_id": chain_id,
+            "config": {
+                "dsn": dsn,
+                "sql": "SELECT name, age, tags FROM rule_rs_users WHERE name = $1",
+                "params": ["user.name"]
+            },
+            "layout": { "x": 500, "y": 100 }
+        }),
+        json!({
+            "id": node_id(5),
+            "type_name": "custom/record",
+            "chain_id": chain_id,
+            "config": {},
+            "layout": { "x": 650, "y": 100 }
+        }),
+    ];
+    let connections: Vec<Value> = (0..4)
+        .map(|i| {
+            json!({
+                "from_id": node_id(if i == 0 { 0 } else { i + 1 }),
+                "to_id": node_id(i + 2),
+                "type_name": "success"
+            })
+        })
+        .collect();
+
+    json!({
+        "id": chain_id,
+        "name": "PostgreSQL示例",
+        "root": true,
+        "nodes": nodes,
+        "connections": connections,
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+    .to_string()
+}
+
+/// 记录查询节点输出的消息数据
+type Records = Arc<Mutex<Vec<Value>>>;
+
+#[derive(Debug)]
+struct RecordNode {
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        info!("查询结果: {}", msg.data);
+        self.records.lock().unwrap().push(msg.data.clone());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 连接地址可通过 DATABASE_URL 覆盖
+    let dsn = std::env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "postgres://postgres@127.0.0.1:5432/postgres".to_string());
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册记录节点
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |_| {
+                Ok(Arc::new(RecordNode {
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    let chain_id = engine.load_chain(&postgres_chain(&dsn)).await?;
+
+    // 参数从 msg.data 中按路径取值并绑定到占位符,包含引号的值不会破坏SQL
+    let msg = Message::new(
+        "user",
+        json!({
+            "user": {
+                "name": "O'Brien",
+                "age": 42,
+                "tags": ["admin", "ops"]
+            }
+        }),
+    );
+    engine.process_msg(chain_id, msg).await?;
+
+    let records = records.lock().unwrap();
+    assert_eq!(records.len(), 1);
+    let data = &records[0];
+    assert_eq!(data["rows_affected"], json!(1));
+    assert_eq!(
+        data["rows"],
+        json!([{ "name": "O'Brien", "age": 42, "tags": ["admin", "ops"] }])
+    );
+
+    info!("PostgreSQL节点验证通过");
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{info, Level};
+
+/// 生成限流规则链
+///
+/// 限流节点之后连接 success 和 throttled 两个记录节点
+fn rate_limit_chain(index: u8, config: Value) -> String {
+    let chain_id = format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}01", 70 + index);
+    let node_id = |n: u8| format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}{:02}", 70 + index, n);
+
+    let mut nodes = vec![
+        json!({
+            "id": node_id(0),
+            "type_name": "start",
+            "chain_id": chain_id,
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        }),
+        json!({
+            "id": node_id(2),
+            "type_name": "rate_limit",
+            "chain_id": chain_id,
+            "config": config,
+            "layout": { "x": 200, "y": 100 }
+        }),
+    ];
+    let mut connections = vec![json!({
+        "from_id": node_id(0),
+        "to_id": node_id(2),
+        "type_name": "success"
+    })];
+    for (i, branch) in ["success", "throttled"].iter().enumerate() {
+        let id = node_id(3 + i as u8);
+        nodes.push(json!({
+            "id": id,
+            "type_name": "custom/record",
+            "chain_id": chain_id,
+            "config": { "name": branch },
+            "layout": { "x": 350, "y": 50 + 100 * i }
+        }));
+        connections.push(json!({
+            "from_id": node_id(2),
+            "to_id": id,
+            "type_name": branch
+        }));
+    }
+
+    json!({
+        "id": chain_id,
+        "name": format!("限流示例 {}", index),
+        "root": true,
+        "nodes": nodes,
+        "connections": connections,
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+    .to_string()
+}
+
+/// 记录各分支收到消息的设备和时间
+type Records = Arc<Mutex<Vec<(String, String, Instant)>>>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RecordConfig {
+    name: String,
+}
+
+/// 记录节点,保存分支名称、设备和到达时间
+#[derive(Debug)]
+struct RecordNode {
+    config: RecordConfig,
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let device = msg.data["device"].as_str().unwrap_or_default().to_string();
+        info!("{} 分支收到消息: {}", self.config.name, msg.data);
+        self.records
+            .lock()
+            .unwrap()
+            .push((self.config.name.clone(), device, Instant::now()));
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+// 暂停 tokio 时间,等待会被自动快进,无需真实等待
+#[tokio::main(flavor = "current_thread", start_paused = true)]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册记录节点
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |config| {
+                let config: RecordConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(RecordNode {
+                    config,
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 每个设备每秒1条,允许突发2条
+    let throttle_chain = engine
+        .load_chain(&rate_limit_chain(
+            1,
+            json!({ "rate_per_sec": 1, "burst": 2, "key_path": "device" }),
+        ))
+        .await?;
+    // 所有消息共用一个令牌桶,每秒2条,超出时等待
+    let wait_chain = engine
+        .load_chain(&rate_limit_chain(
+            2,
+            json!({ "rate_per_sec": 2, "burst": 1, "mode": "wait" }),
+        ))
+        .await?;
+
+    let reading = |device: &str| Message::new("sensor", json!({ "device": device }));
+    let take = |records: &Records| {
+        std::mem::take(&mut *records.lock().unwrap())
+            .into_iter()
+            .map(|(branch, device, _)| (branch, device))
+            .collect::<Vec<_>>()
+    };
+    let pair = |branch: &str, device: &str| (branch.to_string(), device.to_string());
+
+    // 突发2条通过,第3条被限流;其他设备使用独立的令牌桶
+    for device in ["a", "a", "a", "b"] {
+        engine.process_msg(throttle_chain, reading(device)).await?;
+    }
+    assert_eq!(
+        take(&records),
+        vec![
+            pair("success", "a"),
+            pair("success", "a"),
+            pair("throttled", "a"),
+            pair("success", "b"),
+        ]
+    );
+
+    // 1秒后补充1个令牌
+    sleep(Duration::from_millis(1000)).await;
+    for device in ["a", "a"] {
+        engine.process_msg(throttle_chain, reading(device)).await?;
+    }
+    assert_eq!(
+        take(&records),
+        vec![pair("success", "a"), pair("throttled", "a")]
+    );
+
+    // 等待模式: 消息不被丢弃,按速率间隔500毫秒依次转发
+    let start = Instant::now();
+    for _ in 0..3 {
+        engine.process_msg(wait_chain, reading("c")).await?;
+    }
+    let records = std::mem::take(&mut *records.lock().unwrap());
+    assert_eq!(records.len(), 3);
+    for (i, (branch, _, at)) in records.iter().enumerate() {
+        assert_eq!(branch, "success");
+        assert_eq!(
+            at.duration_since(start),
+            Duration::from_millis(500 * i as u64)
+        );
+    }
+
+    info!("限流验证通过");
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
 use redis::{cmd, AsyncCommands, Client, RedisResult, Value as RedisValue};
 use rule_rs;
 use rule_rs::engine::NodeHandler;
@@ -8,6 +9,7 @@ use serde::Deserialize;
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::sync::Mutex;
 use tracing::{info, Level};
 use tracing_subscriber;
 
@@ -176,17 +178,40 @@ impl Default for RedisConfig {
     }
 }
 
+/// Redis节点,首次处理消息时建立多路复用连接,之后的消息共用该连接
 #[derive(Debug)]
 pub struct RedisNode {
     config: RedisConfig,
     client: Client,
+    conn: Mutex<Option<MultiplexedConnection>>,
 }
 
 impl RedisNode {
-    pub fn new(config: RedisConfig) -> Self {
-        let client = Client::open(config.url.clone()).unwrap();
+    /// 创建节点并校验连接地址,连接在处理第一条消息时建立
+    pub fn new(config: RedisConfig) -> Result<Self, RuleError> {
+        let client = Client::open(config.url.as_str())
+            .map_err(|e| RuleError::ConfigError(format!("Redis地址无效: {}", e)))?;
+        Ok(Self {
+            config,
+            client,
+            conn: Mutex::new(None),
+        })
+    }
+
+    /// 获取共享的连接,尚未建立时创建
+    async fn connection(&self) -> Result<MultiplexedConnection, RuleError> {
+        let mut conn = self.conn.lock().await;
+        if let Some(conn) = conn.as_ref() {
+            return Ok(conn.clone());
+        }
 
-        Self { config, client }
+        let new_conn = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| RuleError::ComponentError(format!("获取Redis连接失败: {}", e)))?;
+        *conn = Some(new_conn.clone());
+        Ok(new_conn)
     }
 
     // 添加辅助方法转换Redis值到JSON
@@ -234,11 +259,7 @@ impl NodeHandler for RedisNode {
         ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
-        let mut conn = self
-            .client
-            .get_multiplexed_async_connection()
-            .await
-            .map_err(|e| RuleError::ComponentError(format!("获取Redis连接失败: {}", e)))?;
+        let mut conn = self.connection().await?;
 
         let result = match &self.config.operation {
             RedisOperation::Command(cmd) => {
@@ -530,7 +551,13 @@ impl NodeHandler for RedisNode {
                     ))),
                 }
             }
-        }?;
+        };
+
+        // 命令失败时丢弃连接,下一条消息重新连接,避免一直使用已断开的连接
+        if result.is_err() {
+            *self.conn.lock().await = None;
+        }
+        let result = result?;
 
         // 构造返回消息
         let mut new_msg = msg;
@@ -538,16 +565,12 @@ impl NodeHandler for RedisNode {
             new_msg.data = value;
             // 设置成功分支
             if let Some(branch) = &self.config.success_branch {
-                new_msg
-                    .metadata
-                    .insert("branch_name".into(), branch.clone());
+                new_msg.set_meta("branch_name", branch.clone());
             }
         } else {
             // 设置失败分支
             if let Some(branch) = &self.config.error_branch {
-                new_msg
-                    .metadata
-                    .insert("branch_name".into(), branch.clone());
+                new_msg.set_meta("branch_name", branch.clone());
             }
         }
 
@@ -563,6 +586,7 @@ impl NodeHandler for RedisNode {
             name: "Redis客户端".to_string(),
             description: "执行Redis命令".to_string(),
             node_type: NodeType::Middle,
+            config_schema: None,
         }
     }
 }
@@ -583,10 +607,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             "custom/redis",
             Arc::new(|config| {
                 if config.is_object() && config.as_object().unwrap().is_empty() {
-                    Ok(Arc::new(RedisNode::new(RedisConfig::default())) as Arc<dyn NodeHandler>)
+                    Ok(Arc::new(RedisNode::new(RedisConfig::default())?) as Arc<dyn NodeHandler>)
                 } else {
                     let config: RedisConfig = serde_json::from_value(config)?;
-                    Ok(Arc::new(RedisNode::new(config)) as Arc<dyn NodeHandler>)
+                    Ok(Arc::new(RedisNode::new(config)?) as Arc<dyn NodeHandler>)
                 }
             }),
         )
@@ -597,6 +621,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         info!("- {}: {}", desc.type_name, desc.description);
     }
 
+    // 无效的连接地址在加载时返回配置错误
+    let invalid = RULE_CHAIN.replacen("redis://localhost:6379", "not-a-redis-url", 1);
+    match engine.load_chain(&invalid).await {
+        Err(RuleError::ConfigError(e)) => {
+            info!("加载失败: {}", e);
+            assert!(e.contains("Redis地址无效"));
+        }
+        other => panic!("期望配置错误, 实际: {:?}", other),
+    }
+
     // 加载规则链
     let chain_id = engine.load_chain(RULE_CHAIN).await?;
 
@@ -0,0 +1,101 @@
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, Level};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 同步函数: 金额加税
+    engine
+        .register_fn(
+            "fn/tax",
+            Arc::new(|mut msg: Message| {
+                let amount = msg.data["amount"]
+                    .as_f64()
+                    .ok_or_else(|| RuleError::NodeExecutionError("缺少金额".to_string()))?;
+                msg.data["total"] = json!(amount * 1.1);
+                Ok(msg)
+            }),
+        )
+        .await;
+
+    // 同步函数: 按金额选择分支
+    engine
+        .register_fn(
+            "fn/classify",
+            Arc::new(|mut msg: Message| {
+                let big = msg.data["total"].as_f64().unwrap_or_default() > 100.0;
+                msg.set_meta("branch_name", if big { "big" } else { "small" });
+                Ok(msg)
+            }),
+        )
+        .await;
+
+    // 异步函数: 模拟查询外部服务
+    engine
+        .register_async_fn(
+            "fn/approve",
+            Arc::new(
+                |mut msg: Message| -> BoxFuture<'static, Result<Message, RuleError>> {
+                    async move {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        msg.data["approved"] = json!(true);
+                        Ok(msg)
+                    }
+                    .boxed()
+                },
+            ),
+        )
+        .await;
+
+    let descriptor = engine.get_component_descriptor("fn/approve").await.unwrap();
+    info!(
+        "已注册: {} - {}",
+        descriptor.type_name, descriptor.description
+    );
+
+    // 起始 -> 加税 -> 分类 -big-> 审批,-small-> 日志
+    let mut builder = RuleChainBuilder::new("函数节点示例");
+    let start = builder.add_node("start", json!({}));
+    let tax = builder.add_node("fn/tax", json!({}));
+    let classify = builder.add_node("fn/classify", json!({}));
+    let approve = builder.add_node("fn/approve", json!({}));
+    let log = builder.add_node("log", json!({ "template": "小额订单直接通过" }));
+    builder
+        .connect(start, tax, "success")
+        .connect(tax, classify, "success")
+        .connect(classify, approve, "big")
+        .connect(classify, log, "small");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({ "amount": 200 })))
+        .await?;
+    info!("大额订单: {}", result.data);
+    assert_eq!(result.data["approved"], true);
+
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({ "amount": 50 })))
+        .await?;
+    info!("小额订单: {}", result.data);
+    assert!(result.data.get("approved").is_none());
+
+    // 函数返回错误时节点执行失败
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({})))
+        .await;
+    info!("缺少金额: {:?}", result);
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(_))));
+
+    info!("函数节点验证通过");
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use rule_rs::engine::{DeadLetter, NodeHandler};
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast::error::TryRecvError;
+use tracing::{info, Level};
+
+/// 库存节点,库存服务不可用时执行失败,记录执行次数和重放次数
+#[derive(Debug)]
+struct StockNode {
+    available: Arc<AtomicBool>,
+    runs: Arc<AtomicUsize>,
+    replays: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl NodeHandler for StockNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        if msg.is_replay() {
+            self.replays.fetch_add(1, Ordering::SeqCst);
+        }
+        if !self.available.load(Ordering::SeqCst) {
+            return Err(RuleError::NodeExecutionError("库存服务不可用".to_string()));
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/stock".to_string(),
+            name: "库存节点".to_string(),
+            description: "扣减库存".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let letters: Arc<Mutex<Vec<DeadLetter>>> = Arc::default();
+    let collected = letters.clone();
+    let engine = RuleEngine::new()
+        .await
+        .with_idempotency(Duration::from_secs(60))
+        .with_dead_letter_handler(Arc::new(move |letter| {
+            collected.lock().unwrap().push(letter);
+        }));
+
+    let available = Arc::new(AtomicBool::new(true));
+    let runs = Arc::new(AtomicUsize::new(0));
+    let replays = Arc::new(AtomicUsize::new(0));
+    let (node_available, node_runs, node_replays) =
+        (available.clone(), runs.clone(), replays.clone());
+    engine
+        .register_node_type(
+            "custom/stock",
+            Arc::new(move |_| {
+                Ok(Arc::new(StockNode {
+                    available: node_available.clone(),
+                    runs: node_runs.clone(),
+                    replays: node_replays.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 幂等规则链: 起始 -> 扣减库存 -> 发布订单事件
+    let mut builder = RuleChainBuilder::new("重放示例");
+    let start = builder.add_node("start", json!({}));
+    let stock = builder.add_node("custom/stock", json!({}));
+    let emit = builder.add_node("emit", json!({ "topic": "orders" }));
+    let chain = builder
+        .connect(start, stock, "success")
+        .connect(stock, emit, "success")
+        .root(true)
+        .idempotent(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+    let mut events = engine.subscribe("orders").await;
+
+    // 正常处理,重复投递的消息被幂等缓存拦截
+    let msg = Message::new("order", json!({ "order_id": 1 }));
+    engine.process_msg(chain_id, msg.clone()).await?;
+    engine.process_msg(chain_id, msg.clone()).await?;
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert_eq!(events.try_recv()?.id, msg.id);
+
+    // 重放跳过幂等检查,节点可以识别重放,emit 不再发布事件
+    let result = engine.replay_msg(chain_id, msg.clone()).await?;
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    assert_eq!(replays.load(Ordering::SeqCst), 1);
+    assert_eq!(result.id, msg.id);
+    assert!(result.is_replay());
+    assert!(matches!(events.try_recv(), Err(TryRecvError::Empty)));
+    info!("重放结果: {:?}", result.metadata);
+
+    // 处理失败的消息进入死信,恢复后重放
+    available.store(false, Ordering::SeqCst);
+    let failed = Message::new("order", json!({ "order_id": 2 }));
+    let result = engine.process_msg(chain_id, failed.clone()).await;
+    assert!(matches!(result, Err(RuleError::NodeExecutionError(_))));
+    let letter = letters.lock().unwrap().pop().unwrap();
+    assert_eq!(letter.node_id, Some(stock));
+
+    available.store(true, Ordering::SeqCst);
+    let result = engine.replay_msg(letter.chain_id, letter.msg).await?;
+    assert_eq!(result.id, failed.id);
+    assert_eq!(result.data, json!({ "order_id": 2 }));
+    assert!(letters.lock().unwrap().is_empty());
+    info!("死信消息 {} 重放成功", failed.id);
+
+    info!("消息重放验证通过");
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use axum::http::header::LINK;
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::Html;
+use axum::routing::get;
+use axum::{Json, Router};
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+/// 加载 起始 -> HTTP请求 规则链,发送一条消息并返回处理结果
+async fn call(engine: &RuleEngine, url: String, parse_response: &str) -> Message {
+    let mut builder = RuleChainBuilder::new("HTTP响应解析示例");
+    let start = builder.add_node("start", json!({}));
+    let rest = builder.add_node(
+        "rest_client",
+        json!({ "url": url, "method": "GET", "parse_response": parse_response }),
+    );
+    let chain = builder.connect(start, rest, "success").root(true).build();
+
+    let chain_id = engine.load_rule_chain(chain).await.unwrap();
+    engine
+        .process_msg(chain_id, Message::new("request", json!({})))
+        .await
+        .unwrap()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 启动本地服务,返回不同类型的响应
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let base_url = format!("http://{}", listener.local_addr()?);
+    let app = Router::new()
+        .route("/json", get(|| async { Json(json!({ "ok": true })) }))
+        .route("/text", get(|| async { "pong" }))
+        .route("/html", get(|| async { Html("<p>hello</p>") }))
+        .route("/empty", get(|| async { StatusCode::NO_CONTENT }))
+        .route(
+            "/paged",
+            get(|| async {
+                let mut headers = HeaderMap::new();
+                headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+                headers.append(
+                    LINK,
+                    HeaderValue::from_static("</paged?page=2>; rel=\"next\""),
+                );
+                headers.append(
+                    LINK,
+                    HeaderValue::from_static("</paged?page=9>; rel=\"last\""),
+                );
+                (headers, Json(json!([1, 2, 3])))
+            }),
+        )
+        .route(
+            "/missing",
+            get(|| async { (StatusCode::NOT_FOUND, "not found") }),
+        );
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let engine = RuleEngine::new().await;
+    let url = |path: &str| format!("{}{}", base_url, path);
+
+    // 自动模式: 根据 Content-Type 解析
+    let cases: [(&str, Value); 4] = [
+        ("/json", json!({ "ok": true })),
+        ("/text", json!("pong")),
+        ("/html", json!("<p>hello</p>")),
+        ("/empty", Value::Null),
+    ];
+    for (path, expected) in cases {
+        let result = call(&engine, url(path), "auto").await;
+        info!("{} 响应: {}", path, result.data);
+        assert_eq!(result.data["body"], expected);
+        assert!(!result.metadata.contains_key("error"));
+    }
+
+    // 指定解析方式覆盖 Content-Type
+    let result = call(&engine, url("/json"), "text").await;
+    assert_eq!(result.data["body"], json!("{\"ok\":true}"));
+    let result = call(&engine, url("/text"), "json").await;
+    assert!(result.metadata["error"]
+        .as_str()
+        .unwrap()
+        .contains("响应解析失败"));
+
+    // 响应头以小写名称输出,同名多值的头合并为数组
+    let result = call(&engine, url("/paged"), "auto").await;
+    info!("/paged 响应头: {}", result.data["headers"]);
+    let headers = &result.data["headers"];
+    assert_eq!(headers["x-request-id"], "req-1");
+    assert_eq!(headers["content-type"], "application/json");
+    assert_eq!(
+        headers["link"],
+        json!([
+            "</paged?page=2>; rel=\"next\"",
+            "</paged?page=9>; rel=\"last\""
+        ])
+    );
+
+    // 非JSON的错误响应仍按状态码走失败处理
+    let result = call(&engine, url("/missing"), "auto").await;
+    info!("/missing 错误: {}", result.metadata["error"]);
+    assert!(result.metadata["error"].as_str().unwrap().contains("404"));
+
+    info!("HTTP响应解析验证通过");
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, Level};
+
+/// 各路由收到的请求次数
+#[derive(Default)]
+struct Counters {
+    flaky: AtomicUsize,
+    limited: AtomicUsize,
+    invalid: AtomicUsize,
+}
+
+/// 前两次返回 503,之后返回成功
+async fn flaky(State(counters): State<Arc<Counters>>) -> impl IntoResponse {
+    if counters.flaky.fetch_add(1, Ordering::SeqCst) < 2 {
+        return (StatusCode::SERVICE_UNAVAILABLE, "unavailable").into_response();
+    }
+    Json(json!({ "ok": true })).into_response()
+}
+
+/// 首次返回 429 并要求 1 秒后重试
+async fn limited(State(counters): State<Arc<Counters>>) -> impl IntoResponse {
+    if counters.limited.fetch_add(1, Ordering::SeqCst) == 0 {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, "1")],
+            "slow down",
+        )
+            .into_response();
+    }
+    Json(json!({ "ok": true })).into_response()
+}
+
+/// 总是返回 400
+async fn invalid(State(counters): State<Arc<Counters>>) -> impl IntoResponse {
+    counters.invalid.fetch_add(1, Ordering::SeqCst);
+    (StatusCode::BAD_REQUEST, "bad request")
+}
+
+/// 加载 起始 -> HTTP请求 规则链,发送一条消息并返回处理结果
+async fn call(engine: &RuleEngine, url: String, retry: Value) -> Message {
+    let mut builder = RuleChainBuilder::new("HTTP重试示例");
+    let start = builder.add_node("start", json!({}));
+    let rest = builder.add_node(
+        "rest_client",
+        json!({ "url": url, "method": "GET", "retry": retry }),
+    );
+    let chain = builder.connect(start, rest, "success").root(true).build();
+
+    let chain_id = engine.load_rule_chain(chain).await.unwrap();
+    engine
+        .process_msg(chain_id, Message::new("request", json!({})))
+        .await
+        .unwrap()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 启动本地服务,模拟不稳定的下游接口
+    let counters = Arc::new(Counters::default());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let base_url = format!("http://{}", listener.local_addr()?);
+    let app = Router::new()
+        .route("/flaky", get(flaky))
+        .route("/limited", get(limited))
+        .route("/invalid", get(invalid))
+        .with_state(counters.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let engine = RuleEngine::new().await;
+    let url = |path: &str| format!("{}{}", base_url, path);
+    let retry = json!({ "max_attempts": 3, "base_delay_ms": 50, "max_delay_ms": 200 });
+
+    // 5xx 响应按退避重试,第三次成功
+    let result = call(&engine, url("/flaky"), retry.clone()).await;
+    info!("/flaky 响应: {}", result.data);
+    assert_eq!(result.data["body"], json!({ "ok": true }));
+    assert_eq!(counters.flaky.load(Ordering::SeqCst), 3);
+
+    // 429 响应按 Retry-After 等待后重试
+    let started = Instant::now();
+    let result = call(&engine, url("/limited"), retry.clone()).await;
+    assert_eq!(result.data["body"], json!({ "ok": true }));
+    assert_eq!(counters.limited.load(Ordering::SeqCst), 2);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+
+    // 其他 4xx 响应不重试
+    let result = call(&engine, url("/invalid"), retry.clone()).await;
+    let error = result.metadata["error"].as_str().unwrap();
+    info!("/invalid 错误: {}", error);
+    assert!(error.contains("400") && error.contains("共尝试 1 次"));
+    assert_eq!(counters.invalid.load(Ordering::SeqCst), 1);
+
+    // 连接失败时重试,用尽次数后报告尝试次数
+    let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let closed_url = format!("http://{}/", closed.local_addr()?);
+    drop(closed);
+    let result = call(&engine, closed_url, retry).await;
+    let error = result.metadata["error"].as_str().unwrap();
+    info!("连接失败: {}", error);
+    assert!(error.contains("HTTP请求失败") && error.contains("共尝试 3 次"));
+
+    info!("HTTP请求重试验证通过");
+    Ok(())
+}
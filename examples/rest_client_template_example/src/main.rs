@@ -0,0 +1,117 @@
+use axum::extract::OriginalUri;
+use axum::http::HeaderMap;
+use axum::routing::post;
+use axum::{Json, Router};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+const CHAIN_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c4001";
+
+/// 生成规则链: 起始 -> HTTP请求
+fn rest_chain(base_url: &str) -> Value {
+    json!({
+        "id": CHAIN_ID,
+        "name": "HTTP请求模板示例",
+        "root": true,
+        "nodes": [
+            {
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c4000",
+                "type_name": "start",
+                "chain_id": CHAIN_ID,
+                "config": {},
+                "layout": { "x": 50, "y": 100 }
+            },
+            {
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c4002",
+                "type_name": "rest_client",
+                "chain_id": CHAIN_ID,
+                "config": {
+                    "url": format!("{}/users/${{msg.data.user.id}}?trace=${{msg.metadata.trace_id}}", base_url),
+                    "method": "POST",
+                    "headers": {
+                        "Authorization": "Bearer ${msg.data.token}",
+                        "X-Trace-Id": "${msg.metadata.trace_id}"
+                    },
+                    "body_template": {
+                        "name": "${msg.data.user.name}",
+                        "age": "${msg.data.user.age}",
+                        "summary": "${msg.data.user.name} (${msg.data.user.age})"
+                    }
+                },
+                "layout": { "x": 200, "y": 100 }
+            }
+        ],
+        "connections": [
+            {
+                "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c4000",
+                "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c4002",
+                "type_name": "success"
+            }
+        ],
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+}
+
+/// 回显收到的请求地址、请求头和请求体
+async fn echo(uri: OriginalUri, headers: HeaderMap, Json(body): Json<Value>) -> Json<Value> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from)
+    };
+    Json(json!({
+        "uri": uri.to_string(),
+        "authorization": header("authorization"),
+        "trace_id": header("x-trace-id"),
+        "body": body
+    }))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 启动本地回显服务
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let base_url = format!("http://{}", listener.local_addr()?);
+    let app = Router::new().route("/users/{id}", post(echo));
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    // 创建引擎实例并加载规则链
+    let engine = RuleEngine::new().await;
+    let chain_id = engine.load_chain_value(rest_chain(&base_url)).await?;
+
+    let mut msg = Message::new(
+        "user",
+        json!({
+            "token": "abc123",
+            "user": { "id": 42, "name": "Alice", "age": 30 }
+        }),
+    );
+    msg.set_meta("trace_id", "t-1");
+
+    let result = engine.process_msg(chain_id, msg).await?;
+    info!("响应: {}", result.data);
+
+    // URL、请求头和请求体使用相同的模板变量
+    let echoed = &result.data["body"];
+    assert_eq!(result.data["status"], 200);
+    assert_eq!(echoed["uri"], "/users/42?trace=t-1");
+    assert_eq!(echoed["authorization"], "Bearer abc123");
+    assert_eq!(echoed["trace_id"], "t-1");
+    // 单个变量保留原始类型,嵌入文本的变量渲染为字符串
+    assert_eq!(
+        echoed["body"],
+        json!({ "name": "Alice", "age": 30, "summary": "Alice (30)" })
+    );
+
+    info!("HTTP请求模板验证通过");
+    Ok(())
+}
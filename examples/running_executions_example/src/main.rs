@@ -0,0 +1,86 @@
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, Level};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 模拟耗时的外部调用
+    engine
+        .register_async_fn(
+            "fn/slow",
+            Arc::new(
+                |msg: Message| -> BoxFuture<'static, Result<Message, RuleError>> {
+                    async move {
+                        tokio::time::sleep(Duration::from_millis(300)).await;
+                        Ok(msg)
+                    }
+                    .boxed()
+                },
+            ),
+        )
+        .await;
+
+    // 起始 -> 耗时节点 -> 日志
+    let mut builder = RuleChainBuilder::new("执行查询示例");
+    let start = builder.add_node("start", json!({}));
+    let slow = builder.add_node("fn/slow", json!({}));
+    let log = builder.add_node(
+        "log",
+        json!({ "template": "订单 ${msg.data.order} 处理完成" }),
+    );
+    builder
+        .connect(start, slow, "success")
+        .connect(slow, log, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    // 在后台处理两条消息
+    let mut msg_ids = Vec::new();
+    let mut handles = Vec::new();
+    for order in 1..=2 {
+        let msg = Message::new("order", json!({ "order": order }));
+        msg_ids.push(msg.id);
+        let engine = engine.clone();
+        handles.push(tokio::spawn(async move {
+            engine.process_msg(chain_id, msg).await
+        }));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    // 两条消息都停在耗时节点上,按开始处理的时间排序
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let running = engine.running_executions();
+    for execution in &running {
+        info!(
+            "消息 {} 正在执行节点 {:?}, 已处理 {}ms",
+            execution.msg_id, execution.node_id, execution.elapsed_ms
+        );
+    }
+    assert_eq!(
+        running.iter().map(|e| e.msg_id).collect::<Vec<_>>(),
+        msg_ids
+    );
+    assert!(running
+        .iter()
+        .all(|e| e.chain_id == chain_id && e.node_id == Some(slow)));
+    assert!(running[0].elapsed_ms >= running[1].elapsed_ms);
+    assert!(running[1].elapsed_ms >= 100);
+
+    // 处理完成后不再列出
+    for handle in handles {
+        handle.await??;
+    }
+    assert!(engine.running_executions().is_empty());
+
+    info!("执行查询示例验证通过");
+    Ok(())
+}
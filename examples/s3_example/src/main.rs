@@ -0,0 +1,172 @@
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+
+type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// 模拟对象存储的上传接口,不校验签名
+async fn put_object(
+    State(objects): State<Objects>,
+    Path((bucket, key)): Path<(String, String)>,
+    body: Bytes,
+) -> StatusCode {
+    objects
+        .lock()
+        .unwrap()
+        .insert(format!("{}/{}", bucket, key), body.to_vec());
+    StatusCode::OK
+}
+
+/// 模拟对象存储的下载接口,对象不存在时返回 NoSuchKey
+async fn get_object(
+    State(objects): State<Objects>,
+    Path((bucket, key)): Path<(String, String)>,
+) -> impl IntoResponse {
+    match objects
+        .lock()
+        .unwrap()
+        .get(&format!("{}/{}", bucket, key))
+    {
+        Some(body) => (StatusCode::OK, body.clone()).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <Error><Code>NoSuchKey</Code><Message>The specified key does not exist.</Message></Error>",
+        )
+            .into_response(),
+    }
+}
+
+/// 加载 起始 -> 对象存储 -> 日志/失败 规则链,`binary` 为 true 时下载到 msg.binary
+async fn load_chain(
+    engine: &RuleEngine,
+    endpoint: &str,
+    operation: &str,
+    binary: bool,
+) -> uuid::Uuid {
+    let mut builder = RuleChainBuilder::new("对象存储示例");
+    let start = builder.add_node("start", json!({}));
+    let s3 = builder.add_node(
+        "s3",
+        json!({
+            "endpoint": endpoint,
+            "bucket": "archive",
+            "key_template": "orders/${msg.metadata.order_id}",
+            "operation": operation,
+            "region": "us-east-1",
+            "access_key_id": "minio",
+            "secret_access_key": "minio123",
+            "binary": binary
+        }),
+    );
+    let done = builder.add_node("log", json!({ "template": "对象 ${msg.metadata.s3_key}" }));
+    let failed = builder.add_node("transform", json!({ "template": { "status": "failed" } }));
+    let chain = builder
+        .connect(start, s3, "success")
+        .connect(s3, done, "success")
+        .connect(s3, failed, "error")
+        .root(true)
+        .build();
+    engine.load_rule_chain(chain).await.unwrap()
+}
+
+fn order(order_id: &str, data: Value) -> Message {
+    let mut msg = Message::new("order", data);
+    msg.set_meta("order_id", order_id);
+    msg
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 启动本地的模拟对象存储服务,使用路径风格访问
+    let objects: Objects = Arc::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}", listener.local_addr()?);
+    let app = Router::new()
+        .route("/{bucket}/{*key}", get(get_object).put(put_object))
+        .with_state(objects.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let engine = RuleEngine::new().await;
+    let put_chain = load_chain(&engine, &endpoint, "put", false).await;
+    let get_chain = load_chain(&engine, &endpoint, "get", false).await;
+    let get_binary_chain = load_chain(&engine, &endpoint, "get", true).await;
+
+    // 上传: JSON数据序列化后保存
+    let data = json!({ "id": 1, "items": ["book", "pen"] });
+    let result = engine
+        .process_msg(put_chain, order("1.json", data.clone()))
+        .await?;
+    assert_eq!(result.metadata["s3_key"], "orders/1.json");
+    assert!(!result.metadata.contains_key("error"));
+    let stored = objects.lock().unwrap()["archive/orders/1.json"].clone();
+    assert_eq!(serde_json::from_slice::<Value>(&stored)?, data);
+
+    // 上传: base64字符串按原始字节保存
+    engine
+        .process_msg(put_chain, order("1.txt", json!("aGVsbG8gczM=")))
+        .await?;
+    assert_eq!(objects.lock().unwrap()["archive/orders/1.txt"], b"hello s3");
+
+    // 下载: JSON对象解析后写入 msg.data,文本写入字符串
+    let result = engine
+        .process_msg(get_chain, order("1.json", json!({})))
+        .await?;
+    info!("下载结果: {}", result.data);
+    assert_eq!(result.data, data);
+    let result = engine
+        .process_msg(get_chain, order("1.txt", json!({})))
+        .await?;
+    assert_eq!(result.data, json!("hello s3"));
+
+    // 二进制载荷: 上传 msg.binary 中的原始字节,msg.data 只保存描述信息
+    let png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0x00];
+    let meta = json!({ "file_name": "logo.png", "size": png.len() });
+    engine
+        .process_msg(
+            put_chain,
+            order("logo.png", meta.clone()).with_binary(png.clone()),
+        )
+        .await?;
+    assert_eq!(objects.lock().unwrap()["archive/orders/logo.png"], png);
+
+    // 下载到 msg.binary,msg.data 保持不变
+    let result = engine
+        .process_msg(get_binary_chain, order("logo.png", meta.clone()))
+        .await?;
+    assert_eq!(result.binary.as_deref(), Some(png.as_slice()));
+    assert_eq!(result.data, meta);
+
+    // 序列化为JSON时二进制载荷编码为base64字符串
+    let json = serde_json::to_value(&result)?;
+    info!("序列化的二进制载荷: {}", json["binary"]);
+    assert_eq!(json["binary"], "iVBORw0KGgr/AA==");
+    let decoded: Message = serde_json::from_value(json)?;
+    assert_eq!(decoded.binary, result.binary);
+
+    // 对象不存在时走 error 分支
+    let result = engine
+        .process_msg(get_chain, order("missing", json!({})))
+        .await?;
+    info!("下载失败: {}", result.metadata["error"]);
+    assert_eq!(result.data["status"], "failed");
+    assert!(result.metadata["error"]
+        .as_str()
+        .unwrap()
+        .contains("NoSuchKey"));
+
+    info!("对象存储验证通过");
+    Ok(())
+}
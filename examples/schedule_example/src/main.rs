@@ -12,8 +12,9 @@ const RULE_CHAIN: &str = r#"{
             "type_name": "schedule",
             "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
             "config": {
-                "cron": "*/5 * * * * *",  
-                "timezone_offset": 8
+                "cron": "*/2 * * * * *",
+                "timezone_offset": 8,
+                "max_runs": 10
             },
             "layout": { "x": 100, "y": 100 }
         },
@@ -22,7 +23,7 @@ const RULE_CHAIN: &str = r#"{
             "type_name": "log",
             "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
             "config": {
-                "template": "定时任务执行: ${msg.data.task}, 时间: ${new Date().toLocaleString()}"
+                "template": "定时任务执行: ${msg.data.task}, 消息: ${msg.id}"
             },
             "layout": { "x": 300, "y": 100 }
         }
@@ -72,8 +73,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Err(e) => info!("定时任务启动失败: {:?}", e),
     }
 
-    // 保持程序运行以观察定时执行
-    loop {
-        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-    }
+    // 观察几次定时执行
+    tokio::time::sleep(tokio::time::Duration::from_secs(7)).await;
+
+    // 删除规则链会同时停止其定时任务
+    engine.remove_chain(chain_id).await?;
+    info!("规则链已删除, 定时任务已停止");
+
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+    Ok(())
 }
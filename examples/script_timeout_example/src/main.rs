@@ -0,0 +1,127 @@
+use rule_rs::types::{RuleChainBuilder, ScriptPhase};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 起始 -> 指定节点 规则链
+async fn load(engine: &RuleEngine, type_name: &str, config: Value) -> Result<Uuid, RuleError> {
+    let mut builder = RuleChainBuilder::new("脚本限制示例");
+    let start = builder.add_node("start", json!({}));
+    let node = builder.add_node(type_name, config);
+    let chain = builder.connect(start, node, "success").root(true).build();
+    engine.load_rule_chain(chain).await
+}
+
+async fn run(engine: &RuleEngine, chain_id: Uuid, data: Value) -> Result<Message, RuleError> {
+    engine
+        .process_msg(chain_id, Message::new("test", data))
+        .await
+}
+
+fn is_runtime_error(result: &Result<Message, RuleError>) -> bool {
+    matches!(
+        result,
+        Err(RuleError::ScriptError {
+            phase: ScriptPhase::Runtime,
+            ..
+        })
+    )
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 死循环脚本在超时后中止,try/catch 无法拦截中断
+    for (type_name, config) in [
+        (
+            "script",
+            json!({ "script": "while (true) {}", "script_timeout_ms": 200 }),
+        ),
+        (
+            "transform_js",
+            json!({ "script": "try { while (true) {} } catch (e) {} return msg;", "script_timeout_ms": 200 }),
+        ),
+        (
+            "switch",
+            json!({
+                "cases": [{ "name": "loop", "condition": "(() => { while (true) {} })()" }],
+                "script_timeout_ms": 200
+            }),
+        ),
+    ] {
+        let chain_id = load(&engine, type_name, config).await?;
+        let begin = Instant::now();
+        let result = run(&engine, chain_id, json!({})).await;
+        info!(
+            "{} 死循环: {:?}, 耗时 {:?}",
+            type_name,
+            result,
+            begin.elapsed()
+        );
+        assert!(is_runtime_error(&result));
+        assert!(begin.elapsed() < Duration::from_secs(1));
+    }
+
+    // 未配置时默认 1000ms 超时
+    let chain_id = load(&engine, "script", json!({ "script": "for (;;) {}" })).await?;
+    let begin = Instant::now();
+    let result = run(&engine, chain_id, json!({})).await;
+    assert!(is_runtime_error(&result));
+    assert!(begin.elapsed() >= Duration::from_millis(1000));
+    assert!(begin.elapsed() < Duration::from_secs(3));
+
+    // JS函数节点超时后运行时仍可继续使用,函数库中的状态保留
+    let chain_id = load(
+        &engine,
+        "js_function",
+        json!({
+            "library": "let calls = 0;",
+            "functions": {
+                "main": "calls += 1; if (msg.data.spin) { while (true) {} } return { calls };"
+            },
+            "main": "main",
+            "script_timeout_ms": 200
+        }),
+    )
+    .await?;
+    assert!(is_runtime_error(
+        &run(&engine, chain_id, json!({ "spin": true })).await
+    ));
+    let result = run(&engine, chain_id, json!({})).await?;
+    assert_eq!(result.data, json!({ "calls": 2 }));
+
+    // 函数库加载时的死循环使规则链加载失败
+    let result = load(
+        &engine,
+        "js_function",
+        json!({
+            "library": "while (true) {}",
+            "functions": { "main": "return msg.data;" },
+            "main": "main",
+            "script_timeout_ms": 200
+        }),
+    )
+    .await;
+    info!("函数库死循环: {:?}", result);
+    assert!(matches!(&result, Err(RuleError::ConfigError(e)) if e.contains("超过")));
+
+    // 超过内存上限的脚本同样中止
+    let chain_id = load(
+        &engine,
+        "script",
+        json!({ "script": "const parts = []; while (true) { parts.push('x'.repeat(1 << 20)); }" }),
+    )
+    .await?;
+    let result = run(&engine, chain_id, json!({})).await;
+    info!("内存超限: {:?}", result);
+    assert!(is_runtime_error(&result));
+
+    info!("脚本限制验证通过");
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use rule_rs::engine::SecretResolver;
+use rule_rs::types::RuleError;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, Level};
+
+const CHAIN_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c5001";
+const TRANSFORM_ID: &str = "3f2504e0-4f89-11d3-9a0c-0305e82c5002";
+
+/// 生成规则链: 起始 -> 转换,转换模板引用占位符
+fn chain(template: Value) -> Value {
+    json!({
+        "id": CHAIN_ID,
+        "name": "配置占位符示例",
+        "root": true,
+        "nodes": [
+            {
+                "id": "3f2504e0-4f89-11d3-9a0c-0305e82c5000",
+                "type_name": "start",
+                "chain_id": CHAIN_ID,
+                "config": {},
+                "layout": { "x": 50, "y": 100 }
+            },
+            {
+                "id": TRANSFORM_ID,
+                "type_name": "transform",
+                "chain_id": CHAIN_ID,
+                "config": { "template": template },
+                "layout": { "x": 200, "y": 100 }
+            }
+        ],
+        "connections": [
+            {
+                "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c5000",
+                "to_id": TRANSFORM_ID,
+                "type_name": "success"
+            }
+        ],
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+}
+
+/// 模拟外部密钥服务的解析器
+#[derive(Debug)]
+struct VaultResolver {
+    secrets: HashMap<String, String>,
+}
+
+#[async_trait]
+impl SecretResolver for VaultResolver {
+    async fn resolve(&self, name: &str) -> Result<Option<String>, RuleError> {
+        Ok(self.secrets.get(name).cloned())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 通过环境变量提供配置,规则链定义中不再保存明文
+    std::env::set_var("SECRET_EXAMPLE_API_HOST", "api.example.com");
+
+    // 创建引擎实例并注册 vault 前缀的解析器
+    let vault = VaultResolver {
+        secrets: HashMap::from([("weather/api_key".to_string(), "s3cr3t".to_string())]),
+    };
+    let engine = RuleEngine::new()
+        .await
+        .with_secret_resolver("vault", Arc::new(vault));
+
+    // 加载时替换 env 和 vault 占位符,消息模板变量保持不变
+    let chain_id = engine
+        .load_chain_value(chain(json!({
+            "url": "https://${env:SECRET_EXAMPLE_API_HOST}/v1?key=${vault:weather/api_key}",
+            "city": "${msg.city}"
+        })))
+        .await?;
+    let result = engine
+        .process_msg(
+            chain_id,
+            Message::new("query", json!({ "city": "Shanghai" })),
+        )
+        .await?;
+    info!("转换结果: {}", result.data);
+    assert_eq!(
+        result.data,
+        json!({
+            "url": "https://api.example.com/v1?key=s3cr3t",
+            "city": "Shanghai"
+        })
+    );
+
+    // 缺少环境变量时加载失败,错误信息包含变量名和节点ID
+    for (template, missing) in [
+        (
+            "${env:SECRET_EXAMPLE_MISSING}",
+            "env:SECRET_EXAMPLE_MISSING",
+        ),
+        ("${vault:unknown}", "vault:unknown"),
+    ] {
+        match engine
+            .load_chain_value(chain(json!({ "value": template })))
+            .await
+        {
+            Err(RuleError::ConfigError(e)) => {
+                info!("加载失败: {}", e);
+                assert!(e.contains(missing) && e.contains(TRANSFORM_ID));
+            }
+            other => panic!("期望配置错误, 实际: {:?}", other),
+        }
+    }
+
+    info!("配置占位符验证通过");
+    Ok(())
+}
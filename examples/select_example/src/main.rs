@@ -0,0 +1,203 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration};
+use tracing::{info, Level};
+
+/// 生成规则链: 起始 -> 并行 -> 两个后端 -> 竞争 -> 记录(成功/超时)
+fn hedged_chain(index: u8, fast_ms: u64, slow_ms: u64, timeout_ms: u64) -> String {
+    let chain_id = format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}01", 80 + index);
+    let node_id = |n: u8| format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}{:02}", 80 + index, n);
+    let node = |n: u8, type_name: &str, config: Value| {
+        json!({
+            "id": node_id(n),
+            "type_name": type_name,
+            "chain_id": chain_id,
+            "config": config,
+            "layout": { "x": 100 * n as u32, "y": 100 }
+        })
+    };
+    let connection = |from: u8, to: u8, type_name: &str| {
+        json!({
+            "from_id": node_id(from),
+            "to_id": node_id(to),
+            "type_name": type_name
+        })
+    };
+
+    json!({
+        "id": chain_id,
+        "name": format!("竞争示例 {}", index),
+        "root": true,
+        "nodes": [
+            node(0, "start", json!({})),
+            node(2, "fork", json!({})),
+            node(3, "custom/backend", json!({ "name": "primary", "delay_ms": fast_ms })),
+            node(4, "custom/backend", json!({ "name": "replica", "delay_ms": slow_ms })),
+            node(5, "select", json!({ "timeout_ms": timeout_ms })),
+            node(6, "custom/record", json!({ "tag": "success" })),
+            node(7, "custom/record", json!({ "tag": "error" }))
+        ],
+        "connections": [
+            connection(0, 2, "success"),
+            connection(2, 3, "success"),
+            connection(2, 4, "success"),
+            connection(3, 5, "success"),
+            connection(4, 5, "success"),
+            connection(5, 6, "success"),
+            connection(5, 7, "error")
+        ],
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+    .to_string()
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct BackendConfig {
+    name: String,
+    delay_ms: u64,
+}
+
+/// 后端节点,模拟响应时间不同的冗余服务
+#[derive(Debug)]
+struct BackendNode {
+    config: BackendConfig,
+}
+
+#[async_trait]
+impl NodeHandler for BackendNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        sleep(Duration::from_millis(self.config.delay_ms)).await;
+        let mut msg = msg;
+        msg.data["backend"] = json!(self.config.name);
+        ctx.send_next(msg.clone()).await?;
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/backend".to_string(),
+            name: "后端节点".to_string(),
+            description: "模拟冗余后端服务".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+/// 记录 (分支标签, 响应的后端)
+type Records = Arc<Mutex<Vec<(String, Value)>>>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RecordConfig {
+    tag: String,
+}
+
+#[derive(Debug)]
+struct RecordNode {
+    config: RecordConfig,
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        self.records
+            .lock()
+            .unwrap()
+            .push((self.config.tag.clone(), msg.data["backend"].clone()));
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册后端节点和记录节点
+    engine
+        .register_node_type(
+            "custom/backend",
+            Arc::new(|config| {
+                let config: BackendConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(BackendNode { config }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |config| {
+                let config: RecordConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(RecordNode {
+                    config,
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    let take = || std::mem::take(&mut *records.lock().unwrap());
+
+    // 对冲请求: 只转发较快后端的响应,较慢的响应被丢弃
+    let hedged = engine.load_chain(&hedged_chain(1, 50, 200, 1000)).await?;
+    let result = engine
+        .process_msg(hedged, Message::new("query", json!({ "key": "a" })))
+        .await?;
+    assert_eq!(result.data["backend"], "primary");
+    assert_eq!(take(), vec![("success".to_string(), json!("primary"))]);
+
+    // 每条消息独立竞争
+    for key in ["b", "c"] {
+        engine
+            .process_msg(hedged, Message::new("query", json!({ "key": key })))
+            .await?;
+    }
+    assert_eq!(take().len(), 2);
+
+    // 所有后端都超过等待时间: 第一个到达的分支走超时分支
+    let timeout = engine.load_chain(&hedged_chain(2, 150, 200, 100)).await?;
+    let result = engine
+        .process_msg(timeout, Message::new("query", json!({ "key": "d" })))
+        .await?;
+    info!("超时结果: {:?}", result.metadata.get("error"));
+    assert!(result.metadata.contains_key("error"));
+    assert_eq!(take(), vec![("error".to_string(), json!("primary"))]);
+
+    info!("竞争节点验证通过");
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{NodeDescriptor, NodeType, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, NodeContext, RuleEngine, RuleError};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, Level};
+use uuid::Uuid;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SplitConfig {
+    concurrent: bool,
+}
+
+/// 拆分节点,把 msg.data.items 中的每一项作为一条消息发送到下游
+#[derive(Debug)]
+struct SplitNode {
+    config: SplitConfig,
+}
+
+#[async_trait]
+impl NodeHandler for SplitNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let items = msg.data["items"].as_array().cloned().unwrap_or_default();
+        let msgs = items
+            .into_iter()
+            .map(|item| {
+                let mut item_msg = Message::new("item", item);
+                item_msg.metadata = msg.metadata.clone();
+                item_msg
+            })
+            .collect();
+
+        if self.config.concurrent {
+            ctx.send_all_concurrent(msgs).await?;
+        } else {
+            ctx.send_all(msgs).await?;
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/split".to_string(),
+            name: "拆分节点".to_string(),
+            description: "将列表中的每一项拆分为单独的消息".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+/// 收集节点,按 msg.data.delay_ms 延迟后记录收到的数据,delay_ms 为负数时返回错误
+#[derive(Debug)]
+struct CollectNode {
+    received: Arc<Mutex<Vec<i64>>>,
+}
+
+#[async_trait]
+impl NodeHandler for CollectNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let delay = msg.data["delay_ms"].as_i64().unwrap_or_default();
+        if delay < 0 {
+            return Err(RuleError::ComponentError(format!("无效的延迟: {}", delay)));
+        }
+        tokio::time::sleep(Duration::from_millis(delay as u64)).await;
+        self.received.lock().unwrap().push(delay);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/collect".to_string(),
+            name: "收集节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+/// 加载 起始 -> 拆分 -> 收集 规则链
+async fn load_chain(engine: &RuleEngine, concurrent: bool) -> Uuid {
+    let mut builder = RuleChainBuilder::new("批量发送示例");
+    let start = builder.add_node("start", json!({}));
+    let split = builder.add_node("custom/split", json!({ "concurrent": concurrent }));
+    let collect = builder.add_node("custom/collect", json!({}));
+    let chain = builder
+        .connect(start, split, "success")
+        .connect(split, collect, "success")
+        .root(true)
+        .build();
+    engine.load_rule_chain(chain).await.unwrap()
+}
+
+fn batch(delays: &[i64]) -> Message {
+    let items: Vec<_> = delays.iter().map(|d| json!({ "delay_ms": d })).collect();
+    Message::new("batch", json!({ "items": items }))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    // 注册自定义节点
+    engine
+        .register_node_type(
+            "custom/split",
+            Arc::new(|config| {
+                let config: SplitConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(SplitNode { config }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+    let collected = received.clone();
+    engine
+        .register_node_type(
+            "custom/collect",
+            Arc::new(move |_| {
+                Ok(Arc::new(CollectNode {
+                    received: collected.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+    let take = || std::mem::take(&mut *received.lock().unwrap());
+
+    // send_all: 下游按消息顺序依次执行
+    let sequential = load_chain(&engine, false).await;
+    engine.process_msg(sequential, batch(&[30, 10, 20])).await?;
+    let order = take();
+    info!("顺序发送: {:?}", order);
+    assert_eq!(order, [30, 10, 20]);
+
+    // 部分消息失败时其余消息仍会发送,错误按序号汇总
+    match engine
+        .process_msg(sequential, batch(&[10, -1, 20, -2]))
+        .await
+    {
+        Err(RuleError::SendAllFailed { total, errors }) => {
+            info!("批量发送失败: {:?}", errors);
+            assert_eq!(total, 4);
+            let indexes: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+            assert_eq!(indexes, [1, 3]);
+        }
+        other => panic!("期望批量发送错误, 实际: {:?}", other),
+    }
+    assert_eq!(take(), [10, 20]);
+
+    // send_all_concurrent: 下游并发执行,延迟短的先完成
+    let concurrent = load_chain(&engine, true).await;
+    engine.process_msg(concurrent, batch(&[30, 10, 20])).await?;
+    let order = take();
+    info!("并发发送: {:?}", order);
+    assert_eq!(order, [10, 20, 30]);
+
+    info!("批量发送验证通过");
+    Ok(())
+}
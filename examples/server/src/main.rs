@@ -1,21 +1,23 @@
 use axum::{
     debug_handler,
     extract::{Path, State},
-    http::StatusCode,
+    http::{header::CONTENT_TYPE, StatusCode},
     response::IntoResponse,
     response::Json,
     routing::{delete, get, post, put},
     Router,
 };
 use rule_rs::{
-    engine::rule::RuleEngineTrait,
+    engine::{rule::RuleEngineTrait, ChainMetrics, FileChainStore},
     types::Message,
-    types::{NodeDescriptor, RuleChain},
+    types::{CancellationToken, ChainAnalysis, NodeDescriptor, NodeType, RuleChain, RuleError},
     RuleEngine,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use uuid::Uuid; // 引入 async_trait 宏
 
 // API 响应格式
@@ -60,6 +62,16 @@ struct Metadata {
     version: u32,
     created_at: u64,
     updated_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    input_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_error_rate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    window_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_executions: Option<usize>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -88,6 +100,8 @@ struct Position {
 #[derive(Clone)]
 struct AppState {
     engine: Arc<RuleEngine>,
+    /// 正在执行的消息,key为消息ID
+    executions: Arc<Mutex<HashMap<Uuid, CancellationToken>>>,
 }
 
 #[tokio::main]
@@ -97,25 +111,77 @@ async fn main() {
         .with_max_level(tracing::Level::DEBUG)
         .init();
 
-    // 创建规则引擎
-    let engine = Arc::new(RuleEngine::new().await);
+    // 创建规则引擎,设置 RULE_CHAIN_STORE_DIR 后规则链保存到该目录,重启后自动恢复
+    let engine = RuleEngine::new().await;
+    let engine = match std::env::var("RULE_CHAIN_STORE_DIR") {
+        Ok(dir) => {
+            let store = FileChainStore::new(&dir).expect("创建规则链存储失败");
+            engine
+                .with_store(Arc::new(store))
+                .await
+                .expect("加载已保存的规则链失败")
+        }
+        Err(_) => engine,
+    };
+    let engine = Arc::new(engine);
 
     // 创建路由
     let app = Router::new()
         .route("/api/components", get(list_components))
+        .route("/api/components/types", get(list_component_types))
         .route("/api/chains", post(create_chain))
+        .route("/api/chains/analyze", post(analyze_chain))
+        .route("/api/chains/validate", post(validate_chain))
         .route("/api/chains/{id}", get(get_chain))
         .route("/api/chains/{id}", put(update_chain))
         .route("/api/chains/{id}", delete(delete_chain))
+        .route("/api/chains/{id}/dot", get(get_chain_dot))
+        .route("/api/chains/{id}/mermaid", get(get_chain_mermaid))
+        .route("/api/chains/{id}/metrics", get(get_chain_metrics))
         .route("/api/chains/execute/{id}", post(execute_chain))
+        .route("/api/executions", get(list_executions))
+        .route("/api/executions/{id}/cancel", post(cancel_execution))
         // .layer(TraceLayer::new_for_http())
         .with_state(AppState {
             engine: engine.clone(),
+            executions: Arc::new(Mutex::new(HashMap::new())),
         });
 
     // run our app with hyper, listening globally on port 3000
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal(engine))
+        .await
+        .unwrap();
+}
+
+// 收到 Ctrl+C 或 SIGTERM 后关闭规则引擎,等待正在处理的消息完成
+async fn shutdown_signal(engine: Arc<RuleEngine>) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.unwrap();
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .unwrap()
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("开始关闭规则引擎");
+    match engine.shutdown(Duration::from_secs(30)).await {
+        Ok(()) => tracing::info!("规则引擎已关闭"),
+        Err(e) => tracing::error!("{}", e),
+    }
 }
 
 // 获取所有组件
@@ -125,19 +191,52 @@ async fn list_components(State(state): State<AppState>) -> Json<ApiResponse<Vec<
     Json(ApiResponse::success(components))
 }
 
+// 获取组件类型名称到节点类型的映射
+async fn list_component_types(
+    State(state): State<AppState>,
+) -> Json<ApiResponse<HashMap<String, NodeType>>> {
+    let types = state.engine.component_types().await;
+    Json(ApiResponse::success(types))
+}
+
 // 创建规则链
 async fn create_chain(
     State(state): State<AppState>,
     Json(req): Json<RuleChainRequest>,
 ) -> Result<Json<ApiResponse<Uuid>>, Json<ApiResponse<()>>> {
-    let chain = serde_json::to_string(&req).unwrap();
+    let chain = serde_json::to_value(&req).unwrap();
     println!("chain: {}", chain);
-    match state.engine.load_chain(&chain).await {
+    match state.engine.load_chain_value(chain).await {
         Ok(id) => Ok(Json(ApiResponse::success(id))),
         Err(e) => Err(Json(ApiResponse::error(400, &e.to_string()))),
     }
 }
 
+// 分析规则链结构,保存前标记不可达的节点
+async fn analyze_chain(
+    State(state): State<AppState>,
+    Json(req): Json<RuleChainRequest>,
+) -> Result<Json<ApiResponse<ChainAnalysis>>, Json<ApiResponse<()>>> {
+    let chain: RuleChain = serde_json::to_value(&req)
+        .and_then(serde_json::from_value)
+        .map_err(|e| Json(ApiResponse::error(400, &e.to_string())))?;
+    Ok(Json(ApiResponse::success(
+        state.engine.analyze_chain(&chain),
+    )))
+}
+
+// 只校验规则链定义,不加载也不创建版本
+async fn validate_chain(
+    State(state): State<AppState>,
+    Json(req): Json<RuleChainRequest>,
+) -> Result<Json<ApiResponse<()>>, Json<ApiResponse<()>>> {
+    let chain = serde_json::to_string(&req).unwrap();
+    match state.engine.validate_chain(&chain).await {
+        Ok(()) => Ok(Json(ApiResponse::success(()))),
+        Err(e) => Err(Json(ApiResponse::error(400, &e.to_string()))),
+    }
+}
+
 // 获取规则链
 #[debug_handler]
 async fn get_chain(State(state): State<AppState>, Path(id): Path<Uuid>) -> impl IntoResponse {
@@ -154,6 +253,51 @@ async fn get_chain(State(state): State<AppState>, Path(id): Path<Uuid>) -> impl
     }
 }
 
+// 导出规则链的 Graphviz DOT 图
+async fn get_chain_dot(State(state): State<AppState>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    match state.engine.export_chain_dot(id).await {
+        Some(dot) => ([(CONTENT_TYPE, "text/vnd.graphviz")], dot).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error(404, "Rule chain not found")),
+        )
+            .into_response(),
+    }
+}
+
+// 导出规则链的 Mermaid 流程图
+async fn get_chain_mermaid(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.engine.export_chain_mermaid(id).await {
+        Some(mermaid) => ([(CONTENT_TYPE, "text/plain; charset=utf-8")], mermaid).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error(404, "Rule chain not found")),
+        )
+            .into_response(),
+    }
+}
+
+// 获取规则链的执行统计
+async fn get_chain_metrics(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.engine.chain_metrics(id).await {
+        Some(metrics) => Json(ApiResponse::success(metrics)).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<ChainMetrics>::error(
+                404,
+                "Rule chain not found",
+            )),
+        )
+            .into_response(),
+    }
+}
+
 // 更新规则链
 #[debug_handler]
 async fn update_chain(
@@ -163,14 +307,6 @@ async fn update_chain(
 ) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
     println!("update_chain: {:?}", req);
     println!("id: {:?}", id);
-    // 检查规则链是否存在
-    if state.engine.get_chain(id).await.is_none() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(ApiResponse::error(404, "Rule chain not found")),
-        ));
-    }
-
     // 构造更新后的规则链配置
     let chain = json!({
         "id": id,
@@ -181,13 +317,19 @@ async fn update_chain(
         "metadata": {
             "version": state.engine.get_current_version().await + 1,
             "created_at": chrono::Utc::now().timestamp_millis(),
-            "updated_at": chrono::Utc::now().timestamp_millis()
+            "updated_at": chrono::Utc::now().timestamp_millis(),
+            "input_type": req.metadata.input_type,
+            "output_type": req.metadata.output_type
         }
     });
 
     // 更新规则链
-    match state.engine.load_chain(&chain.to_string()).await {
+    match state.engine.update_chain_value(id, chain).await {
         Ok(_) => Ok(Json(ApiResponse::success(()))),
+        Err(RuleError::ChainNotFound(_)) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(404, "Rule chain not found")),
+        )),
         Err(e) => Err((
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(400, &e.to_string())),
@@ -228,18 +370,65 @@ async fn execute_chain(
     if state.engine.get_chain(id).await.is_none() {
         return Json(ApiResponse::<()>::error(404, "Rule chain not found")).into_response();
     }
-    // 启动异步任务处理消息
+    // 关闭中不再接收新消息
+    if state.engine.is_shutting_down() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::<()>::error(
+                503,
+                "Rule engine is shutting down",
+            )),
+        )
+            .into_response();
+    }
+    // 启动异步任务处理消息,执行ID为消息ID,可用于取消本次执行
+    let execution_id = msg.id;
+    let token = CancellationToken::new();
+    state
+        .executions
+        .lock()
+        .unwrap()
+        .insert(execution_id, token.clone());
     tokio::spawn({
         let engine = state.engine.clone();
+        let executions = state.executions.clone();
         async move {
-            if let Err(e) = engine.process_msg(id, msg).await {
+            if let Err(e) = engine.process_msg_with_token(id, msg, token).await {
                 tracing::error!("处理消息失败: {}", e);
             }
+            executions.lock().unwrap().remove(&execution_id);
         }
     });
 
     // 立即返回成功响应
-    Json(ApiResponse::success("消息已接收并开始处理")).into_response()
+    Json(ApiResponse::success(
+        json!({ "execution_id": execution_id }),
+    ))
+    .into_response()
+}
+
+// 列出正在处理的消息及其正在执行的节点
+async fn list_executions(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.engine.running_executions()))
+}
+
+// 取消正在执行的消息
+#[debug_handler]
+async fn cancel_execution(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.executions.lock().unwrap().get(&id) {
+        Some(token) => {
+            token.cancel();
+            Json(ApiResponse::success(())).into_response()
+        }
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error(404, "Execution not found")),
+        )
+            .into_response(),
+    }
 }
 
 // // 执行规则链
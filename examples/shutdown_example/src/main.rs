@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration};
+use tracing::{info, Level};
+
+const RULE_CHAIN: &str = r#"{
+    "id": "3f2504e0-4f89-11d3-9a0c-0305e82c9501",
+    "name": "关闭示例",
+    "root": true,
+    "nodes": [
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c9500",
+            "type_name": "start",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9501",
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c9502",
+            "type_name": "custom/slow",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9501",
+            "config": {},
+            "layout": { "x": 200, "y": 100 }
+        }
+    ],
+    "connections": [
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9500",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c9502",
+            "type_name": "success"
+        }
+    ],
+    "metadata": {
+        "version": 1,
+        "created_at": 1679800000,
+        "updated_at": 1679800000
+    }
+}"#;
+
+/// 已处理完成的消息
+type Completed = Arc<Mutex<Vec<String>>>;
+
+/// 耗时节点,处理2秒后记录消息
+#[derive(Debug)]
+struct SlowNode {
+    completed: Completed,
+}
+
+#[async_trait]
+impl NodeHandler for SlowNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        sleep(Duration::from_secs(2)).await;
+        info!("消息处理完成: {}", msg.data);
+        self.completed
+            .lock()
+            .unwrap()
+            .push(msg.data["name"].as_str().unwrap_or_default().to_string());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/slow".to_string(),
+            name: "耗时节点".to_string(),
+            description: "模拟耗时的处理".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+// 暂停 tokio 时间,等待会被自动快进,无需真实等待
+#[tokio::main(flavor = "current_thread", start_paused = true)]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = Arc::new(RuleEngine::new().await);
+
+    // 注册耗时节点
+    let completed: Completed = Arc::new(Mutex::new(Vec::new()));
+    let node_completed = completed.clone();
+    engine
+        .register_node_type(
+            "custom/slow",
+            Arc::new(move |_| {
+                Ok(Arc::new(SlowNode {
+                    completed: node_completed.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    let chain_id = engine.load_chain(RULE_CHAIN).await?;
+
+    // 后台处理一条耗时消息
+    let task = tokio::spawn({
+        let engine = engine.clone();
+        async move {
+            engine
+                .process_msg(
+                    chain_id,
+                    Message::new("job", json!({ "name": "in-flight" })),
+                )
+                .await
+        }
+    });
+    sleep(Duration::from_millis(10)).await;
+
+    // 等待时间不足时返回超时错误,正在执行的消息不受影响
+    match engine.shutdown(Duration::from_millis(500)).await {
+        Err(e) => info!("关闭超时: {}", e),
+        Ok(()) => panic!("期望关闭超时"),
+    }
+    assert!(engine.is_shutting_down());
+
+    // 关闭后拒绝新消息
+    match engine
+        .process_msg(chain_id, Message::new("job", json!({ "name": "rejected" })))
+        .await
+    {
+        Err(RuleError::ShuttingDown) => info!("新消息被拒绝"),
+        other => panic!("期望关闭中错误, 实际: {:?}", other),
+    }
+
+    // 再次等待,正在执行的消息处理完成后关闭成功
+    engine.shutdown(Duration::from_secs(5)).await?;
+    assert!(task.await?.is_ok());
+    assert_eq!(*completed.lock().unwrap(), vec!["in-flight"]);
+
+    info!("优雅关闭验证通过");
+    Ok(())
+}
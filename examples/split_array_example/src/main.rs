@@ -0,0 +1,99 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{info, Event, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+/// 记录日志节点输出的内容
+type Captured = Arc<Mutex<Vec<String>>>;
+
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+/// 收集日志节点输出的日志
+struct CaptureLayer(Captured);
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        if let Some(text) = visitor.0.strip_prefix("log组件输出: ") {
+            self.0.lock().unwrap().push(text.to_string());
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统,同时收集日志节点的输出
+    let captured = Captured::default();
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(CaptureLayer(captured.clone()))
+        .init();
+
+    let engine = RuleEngine::new().await;
+
+    // 起始 -> 拆分 orders 数组 -> 日志
+    let mut builder = RuleChainBuilder::new("数组拆分示例");
+    let start = builder.add_node("start", json!({}));
+    let split = builder.add_node("split_array", json!({ "field": "orders" }));
+    let log = builder.add_node(
+        "log",
+        json!({ "template": "订单 ${msg.data.id} (${msg.metadata.index}/${msg.metadata.total})" }),
+    );
+    builder
+        .connect(start, split, "success")
+        .connect(split, log, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    let batch = json!({ "orders": [{ "id": "A" }, { "id": "B" }, { "id": "C" }] });
+    engine
+        .process_msg(chain_id, Message::new("batch", batch.clone()))
+        .await?;
+
+    // 默认按顺序发送,日志顺序与数组顺序一致
+    let outputs = std::mem::take(&mut *captured.lock().unwrap());
+    assert_eq!(outputs, vec!["订单 A (0/3)", "订单 B (1/3)", "订单 C (2/3)"]);
+
+    // 并发发送: 同样输出三条日志,但顺序不确定
+    let mut builder = RuleChainBuilder::new("数组并发拆分示例");
+    let start = builder.add_node("start", json!({}));
+    let split = builder.add_node(
+        "split_array",
+        json!({ "field": "orders", "concurrent": true }),
+    );
+    let log = builder.add_node("log", json!({ "template": "订单 ${msg.data.id}" }));
+    builder
+        .connect(start, split, "success")
+        .connect(split, log, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+    engine
+        .process_msg(chain_id, Message::new("batch", batch))
+        .await?;
+
+    let mut outputs = std::mem::take(&mut *captured.lock().unwrap());
+    outputs.sort();
+    assert_eq!(outputs, vec!["订单 A", "订单 B", "订单 C"]);
+
+    // 不是数组时节点返回错误
+    let result = engine
+        .process_msg(chain_id, Message::new("batch", json!({ "orders": 1 })))
+        .await;
+    assert!(result.is_err());
+
+    info!("数组拆分示例验证通过");
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use rule_rs::testing::register_mock;
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tracing::{info, Level};
+
+/// 起始 -> 分流 -> 每个分支一个测试节点,返回每条消息经过的分支
+async fn route(engine: &RuleEngine, config: Value, count: usize) -> Result<Vec<String>, RuleError> {
+    let mut builder = RuleChainBuilder::new("分流示例");
+    let start = builder.add_node("start", json!({}));
+    let split = builder.add_node("split", config.clone());
+    builder.connect(start, split, "success");
+    let mut mocks = Vec::new();
+    for branch in config["branches"].as_array().unwrap() {
+        let name = branch["name"].as_str().unwrap().to_string();
+        let type_name = format!("mock/{}", name);
+        let mock = register_mock(engine, &type_name).await;
+        let node = builder.add_node(&type_name, json!({}));
+        builder.connect(split, node, &name);
+        mocks.push((name, mock));
+    }
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    let mut branches = Vec::new();
+    for seq in 0..count {
+        mocks.iter().for_each(|(_, mock)| mock.clear());
+        engine
+            .process_msg(chain_id, Message::new("request", json!({ "seq": seq })))
+            .await?;
+        // 每条消息只会到达一个分支
+        let routed: Vec<_> = mocks
+            .iter()
+            .filter(|(_, mock)| mock.count() > 0)
+            .map(|(name, _)| name.clone())
+            .collect();
+        assert_eq!(routed.len(), 1);
+        branches.push(routed[0].clone());
+    }
+    Ok(branches)
+}
+
+fn share(branches: &[String], name: &str) -> f64 {
+    branches.iter().filter(|b| *b == name).count() as f64 / branches.len() as f64
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 90/10 灰度,10000 条消息的分布误差在 1.5% 以内
+    let config = json!({
+        "branches": [
+            { "name": "stable", "weight": 90 },
+            { "name": "canary", "weight": 10 }
+        ]
+    });
+    let branches = route(&engine, config, 10_000).await?;
+    let canary = share(&branches, "canary");
+    info!("canary 占比 {:.4}", canary);
+    assert!((canary - 0.10).abs() < 0.015);
+
+    // 权重不要求总和为 100,按总和归一化;权重为 0 的分支不会被选中
+    let config = json!({
+        "branches": [
+            { "name": "a", "weight": 1 },
+            { "name": "b", "weight": 3 },
+            { "name": "off", "weight": 0 }
+        ],
+        "seed": 7
+    });
+    let branches = route(&engine, config.clone(), 8_000).await?;
+    let counts = branches.iter().fold(HashMap::new(), |mut counts, b| {
+        *counts.entry(b.clone()).or_insert(0) += 1;
+        counts
+    });
+    info!("1:3 分流结果: {:?}", counts);
+    assert!((share(&branches, "a") - 0.25).abs() < 0.02);
+    assert!((share(&branches, "b") - 0.75).abs() < 0.02);
+    assert!(!counts.contains_key("off"));
+
+    // 相同的种子得到相同的分流序列
+    assert_eq!(route(&engine, config, 8_000).await?, branches);
+
+    // 无效的权重配置在加载时报错
+    for branches in [
+        json!([]),
+        json!([{ "name": "a", "weight": 0 }]),
+        json!([{ "name": "a", "weight": -1 }, { "name": "b", "weight": 2 }]),
+        json!([{ "name": "a", "weight": 1 }, { "name": "a", "weight": 2 }]),
+    ] {
+        let result = route(&engine, json!({ "branches": branches }), 1).await;
+        assert!(matches!(result, Err(RuleError::ConfigError(_))));
+    }
+
+    info!("分流节点验证通过");
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::json;
+use tracing::{info, Level};
+
+// 子规则链: 对消息数据进行转换
+const SUB_CHAIN: &str = r#"{
+    "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3401",
+    "name": "子规则链",
+    "root": false,
+    "nodes": [
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3400",
+            "type_name": "start",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3401",
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3402",
+            "type_name": "script",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3401",
+            "config": {
+                "script": "return { greeting: 'Hello ' + msg.data.name, value: msg.data.value * 2 };"
+            },
+            "layout": { "x": 200, "y": 100 }
+        }
+    ],
+    "connections": [
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3400",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3402",
+            "type_name": "success"
+        }
+    ],
+    "metadata": {
+        "version": 1,
+        "created_at": 1679800000,
+        "updated_at": 1679800000
+    }
+}"#;
+
+// 主规则链: 调用子规则链,并由下游节点输出子规则链的转换结果
+const MAIN_CHAIN: &str = r#"{
+    "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+    "name": "主规则链",
+    "root": true,
+    "nodes": [
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3300",
+            "type_name": "start",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "type_name": "subchain",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": {
+                "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3401"
+            },
+            "layout": { "x": 200, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3303",
+            "type_name": "log",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": {
+                "template": "子规则链输出: ${msg.data.greeting}, value: ${msg.data.value}"
+            },
+            "layout": { "x": 350, "y": 100 }
+        }
+    ],
+    "connections": [
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3300",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "type_name": "success"
+        },
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3303",
+            "type_name": "success"
+        }
+    ],
+    "metadata": {
+        "version": 1,
+        "created_at": 1679800000,
+        "updated_at": 1679800000
+    }
+}"#;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 先加载子规则链,再加载引用它的主规则链
+    let subchain_id = engine.load_chain(SUB_CHAIN).await?;
+    info!("子规则链加载成功: {}", subchain_id);
+    let chain_id = engine.load_chain(MAIN_CHAIN).await?;
+    info!("主规则链加载成功: {}", chain_id);
+
+    // 创建测试消息
+    let msg = Message::new(
+        "test",
+        json!({
+            "name": "rule-rs",
+            "value": 21
+        }),
+    );
+
+    // 处理消息,主规则链的 log 节点收到子规则链转换后的数据
+    let result = engine.process_msg(chain_id, msg).await?;
+    info!("处理结果: {:?}", result.data);
+    assert_eq!(result.data["greeting"], "Hello rule-rs");
+    assert_eq!(result.data["value"], 42);
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use rule_rs::engine::rule::RuleEngineTrait;
+use rule_rs::testing::{register_mock, MockNode};
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{Message, RuleEngine, RuleError};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tracing::{info, Level};
+
+const BRANCHES: [&str; 6] = ["vip_big", "vip", "internal", "big", "fallback", "unmatched"];
+
+/// 处理消息并返回收到消息的分支
+async fn route(
+    engine: &RuleEngine,
+    chain_id: uuid::Uuid,
+    mocks: &HashMap<&str, MockNode>,
+    data: Value,
+) -> Result<Vec<&'static str>, RuleError> {
+    mocks.values().for_each(MockNode::clear);
+    engine
+        .process_msg(chain_id, Message::new("order", data))
+        .await?;
+    Ok(BRANCHES
+        .into_iter()
+        .filter(|branch| mocks[branch].count() > 0)
+        .collect())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+    let mut mocks = HashMap::new();
+    for branch in BRANCHES {
+        let mock = register_mock(&engine, &format!("mock/{}", branch)).await;
+        mocks.insert(branch, mock);
+    }
+
+    // 混合等值、正则和 JS 条件的分支
+    let mut builder = RuleChainBuilder::new("结构化匹配示例");
+    let start = builder.add_node("start", json!({}));
+    let switch = builder.add_node(
+        "switch",
+        json!({
+            "cases": [
+                // 等值匹配
+                { "name": "vip", "field": "user.level", "equals": "vip" },
+                // 正则匹配
+                { "name": "internal", "field": "user.email", "matches_regex": "@example\\.com$" },
+                // JS 条件
+                { "name": "big", "condition": "data.amount > 1000" },
+                // 兜底分支,优先级最低
+                { "name": "fallback", "field": "amount", "matches_regex": "", "priority": -1 },
+                // 等值匹配和 JS 条件同时满足,优先级最高
+                {
+                    "name": "vip_big",
+                    "field": "user.level",
+                    "equals": "vip",
+                    "condition": "data.amount > 1000",
+                    "priority": 10
+                }
+            ],
+            "default_next": "unmatched"
+        }),
+    );
+    builder.connect(start, switch, "success");
+    for branch in BRANCHES {
+        let node = builder.add_node(&format!("mock/{}", branch), json!({}));
+        builder.connect(switch, node, branch);
+    }
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    let cases = [
+        (
+            json!({ "user": { "level": "vip" }, "amount": 5000 }),
+            "vip_big",
+        ),
+        (json!({ "user": { "level": "vip" }, "amount": 10 }), "vip"),
+        // 正则分支配置在 JS 条件分支之前
+        (
+            json!({ "user": { "email": "a@example.com" }, "amount": 5000 }),
+            "internal",
+        ),
+        (
+            json!({ "user": { "email": "a@other.com" }, "amount": 5000 }),
+            "big",
+        ),
+        (json!({ "user": {}, "amount": 1 }), "fallback"),
+        // 字段不存在时不匹配
+        (json!({ "user": {} }), "unmatched"),
+    ];
+    for (data, expected) in cases {
+        let routed = route(&engine, chain_id, &mocks, data.clone()).await?;
+        info!("{} -> {:?}", data, routed);
+        assert_eq!(routed, vec![expected]);
+    }
+
+    // all_matches 模式按优先级发送到所有匹配的分支
+    let mut builder = RuleChainBuilder::new("全部匹配");
+    let start = builder.add_node("start", json!({}));
+    let switch = builder.add_node(
+        "switch",
+        json!({
+            "mode": "all_matches",
+            "cases": [
+                { "name": "vip", "field": "user.level", "equals": "vip" },
+                { "name": "big", "field": "amount", "matches_regex": "^\\d{4,}$" }
+            ]
+        }),
+    );
+    builder.connect(start, switch, "success");
+    for branch in ["vip", "big"] {
+        let node = builder.add_node(&format!("mock/{}", branch), json!({}));
+        builder.connect(switch, node, branch);
+    }
+    let all_id = engine.load_rule_chain(builder.root(true).build()).await?;
+    let routed = route(
+        &engine,
+        all_id,
+        &mocks,
+        json!({ "user": { "level": "vip" }, "amount": 5000 }),
+    )
+    .await?;
+    assert_eq!(routed, vec!["vip", "big"]);
+
+    // 无效的分支配置在加载时报错
+    for case in [
+        json!({ "name": "bad", "field": "amount", "matches_regex": "(" }),
+        json!({ "name": "bad", "field": "amount" }),
+        json!({ "name": "bad", "equals": 1 }),
+        json!({ "name": "bad" }),
+    ] {
+        let mut builder = RuleChainBuilder::new("无效配置");
+        let start = builder.add_node("start", json!({}));
+        let switch = builder.add_node("switch", json!({ "cases": [case] }));
+        let chain = builder.connect(start, switch, "success").root(true).build();
+        let result = engine.load_rule_chain(chain).await;
+        info!("无效配置: {:?}", result);
+        assert!(matches!(result, Err(RuleError::ConfigError(_))));
+    }
+
+    info!("结构化分支匹配验证通过");
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+
+/// 生成条件分支规则链
+///
+/// 规则链包含 hot(温度 > 30) 和 humid(湿度 > 80) 两个分支,
+/// `with_default` 为 true 时通过 `default_next` 路由到 normal 分支
+fn switch_chain(index: u8, mode: &str, with_default: bool) -> String {
+    let chain_id = format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}01", index);
+    let node_id = |n: u8| format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}{:02}", index, n);
+
+    let mut switch_config = json!({
+        "mode": mode,
+        "cases": [
+            {
+                "name": "hot",
+                "condition": "data.temperature > 30",
+                "description": "高温"
+            },
+            {
+                "name": "humid",
+                "condition": "msg.data.humidity > 80",
+                "description": "高湿"
+            }
+        ]
+    });
+    if with_default {
+        switch_config["default_next"] = json!("normal");
+    }
+
+    let mut nodes = vec![
+        json!({
+            "id": node_id(0),
+            "type_name": "start",
+            "chain_id": chain_id,
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        }),
+        json!({
+            "id": node_id(2),
+            "type_name": "switch",
+            "chain_id": chain_id,
+            "config": switch_config,
+            "layout": { "x": 200, "y": 100 }
+        }),
+    ];
+    let mut connections = vec![json!({
+        "from_id": node_id(0),
+        "to_id": node_id(2),
+        "type_name": "success"
+    })];
+    for (i, branch) in ["hot", "humid", "normal"].iter().enumerate() {
+        let id = node_id(3 + i as u8);
+        nodes.push(json!({
+            "id": id,
+            "type_name": "custom/record",
+            "chain_id": chain_id,
+            "config": { "name": branch },
+            "layout": { "x": 350, "y": 50 + 50 * i }
+        }));
+        connections.push(json!({
+            "from_id": node_id(2),
+            "to_id": id,
+            "type_name": branch
+        }));
+    }
+
+    json!({
+        "id": chain_id,
+        "name": format!("条件分支 {}", mode),
+        "root": true,
+        "nodes": nodes,
+        "connections": connections,
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+    .to_string()
+}
+
+/// 记录各分支收到的消息
+type Records = Arc<Mutex<Vec<String>>>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RecordConfig {
+    name: String,
+}
+
+/// 记录节点,保存收到消息的分支名称
+#[derive(Debug)]
+struct RecordNode {
+    config: RecordConfig,
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        info!("{} 分支收到消息: {}", self.config.name, msg.data);
+        self.records.lock().unwrap().push(self.config.name.clone());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册记录节点
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |config| {
+                let config: RecordConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(RecordNode {
+                    config,
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    let first_match = engine
+        .load_chain(&switch_chain(1, "first_match", true))
+        .await?;
+    let all_matches = engine
+        .load_chain(&switch_chain(2, "all_matches", true))
+        .await?;
+    let no_default = engine
+        .load_chain(&switch_chain(3, "first_match", false))
+        .await?;
+
+    let reading = |temperature: i64, humidity: i64| {
+        Message::new(
+            "sensor",
+            json!({ "temperature": temperature, "humidity": humidity }),
+        )
+    };
+    let take = |records: &Records| std::mem::take(&mut *records.lock().unwrap());
+
+    // first_match: 两个条件都成立时只发送到第一个分支
+    engine.process_msg(first_match, reading(35, 90)).await?;
+    assert_eq!(take(&records), vec!["hot"]);
+
+    // first_match: 没有条件成立时发送到默认分支
+    engine.process_msg(first_match, reading(20, 50)).await?;
+    assert_eq!(take(&records), vec!["normal"]);
+
+    // all_matches: 发送到所有条件成立的分支
+    engine.process_msg(all_matches, reading(35, 90)).await?;
+    assert_eq!(take(&records), vec!["hot", "humid"]);
+
+    engine.process_msg(all_matches, reading(20, 90)).await?;
+    assert_eq!(take(&records), vec!["humid"]);
+
+    // 没有条件成立且未配置默认分支: 不发送,原样返回消息
+    let msg = reading(20, 50);
+    let result = engine.process_msg(no_default, msg.clone()).await?;
+    assert!(take(&records).is_empty());
+    assert_eq!(result.data, msg.data);
+    assert!(result.get_meta_str("branch_name").is_none());
+
+    info!("条件分支模式验证通过");
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use rule_rs::types::{RuleChainBuilder, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use tracing::{info, Level};
+
+/// 加载 起始 -> 模板渲染 规则链,处理一条消息
+async fn render(engine: &RuleEngine, config: Value, msg: Message) -> Result<Message, RuleError> {
+    let mut builder = RuleChainBuilder::new("模板渲染示例");
+    let start = builder.add_node("start", json!({}));
+    let template = builder.add_node("template", config);
+    let chain = builder
+        .connect(start, template, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+    engine.process_msg(chain_id, msg).await
+}
+
+/// 订单消息,包含明细列表和元数据
+fn order() -> Message {
+    let mut msg = Message::new(
+        "order",
+        json!({
+            "customer": "Alice",
+            "items": [
+                { "name": "Book", "qty": 2 },
+                { "name": "Pen & Ink", "qty": 1 }
+            ],
+            "vip": true
+        }),
+    );
+    msg.set_meta("order_no", "A-1001");
+    msg
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // Handlebars: 循环和条件,输出不做 HTML 转义
+    let result = render(
+        &engine,
+        json!({
+            "engine": "handlebars",
+            "template": "Dear {{msg.data.customer}}, order {{msg.metadata.order_no}}:\n\
+                {{#each msg.data.items}}- {{name}} x{{qty}}\n{{/each}}\
+                {{#if msg.data.vip}}Thanks for being a VIP!{{/if}}",
+            "output_field": "email"
+        }),
+        order(),
+    )
+    .await?;
+    info!("邮件内容:\n{}", result.data["email"].as_str().unwrap());
+    assert_eq!(
+        result.data["email"],
+        "Dear Alice, order A-1001:\n- Book x2\n- Pen & Ink x1\nThanks for being a VIP!"
+    );
+    // 原有数据保留
+    assert_eq!(result.data["customer"], "Alice");
+
+    // Tera: 过滤器和表达式,结果写入默认的 output 字段
+    let result = render(
+        &engine,
+        json!({
+            "engine": "tera",
+            "template": "INSERT INTO orders (no, items) VALUES ('{{ msg.metadata.order_no }}', {{ msg.data.items | length }});"
+        }),
+        order(),
+    )
+    .await?;
+    info!("SQL: {}", result.data["output"]);
+    assert_eq!(
+        result.data["output"],
+        "INSERT INTO orders (no, items) VALUES ('A-1001', 2);"
+    );
+
+    // 模板语法错误在加载时返回配置错误
+    match render(
+        &engine,
+        json!({ "engine": "handlebars", "template": "{{#each msg.data.items}}" }),
+        order(),
+    )
+    .await
+    {
+        Err(RuleError::ConfigError(e)) => info!("加载失败: {}", e),
+        other => panic!("期望配置错误, 实际: {:?}", other),
+    }
+
+    info!("模板渲染验证通过");
+    Ok(())
+}
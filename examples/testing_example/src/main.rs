@@ -0,0 +1,111 @@
+use rule_rs::engine::rule::RuleEngineTrait;
+use rule_rs::testing::{assert_not_received, assert_received, register_mock};
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{Message, RuleEngine, RuleError};
+use serde_json::json;
+use tracing::{info, Level};
+
+/// 验证 switch 节点按条件路由到对应分支
+async fn test_switch_routing() -> Result<(), RuleError> {
+    let engine = RuleEngine::new().await;
+    let high = register_mock(&engine, "mock/high").await;
+    let low = register_mock(&engine, "mock/low").await;
+
+    // 起始 -> 条件分支 -high-> 测试节点
+    //                  -low->  测试节点
+    let mut builder = RuleChainBuilder::new("switch 测试");
+    let start = builder.add_node("start", json!({}));
+    let switch = builder.add_node(
+        "switch",
+        json!({
+            "cases": [
+                { "name": "high", "condition": "data.value > 10", "description": "大于 10" }
+            ],
+            "default_next": "low"
+        }),
+    );
+    let high_node = builder.add_node("mock/high", json!({}));
+    let low_node = builder.add_node("mock/low", json!({}));
+    let chain = builder
+        .connect(start, switch, "success")
+        .connect(switch, high_node, "high")
+        .connect(switch, low_node, "low")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    engine
+        .process_msg(chain_id, Message::new("test", json!({ "value": 42 })))
+        .await?;
+    assert_received(&high, |msg| msg.data["value"] == 42);
+    assert_not_received(&low);
+
+    engine
+        .process_msg(chain_id, Message::new("test", json!({ "value": 1 })))
+        .await?;
+    assert_received(&low, |msg| msg.data["value"] == 1);
+    assert_eq!(high.count(), 1);
+    Ok(())
+}
+
+/// 验证 fork 的每个分支都收到消息,join 合并所有分支的结果
+async fn test_fork_join() -> Result<(), RuleError> {
+    let engine = RuleEngine::new().await;
+    let left = register_mock(&engine, "mock/left").await;
+    let right = register_mock(&engine, "mock/right").await;
+    let joined = register_mock(&engine, "mock/joined").await;
+
+    // 起始 -> 分叉 -> 转换 -> 测试节点 -> 汇聚 -> 测试节点
+    //            -> 转换 -> 测试节点 ->
+    let mut builder = RuleChainBuilder::new("fork/join 测试");
+    let start = builder.add_node("start", json!({}));
+    let fork = builder.add_node("fork", json!({}));
+    let left_transform = builder.add_node("transform", json!({ "template": { "side": "left" } }));
+    let right_transform = builder.add_node("transform", json!({ "template": { "side": "right" } }));
+    let left_node = builder.add_node("mock/left", json!({}));
+    let right_node = builder.add_node("mock/right", json!({}));
+    let join = builder.add_node("join", json!({}));
+    let joined_node = builder.add_node("mock/joined", json!({}));
+    let chain = builder
+        .connect(start, fork, "success")
+        .connect(fork, left_transform, "success")
+        .connect(fork, right_transform, "success")
+        .connect(left_transform, left_node, "success")
+        .connect(right_transform, right_node, "success")
+        .connect(left_node, join, "success")
+        .connect(right_node, join, "success")
+        .connect(join, joined_node, "success")
+        .root(true)
+        .build();
+    let chain_id = engine.load_rule_chain(chain).await?;
+
+    let msg = Message::new("test", json!({ "value": 1 }));
+    engine.process_msg(chain_id, msg.clone()).await?;
+
+    assert_received(&left, |received| received.data == json!({ "side": "left" }));
+    assert_received(&right, |received| {
+        received.data == json!({ "side": "right" })
+    });
+    assert_eq!(joined.count(), 1);
+    assert_received(&joined, |received| {
+        let branches = received.data["branches"].as_array().unwrap();
+        received.id == msg.id
+            && branches.len() == 2
+            && branches.contains(&json!({ "data": { "side": "left" } }))
+            && branches.contains(&json!({ "data": { "side": "right" } }))
+    });
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    test_switch_routing().await?;
+    info!("switch 路由测试通过");
+
+    test_fork_join().await?;
+    info!("fork/join 测试通过");
+    Ok(())
+}
@@ -93,7 +93,9 @@ async fn main() -> Result<(), RuleError> {
                 metadata: HashMap::new(),
                 id: Uuid::new_v4(),
                 msg_type: "test".to_string(),
+                binary: None,
                 timestamp: chrono::Utc::now().timestamp_millis(),
+                ack: None,
             };
             println!("开始执行任务 {}", i);
             let result = engine.process_msg(chain_id, msg).await;
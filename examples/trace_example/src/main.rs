@@ -0,0 +1,98 @@
+use rule_rs::types::RuleChainBuilder;
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine, RuleError};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{info, Level};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let engine = RuleEngine::new().await;
+
+    // 金额为负数时执行失败
+    engine
+        .register_fn(
+            "fn/check",
+            Arc::new(|msg: Message| {
+                if msg.data["amount"].as_f64().unwrap_or_default() < 0.0 {
+                    return Err(RuleError::NodeExecutionError("金额不能为负数".to_string()));
+                }
+                Ok(msg)
+            }),
+        )
+        .await;
+
+    // 计税子规则链: 起始 -> 脚本
+    let mut builder = RuleChainBuilder::new("计税");
+    let tax_start = builder.add_node("start", json!({}));
+    let tax = builder.add_node(
+        "script",
+        json!({ "script": "return { ...msg.data, tax: msg.data.amount / 10 };" }),
+    );
+    builder.connect(tax_start, tax, "success");
+    let tax_id = engine.load_rule_chain(builder.build()).await?;
+
+    // 订单规则链: 起始 -> 校验 -success-> 计税子规则链 -> 日志
+    //                        -failure-> 转换
+    let mut builder = RuleChainBuilder::new("订单");
+    let start = builder.add_node("start", json!({}));
+    let check = builder.add_node("fn/check", json!({ "error_branch": "failure" }));
+    let subchain = builder.add_node("subchain", json!({ "chain_id": tax_id }));
+    let log = builder.add_node("log", json!({ "template": "税额 ${msg.data.tax}" }));
+    let fallback = builder.add_node("transform", json!({ "template": { "status": "rejected" } }));
+    builder
+        .connect(start, check, "success")
+        .connect(check, subchain, "success")
+        .connect(check, fallback, "failure")
+        .connect(subchain, log, "success");
+    let chain_id = engine.load_rule_chain(builder.root(true).build()).await?;
+
+    // 子规则链中的节点紧跟在子规则链节点之后
+    let (result, trace) = engine
+        .process_msg_traced(chain_id, Message::new("order", json!({ "amount": 100 })))
+        .await?;
+    for entry in &trace.entries {
+        info!(
+            "{} {} 分支 {:?} 耗时 {:.3}ms",
+            entry.type_name, entry.node_id, entry.branch, entry.duration_ms
+        );
+    }
+    assert_eq!(result.data["tax"], 10);
+    assert_eq!(
+        trace.path(),
+        vec![start, check, subchain, tax_start, tax, log]
+    );
+    assert!(trace.first_error().is_none());
+    assert_eq!(trace.entries[3].chain_id, tax_id);
+    assert!(trace
+        .entries
+        .iter()
+        .all(|entry| entry.started_at <= entry.ended_at));
+
+    // 被错误分支处理的失败同样记录错误信息和实际走的分支
+    let (result, trace) = engine
+        .process_msg_traced(chain_id, Message::new("order", json!({ "amount": -1 })))
+        .await?;
+    assert_eq!(result.data["status"], "rejected");
+    assert_eq!(trace.path(), vec![start, check, fallback]);
+    let failed = trace.first_error().unwrap();
+    info!("失败节点: {} {:?}", failed.type_name, failed.error);
+    assert_eq!(failed.node_id, check);
+    assert_eq!(failed.branch.as_deref(), Some("failure"));
+    assert!(failed.error.as_ref().unwrap().contains("金额不能为负数"));
+
+    // 执行轨迹可以序列化后返回给调用方
+    let json = serde_json::to_value(&trace)?;
+    assert_eq!(json["entries"][1]["type_name"], "fn/check");
+
+    // 普通处理不记录执行轨迹,结果一致
+    let result = engine
+        .process_msg(chain_id, Message::new("order", json!({ "amount": 100 })))
+        .await?;
+    assert_eq!(result.data["tax"], 10);
+
+    info!("执行轨迹示例验证通过");
+    Ok(())
+}
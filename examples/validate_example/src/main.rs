@@ -0,0 +1,195 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+
+/// 生成校验规则链
+///
+/// 校验节点之后连接 success 和 invalid 两个记录节点
+fn validate_chain(index: u8, on_invalid: &str) -> String {
+    let chain_id = format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}01", 60 + index);
+    let node_id = |n: u8| format!("3f2504e0-4f89-11d3-9a0c-0305e82c{:02}{:02}", 60 + index, n);
+
+    let mut nodes = vec![
+        json!({
+            "id": node_id(0),
+            "type_name": "start",
+            "chain_id": chain_id,
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        }),
+        json!({
+            "id": node_id(2),
+            "type_name": "validate",
+            "chain_id": chain_id,
+            "config": {
+                "schema": {
+                    "type": "object",
+                    "required": ["device_id", "temperature"],
+                    "properties": {
+                        "device_id": { "type": "string" },
+                        "temperature": { "type": "number", "minimum": -50, "maximum": 150 }
+                    }
+                },
+                "on_invalid": on_invalid
+            },
+            "layout": { "x": 200, "y": 100 }
+        }),
+    ];
+    let mut connections = vec![json!({
+        "from_id": node_id(0),
+        "to_id": node_id(2),
+        "type_name": "success"
+    })];
+    for (i, branch) in ["success", "invalid"].iter().enumerate() {
+        let id = node_id(3 + i as u8);
+        nodes.push(json!({
+            "id": id,
+            "type_name": "custom/record",
+            "chain_id": chain_id,
+            "config": { "name": branch },
+            "layout": { "x": 350, "y": 50 + 100 * i }
+        }));
+        connections.push(json!({
+            "from_id": node_id(2),
+            "to_id": id,
+            "type_name": branch
+        }));
+    }
+
+    json!({
+        "id": chain_id,
+        "name": format!("数据校验 {}", on_invalid),
+        "root": true,
+        "nodes": nodes,
+        "connections": connections,
+        "metadata": {
+            "version": 1,
+            "created_at": 1679800000,
+            "updated_at": 1679800000
+        }
+    })
+    .to_string()
+}
+
+/// 记录各分支收到的消息
+type Records = Arc<Mutex<Vec<(String, Message)>>>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RecordConfig {
+    name: String,
+}
+
+/// 记录节点,保存收到的消息及分支名称
+#[derive(Debug)]
+struct RecordNode {
+    config: RecordConfig,
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        info!("{} 分支收到消息: {}", self.config.name, msg.data);
+        self.records
+            .lock()
+            .unwrap()
+            .push((self.config.name.clone(), msg.clone()));
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册记录节点
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |config| {
+                let config: RecordConfig = serde_json::from_value(config)?;
+                Ok(Arc::new(RecordNode {
+                    config,
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    let branch_chain = engine.load_chain(&validate_chain(1, "branch")).await?;
+    let error_chain = engine.load_chain(&validate_chain(2, "error")).await?;
+
+    let take = |records: &Records| std::mem::take(&mut *records.lock().unwrap());
+
+    // 合法数据原样走 success 分支
+    let valid = json!({ "device_id": "dev-1", "temperature": 25.5 });
+    engine
+        .process_msg(branch_chain, Message::new("sensor", valid.clone()))
+        .await?;
+    let received = take(&records);
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].0, "success");
+    assert_eq!(received[0].1.data, valid);
+
+    // 非法数据走 invalid 分支,校验错误写入元数据
+    let invalid = json!({ "device_id": 1, "temperature": 200 });
+    engine
+        .process_msg(branch_chain, Message::new("sensor", invalid))
+        .await?;
+    let received = take(&records);
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].0, "invalid");
+    let errors = received[0]
+        .1
+        .get_meta("validation_errors")
+        .and_then(Value::as_array)
+        .expect("缺少校验错误");
+    info!("校验错误: {:?}", errors);
+    let paths: Vec<&str> = errors.iter().filter_map(|e| e["path"].as_str()).collect();
+    assert!(paths.contains(&"/device_id"));
+    assert!(paths.contains(&"/temperature"));
+
+    // on_invalid 为 error 时返回校验错误,后续节点不执行
+    let result = engine
+        .process_msg(
+            error_chain,
+            Message::new("sensor", json!({ "device_id": "dev-1" })),
+        )
+        .await;
+    match result {
+        Err(RuleError::ValidationError(e)) if e.contains("temperature") => {
+            info!("校验失败: {}", e)
+        }
+        other => panic!("期望校验错误, 实际: {:?}", other),
+    }
+    assert!(take(&records).is_empty());
+
+    info!("数据校验验证通过");
+    Ok(())
+}
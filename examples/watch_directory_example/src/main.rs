@@ -0,0 +1,110 @@
+use rule_rs::types::{RuleChain, RuleChainBuilder};
+use rule_rs::{engine::rule::RuleEngineTrait, Message, RuleEngine};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{info, Level};
+use uuid::Uuid;
+
+/// 起始 -> 转换 规则链,转换结果为给定的数据
+fn chain(id: Uuid, output: Value) -> RuleChain {
+    let mut builder = RuleChainBuilder::new("文件规则链");
+    builder.id(id);
+    let start = builder.add_node("start", json!({}));
+    let transform = builder.add_node("transform", json!({ "template": output }));
+    builder
+        .connect(start, transform, "success")
+        .root(true)
+        .build()
+}
+
+fn write_chain(path: &Path, chain: &RuleChain) {
+    std::fs::write(path, serde_json::to_string_pretty(chain).unwrap()).unwrap();
+}
+
+/// 轮询直到条件满足,超时后失败
+async fn wait_until<F, Fut>(what: &str, mut check: F)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    for _ in 0..100 {
+        if check().await {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("等待超时: {}", what);
+}
+
+async fn output(engine: &RuleEngine, id: Uuid) -> Option<Value> {
+    engine.get_chain(id).await?;
+    let result = engine
+        .process_msg(id, Message::new("test", json!({})))
+        .await
+        .ok()?;
+    Some(result.data)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    let dir = std::env::temp_dir().join(format!("rule_rs_watch_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+
+    // 启动前已存在的文件在开始监听时加载,非 JSON 文件被忽略
+    let first = Uuid::new_v4();
+    write_chain(&dir.join("first.json"), &chain(first, json!({ "v": 1 })));
+    std::fs::write(dir.join("README.txt"), "not a chain")?;
+
+    let engine = RuleEngine::new().await;
+    let watcher = engine.watch_directory(&dir).await?;
+    assert_eq!(output(&engine, first).await, Some(json!({ "v": 1 })));
+    assert_eq!(engine.get_loaded_chains().await.len(), 1);
+
+    // 新增文件被加载
+    let second = Uuid::new_v4();
+    write_chain(&dir.join("second.json"), &chain(second, json!({ "v": 1 })));
+    wait_until("加载新文件", || async {
+        engine.get_chain(second).await.is_some()
+    })
+    .await;
+    info!("新文件已加载");
+
+    // 修改文件后原地更新规则链
+    write_chain(&dir.join("first.json"), &chain(first, json!({ "v": 2 })));
+    wait_until("更新规则链", || async {
+        output(&engine, first).await == Some(json!({ "v": 2 }))
+    })
+    .await;
+    let versions = engine.get_chain_versions(first).await;
+    info!("规则链更新后的版本: {:?}", versions);
+    assert_eq!(versions.len(), 2);
+
+    // 内容无法解析时保留之前的版本
+    std::fs::write(dir.join("first.json"), "{ invalid json")?;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(output(&engine, first).await, Some(json!({ "v": 2 })));
+
+    // 删除文件后删除对应的规则链
+    std::fs::remove_file(dir.join("second.json"))?;
+    wait_until("删除规则链", || async {
+        engine.get_chain(second).await.is_none()
+    })
+    .await;
+    info!("文件删除后规则链已删除");
+
+    // 停止监听后不再同步
+    watcher.stop();
+    let third = Uuid::new_v4();
+    write_chain(&dir.join("third.json"), &chain(third, json!({ "v": 1 })));
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(engine.get_chain(third).await.is_none());
+    assert!(engine.get_chain(first).await.is_some());
+
+    std::fs::remove_dir_all(&dir)?;
+    info!("目录监听验证通过");
+    Ok(())
+}
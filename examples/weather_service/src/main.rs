@@ -165,6 +165,7 @@ impl NodeHandler for WeatherNode {
             name: "天气服务".to_string(),
             description: "获取指定城市的天气信息".to_string(),
             node_type: NodeType::Middle,
+            config_schema: None,
         }
     }
 }
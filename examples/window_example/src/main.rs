@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use rule_rs::engine::NodeHandler;
+use rule_rs::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use rule_rs::{engine::rule::RuleEngineTrait, RuleEngine};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, Level};
+
+const RULE_CHAIN: &str = r#"{
+    "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+    "name": "窗口聚合示例",
+    "root": true,
+    "nodes": [
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3300",
+            "type_name": "start",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": {},
+            "layout": { "x": 50, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "type_name": "window",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": {
+                "size": 3,
+                "timeout_ms": 300,
+                "aggregate": "sum",
+                "field": "reading.value"
+            },
+            "layout": { "x": 200, "y": 100 }
+        },
+        {
+            "id": "3f2504e0-4f89-11d3-9a0c-0305e82c3303",
+            "type_name": "custom/record",
+            "chain_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            "config": {},
+            "layout": { "x": 350, "y": 100 }
+        }
+    ],
+    "connections": [
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3300",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "type_name": "success"
+        },
+        {
+            "from_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3302",
+            "to_id": "3f2504e0-4f89-11d3-9a0c-0305e82c3303",
+            "type_name": "success"
+        }
+    ],
+    "metadata": {
+        "version": 1,
+        "created_at": 1679800000,
+        "updated_at": 1679800000
+    }
+}"#;
+
+/// 记录下游收到的聚合结果
+type Records = Arc<Mutex<Vec<Value>>>;
+
+/// 记录节点,保存收到的消息数据
+#[derive(Debug)]
+struct RecordNode {
+    records: Records,
+}
+
+#[async_trait]
+impl NodeHandler for RecordNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        info!("收到聚合结果: {}", msg.data);
+        self.records.lock().unwrap().push(msg.data.clone());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "custom/record".to_string(),
+            name: "记录节点".to_string(),
+            description: "记录收到的消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 初始化日志系统
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    // 创建引擎实例并等待组件注册完成
+    let engine = RuleEngine::new().await;
+
+    // 注册记录节点
+    let records: Records = Arc::new(Mutex::new(Vec::new()));
+    let node_records = records.clone();
+    engine
+        .register_node_type(
+            "custom/record",
+            Arc::new(move |_| {
+                Ok(Arc::new(RecordNode {
+                    records: node_records.clone(),
+                }) as Arc<dyn NodeHandler>)
+            }),
+        )
+        .await;
+
+    // 加载规则链
+    let chain_id = engine.load_chain(RULE_CHAIN).await?;
+    info!("规则链加载成功: {}", chain_id);
+
+    let reading = |value: i64| Message::new("sensor", json!({ "reading": { "value": value } }));
+
+    // 按数量触发: 第3条消息到达时立即输出
+    for value in [1, 2, 3] {
+        engine.process_msg(chain_id, reading(value)).await?;
+    }
+    assert_eq!(
+        *records.lock().unwrap(),
+        vec![json!({ "value": 6.0, "count": 3 })]
+    );
+
+    // 按时间触发: 不足3条时在超时后输出
+    for value in [4, 5] {
+        engine.process_msg(chain_id, reading(value)).await?;
+    }
+    assert_eq!(records.lock().unwrap().len(), 1);
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(
+        records.lock().unwrap()[1],
+        json!({ "value": 9.0, "count": 2 })
+    );
+    info!("窗口聚合验证通过: {:?}", records.lock().unwrap());
+
+    // 删除规则链时丢弃未输出的缓冲
+    engine.remove_chain(chain_id).await?;
+
+    Ok(())
+}
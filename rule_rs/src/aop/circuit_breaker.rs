@@ -0,0 +1,142 @@
+use crate::aop::NodeInterceptor;
+use crate::types::{Message, NodeContext, RuleError};
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// 熔断器状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CircuitState {
+    /// 关闭 - 正常放行
+    Closed,
+    /// 打开 - 拒绝执行,记录打开时间
+    Open(Instant),
+    /// 半开 - 冷却结束后放行一次试探执行
+    HalfOpen,
+}
+
+/// 单个节点类型的熔断状态
+#[derive(Debug)]
+struct Breaker {
+    state: CircuitState,
+    /// 统计窗口内的失败时间点
+    failures: VecDeque<Instant>,
+}
+
+impl Breaker {
+    fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            failures: VecDeque::new(),
+        }
+    }
+}
+
+/// 熔断拦截器,按节点类型统计失败次数
+///
+/// 统计窗口内失败次数达到阈值后打开熔断器,`before` 直接返回错误,节点不会被执行;
+/// 冷却时间结束后进入半开状态放行一次试探执行,成功则关闭,失败则重新打开
+#[derive(Debug)]
+pub struct CircuitBreakerInterceptor {
+    /// 触发熔断的失败次数阈值
+    failure_threshold: usize,
+    /// 失败次数统计窗口
+    window: Duration,
+    /// 熔断打开后的冷却时间
+    cooldown: Duration,
+    /// 各节点类型的熔断状态,key为节点类型名称
+    breakers: Mutex<HashMap<String, Breaker>>,
+}
+
+impl CircuitBreakerInterceptor {
+    /// 创建熔断拦截器
+    ///
+    /// # Arguments
+    /// * `failure_threshold` - 触发熔断的失败次数阈值
+    /// * `window` - 失败次数统计窗口
+    /// * `cooldown` - 熔断打开后进入半开状态前的冷却时间
+    pub fn new(failure_threshold: usize, window: Duration, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            window,
+            cooldown,
+            breakers: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeInterceptor for CircuitBreakerInterceptor {
+    /// 熔断器打开时拒绝执行节点
+    async fn before<'a>(&self, ctx: &NodeContext<'a>, _msg: &Message) -> Result<(), RuleError> {
+        let mut breakers = self.breakers.lock().await;
+        let Some(breaker) = breakers.get_mut(&ctx.node.type_name) else {
+            return Ok(());
+        };
+
+        match breaker.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open(opened_at) if opened_at.elapsed() >= self.cooldown => {
+                // 冷却结束,放行一次试探执行
+                breaker.state = CircuitState::HalfOpen;
+                Ok(())
+            }
+            CircuitState::Open(_) | CircuitState::HalfOpen => Err(RuleError::NodeExecutionError(
+                format!("节点类型 {} 已熔断", ctx.node.type_name),
+            )),
+        }
+    }
+
+    /// 执行成功后关闭熔断器
+    async fn after<'a>(&self, ctx: &NodeContext<'a>, _msg: &Message) -> Result<(), RuleError> {
+        let mut breakers = self.breakers.lock().await;
+        if let Some(breaker) = breakers.get_mut(&ctx.node.type_name) {
+            breaker.state = CircuitState::Closed;
+            breaker.failures.clear();
+        }
+        Ok(())
+    }
+
+    /// 记录失败,达到阈值时打开熔断器
+    async fn error<'a>(&self, ctx: &NodeContext<'a>, _error: &RuleError) -> Result<(), RuleError> {
+        let now = Instant::now();
+        let mut breakers = self.breakers.lock().await;
+        let breaker = breakers
+            .entry(ctx.node.type_name.clone())
+            .or_insert_with(Breaker::new);
+
+        match breaker.state {
+            CircuitState::HalfOpen => {
+                // 试探执行失败,重新打开
+                breaker.state = CircuitState::Open(now);
+                warn!(
+                    "节点类型 {} 试探执行失败,熔断器重新打开",
+                    ctx.node.type_name
+                );
+            }
+            CircuitState::Closed => {
+                breaker.failures.push_back(now);
+                while let Some(first) = breaker.failures.front() {
+                    if now.duration_since(*first) > self.window {
+                        breaker.failures.pop_front();
+                    } else {
+                        break;
+                    }
+                }
+
+                if breaker.failures.len() >= self.failure_threshold {
+                    breaker.state = CircuitState::Open(now);
+                    breaker.failures.clear();
+                    warn!(
+                        "节点类型 {} 在 {:?} 内失败 {} 次,熔断器打开",
+                        ctx.node.type_name, self.window, self.failure_threshold
+                    );
+                }
+            }
+            CircuitState::Open(_) => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use crate::aop::NodeInterceptor;
+use crate::types::{Message, NodeContext, RuleError};
+use async_trait::async_trait;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Prometheus 指标拦截器,按节点类型统计执行次数、错误次数和执行耗时
+///
+/// 指标注册在独立的 `Registry` 中,可通过 [`PrometheusInterceptor::registry`]
+/// 获取后挂载到自己的 `/metrics` 路由
+#[derive(Debug)]
+pub struct PrometheusInterceptor {
+    registry: Registry,
+    /// 节点执行次数
+    executions: IntCounterVec,
+    /// 节点错误次数
+    errors: IntCounterVec,
+    /// 节点执行耗时(秒)
+    latency: HistogramVec,
+    /// 节点开始执行时间,key为(节点ID, 消息ID)
+    start_times: Mutex<HashMap<(Uuid, Uuid), Instant>>,
+}
+
+impl PrometheusInterceptor {
+    /// 使用默认的耗时分桶创建指标拦截器
+    pub fn new() -> Result<Self, RuleError> {
+        Self::with_buckets(prometheus::DEFAULT_BUCKETS.to_vec())
+    }
+
+    /// 使用自定义的耗时分桶(秒)创建指标拦截器
+    ///
+    /// # Arguments
+    /// * `buckets` - 耗时直方图的分桶边界,必须严格递增
+    pub fn with_buckets(buckets: Vec<f64>) -> Result<Self, RuleError> {
+        let map_err = |e: prometheus::Error| RuleError::ConfigError(format!("指标注册失败: {}", e));
+
+        let executions = IntCounterVec::new(
+            Opts::new("rule_node_executions_total", "节点执行次数"),
+            &["node_type"],
+        )
+        .map_err(map_err)?;
+        let errors = IntCounterVec::new(
+            Opts::new("rule_node_errors_total", "节点错误次数"),
+            &["node_type"],
+        )
+        .map_err(map_err)?;
+        let latency = HistogramVec::new(
+            HistogramOpts::new("rule_node_duration_seconds", "节点执行耗时").buckets(buckets),
+            &["node_type"],
+        )
+        .map_err(map_err)?;
+
+        let registry = Registry::new();
+        registry
+            .register(Box::new(executions.clone()))
+            .map_err(map_err)?;
+        registry
+            .register(Box::new(errors.clone()))
+            .map_err(map_err)?;
+        registry
+            .register(Box::new(latency.clone()))
+            .map_err(map_err)?;
+
+        Ok(Self {
+            registry,
+            executions,
+            errors,
+            latency,
+            start_times: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 获取指标注册表
+    pub fn registry(&self) -> Registry {
+        self.registry.clone()
+    }
+
+    /// 以 Prometheus 文本格式导出所有指标
+    pub fn encode_text(&self) -> Result<String, RuleError> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| RuleError::ComponentError(format!("指标导出失败: {}", e)))?;
+        String::from_utf8(buffer)
+            .map_err(|e| RuleError::ComponentError(format!("指标导出失败: {}", e)))
+    }
+
+    /// 记录节点执行耗时
+    async fn observe_latency(&self, ctx: &NodeContext<'_>) {
+        let start = self
+            .start_times
+            .lock()
+            .await
+            .remove(&(ctx.node.id, ctx.msg.id));
+        if let Some(start) = start {
+            self.latency
+                .with_label_values(&[&ctx.node.type_name])
+                .observe(start.elapsed().as_secs_f64());
+        }
+    }
+}
+
+#[async_trait]
+impl NodeInterceptor for PrometheusInterceptor {
+    /// 记录执行次数和开始时间
+    async fn before<'a>(&self, ctx: &NodeContext<'a>, _msg: &Message) -> Result<(), RuleError> {
+        self.executions
+            .with_label_values(&[&ctx.node.type_name])
+            .inc();
+        self.start_times
+            .lock()
+            .await
+            .insert((ctx.node.id, ctx.msg.id), Instant::now());
+        Ok(())
+    }
+
+    /// 记录执行耗时
+    async fn after<'a>(&self, ctx: &NodeContext<'a>, _msg: &Message) -> Result<(), RuleError> {
+        self.observe_latency(ctx).await;
+        Ok(())
+    }
+
+    /// 记录错误次数和执行耗时
+    async fn error<'a>(&self, ctx: &NodeContext<'a>, _error: &RuleError) -> Result<(), RuleError> {
+        self.errors.with_label_values(&[&ctx.node.type_name]).inc();
+        self.observe_latency(ctx).await;
+        Ok(())
+    }
+}
@@ -1,5 +1,16 @@
+mod circuit_breaker;
+mod metrics;
+mod otel;
+
+pub use circuit_breaker::CircuitBreakerInterceptor;
+pub use metrics::PrometheusInterceptor;
+pub use otel::OpenTelemetryInterceptor;
+
 use crate::types::{Message, NodeContext, RuleError};
 use async_trait::async_trait;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use tracing::{debug, info};
 
@@ -26,6 +37,22 @@ pub trait NodeInterceptor: Send + Sync + std::fmt::Debug {
     /// * `ctx` - 节点执行上下文
     /// * `error` - 错误信息
     async fn error<'a>(&self, ctx: &NodeContext<'a>, error: &RuleError) -> Result<(), RuleError>;
+
+    /// 节点执行前修改或替换消息,默认原样返回
+    ///
+    /// 在所有 `before` 拦截之后执行。多个拦截器按注册顺序依次调用,
+    /// 每个拦截器接收上一个拦截器返回的消息,最后一个拦截器的结果交给节点处理
+    ///
+    /// # Arguments
+    /// * `ctx` - 节点执行上下文
+    /// * `msg` - 待处理的消息
+    async fn transform<'a>(
+        &self,
+        _ctx: &NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        Ok(msg)
+    }
 }
 
 /// 消息拦截器特征,用于在消息处理的不同阶段进行拦截处理
@@ -51,6 +78,8 @@ pub struct InterceptorManager {
     node_interceptors: Vec<Arc<dyn NodeInterceptor>>,
     /// 已注册的消息拦截器列表
     msg_interceptors: Vec<Arc<dyn MessageInterceptor>>,
+    /// 日志拦截器在节点拦截器列表中的位置
+    logging_index: Option<usize>,
 }
 
 impl InterceptorManager {
@@ -59,6 +88,7 @@ impl InterceptorManager {
         Self {
             node_interceptors: Vec::new(),
             msg_interceptors: Vec::new(),
+            logging_index: None,
         }
     }
 
@@ -67,6 +97,17 @@ impl InterceptorManager {
         self.node_interceptors.push(interceptor);
     }
 
+    /// 设置日志拦截器,已设置时在原来的位置替换
+    pub fn set_logging_interceptor(&mut self, interceptor: Arc<LoggingInterceptor>) {
+        match self.logging_index {
+            Some(index) => self.node_interceptors[index] = interceptor,
+            None => {
+                self.logging_index = Some(self.node_interceptors.len());
+                self.node_interceptors.push(interceptor);
+            }
+        }
+    }
+
     /// 注册消息拦截器
     pub fn register_msg_interceptor(&mut self, interceptor: Arc<dyn MessageInterceptor>) {
         self.msg_interceptors.push(interceptor);
@@ -84,6 +125,19 @@ impl InterceptorManager {
         Ok(())
     }
 
+    /// 按注册顺序执行所有节点消息转换拦截器
+    pub async fn transform_node<'a>(
+        &self,
+        ctx: &NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        for interceptor in &self.node_interceptors {
+            msg = interceptor.transform(ctx, msg).await?;
+        }
+        Ok(msg)
+    }
+
     /// 执行所有节点后置拦截器
     pub async fn after_node<'a>(
         &self,
@@ -128,13 +182,104 @@ impl InterceptorManager {
 }
 
 /// 日志节点拦截器,用于记录节点执行的关键信息
-#[derive(Debug)]
-pub struct LoggingInterceptor;
+///
+/// 默认记录所有消息经过的所有节点,可通过 [`LoggingInterceptor::builder`] 设置采样率和节点类型过滤。
+/// 采样按消息ID的哈希决定,同一条消息经过的节点要么全部记录,要么全部跳过
+#[derive(Debug, Clone, Default)]
+pub struct LoggingInterceptor {
+    /// 每 `sample_rate` 条消息记录 1 条,0 和 1 表示全部记录
+    sample_rate: u64,
+    /// 只记录这些类型的节点,为 None 时不限制
+    allow_types: Option<HashSet<String>>,
+    /// 不记录这些类型的节点,优先于 `allow_types`
+    deny_types: HashSet<String>,
+}
+
+impl LoggingInterceptor {
+    /// 创建记录所有节点的日志拦截器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建日志拦截器构建器
+    pub fn builder() -> LoggingInterceptorBuilder {
+        LoggingInterceptorBuilder::default()
+    }
+
+    /// 判断是否记录该节点的日志
+    fn enabled(&self, ctx: &NodeContext<'_>) -> bool {
+        let type_name = &ctx.node.type_name;
+        if self.deny_types.contains(type_name) {
+            return false;
+        }
+        if let Some(allow_types) = &self.allow_types {
+            if !allow_types.contains(type_name) {
+                return false;
+            }
+        }
+        if self.sample_rate <= 1 {
+            return true;
+        }
+        // DefaultHasher::new 使用固定的密钥,同一消息ID总是得到相同的结果
+        let mut hasher = DefaultHasher::new();
+        ctx.msg.id.hash(&mut hasher);
+        hasher.finish().is_multiple_of(self.sample_rate)
+    }
+}
+
+/// 日志拦截器构建器
+#[derive(Debug, Default)]
+pub struct LoggingInterceptorBuilder {
+    interceptor: LoggingInterceptor,
+}
+
+impl LoggingInterceptorBuilder {
+    /// 设置采样率,每 `rate` 条消息记录 1 条
+    ///
+    /// # Arguments
+    /// * `rate` - 采样间隔,0 和 1 表示全部记录
+    pub fn sample_rate(mut self, rate: u64) -> Self {
+        self.interceptor.sample_rate = rate;
+        self
+    }
+
+    /// 只记录指定类型的节点,可多次调用追加
+    pub fn allow_node_types<I, S>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.interceptor
+            .allow_types
+            .get_or_insert_with(HashSet::new)
+            .extend(types.into_iter().map(Into::into));
+        self
+    }
+
+    /// 不记录指定类型的节点,可多次调用追加
+    pub fn deny_node_types<I, S>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.interceptor
+            .deny_types
+            .extend(types.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn build(self) -> LoggingInterceptor {
+        self.interceptor
+    }
+}
 
 #[async_trait]
 impl NodeInterceptor for LoggingInterceptor {
     /// 记录节点开始执行的日志
     async fn before<'a>(&self, ctx: &NodeContext<'a>, msg: &Message) -> Result<(), RuleError> {
+        if !self.enabled(ctx) {
+            return Ok(());
+        }
         info!(
             "开始执行节点 [{}], 类型: {}, 输入消息: {:?}",
             ctx.node.id, ctx.node.type_name, msg
@@ -144,17 +289,23 @@ impl NodeInterceptor for LoggingInterceptor {
 
     /// 记录节点执行成功的日志
     async fn after<'a>(&self, ctx: &NodeContext<'a>, msg: &Message) -> Result<(), RuleError> {
+        if !self.enabled(ctx) {
+            return Ok(());
+        }
         info!(
             "节点 [{}] 执行成功, 输出消息: {:?}, 下一个分支: {:?}",
             ctx.node.id,
             msg.data,
-            msg.metadata.get("branch_name")
+            msg.get_meta_str("branch_name")
         );
         Ok(())
     }
 
     /// 记录节点执行错误的日志
     async fn error<'a>(&self, ctx: &NodeContext<'a>, error: &RuleError) -> Result<(), RuleError> {
+        if !self.enabled(ctx) {
+            return Ok(());
+        }
         info!("节点 [{}] 执行出错: {:?}", ctx.node.id, error);
         Ok(())
     }
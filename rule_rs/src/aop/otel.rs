@@ -0,0 +1,147 @@
+use crate::aop::NodeInterceptor;
+use crate::types::{Message, NodeContext, RuleError};
+use async_trait::async_trait;
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry::trace::Status;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use uuid::Uuid;
+
+/// W3C trace context 在消息元数据中的键名
+const TRACEPARENT: &str = "traceparent";
+
+/// 从消息元数据读取 trace context
+struct MetadataExtractor<'a>(&'a HashMap<String, Value>);
+
+impl Extractor for MetadataExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(Value::as_str)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(String::as_str).collect()
+    }
+}
+
+/// 向消息元数据写入 trace context
+struct MetadataInjector<'a>(&'a mut HashMap<String, Value>);
+
+impl Injector for MetadataInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_string(), Value::String(value));
+    }
+}
+
+/// 分布式追踪拦截器,为每个节点的执行创建一个 `tracing` span
+///
+/// 通过 `tracing-opentelemetry` 桥接导出为 OpenTelemetry span,使用前需要在
+/// `tracing_subscriber` 中注册 `tracing_opentelemetry::layer()`。
+/// 同一消息在执行中的上一个节点 span 作为父 span;链路的第一个节点会从
+/// `msg.metadata` 中的 `traceparent` 恢复父 span,并在执行前把当前 span 写回
+/// `traceparent`,使子规则链和跨规则链的流程能够串联起来
+#[derive(Debug)]
+pub struct OpenTelemetryInterceptor {
+    propagator: TraceContextPropagator,
+    /// 正在执行的节点 span,key为消息ID,按执行顺序排列
+    spans: Mutex<HashMap<Uuid, Vec<(Uuid, Span)>>>,
+}
+
+impl OpenTelemetryInterceptor {
+    pub fn new() -> Self {
+        Self {
+            propagator: TraceContextPropagator::new(),
+            spans: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 结束节点 span
+    async fn close_span(&self, ctx: &NodeContext<'_>) -> Option<Span> {
+        let mut spans = self.spans.lock().await;
+        let node_spans = spans.get_mut(&ctx.msg.id)?;
+        let pos = node_spans.iter().rposition(|(id, _)| *id == ctx.node.id)?;
+        let (_, span) = node_spans.remove(pos);
+        if node_spans.is_empty() {
+            spans.remove(&ctx.msg.id);
+        }
+        Some(span)
+    }
+}
+
+impl Default for OpenTelemetryInterceptor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl NodeInterceptor for OpenTelemetryInterceptor {
+    /// 创建节点 span
+    async fn before<'a>(&self, ctx: &NodeContext<'a>, msg: &Message) -> Result<(), RuleError> {
+        let mut spans = self.spans.lock().await;
+        let node_spans = spans.entry(msg.id).or_default();
+
+        let span = match node_spans.last() {
+            Some((_, parent)) => tracing::info_span!(
+                parent: parent,
+                "node",
+                otel.name = %ctx.node.type_name,
+                node.id = %ctx.node.id,
+                msg.id = %msg.id,
+                chain_id = %ctx.node.chain_id,
+            ),
+            None => {
+                let span = tracing::info_span!(
+                    parent: None,
+                    "node",
+                    otel.name = %ctx.node.type_name,
+                    node.id = %ctx.node.id,
+                    msg.id = %msg.id,
+                    chain_id = %ctx.node.chain_id,
+                );
+                if msg.metadata.contains_key(TRACEPARENT) {
+                    span.set_parent(self.propagator.extract(&MetadataExtractor(&msg.metadata)));
+                }
+                span
+            }
+        };
+
+        node_spans.push((ctx.node.id, span));
+        Ok(())
+    }
+
+    /// 将当前节点 span 写入消息元数据,传播给后续规则链
+    async fn transform<'a>(
+        &self,
+        ctx: &NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        let spans = self.spans.lock().await;
+        if let Some((_, span)) = spans
+            .get(&msg.id)
+            .and_then(|node_spans| node_spans.iter().rfind(|(id, _)| *id == ctx.node.id))
+        {
+            self.propagator
+                .inject_context(&span.context(), &mut MetadataInjector(&mut msg.metadata));
+        }
+        Ok(msg)
+    }
+
+    /// 结束节点 span
+    async fn after<'a>(&self, ctx: &NodeContext<'a>, _msg: &Message) -> Result<(), RuleError> {
+        self.close_span(ctx).await;
+        Ok(())
+    }
+
+    /// 记录错误并结束节点 span
+    async fn error<'a>(&self, ctx: &NodeContext<'a>, error: &RuleError) -> Result<(), RuleError> {
+        if let Some(span) = self.close_span(ctx).await {
+            span.set_status(Status::error(error.to_string()));
+        }
+        Ok(())
+    }
+}
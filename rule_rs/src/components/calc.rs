@@ -0,0 +1,149 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::set_value_by_path;
+use async_trait::async_trait;
+use evalexpr::{
+    build_operator_tree, ContextWithMutableVariables, DefaultNumericTypes, HashMapContext,
+    Node as ExprNode, Value as ExprValue,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CalcConfig {
+    /// 输出字段路径 -> 表达式,表达式中的变量从 msg.data 中读取
+    pub expressions: BTreeMap<String, String>,
+}
+
+/// 算术表达式节点
+///
+/// 使用 `evalexpr` 计算表达式,不需要启动 JS 运行时。msg.data 中的数值、布尔值和字符串字段
+/// 按名称作为变量,嵌套字段以点分隔,例如 `order.amount * 2`。所有表达式都基于输入的 msg.data 计算,
+/// 结果写入对应的输出字段。整数之间的运算结果仍为整数,除零或结果不是有限数值时返回错误
+#[derive(Debug)]
+pub struct CalcNode {
+    expressions: Vec<(String, ExprNode<DefaultNumericTypes>)>,
+}
+
+impl CalcNode {
+    pub fn new(config: CalcConfig) -> Result<Self, RuleError> {
+        // 表达式只在创建节点时解析一次
+        let expressions = config
+            .expressions
+            .into_iter()
+            .map(|(field, expr)| {
+                let tree = build_operator_tree(&expr).map_err(|e| {
+                    RuleError::ConfigError(format!("字段 {} 的表达式 {} 无效: {}", field, expr, e))
+                })?;
+                Ok((field, tree))
+            })
+            .collect::<Result<_, RuleError>>()?;
+        Ok(Self { expressions })
+    }
+
+    /// 计算所有表达式,返回 (输出字段, 结果)
+    fn evaluate(&self, data: &Value) -> Result<Vec<(String, Value)>, RuleError> {
+        let mut context = HashMapContext::<DefaultNumericTypes>::new();
+        bind_variables(&mut context, "", data);
+        self.expressions
+            .iter()
+            .map(|(field, expr)| {
+                let result = expr.eval_with_context(&context).map_err(|e| {
+                    RuleError::ComponentError(format!("字段 {} 的表达式计算失败: {}", field, e))
+                })?;
+                let value = to_json(result).ok_or_else(|| {
+                    RuleError::ComponentError(format!("字段 {} 的计算结果不是有限数值", field))
+                })?;
+                Ok((field.clone(), value))
+            })
+            .collect()
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "calc".to_string(),
+            name: "算术表达式节点".to_string(),
+            description: "使用 msg.data 中的字段计算算术表达式".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["expressions"],
+                "properties": {
+                    "expressions": {
+                        "type": "object",
+                        "description": "输出字段路径到表达式的映射,例如 {\"total\": \"price * count\"}",
+                        "additionalProperties": { "type": "string" }
+                    }
+                }
+            })),
+        }
+    }
+}
+
+/// 把 msg.data 中的字段绑定为表达式变量,嵌套对象的字段名以点连接
+fn bind_variables(context: &mut HashMapContext<DefaultNumericTypes>, prefix: &str, value: &Value) {
+    let variable = match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let name = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                bind_variables(context, &name, value);
+            }
+            return;
+        }
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => ExprValue::Int(i),
+            None => ExprValue::Float(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        Value::Bool(b) => ExprValue::Boolean(*b),
+        Value::String(s) => ExprValue::String(s.clone()),
+        Value::Null | Value::Array(_) => return,
+    };
+    if !prefix.is_empty() {
+        // 新建的上下文中变量名唯一,不会因类型冲突而失败
+        let _ = context.set_value(prefix.to_string(), variable);
+    }
+}
+
+/// 把计算结果转换为 JSON,非有限的浮点数返回 None
+pub(crate) fn to_json(value: ExprValue<DefaultNumericTypes>) -> Option<Value> {
+    Some(match value {
+        ExprValue::Int(i) => json!(i),
+        ExprValue::Float(f) if f.is_finite() => json!(f),
+        ExprValue::Float(_) => return None,
+        ExprValue::Boolean(b) => json!(b),
+        ExprValue::String(s) => json!(s),
+        ExprValue::Tuple(items) => {
+            Value::Array(items.into_iter().map(to_json).collect::<Option<Vec<_>>>()?)
+        }
+        ExprValue::Empty => Value::Null,
+    })
+}
+
+#[async_trait]
+impl NodeHandler for CalcNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        for (field, value) in self.evaluate(&msg.data)? {
+            if !set_value_by_path(&mut msg.data, &field, value) {
+                return Err(RuleError::NodeExecutionError(format!(
+                    "无法写入字段 {}",
+                    field
+                )));
+            }
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -0,0 +1,116 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::{get_value_by_path, set_value_by_path};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+
+/// 共享数据操作类型
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContextOperation {
+    /// 将 msg.data 或其中的字段写入共享数据
+    Set,
+    /// 将共享数据读取到 msg.data 或其中的字段
+    Get,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContextConfig {
+    pub operation: ContextOperation,
+    /// 共享数据的键名
+    pub key: String,
+    /// msg.data 中的路径,以点分隔,数组使用数字下标;为空时表示整个 msg.data
+    #[serde(default)]
+    pub value_path: Option<String>,
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            operation: ContextOperation::Get,
+            key: "value".to_string(),
+            value_path: None,
+        }
+    }
+}
+
+/// 共享数据节点,在一次消息处理中暂存和取回数据
+///
+/// `set` 将 `value_path` 处的值(默认为整个 msg.data)写入共享数据的 `key`;
+/// `get` 将 `key` 的值写回 msg.data 的 `value_path` 处,未配置路径时替换整个 msg.data,
+/// 共享数据中不存在该键时消息保持不变
+#[derive(Debug)]
+pub struct ContextNode {
+    config: ContextConfig,
+}
+
+impl ContextNode {
+    pub fn new(config: ContextConfig) -> Result<Self, RuleError> {
+        if config.key.is_empty() {
+            return Err(RuleError::ConfigError("共享数据键名不能为空".to_string()));
+        }
+        Ok(Self { config })
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "context".to_string(),
+            name: "共享数据节点".to_string(),
+            description: "在一次消息处理中暂存和取回数据".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["operation", "key"],
+                "properties": {
+                    "operation": { "type": "string", "enum": ["set", "get"], "description": "set 写入共享数据, get 读取到消息" },
+                    "key": { "type": "string", "minLength": 1, "description": "共享数据的键名" },
+                    "value_path": { "type": "string", "description": "msg.data 中的路径,以点分隔;为空时表示整个 msg.data" }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for ContextNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        let path = self.config.value_path.as_deref();
+        match self.config.operation {
+            ContextOperation::Set => {
+                let value = match path {
+                    Some(path) => get_value_by_path(&msg.data, path).ok_or_else(|| {
+                        RuleError::NodeExecutionError(format!("字段 {} 不存在", path))
+                    })?,
+                    None => &msg.data,
+                };
+                ctx.ctx_set(&self.config.key, value.clone()).await;
+            }
+            ContextOperation::Get => {
+                if let Some(value) = ctx.ctx_get(&self.config.key).await {
+                    match path {
+                        Some(path) => {
+                            if !set_value_by_path(&mut msg.data, path, value) {
+                                return Err(RuleError::NodeExecutionError(format!(
+                                    "无法写入字段 {}",
+                                    path
+                                )));
+                            }
+                        }
+                        None => msg.data = value,
+                    }
+                }
+            }
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -0,0 +1,340 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::get_value_by_path;
+use async_trait::async_trait;
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Sha256, Sha384, Sha512};
+use std::fmt;
+
+/// 签名操作类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CryptoOperation {
+    /// 计算 HMAC 签名
+    #[default]
+    HmacSign,
+    /// 校验 HMAC 签名
+    HmacVerify,
+    /// 使用 msg.data 中的声明签发 JWT
+    JwtSign,
+    /// 校验 JWT
+    JwtVerify,
+}
+
+/// 签名算法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CryptoAlgorithm {
+    /// HMAC-SHA256,JWT 中为 HS256
+    #[default]
+    Sha256,
+    /// HMAC-SHA384,JWT 中为 HS384
+    Sha384,
+    /// HMAC-SHA512,JWT 中为 HS512
+    Sha512,
+}
+
+impl CryptoAlgorithm {
+    fn jwt(self) -> Algorithm {
+        match self {
+            Self::Sha256 => Algorithm::HS256,
+            Self::Sha384 => Algorithm::HS384,
+            Self::Sha512 => Algorithm::HS512,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct CryptoConfig {
+    #[serde(default)]
+    pub operation: CryptoOperation,
+    /// 密钥,可以使用 `${env:NAME}` 等占位符从外部读取
+    pub secret: String,
+    #[serde(default)]
+    pub algorithm: CryptoAlgorithm,
+    /// 保存签名或 JWT 的元数据键,签名操作写入,校验操作读取。
+    /// 默认 HMAC 为 `signature`,JWT 为 `token`
+    #[serde(default)]
+    pub field: Option<String>,
+    /// 参与签名的数据在 msg.data 中的路径,未设置时使用整个 msg.data
+    #[serde(default)]
+    pub payload_field: Option<String>,
+    /// 签发的 JWT 有效期(秒),设置后在声明中加入 `iat` 和 `exp`
+    #[serde(default)]
+    pub expires_in_secs: Option<u64>,
+}
+
+// 手动实现 Debug,避免密钥出现在日志中
+impl fmt::Debug for CryptoConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CryptoConfig")
+            .field("operation", &self.operation)
+            .field("secret", &"***")
+            .field("algorithm", &self.algorithm)
+            .field("field", &self.field)
+            .field("payload_field", &self.payload_field)
+            .field("expires_in_secs", &self.expires_in_secs)
+            .finish()
+    }
+}
+
+impl Default for CryptoConfig {
+    fn default() -> Self {
+        Self {
+            operation: CryptoOperation::HmacSign,
+            secret: String::new(),
+            algorithm: CryptoAlgorithm::Sha256,
+            field: None,
+            payload_field: None,
+            expires_in_secs: None,
+        }
+    }
+}
+
+/// 签名节点
+///
+/// - `hmac_sign` 对载荷计算十六进制 HMAC 签名,写入元数据 `field`
+/// - `hmac_verify` 用元数据 `field` 中的签名校验载荷,签名可带 `sha256=` 形式的前缀
+/// - `jwt_sign` 使用载荷中的声明签发 JWT,写入元数据 `field`
+/// - `jwt_verify` 校验元数据 `field` 中的 JWT (可带 `Bearer ` 前缀),声明写入元数据 `claims`,
+///   存在 `exp`/`nbf` 时检查有效期
+///
+/// 载荷为字符串时直接使用其内容,否则使用紧凑的 JSON 文本。
+/// 签名操作走 `success` 分支,校验操作走 `valid` 或 `invalid` 分支,失败原因写入元数据 `error`。
+/// 密钥不会出现在日志和错误信息中
+#[derive(Debug)]
+pub struct CryptoNode {
+    config: CryptoConfig,
+}
+
+impl CryptoNode {
+    pub fn new(config: CryptoConfig) -> Result<Self, RuleError> {
+        if config.secret.is_empty() {
+            return Err(RuleError::ConfigError("签名密钥不能为空".to_string()));
+        }
+        if config.field.as_deref() == Some("") {
+            return Err(RuleError::ConfigError("签名字段不能为空".to_string()));
+        }
+        Ok(Self { config })
+    }
+
+    fn field(&self) -> &str {
+        match (&self.config.field, self.config.operation) {
+            (Some(field), _) => field,
+            (None, CryptoOperation::HmacSign | CryptoOperation::HmacVerify) => "signature",
+            (None, CryptoOperation::JwtSign | CryptoOperation::JwtVerify) => "token",
+        }
+    }
+
+    fn payload<'a>(&self, msg: &'a Message) -> Result<&'a Value, RuleError> {
+        match &self.config.payload_field {
+            Some(path) => get_value_by_path(&msg.data, path).ok_or_else(|| {
+                RuleError::NodeExecutionError(format!("签名数据字段 {} 不存在", path))
+            }),
+            None => Ok(&msg.data),
+        }
+    }
+
+    /// 参与 HMAC 计算的原始字节
+    fn payload_bytes(payload: &Value) -> Vec<u8> {
+        match payload {
+            Value::String(s) => s.as_bytes().to_vec(),
+            other => other.to_string().into_bytes(),
+        }
+    }
+
+    fn mac<M: Mac + KeyInit>(&self) -> M {
+        <M as KeyInit>::new_from_slice(self.config.secret.as_bytes())
+            .expect("HMAC 接受任意长度的密钥")
+    }
+
+    fn hmac_sign(&self, data: &[u8]) -> String {
+        match self.config.algorithm {
+            CryptoAlgorithm::Sha256 => {
+                let mut mac = self.mac::<Hmac<Sha256>>();
+                mac.update(data);
+                hex::encode(mac.finalize().into_bytes())
+            }
+            CryptoAlgorithm::Sha384 => {
+                let mut mac = self.mac::<Hmac<Sha384>>();
+                mac.update(data);
+                hex::encode(mac.finalize().into_bytes())
+            }
+            CryptoAlgorithm::Sha512 => {
+                let mut mac = self.mac::<Hmac<Sha512>>();
+                mac.update(data);
+                hex::encode(mac.finalize().into_bytes())
+            }
+        }
+    }
+
+    /// 以常量时间比较签名
+    fn hmac_verify(&self, data: &[u8], signature: &str) -> Result<(), String> {
+        // 兼容 `sha256=<hex>` 形式的签名头
+        let signature = signature
+            .split_once('=')
+            .map_or(signature, |(_, hex)| hex)
+            .trim();
+        let expected = hex::decode(signature).map_err(|_| "签名不是有效的十六进制".to_string())?;
+        let result = match self.config.algorithm {
+            CryptoAlgorithm::Sha256 => {
+                let mut mac = self.mac::<Hmac<Sha256>>();
+                mac.update(data);
+                mac.verify_slice(&expected)
+            }
+            CryptoAlgorithm::Sha384 => {
+                let mut mac = self.mac::<Hmac<Sha384>>();
+                mac.update(data);
+                mac.verify_slice(&expected)
+            }
+            CryptoAlgorithm::Sha512 => {
+                let mut mac = self.mac::<Hmac<Sha512>>();
+                mac.update(data);
+                mac.verify_slice(&expected)
+            }
+        };
+        result.map_err(|_| "签名不匹配".to_string())
+    }
+
+    fn jwt_sign(&self, claims: &Value) -> Result<String, RuleError> {
+        let Value::Object(claims) = claims else {
+            return Err(RuleError::NodeExecutionError(
+                "JWT 声明必须是对象".to_string(),
+            ));
+        };
+        let mut claims = claims.clone();
+        if let Some(expires_in) = self.config.expires_in_secs {
+            let now = chrono::Utc::now().timestamp();
+            claims.insert("iat".to_string(), json!(now));
+            claims.insert("exp".to_string(), json!(now + expires_in as i64));
+        }
+        jsonwebtoken::encode(
+            &Header::new(self.config.algorithm.jwt()),
+            &claims,
+            &EncodingKey::from_secret(self.config.secret.as_bytes()),
+        )
+        .map_err(|e| RuleError::NodeExecutionError(format!("JWT 签发失败: {}", e)))
+    }
+
+    fn jwt_verify(&self, token: &str) -> Result<Value, String> {
+        let token = token.strip_prefix("Bearer ").unwrap_or(token).trim();
+        let mut validation = Validation::new(self.config.algorithm.jwt());
+        // 不要求特定声明,存在 exp/nbf 时仍然检查有效期
+        validation.required_spec_claims.clear();
+        validation.validate_nbf = true;
+        jsonwebtoken::decode::<Value>(
+            token,
+            &DecodingKey::from_secret(self.config.secret.as_bytes()),
+            &validation,
+        )
+        .map(|data| data.claims)
+        .map_err(|e| format!("JWT 无效: {}", e))
+    }
+
+    /// 读取待校验的签名或 JWT
+    fn credential(&self, msg: &Message) -> Result<String, String> {
+        msg.get_meta_str(self.field())
+            .map(str::to_string)
+            .ok_or_else(|| format!("元数据 {} 不存在", self.field()))
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "crypto".to_string(),
+            name: "签名节点".to_string(),
+            description: "计算或校验 HMAC 签名,签发或校验 JWT".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["secret"],
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": ["hmac_sign", "hmac_verify", "jwt_sign", "jwt_verify"],
+                        "default": "hmac_sign"
+                    },
+                    "secret": {
+                        "type": "string",
+                        "minLength": 1,
+                        "description": "密钥,建议使用 ${env:NAME} 占位符"
+                    },
+                    "algorithm": {
+                        "type": "string",
+                        "enum": ["sha256", "sha384", "sha512"],
+                        "description": "HMAC 摘要算法,JWT 对应 HS256/HS384/HS512",
+                        "default": "sha256"
+                    },
+                    "field": {
+                        "type": "string",
+                        "description": "保存签名或 JWT 的元数据键,默认 HMAC 为 signature,JWT 为 token"
+                    },
+                    "payload_field": {
+                        "type": "string",
+                        "description": "参与签名的数据在 msg.data 中的路径,未设置时使用整个 msg.data"
+                    },
+                    "expires_in_secs": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "签发的 JWT 有效期(秒)"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for CryptoNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        let payload = self.payload(&msg)?;
+
+        let verified = match self.config.operation {
+            CryptoOperation::HmacSign => {
+                let signature = self.hmac_sign(&Self::payload_bytes(payload));
+                msg.set_meta(self.field(), signature);
+                None
+            }
+            CryptoOperation::JwtSign => {
+                let token = self.jwt_sign(payload)?;
+                msg.set_meta(self.field(), token);
+                None
+            }
+            CryptoOperation::HmacVerify => {
+                let data = Self::payload_bytes(payload);
+                Some(
+                    self.credential(&msg)
+                        .and_then(|signature| self.hmac_verify(&data, &signature)),
+                )
+            }
+            CryptoOperation::JwtVerify => Some(
+                self.credential(&msg)
+                    .and_then(|token| self.jwt_verify(&token))
+                    .map(|claims| msg.set_meta("claims", claims)),
+            ),
+        };
+
+        let branch = match verified {
+            None => "success",
+            Some(Ok(())) => "valid",
+            Some(Err(reason)) => {
+                msg.set_meta("error", reason);
+                "invalid"
+            }
+        };
+        msg.set_meta("branch_name", branch);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
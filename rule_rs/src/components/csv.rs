@@ -0,0 +1,232 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// CSV 转换方向
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CsvDirection {
+    /// CSV 字符串解析为JSON数组
+    #[default]
+    Parse,
+    /// JSON数组序列化为 CSV 字符串
+    Serialize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CsvConfig {
+    #[serde(default)]
+    pub direction: CsvDirection,
+    /// 第一行是否为表头,默认为 true
+    #[serde(default = "default_has_headers")]
+    pub has_headers: bool,
+    /// 分隔符,必须是单个 ASCII 字符,默认为逗号
+    #[serde(default = "default_delimiter")]
+    pub delimiter: String,
+}
+
+fn default_has_headers() -> bool {
+    true
+}
+
+fn default_delimiter() -> String {
+    ",".to_string()
+}
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            direction: CsvDirection::Parse,
+            has_headers: default_has_headers(),
+            delimiter: default_delimiter(),
+        }
+    }
+}
+
+/// CSV 转换节点
+///
+/// 解析时读取字符串类型的 msg.data,或 HTTP 请求节点输出的 msg.data.body,
+/// 有表头时每行输出为以表头为键的对象,否则输出为字符串数组;
+/// 序列化时将 msg.data 中的对象数组或数组的数组转换为 CSV 字符串
+#[derive(Debug)]
+pub struct CsvNode {
+    config: CsvConfig,
+    delimiter: u8,
+}
+
+impl CsvNode {
+    pub fn new(config: CsvConfig) -> Result<Self, RuleError> {
+        let delimiter = match config.delimiter.as_bytes() {
+            [b] if b.is_ascii() => *b,
+            _ => {
+                return Err(RuleError::ConfigError(format!(
+                    "CSV分隔符必须是单个ASCII字符: {}",
+                    config.delimiter
+                )))
+            }
+        };
+        Ok(Self { config, delimiter })
+    }
+
+    /// 将 CSV 字符串解析为JSON数组
+    fn parse(&self, text: &str) -> Result<Value, RuleError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(self.config.has_headers)
+            .delimiter(self.delimiter)
+            .from_reader(text.as_bytes());
+
+        let headers = if self.config.has_headers {
+            Some(reader.headers().map_err(csv_error)?.clone())
+        } else {
+            None
+        };
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(csv_error)?;
+            let row = match &headers {
+                Some(headers) => Value::Object(
+                    headers
+                        .iter()
+                        .zip(record.iter())
+                        .map(|(key, value)| (key.to_string(), Value::from(value)))
+                        .collect(),
+                ),
+                None => record.iter().map(Value::from).collect(),
+            };
+            rows.push(row);
+        }
+        Ok(Value::Array(rows))
+    }
+
+    /// 将对象数组或数组的数组序列化为 CSV 字符串
+    fn serialize(&self, data: &Value) -> Result<String, RuleError> {
+        let rows = data
+            .as_array()
+            .ok_or_else(|| RuleError::ComponentError("CSV序列化需要数组类型的数据".to_string()))?;
+
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(Vec::new());
+
+        // 对象按所有行中出现过的字段依次输出,缺少的字段为空
+        let columns = object_columns(rows);
+        if self.config.has_headers && !columns.is_empty() {
+            writer.write_record(&columns).map_err(csv_error)?;
+        }
+
+        for row in rows {
+            let fields: Vec<String> = match row {
+                Value::Object(obj) => columns
+                    .iter()
+                    .map(|column| obj.get(column).map(field_text).unwrap_or_default())
+                    .collect(),
+                Value::Array(items) => items.iter().map(field_text).collect(),
+                other => {
+                    return Err(RuleError::ComponentError(format!(
+                        "CSV序列化的每一行必须是对象或数组: {}",
+                        other
+                    )))
+                }
+            };
+            writer.write_record(&fields).map_err(csv_error)?;
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| RuleError::ComponentError(format!("CSV写入失败: {}", e)))?;
+        String::from_utf8(bytes)
+            .map_err(|e| RuleError::ComponentError(format!("CSV写入失败: {}", e)))
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "csv".to_string(),
+            name: "CSV转换".to_string(),
+            description: "CSV字符串与JSON数组互相转换".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "direction": {
+                        "type": "string",
+                        "enum": ["parse", "serialize"],
+                        "description": "parse 把CSV文本解析为JSON,serialize 把JSON数组序列化为CSV文本",
+                        "default": "parse"
+                    },
+                    "has_headers": {
+                        "type": "boolean",
+                        "description": "第一行是否为表头",
+                        "default": true
+                    },
+                    "delimiter": {
+                        "type": "string",
+                        "minLength": 1,
+                        "maxLength": 1,
+                        "description": "分隔符,单个ASCII字符",
+                        "default": ","
+                    }
+                }
+            })),
+        }
+    }
+}
+
+/// 按首次出现的顺序收集对象行的字段名
+fn object_columns(rows: &[Value]) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    for obj in rows.iter().filter_map(Value::as_object) {
+        for key in obj.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    columns
+}
+
+/// 单元格文本: 字符串原样输出,null 为空,其他值输出JSON文本
+fn field_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_error(e: csv::Error) -> RuleError {
+    RuleError::ComponentError(format!("CSV处理失败: {}", e))
+}
+
+#[async_trait]
+impl NodeHandler for CsvNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.data = match self.config.direction {
+            CsvDirection::Parse => {
+                let text = msg
+                    .data
+                    .as_str()
+                    .or_else(|| msg.data.get("body").and_then(Value::as_str))
+                    .ok_or_else(|| {
+                        RuleError::ComponentError(
+                            "CSV解析需要字符串类型的 msg.data 或 msg.data.body".to_string(),
+                        )
+                    })?;
+                self.parse(text)?
+            }
+            CsvDirection::Serialize => Value::String(self.serialize(&msg.data)?),
+        };
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
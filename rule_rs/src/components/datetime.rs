@@ -0,0 +1,242 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::{get_value_by_path, set_value_by_path};
+use async_trait::async_trait;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// 时间操作类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateTimeOperation {
+    /// 写入当前时间
+    #[default]
+    Now,
+    /// 把时间字符串解析为毫秒时间戳
+    Parse,
+    /// 按 `output_format` 格式化时间
+    Format,
+    /// 把时间转换到 `to_tz` 时区
+    ConvertTz,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DateTimeConfig {
+    /// 读取和写回时间的 msg.data 字段路径,以点分隔
+    pub field: String,
+    #[serde(default)]
+    pub operation: DateTimeOperation,
+    /// 解析时间字符串的 strftime 格式,未设置时按 RFC 3339 解析
+    #[serde(default)]
+    pub input_format: Option<String>,
+    /// 输出的 strftime 格式,未设置时 `now`/`parse` 输出毫秒时间戳,`convert_tz` 输出 RFC 3339
+    #[serde(default)]
+    pub output_format: Option<String>,
+    /// 不带时区的输入时间所在的 IANA 时区,默认 UTC
+    #[serde(default)]
+    pub from_tz: Option<String>,
+    /// 输出时间使用的 IANA 时区,默认 UTC
+    #[serde(default)]
+    pub to_tz: Option<String>,
+}
+
+impl Default for DateTimeConfig {
+    fn default() -> Self {
+        Self {
+            field: "timestamp".to_string(),
+            operation: DateTimeOperation::Now,
+            input_format: None,
+            output_format: None,
+            from_tz: None,
+            to_tz: None,
+        }
+    }
+}
+
+/// 时间处理节点
+///
+/// 读取 `field` 中的时间,按 `operation` 处理后写回 `field`。输入可以是毫秒时间戳,
+/// 也可以是时间字符串: 配置了 `input_format` 时按该格式解析,格式不含时区时视为 `from_tz` 的本地时间,
+/// 否则按 RFC 3339 解析。夏令时切换造成的重复本地时间取较早的一个,不存在的本地时间返回错误
+#[derive(Debug)]
+pub struct DateTimeNode {
+    config: DateTimeConfig,
+    from_tz: Tz,
+    to_tz: Tz,
+}
+
+impl DateTimeNode {
+    pub fn new(config: DateTimeConfig) -> Result<Self, RuleError> {
+        if config.field.is_empty() {
+            return Err(RuleError::ConfigError("时间字段不能为空".to_string()));
+        }
+        if config.operation == DateTimeOperation::Format && config.output_format.is_none() {
+            return Err(RuleError::ConfigError(
+                "format 操作需要配置 output_format".to_string(),
+            ));
+        }
+        // 格式在创建节点时校验,无效的格式在格式化时会导致 panic
+        for format in [&config.input_format, &config.output_format]
+            .into_iter()
+            .flatten()
+        {
+            if StrftimeItems::new(format).any(|item| item == Item::Error) {
+                return Err(RuleError::ConfigError(format!(
+                    "无效的时间格式: {}",
+                    format
+                )));
+            }
+        }
+        let from_tz = parse_tz(config.from_tz.as_deref())?;
+        let to_tz = parse_tz(config.to_tz.as_deref())?;
+        Ok(Self {
+            config,
+            from_tz,
+            to_tz,
+        })
+    }
+
+    /// 读取时间,数值按毫秒时间戳处理
+    fn read(&self, value: &Value) -> Result<DateTime<Utc>, RuleError> {
+        match value {
+            Value::Number(n) => n
+                .as_i64()
+                .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+                .ok_or_else(|| RuleError::ComponentError(format!("无效的时间戳: {}", n))),
+            Value::String(text) => match &self.config.input_format {
+                Some(format) => self.parse_with_format(text, format),
+                None => DateTime::parse_from_rfc3339(text)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| {
+                        RuleError::ComponentError(format!(
+                            "无法按 RFC 3339 解析时间 {}: {}",
+                            text, e
+                        ))
+                    }),
+            },
+            other => Err(RuleError::ComponentError(format!(
+                "时间字段 {} 必须是字符串或毫秒时间戳: {}",
+                self.config.field, other
+            ))),
+        }
+    }
+
+    /// 按格式解析时间,格式不含时区时视为 `from_tz` 的本地时间,只含日期时取当天零点
+    fn parse_with_format(&self, text: &str, format: &str) -> Result<DateTime<Utc>, RuleError> {
+        if let Ok(dt) = DateTime::parse_from_str(text, format) {
+            return Ok(dt.with_timezone(&Utc));
+        }
+        let naive = NaiveDateTime::parse_from_str(text, format)
+            .or_else(|e| {
+                NaiveDate::parse_from_str(text, format)
+                    .map(|date| date.and_time(NaiveTime::MIN))
+                    .map_err(|_| e)
+            })
+            .map_err(|e| {
+                RuleError::ComponentError(format!("无法按格式 {} 解析时间 {}: {}", format, text, e))
+            })?;
+        match self.from_tz.from_local_datetime(&naive) {
+            LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => Ok(dt.with_timezone(&Utc)),
+            LocalResult::None => Err(RuleError::ComponentError(format!(
+                "时间 {} 在时区 {} 中不存在",
+                text, self.from_tz
+            ))),
+        }
+    }
+
+    /// 按配置输出时间
+    fn write(&self, instant: DateTime<Utc>) -> Value {
+        let local = instant.with_timezone(&self.to_tz);
+        match (&self.config.output_format, self.config.operation) {
+            (Some(format), _) => json!(local.format(format).to_string()),
+            (None, DateTimeOperation::ConvertTz) => json!(local.fixed_offset().to_rfc3339()),
+            (None, _) => json!(instant.timestamp_millis()),
+        }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "datetime".to_string(),
+            name: "时间处理节点".to_string(),
+            description: "生成、解析、格式化时间以及转换时区".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["field"],
+                "properties": {
+                    "field": {
+                        "type": "string",
+                        "description": "读取和写回时间的 msg.data 字段路径"
+                    },
+                    "operation": {
+                        "type": "string",
+                        "enum": ["now", "parse", "format", "convert_tz"],
+                        "default": "now"
+                    },
+                    "input_format": {
+                        "type": "string",
+                        "description": "解析时间字符串的 strftime 格式,未设置时按 RFC 3339 解析"
+                    },
+                    "output_format": {
+                        "type": "string",
+                        "description": "输出的 strftime 格式,format 操作必填"
+                    },
+                    "from_tz": {
+                        "type": "string",
+                        "description": "不带时区的输入时间所在的 IANA 时区,默认 UTC"
+                    },
+                    "to_tz": {
+                        "type": "string",
+                        "description": "输出时间使用的 IANA 时区,默认 UTC"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+/// 解析 IANA 时区名称,未设置时为 UTC
+fn parse_tz(name: Option<&str>) -> Result<Tz, RuleError> {
+    match name {
+        Some(name) => name
+            .parse()
+            .map_err(|_| RuleError::ConfigError(format!("未知的时区: {}", name))),
+        None => Ok(Tz::UTC),
+    }
+}
+
+#[async_trait]
+impl NodeHandler for DateTimeNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        let field = &self.config.field;
+        let instant = match self.config.operation {
+            DateTimeOperation::Now => Utc::now(),
+            _ => {
+                let value = get_value_by_path(&msg.data, field).ok_or_else(|| {
+                    RuleError::ComponentError(format!("时间字段 {} 不存在", field))
+                })?;
+                self.read(value)?
+            }
+        };
+
+        if !set_value_by_path(&mut msg.data, field, self.write(instant)) {
+            return Err(RuleError::NodeExecutionError(format!(
+                "无法写入字段 {}",
+                field
+            )));
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -0,0 +1,188 @@
+use crate::engine::rule::DynRuleEngine;
+use crate::engine::NodeHandler;
+use crate::types::{ExecutionContext, Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::get_value_by_path;
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::error;
+use uuid::Uuid;
+
+/// 单个键等待转发的状态
+struct PendingMessage {
+    /// 静默期结束后转发最新消息的任务
+    timer: JoinHandle<()>,
+    /// 每收到一条消息递增,用于让过期的任务失效
+    generation: u64,
+}
+
+lazy_static! {
+    // 节点处理器会被重复创建,等待转发的消息按 (规则链ID, 节点ID, 键) 保存在全局状态中
+    static ref GLOBAL_DEBOUNCE_STATE: Mutex<HashMap<(Uuid, Uuid, String), PendingMessage>> =
+        Mutex::new(HashMap::new());
+}
+
+/// 停止规则链下所有防抖节点等待中的转发,在删除规则链时调用
+pub(crate) async fn remove_chain_state(chain_id: Uuid) {
+    GLOBAL_DEBOUNCE_STATE
+        .lock()
+        .await
+        .retain(|(id, _, _), pending| {
+            if *id == chain_id {
+                pending.timer.abort();
+            }
+            *id != chain_id
+        });
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DebounceConfig {
+    /// 防抖键在 msg.data 中的路径,以点分隔,数组使用数字下标
+    pub key_path: String,
+    /// 静默期(毫秒),同一个键在静默期内没有新消息时才转发最新的消息
+    pub quiet_period_ms: u64,
+}
+
+impl Default for DebounceConfig {
+    fn default() -> Self {
+        Self {
+            key_path: "id".to_string(),
+            quiet_period_ms: 1000,
+        }
+    }
+}
+
+/// 防抖节点
+///
+/// 按键分别计时,每条消息都会重新开始该键的静默期,静默期结束时只转发该键最新的一条消息,
+/// 之前的消息被丢弃。消息到达时不向下游转发,转发在静默期结束后异步进行
+#[derive(Debug)]
+pub struct DebounceNode {
+    config: DebounceConfig,
+}
+
+impl DebounceNode {
+    pub fn new(config: DebounceConfig) -> Self {
+        Self { config }
+    }
+
+    /// 从消息数据中解析防抖键
+    fn resolve_key(&self, data: &Value) -> Option<String> {
+        match get_value_by_path(data, &self.config.key_path)? {
+            Value::String(s) => Some(s.clone()),
+            Value::Null => None,
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// 启动静默期任务,期间没有新消息时转发该消息
+    fn spawn_timer(
+        &self,
+        engine: DynRuleEngine,
+        state_key: (Uuid, Uuid, String),
+        generation: u64,
+        msg: Message,
+    ) -> JoinHandle<()> {
+        let quiet_period = Duration::from_millis(self.config.quiet_period_ms);
+
+        tokio::spawn(async move {
+            sleep(quiet_period).await;
+
+            {
+                let mut states = GLOBAL_DEBOUNCE_STATE.lock().await;
+                match states.get(&state_key) {
+                    // 静默期内又收到了新消息,本次转发失效
+                    Some(pending) if pending.generation == generation => {
+                        states.remove(&state_key);
+                    }
+                    _ => return,
+                }
+            }
+
+            let (chain_id, node_id, _) = state_key;
+            let Some(chain) = engine.get_chain(chain_id).await else {
+                return;
+            };
+            let Some(chain_node) = chain.nodes.iter().find(|n| n.id == node_id) else {
+                return;
+            };
+
+            let ctx = NodeContext::new(
+                chain_node,
+                &ExecutionContext::new(msg.clone()),
+                engine.clone(),
+            );
+            if let Err(e) = ctx.send_next(msg).await {
+                error!("防抖节点 {} 转发消息失败: {}", node_id, e);
+            }
+        })
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "debounce".to_string(),
+            name: "防抖节点".to_string(),
+            description: "按键防抖,静默期内没有新消息时只转发最新的消息".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["key_path", "quiet_period_ms"],
+                "properties": {
+                    "key_path": { "type": "string", "description": "防抖键在 msg.data 中的路径,以点分隔" },
+                    "quiet_period_ms": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "静默期(毫秒),同一个键在静默期内没有新消息时才转发最新的消息"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for DebounceNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let key = self.resolve_key(&msg.data).ok_or_else(|| {
+            RuleError::NodeExecutionError(format!("防抖字段 {} 不存在", self.config.key_path))
+        })?;
+        let state_key = (ctx.node.chain_id, ctx.node.id, key);
+
+        {
+            let mut states = GLOBAL_DEBOUNCE_STATE.lock().await;
+            // 取消上一条消息的转发,重新开始静默期
+            let generation = match states.get(&state_key) {
+                Some(pending) => {
+                    pending.timer.abort();
+                    pending.generation + 1
+                }
+                None => 0,
+            };
+            let timer = self.spawn_timer(
+                ctx.engine.clone(),
+                state_key.clone(),
+                generation,
+                msg.clone(),
+            );
+            states.insert(state_key, PendingMessage { timer, generation });
+        }
+
+        // 静默期结束后再向下游发送
+        ctx.mark_routed();
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// 单个去重节点的状态
+struct DedupState {
+    /// 窗口内出现过的键及其记录时间
+    seen: HashMap<String, Instant>,
+    /// 上次清理过期键的时间
+    last_eviction: Instant,
+}
+
+lazy_static! {
+    // 节点处理器会被重复创建,去重状态按 (规则链ID, 节点ID) 保存在全局状态中
+    static ref GLOBAL_DEDUP_STATE: Mutex<HashMap<(Uuid, Uuid), DedupState>> =
+        Mutex::new(HashMap::new());
+}
+
+/// 清理规则链下所有去重节点的状态,在删除规则链时调用
+pub(crate) async fn remove_chain_state(chain_id: Uuid) {
+    GLOBAL_DEDUP_STATE
+        .lock()
+        .await
+        .retain(|(id, _), _| *id != chain_id);
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DedupConfig {
+    /// 去重键在 msg.data 中的路径,以点分隔,数组使用数字下标
+    pub key_path: String,
+    /// 去重时间窗口(毫秒)
+    pub window_ms: u64,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            key_path: "id".to_string(),
+            window_ms: 60000,
+        }
+    }
+}
+
+/// 去重节点
+///
+/// 窗口期内首次出现的键走 `success` 分支继续转发;重复的键走 `duplicate` 分支,
+/// 未配置 `duplicate` 连接时消息被丢弃
+#[derive(Debug)]
+pub struct DedupNode {
+    config: DedupConfig,
+}
+
+impl DedupNode {
+    pub fn new(config: DedupConfig) -> Self {
+        Self { config }
+    }
+
+    /// 从消息数据中解析去重键
+    fn resolve_key(&self, data: &Value) -> Option<String> {
+        let mut current = data;
+        for part in self.config.key_path.split('.') {
+            current = match current {
+                Value::Array(items) => items.get(part.parse::<usize>().ok()?)?,
+                _ => current.get(part)?,
+            };
+        }
+        match current {
+            Value::String(s) => Some(s.clone()),
+            Value::Null => None,
+            _ => Some(current.to_string()),
+        }
+    }
+
+    /// 检查键是否在窗口期内出现过,未出现过则记录
+    async fn check_duplicate(&self, chain_id: Uuid, node_id: Uuid, key: String) -> bool {
+        let window = Duration::from_millis(self.config.window_ms);
+        let now = Instant::now();
+
+        let mut states = GLOBAL_DEDUP_STATE.lock().await;
+        let state = states
+            .entry((chain_id, node_id))
+            .or_insert_with(|| DedupState {
+                seen: HashMap::new(),
+                last_eviction: now,
+            });
+
+        // 每个窗口周期清理一次过期的键
+        if now.duration_since(state.last_eviction) >= window {
+            state
+                .seen
+                .retain(|_, seen_at| now.duration_since(*seen_at) < window);
+            state.last_eviction = now;
+        }
+
+        match state.seen.get(&key) {
+            Some(seen_at) if now.duration_since(*seen_at) < window => true,
+            _ => {
+                state.seen.insert(key, now);
+                false
+            }
+        }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "dedup".to_string(),
+            name: "去重节点".to_string(),
+            description: "按字段在时间窗口内对消息去重".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["key_path", "window_ms"],
+                "properties": {
+                    "key_path": { "type": "string", "description": "去重键在 msg.data 中的路径,以点分隔" },
+                    "window_ms": { "type": "integer", "minimum": 0, "description": "去重时间窗口(毫秒)" }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for DedupNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        let key = self.resolve_key(&msg.data).ok_or_else(|| {
+            RuleError::NodeExecutionError(format!("去重字段 {} 不存在", self.config.key_path))
+        })?;
+
+        if self
+            .check_duplicate(ctx.node.chain_id, ctx.node.id, key)
+            .await
+        {
+            // 重复消息只发送到 duplicate 分支
+            if ctx.get_next_connections("duplicate").await?.is_empty() {
+                ctx.mark_routed();
+                return Ok(msg);
+            }
+            msg.set_meta("branch_name", "duplicate");
+        } else {
+            msg.set_meta("branch_name", "success");
+        }
+
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
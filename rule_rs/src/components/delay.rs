@@ -2,6 +2,7 @@ use crate::engine::NodeHandler;
 use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
 use async_trait::async_trait;
 use serde::Deserialize;
+use serde_json::json;
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -29,6 +30,9 @@ impl Default for DelayConfig {
 }
 
 /// 延迟处理节点
+///
+/// 非周期模式延迟一次后转发消息;周期模式每隔 `delay_ms` 转发一次,共 `period_count` 次,
+/// 每次转发的消息元数据 `iteration` 从1开始递增
 #[derive(Debug)]
 pub struct DelayNode {
     config: DelayConfig,
@@ -38,6 +42,28 @@ impl DelayNode {
     pub fn new(config: DelayConfig) -> Self {
         Self { config }
     }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "delay".to_string(),
+            name: "延时节点".to_string(),
+            description: "延迟处理消息,支持一次性延迟和周期性延迟".to_string(),
+            node_type: NodeType::Head,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["delay_ms", "periodic", "period_count"],
+                "properties": {
+                    "delay_ms": { "type": "integer", "minimum": 0, "description": "延迟时间(毫秒)" },
+                    "periodic": { "type": "boolean", "description": "是否周期性延迟" },
+                    "period_count": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "周期执行次数,0表示无限循环"
+                    }
+                }
+            })),
+        }
+    }
 }
 
 #[async_trait]
@@ -48,31 +74,28 @@ impl NodeHandler for DelayNode {
         msg: Message,
     ) -> Result<Message, RuleError> {
         if self.config.periodic {
-            let mut count = 0;
+            // 每个周期延迟后向下游发送一次,并在元数据中标记当前是第几次发送
+            let mut iteration = 0;
             loop {
                 sleep(Duration::from_millis(self.config.delay_ms)).await;
+
+                iteration += 1;
+                let mut next_msg = msg.clone();
+                next_msg.set_meta("iteration", iteration);
                 // 发送到下一个节点
-                ctx.send_next(msg.clone()).await?;
+                ctx.send_next(next_msg).await?;
 
-                count += 1;
-                if self.config.period_count > 0 && count >= self.config.period_count {
+                if self.config.period_count > 0 && iteration >= self.config.period_count {
                     break;
                 }
             }
         } else {
             sleep(Duration::from_millis(self.config.delay_ms)).await;
-            // 发送到下一个节点
-            ctx.send_next(msg.clone()).await?;
         }
         Ok(msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "delay".to_string(),
-            name: "延时节点".to_string(),
-            description: "延迟处理消息,支持一次性延迟和周期性延迟".to_string(),
-            node_type: NodeType::Head,
-        }
+        Self::descriptor()
     }
 }
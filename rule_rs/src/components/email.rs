@@ -0,0 +1,229 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::render_text;
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message as Email, Tokio1Executor};
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+/// SMTP 连接的加密方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// 明文连接后通过 STARTTLS 升级
+    #[default]
+    Starttls,
+    /// 直接建立 TLS 连接,通常使用 465 端口
+    Tls,
+    /// 不加密,仅用于本地或内网的 SMTP 服务
+    None,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmailConfig {
+    pub smtp_host: String,
+    /// SMTP 端口,未设置时按加密方式使用默认端口
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// 发件人,例如 `Alerts <alerts@example.com>`
+    pub from: String,
+    /// 收件人模板,多个收件人以逗号分隔
+    pub to_template: String,
+    pub subject_template: String,
+    pub body_template: String,
+    pub timeout_ms: Option<u64>,
+    /// 成功分支名称
+    #[serde(default = "default_success_branch")]
+    pub success_branch: String,
+    /// 失败分支名称
+    #[serde(default = "default_error_branch")]
+    pub error_branch: String,
+}
+
+fn default_success_branch() -> String {
+    "success".to_string()
+}
+
+fn default_error_branch() -> String {
+    "error".to_string()
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            smtp_host: "localhost".to_string(),
+            port: None,
+            tls: SmtpTls::Starttls,
+            username: None,
+            password: None,
+            from: "rule-rs@localhost".to_string(),
+            to_template: String::new(),
+            subject_template: String::new(),
+            body_template: String::new(),
+            timeout_ms: None,
+            success_branch: default_success_branch(),
+            error_branch: default_error_branch(),
+        }
+    }
+}
+
+/// 邮件发送节点
+///
+/// 收件人、主题和正文支持 `${msg.data.x}`、`${msg.metadata.y}` 等模板变量,
+/// 发送成功走成功分支,失败时错误写入 msg.metadata.error 并走失败分支。
+/// SMTP 连接池在创建节点时建立,所有消息共用。
+/// 重放的消息(`Message::is_replay`)不发送邮件,直接走成功分支
+#[derive(Debug)]
+pub struct EmailNode {
+    config: EmailConfig,
+    from: Mailbox,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl EmailNode {
+    pub fn new(config: EmailConfig) -> Result<Self, RuleError> {
+        let from = config
+            .from
+            .parse()
+            .map_err(|e| RuleError::ConfigError(format!("发件人地址无效: {}", e)))?;
+
+        let mut builder = match config.tls {
+            SmtpTls::Starttls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)
+            }
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host),
+            SmtpTls::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &config.smtp_host,
+            )),
+        }
+        .map_err(|e| RuleError::ConfigError(format!("SMTP配置无效: {}", e)))?
+        .timeout(Some(Duration::from_millis(
+            config.timeout_ms.unwrap_or(10000),
+        )));
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+        if let Some(username) = &config.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                config.password.clone().unwrap_or_default(),
+            ));
+        }
+
+        Ok(Self {
+            from,
+            transport: builder.build(),
+            config,
+        })
+    }
+
+    /// 使用消息渲染邮件内容
+    fn build_email(&self, msg: &Message) -> Result<Email, String> {
+        let mut builder = Email::builder()
+            .from(self.from.clone())
+            .subject(render_text(&self.config.subject_template, msg));
+
+        let to = render_text(&self.config.to_template, msg);
+        for address in to.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+            let mailbox: Mailbox = address
+                .parse()
+                .map_err(|e| format!("收件人地址无效 {}: {}", address, e))?;
+            builder = builder.to(mailbox);
+        }
+
+        builder
+            .body(render_text(&self.config.body_template, msg))
+            .map_err(|e| format!("邮件构造失败: {}", e))
+    }
+
+    async fn send(&self, msg: &Message) -> Result<(), String> {
+        let email = self.build_email(msg)?;
+        self.transport
+            .send(email)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("邮件发送失败: {}", e))
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "email".to_string(),
+            name: "邮件发送".to_string(),
+            description: "通过SMTP发送邮件,按结果走成功或失败分支".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["smtp_host", "from", "to_template", "subject_template", "body_template"],
+                "properties": {
+                    "smtp_host": { "type": "string", "description": "SMTP 服务地址" },
+                    "port": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": 65535,
+                        "description": "SMTP 端口,未设置时按加密方式使用默认端口"
+                    },
+                    "tls": {
+                        "type": "string",
+                        "enum": ["starttls", "tls", "none"],
+                        "description": "加密方式",
+                        "default": "starttls"
+                    },
+                    "username": { "type": "string", "description": "SMTP 用户名" },
+                    "password": { "type": "string", "description": "SMTP 密码" },
+                    "from": {
+                        "type": "string",
+                        "description": "发件人,例如 Alerts <alerts@example.com>"
+                    },
+                    "to_template": { "type": "string", "description": "收件人模板,多个收件人以逗号分隔" },
+                    "subject_template": { "type": "string", "description": "主题模板" },
+                    "body_template": { "type": "string", "description": "正文模板" },
+                    "timeout_ms": { "type": "integer", "minimum": 0, "description": "超时时间(毫秒)" },
+                    "success_branch": {
+                        "type": "string",
+                        "description": "成功分支名称",
+                        "default": "success"
+                    },
+                    "error_branch": {
+                        "type": "string",
+                        "description": "失败分支名称",
+                        "default": "error"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for EmailNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        // 重放的消息不再发送邮件,直接走成功分支
+        if msg.is_replay() {
+            msg.set_meta("branch_name", self.config.success_branch.clone());
+            return Ok(msg);
+        }
+        match self.send(&msg).await {
+            Ok(()) => msg.set_meta("branch_name", self.config.success_branch.clone()),
+            Err(e) => {
+                msg.set_meta("error", e);
+                msg.set_meta("branch_name", self.config.error_branch.clone());
+            }
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
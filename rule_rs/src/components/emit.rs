@@ -0,0 +1,64 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct EmitConfig {
+    /// 发布的主题,外部通过 `RuleEngine::subscribe` 订阅
+    pub topic: String,
+}
+
+/// 发布节点
+///
+/// 把消息发布到引擎内部的广播主题,并原样转发给下一个节点;重放的消息不发布
+#[derive(Debug)]
+pub struct EmitNode {
+    config: EmitConfig,
+}
+
+impl EmitNode {
+    pub fn new(config: EmitConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "emit".to_string(),
+            name: "发布节点".to_string(),
+            description: "把消息发布到引擎内部的广播主题".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["topic"],
+                "properties": {
+                    "topic": { "type": "string", "description": "广播主题" }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for EmitNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        // 重放的消息不再发布,避免订阅者重复处理
+        if msg.is_replay() {
+            return Ok(msg);
+        }
+
+        // 没有订阅者时消息直接丢弃,不影响后续处理
+        ctx.engine.publish(&self.config.topic, msg.clone()).await;
+
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
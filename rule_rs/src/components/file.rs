@@ -0,0 +1,196 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::fs::{self, OpenOptions};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// 文件操作模式
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileMode {
+    /// 读取文件,作为头节点把每条记录作为独立消息发送
+    Read,
+    /// 追加写入文件
+    Append,
+}
+
+/// 文件内容格式
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    /// 每行一个JSON值
+    Ndjson,
+    /// 完整JSON,读取时数组的每个元素作为一条记录
+    Json,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileConfig {
+    pub path: String,
+    #[serde(default = "default_mode")]
+    pub mode: FileMode,
+    #[serde(default = "default_format")]
+    pub format: FileFormat,
+}
+
+fn default_mode() -> FileMode {
+    FileMode::Append
+}
+
+fn default_format() -> FileFormat {
+    FileFormat::Ndjson
+}
+
+impl Default for FileConfig {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            mode: default_mode(),
+            format: default_format(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FileNode {
+    config: FileConfig,
+}
+
+impl FileNode {
+    pub fn new(config: FileConfig) -> Self {
+        Self { config }
+    }
+
+    fn file_error(&self, action: &str, e: impl std::fmt::Display) -> RuleError {
+        RuleError::ComponentError(format!("文件 {} {}失败: {}", self.config.path, action, e))
+    }
+
+    /// 追加写入消息数据
+    async fn append(&self, msg: &Message) -> Result<(), RuleError> {
+        let mut line = match self.config.format {
+            FileFormat::Ndjson => serde_json::to_string(&msg.data),
+            FileFormat::Json => serde_json::to_string_pretty(&msg.data),
+        }
+        .map_err(|e| self.file_error("序列化", e))?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.config.path)
+            .await
+            .map_err(|e| self.file_error("打开", e))?;
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|e| self.file_error("写入", e))?;
+        file.flush().await.map_err(|e| self.file_error("写入", e))
+    }
+
+    /// 读取文件,把每条记录作为独立消息发送到下一个节点
+    async fn read<'a>(&self, ctx: &NodeContext<'a>, msg: &Message) -> Result<(), RuleError> {
+        match self.config.format {
+            FileFormat::Ndjson => {
+                let file = fs::File::open(&self.config.path)
+                    .await
+                    .map_err(|e| self.file_error("打开", e))?;
+                let mut lines = BufReader::new(file).lines();
+                let mut line_no = 0;
+                while let Some(line) = lines
+                    .next_line()
+                    .await
+                    .map_err(|e| self.file_error("读取", e))?
+                {
+                    line_no += 1;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let data: Value = serde_json::from_str(&line)
+                        .map_err(|e| self.file_error(&format!("解析第 {} 行", line_no), e))?;
+                    ctx.send_next(self.record_message(msg, data)).await?;
+                }
+            }
+            FileFormat::Json => {
+                let content = fs::read_to_string(&self.config.path)
+                    .await
+                    .map_err(|e| self.file_error("读取", e))?;
+                let data: Value =
+                    serde_json::from_str(&content).map_err(|e| self.file_error("解析", e))?;
+                let records = match data {
+                    Value::Array(items) => items,
+                    data => vec![data],
+                };
+                for data in records {
+                    ctx.send_next(self.record_message(msg, data)).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 为文件中的一条记录创建消息,继承触发消息的元数据
+    fn record_message(&self, msg: &Message, data: Value) -> Message {
+        let mut record = Message::new("file", data);
+        record.metadata = msg.metadata.clone();
+        record
+    }
+
+    /// 默认追加模式下的描述符,读取模式的节点类型为头节点
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "file".to_string(),
+            name: "文件节点".to_string(),
+            description: "读取或追加写入行分隔JSON文件".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["path"],
+                "properties": {
+                    "path": { "type": "string", "description": "文件路径" },
+                    "mode": {
+                        "type": "string",
+                        "enum": ["read", "append"],
+                        "description": "read 读取文件并逐条发送记录,append 把消息数据追加到文件",
+                        "default": "append"
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["ndjson", "json"],
+                        "description": "文件格式",
+                        "default": "ndjson"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for FileNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        match self.config.mode {
+            FileMode::Read => self.read(&ctx, &msg).await?,
+            FileMode::Append => {
+                self.append(&msg).await?;
+            }
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        // 读取模式作为数据源启动规则链,追加模式作为中间节点写出数据
+        let node_type = match self.config.mode {
+            FileMode::Read => NodeType::Head,
+            FileMode::Append => NodeType::Middle,
+        };
+        NodeDescriptor {
+            node_type,
+            ..Self::descriptor()
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::engine::NodeHandler;
 use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::get_value_by_path;
 use async_trait::async_trait;
 use serde::Deserialize;
+use serde_json::json;
 
 #[derive(Debug)]
 pub struct FilterNode {
@@ -12,6 +14,12 @@ pub struct FilterNode {
 pub struct FilterConfig {
     pub condition: String,
     pub js_script: Option<String>,
+    /// 必须存在于 msg.data 中的字段路径,以点分隔,在 `condition` 之前检查
+    #[serde(default, alias = "checkFields")]
+    pub check_fields: Vec<String>,
+    /// 缺少字段时是否返回 `RuleError::ValidationError`,否则按未通过过滤处理
+    #[serde(default)]
+    pub strict: bool,
 }
 
 impl Default for FilterConfig {
@@ -19,6 +27,8 @@ impl Default for FilterConfig {
         Self {
             condition: "true".to_string(),
             js_script: None,
+            check_fields: Vec::new(),
+            strict: false,
         }
     }
 }
@@ -28,6 +38,16 @@ impl FilterNode {
         Self { config }
     }
 
+    /// 返回 `check_fields` 中 msg.data 缺少的字段
+    fn missing_fields(&self, msg: &Message) -> Vec<&str> {
+        self.config
+            .check_fields
+            .iter()
+            .filter(|field| get_value_by_path(&msg.data, field).is_none())
+            .map(String::as_str)
+            .collect()
+    }
+
     fn eval_condition(&self, _ctx: &NodeContext, msg: &Message) -> Result<bool, RuleError> {
         if let Some(value) = msg.data.get("value") {
             if let Some(num) = value.as_f64() {
@@ -46,6 +66,33 @@ impl FilterNode {
             ))
         }
     }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "filter".to_string(),
+            name: "消息过滤器".to_string(),
+            description: "根据条件过滤消息".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["condition"],
+                "properties": {
+                    "condition": { "type": "string", "description": "过滤条件" },
+                    "js_script": { "type": "string", "description": "JS过滤脚本" },
+                    "check_fields": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "必须存在于 msg.data 中的字段路径,也可写作 checkFields"
+                    },
+                    "strict": {
+                        "type": "boolean",
+                        "description": "缺少字段时返回校验错误,否则按未通过过滤处理",
+                        "default": false
+                    }
+                }
+            })),
+        }
+    }
 }
 
 #[async_trait]
@@ -55,21 +102,37 @@ impl NodeHandler for FilterNode {
         ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
-        if self.eval_condition(&ctx, &msg)? {
-            // 条件满足,发送到下一个节点
-            ctx.send_next(msg.clone()).await?;
-            Ok(msg)
+        let missing = self.missing_fields(&msg);
+        if !missing.is_empty() && self.config.strict {
+            return Err(RuleError::ValidationError(format!(
+                "缺少字段: {}",
+                missing.join(", ")
+            )));
+        }
+        let passed = missing.is_empty() && self.eval_condition(&ctx, &msg)?;
+
+        // 配置了 false 分支时,未通过过滤的消息发送到该分支,通过的消息发送到其他连接
+        let connections = ctx.outgoing_connections().await?;
+        if !connections.iter().any(|conn| conn.type_name == "false") {
+            return if passed {
+                Ok(msg)
+            } else {
+                Err(RuleError::FilterReject)
+            };
+        }
+        let mut msg = msg;
+        if !passed {
+            msg.set_meta("branch_name", "false");
+        } else if let Some(conn) = connections.iter().find(|conn| conn.type_name != "false") {
+            msg.set_meta("branch_name", conn.type_name.as_str());
         } else {
-            Err(RuleError::FilterReject)
+            // 只有 false 分支,通过的消息到此结束
+            ctx.mark_routed();
         }
+        Ok(msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "filter".to_string(),
-            name: "消息过滤器".to_string(),
-            description: "根据条件过滤消息".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
@@ -1,6 +1,7 @@
 use crate::engine::NodeHandler;
-use crate::types::{ExecutionContext, Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::types::{run_node, Message, NodeContext, NodeDescriptor, NodeType, RuleError};
 use async_trait::async_trait;
+use serde_json::json;
 
 #[derive(Debug)]
 pub struct ForkNode;
@@ -9,6 +10,16 @@ impl ForkNode {
     pub fn new() -> Self {
         Self
     }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "fork".to_string(),
+            name: "并行网关".to_string(),
+            description: "将消息并行发送到多个分支进行处理".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({ "type": "object" })),
+        }
+    }
 }
 
 #[async_trait]
@@ -18,17 +29,7 @@ impl NodeHandler for ForkNode {
         ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
-        let chain = ctx
-            .engine
-            .get_chain(ctx.node.chain_id)
-            .await
-            .ok_or(RuleError::ChainNotFound(ctx.node.chain_id))?;
-
-        let connections = chain
-            .connections
-            .iter()
-            .filter(|conn| conn.from_id == ctx.node.id)
-            .collect::<Vec<_>>();
+        let connections = ctx.outgoing_connections().await?;
 
         // 创建分支消息
         let mut branch_msgs = Vec::with_capacity(connections.len());
@@ -45,16 +46,12 @@ impl NodeHandler for ForkNode {
             let engine = ctx.engine.clone();
             let chain_id = ctx.node.chain_id;
             let to_id = conn.to_id;
+            let exec_ctx = ctx.create_next_context(branch_msg);
 
             let handle = tokio::spawn(async move {
                 if let Some(chain) = engine.get_chain(chain_id).await {
                     if let Some(target_node) = chain.nodes.iter().find(|n| n.id == to_id) {
-                        let ctx = NodeContext::new(
-                            target_node,
-                            &ExecutionContext::new(branch_msg.clone()),
-                            engine.clone(),
-                        );
-                        engine.execute_node(target_node, &ctx, branch_msg).await
+                        run_node(engine.clone(), target_node, exec_ctx).await
                     } else {
                         Err(RuleError::ConfigError(format!("节点 {} 不存在", to_id)))
                     }
@@ -65,6 +62,9 @@ impl NodeHandler for ForkNode {
             handles.push(handle);
         }
 
+        // 各分支已直接执行,引擎无需再转发
+        ctx.mark_routed();
+
         // 等待所有分支执行完成
         for handle in handles {
             match handle.await {
@@ -77,11 +77,6 @@ impl NodeHandler for ForkNode {
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "fork".to_string(),
-            name: "并行网关".to_string(),
-            description: "将消息并行发送到多个分支进行处理".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
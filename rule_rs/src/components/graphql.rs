@@ -0,0 +1,198 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::{render_template, render_text};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize)]
+pub struct GraphqlConfig {
+    /// GraphQL 服务地址
+    pub endpoint: String,
+    /// 查询语句
+    pub query: String,
+    /// 变量模板,使用消息渲染;未设置时使用 msg.data 作为变量
+    pub variables_template: Option<Value>,
+    /// 请求头,值中可以使用 `${msg.data.x}`、`${msg.metadata.y}` 等模板变量
+    pub headers: Option<HashMap<String, String>>,
+    pub timeout_ms: Option<u64>,
+    /// 成功分支名称
+    #[serde(default = "default_success_branch")]
+    pub success_branch: String,
+    /// 失败分支名称,请求失败或响应包含 `errors` 时使用
+    #[serde(default = "default_error_branch")]
+    pub error_branch: String,
+}
+
+fn default_success_branch() -> String {
+    "success".to_string()
+}
+
+fn default_error_branch() -> String {
+    "error".to_string()
+}
+
+impl Default for GraphqlConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://localhost/graphql".to_string(),
+            query: String::new(),
+            variables_template: None,
+            headers: None,
+            timeout_ms: None,
+            success_branch: default_success_branch(),
+            error_branch: default_error_branch(),
+        }
+    }
+}
+
+/// GraphQL 查询节点
+///
+/// 成功时将响应的 `data` 写入 msg.data;HTTP 状态码为 200 但响应包含 `errors` 时,
+/// 错误列表写入 msg.metadata.graphql_errors 并走失败分支
+#[derive(Debug)]
+pub struct GraphqlNode {
+    config: GraphqlConfig,
+    client: Client,
+}
+
+impl GraphqlNode {
+    pub fn new(config: GraphqlConfig) -> Result<Self, RuleError> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms.unwrap_or(5000)))
+            .build()
+            .map_err(|e| RuleError::ConfigError(format!("HTTP客户端创建失败: {}", e)))?;
+        Ok(Self { config, client })
+    }
+
+    /// 发送查询,返回解析后的响应体
+    async fn execute_query(&self, msg: &Message) -> Result<Value, RuleError> {
+        let variables = match &self.config.variables_template {
+            Some(template) => render_template(template, msg),
+            None => msg.data.clone(),
+        };
+
+        let mut request = self
+            .client
+            .post(&self.config.endpoint)
+            .json(&json!({ "query": self.config.query, "variables": variables }));
+        if let Some(headers) = &self.config.headers {
+            for (key, value) in headers {
+                request = request.header(key, render_text(value, msg));
+            }
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| RuleError::NodeExecutionError(format!("GraphQL请求失败: {}", e)))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| RuleError::NodeExecutionError(format!("响应读取失败: {}", e)))?;
+
+        // 部分服务在非 200 状态码下也返回 errors,优先使用其中的错误信息
+        match serde_json::from_str::<Value>(&text) {
+            Ok(body) if body.is_object() => Ok(body),
+            _ if !status.is_success() => Err(RuleError::NodeExecutionError(format!(
+                "GraphQL请求返回错误状态码: {}",
+                status
+            ))),
+            Ok(_) => Err(RuleError::NodeExecutionError(
+                "GraphQL响应不是JSON对象".to_string(),
+            )),
+            Err(e) => Err(RuleError::NodeExecutionError(format!(
+                "响应解析失败: {}",
+                e
+            ))),
+        }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "graphql".to_string(),
+            name: "GraphQL查询".to_string(),
+            description: "发送GraphQL查询,响应包含errors时走失败分支".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["endpoint", "query"],
+                "properties": {
+                    "endpoint": { "type": "string", "description": "GraphQL 服务地址" },
+                    "query": { "type": "string", "description": "查询语句" },
+                    "variables_template": { "type": "object", "description": "查询变量模板,字符串值支持模板变量" },
+                    "headers": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "请求头,值支持模板变量"
+                    },
+                    "timeout_ms": { "type": "integer", "minimum": 0, "description": "超时时间(毫秒)" },
+                    "success_branch": {
+                        "type": "string",
+                        "description": "成功分支名称",
+                        "default": "success"
+                    },
+                    "error_branch": {
+                        "type": "string",
+                        "description": "失败分支名称",
+                        "default": "error"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+/// 拼接 GraphQL 错误列表中的 message 字段
+fn error_messages(errors: &[Value]) -> String {
+    errors
+        .iter()
+        .map(|error| error["message"].as_str().unwrap_or("Unknown error"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[async_trait]
+impl NodeHandler for GraphqlNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+
+        match self.execute_query(&msg).await {
+            Ok(mut body) => {
+                let data = body["data"].take();
+                if !data.is_null() {
+                    msg.data = data;
+                }
+                msg.msg_type = "graphql_response".to_string();
+
+                // 响应包含 errors 时即使 HTTP 请求成功也视为失败,保留部分返回的 data
+                match body["errors"].take() {
+                    Value::Array(errors) if !errors.is_empty() => {
+                        msg.set_meta("error", error_messages(&errors));
+                        msg.set_meta("graphql_errors", errors);
+                        msg.set_meta("branch_name", self.config.error_branch.clone());
+                    }
+                    _ => msg.set_meta("branch_name", self.config.success_branch.clone()),
+                }
+            }
+            Err(e) => {
+                msg.set_meta("error", e.to_string());
+                msg.set_meta("branch_name", self.config.error_branch.clone());
+            }
+        }
+
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -0,0 +1,356 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::render_template;
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use prost::Message as _;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor};
+use prost_types::FileDescriptorProto;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+use tonic::Status;
+use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::ServerReflectionRequest;
+
+lazy_static! {
+    // 节点处理器会被重复创建,通道按 endpoint 保存在全局状态中以便复用
+    static ref GLOBAL_GRPC_CHANNELS: std::sync::Mutex<HashMap<String, Channel>> =
+        std::sync::Mutex::new(HashMap::new());
+    // 描述符按描述文件路径或反射地址缓存
+    static ref GLOBAL_GRPC_DESCRIPTORS: Mutex<HashMap<String, DescriptorPool>> =
+        Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GrpcClientConfig {
+    /// 服务地址,如 http://localhost:50051
+    pub endpoint: String,
+    /// 完整服务名,如 helloworld.Greeter
+    pub service: String,
+    /// 方法名,如 SayHello
+    pub method: String,
+    /// FileDescriptorSet 文件路径,未配置时通过服务端反射获取描述符
+    pub descriptor_set: Option<String>,
+    /// 请求模板,未配置时直接使用 msg.data 作为请求
+    pub request_template: Option<Value>,
+    pub timeout_ms: Option<u64>,
+    pub success_branch: Option<String>, // 成功分支名称
+    pub error_branch: Option<String>,   // 失败分支名称
+}
+
+impl Default for GrpcClientConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://localhost:50051".to_string(),
+            service: String::new(),
+            method: String::new(),
+            descriptor_set: None,
+            request_template: None,
+            timeout_ms: None,
+            success_branch: None,
+            error_branch: None,
+        }
+    }
+}
+
+/// gRPC客户端节点
+///
+/// 根据描述符动态构造请求消息,调用一元RPC,并把响应按 proto3 JSON 映射写入 msg.data
+#[derive(Debug)]
+pub struct GrpcClientNode {
+    config: GrpcClientConfig,
+    channel: Channel,
+}
+
+impl GrpcClientNode {
+    pub fn new(config: GrpcClientConfig) -> Result<Self, RuleError> {
+        let channel = Self::channel(&config)?;
+        Ok(Self { config, channel })
+    }
+
+    /// 获取或创建到服务地址的通道,连接在首次调用时建立
+    fn channel(config: &GrpcClientConfig) -> Result<Channel, RuleError> {
+        let mut channels = GLOBAL_GRPC_CHANNELS.lock().unwrap();
+        if let Some(channel) = channels.get(&config.endpoint) {
+            return Ok(channel.clone());
+        }
+
+        let mut endpoint = Endpoint::from_shared(config.endpoint.clone())
+            .map_err(|e| RuleError::ConfigError(format!("gRPC地址无效: {}", e)))?;
+        if let Some(timeout_ms) = config.timeout_ms {
+            endpoint = endpoint.timeout(Duration::from_millis(timeout_ms));
+        }
+        let channel = endpoint.connect_lazy();
+        channels.insert(config.endpoint.clone(), channel.clone());
+        Ok(channel)
+    }
+
+    /// 获取调用方法的描述符
+    async fn method_descriptor(&self) -> Result<MethodDescriptor, RuleError> {
+        let key = match &self.config.descriptor_set {
+            Some(path) => path.clone(),
+            None => format!(
+                "reflection:{}/{}",
+                self.config.endpoint, self.config.service
+            ),
+        };
+
+        let mut pools = GLOBAL_GRPC_DESCRIPTORS.lock().await;
+        let pool = match pools.get(&key) {
+            Some(pool) => pool.clone(),
+            None => {
+                let pool = match &self.config.descriptor_set {
+                    Some(path) => Self::load_descriptor_set(path).await?,
+                    None => self.fetch_descriptors().await?,
+                };
+                pools.insert(key, pool.clone());
+                pool
+            }
+        };
+
+        pool.get_service_by_name(&self.config.service)
+            .ok_or_else(|| {
+                RuleError::ConfigError(format!("未找到gRPC服务: {}", self.config.service))
+            })?
+            .methods()
+            .find(|method| method.name() == self.config.method)
+            .ok_or_else(|| {
+                RuleError::ConfigError(format!(
+                    "gRPC服务 {} 不存在方法 {}",
+                    self.config.service, self.config.method
+                ))
+            })
+    }
+
+    /// 从 FileDescriptorSet 文件加载描述符
+    async fn load_descriptor_set(path: &str) -> Result<DescriptorPool, RuleError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| RuleError::ConfigError(format!("读取描述文件 {} 失败: {}", path, e)))?;
+        DescriptorPool::decode(bytes.as_slice())
+            .map_err(|e| RuleError::ConfigError(format!("解析描述文件 {} 失败: {}", path, e)))
+    }
+
+    /// 通过服务端反射获取服务及其依赖的描述符
+    async fn fetch_descriptors(&self) -> Result<DescriptorPool, RuleError> {
+        let mut client = ServerReflectionClient::new(self.channel.clone());
+        let mut files: HashMap<String, FileDescriptorProto> = HashMap::new();
+        let mut requests = vec![MessageRequest::FileContainingSymbol(
+            self.config.service.clone(),
+        )];
+
+        while !requests.is_empty() {
+            let stream =
+                futures::stream::iter(std::mem::take(&mut requests).into_iter().map(|request| {
+                    ServerReflectionRequest {
+                        host: String::new(),
+                        message_request: Some(request),
+                    }
+                }));
+            let mut responses = client
+                .server_reflection_info(stream)
+                .await
+                .map_err(Self::status_error)?
+                .into_inner();
+
+            while let Some(response) = responses.message().await.map_err(Self::status_error)? {
+                match response.message_response {
+                    Some(MessageResponse::FileDescriptorResponse(response)) => {
+                        for bytes in response.file_descriptor_proto {
+                            let file =
+                                FileDescriptorProto::decode(bytes.as_slice()).map_err(|e| {
+                                    RuleError::ComponentError(format!("解析反射描述符失败: {}", e))
+                                })?;
+                            files.insert(file.name().to_string(), file);
+                        }
+                    }
+                    Some(MessageResponse::ErrorResponse(e)) => {
+                        return Err(RuleError::ComponentError(format!(
+                            "gRPC反射失败: {}",
+                            e.error_message
+                        )));
+                    }
+                    _ => {}
+                }
+            }
+
+            // 继续获取尚未返回的依赖文件
+            let missing: HashSet<String> = files
+                .values()
+                .flat_map(|file| file.dependency.iter())
+                .filter(|name| !files.contains_key(*name))
+                .cloned()
+                .collect();
+            requests.extend(missing.into_iter().map(MessageRequest::FileByFilename));
+        }
+
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_protos(files.into_values())
+            .map_err(|e| RuleError::ComponentError(format!("构建反射描述符失败: {}", e)))?;
+        Ok(pool)
+    }
+
+    fn status_error(status: Status) -> RuleError {
+        RuleError::ComponentError(format!(
+            "gRPC调用失败: {:?} {}",
+            status.code(),
+            status.message()
+        ))
+    }
+
+    async fn call(&self, msg: &Message) -> Result<Value, RuleError> {
+        let method = self.method_descriptor().await?;
+
+        // 构造请求
+        let request_json = match &self.config.request_template {
+            Some(template) => render_template(template, msg),
+            None => msg.data.clone(),
+        };
+        let request = DynamicMessage::deserialize(method.input(), request_json)
+            .map_err(|e| RuleError::ComponentError(format!("构造gRPC请求失败: {}", e)))?;
+
+        let path =
+            PathAndQuery::from_str(&format!("/{}/{}", self.config.service, self.config.method))
+                .map_err(|e| RuleError::ConfigError(format!("gRPC方法路径无效: {}", e)))?;
+
+        // 调用一元RPC
+        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
+        grpc.ready()
+            .await
+            .map_err(|e| RuleError::ComponentError(format!("gRPC连接失败: {}", e)))?;
+        let response = grpc
+            .unary(
+                tonic::Request::new(request),
+                path,
+                DynamicCodec(method.output()),
+            )
+            .await
+            .map_err(Self::status_error)?;
+
+        serde_json::to_value(response.into_inner())
+            .map_err(|e| RuleError::ComponentError(format!("解析gRPC响应失败: {}", e)))
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "grpc_client".to_string(),
+            name: "gRPC客户端".to_string(),
+            description: "动态调用gRPC一元方法,支持成功/失败分支路由".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["endpoint", "service", "method"],
+                "properties": {
+                    "endpoint": { "type": "string", "description": "gRPC 服务地址" },
+                    "service": {
+                        "type": "string",
+                        "description": "完整的服务名称,例如 helloworld.Greeter"
+                    },
+                    "method": { "type": "string", "description": "方法名称" },
+                    "descriptor_set": {
+                        "type": "string",
+                        "description": "FileDescriptorSet 文件路径,未设置时通过服务端反射获取"
+                    },
+                    "request_template": {
+                        "type": "object",
+                        "description": "请求模板,未设置时直接使用 msg.data"
+                    },
+                    "timeout_ms": { "type": "integer", "minimum": 0, "description": "超时时间(毫秒)" },
+                    "success_branch": { "type": "string", "description": "成功分支名称,未设置时按默认分支转发" },
+                    "error_branch": { "type": "string", "description": "失败分支名称,未设置时返回错误" }
+                }
+            })),
+        }
+    }
+}
+
+/// 动态消息编解码器
+struct DynamicCodec(MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.0.clone())
+    }
+}
+
+struct DynamicEncoder;
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|e| Status::internal(format!("编码请求失败: {}", e)))
+    }
+}
+
+struct DynamicDecoder(MessageDescriptor);
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        DynamicMessage::decode(self.0.clone(), src)
+            .map(Some)
+            .map_err(|e| Status::internal(format!("解码响应失败: {}", e)))
+    }
+}
+
+#[async_trait]
+impl NodeHandler for GrpcClientNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+
+        match self.call(&msg).await {
+            Ok(response) => {
+                msg.data = response;
+                msg.msg_type = "grpc_response".to_string();
+
+                // 设置成功分支
+                if let Some(branch) = &self.config.success_branch {
+                    msg.set_meta("branch_name", branch.clone());
+                }
+
+                Ok(msg)
+            }
+            Err(e) => {
+                // 未配置失败分支时直接返回错误
+                let Some(branch) = &self.config.error_branch else {
+                    return Err(e);
+                };
+                msg.set_meta("error", e.to_string());
+                msg.set_meta("branch_name", branch.clone());
+
+                Ok(msg)
+            }
+        }
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
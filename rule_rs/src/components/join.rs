@@ -33,6 +33,16 @@ impl JoinNode {
     pub fn new(config: JoinConfig) -> Self {
         Self { config }
     }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "join".to_string(),
+            name: "汇聚节点".to_string(),
+            description: "汇聚并合并多个并行分支的执行结果".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({ "type": "object" })),
+        }
+    }
 }
 
 #[async_trait]
@@ -42,17 +52,7 @@ impl NodeHandler for JoinNode {
         ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
-        let chain = ctx
-            .engine
-            .get_chain(ctx.node.chain_id)
-            .await
-            .ok_or(RuleError::ChainNotFound(ctx.node.chain_id))?;
-
-        let expected_branches = chain
-            .connections
-            .iter()
-            .filter(|conn| conn.to_id == ctx.node.id)
-            .count();
+        let expected_branches = ctx.incoming_connections().await?.len();
 
         // 使用全局状态存储
         let mut global_state = GLOBAL_JOIN_STATE.lock().await;
@@ -74,14 +74,15 @@ impl NodeHandler for JoinNode {
                         "data": msg.data,
                     })).collect::<Vec<_>>()
                 }),
+                binary: msg.binary,
                 timestamp: msg.timestamp,
+                ack: msg.ack,
             };
 
             debug!(
                 "Join节点 {} 合并完成，发送结果消息: {:?}",
                 ctx.node.id, result_msg
             );
-            ctx.send_next(result_msg.clone()).await?;
             Ok(result_msg)
         } else {
             debug!(
@@ -90,16 +91,13 @@ impl NodeHandler for JoinNode {
                 messages.len(),
                 expected_branches
             );
+            // 等待其他分支,合并前不向下游发送
+            ctx.mark_routed();
             Ok(msg)
         }
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "join".to_string(),
-            name: "汇聚节点".to_string(),
-            description: "汇聚并合并多个并行分支的执行结果".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
@@ -0,0 +1,176 @@
+use crate::types::{Message, Node, RuleError, ScriptPhase};
+use rquickjs::function::Rest;
+use rquickjs::{Context, Ctx, Exception, Function, Object, Runtime, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::Level;
+use uuid::Uuid;
+
+/// 在 JS 上下文中安装 `console` 对象
+///
+/// `console.log`/`console.info`/`console.debug`/`console.warn`/`console.error` 转为对应级别的
+/// tracing 事件,带有 `node.id`、`node.type_name` 和 `msg.id` 字段。
+/// 多个参数以空格连接,非字符串参数按 JSON 输出
+pub(crate) fn install_console(ctx: &Ctx<'_>, node: &Node, msg: &Message) -> rquickjs::Result<()> {
+    let console = Object::new(ctx.clone())?;
+    for (name, level) in [
+        ("log", Level::INFO),
+        ("info", Level::INFO),
+        ("debug", Level::DEBUG),
+        ("warn", Level::WARN),
+        ("error", Level::ERROR),
+    ] {
+        let (node_id, node_type, msg_id) = (node.id, node.type_name.clone(), msg.id);
+        let function = Function::new(ctx.clone(), move |args: Rest<Value<'_>>| {
+            let text = format_args(args);
+            emit(level, node_id, &node_type, msg_id, &text);
+        })?;
+        console.set(name, function)?;
+    }
+    ctx.globals().set("console", console)
+}
+
+/// 拼接 console 参数
+fn format_args(args: Rest<Value<'_>>) -> String {
+    args.0
+        .into_iter()
+        .map(|value| {
+            if let Some(text) = value.as_string() {
+                return text.to_string().unwrap_or_default();
+            }
+            if value.is_undefined() {
+                return "undefined".to_string();
+            }
+            value
+                .ctx()
+                .json_stringify(value.clone())
+                .ok()
+                .flatten()
+                .and_then(|text| text.to_string().ok())
+                .unwrap_or_else(|| format!("{:?}", value))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn emit(level: Level, node_id: Uuid, node_type: &str, msg_id: Uuid, text: &str) {
+    match level {
+        Level::ERROR => tracing::error!(
+            node.id = %node_id, node.type_name = node_type, msg.id = %msg_id,
+            "{}", text
+        ),
+        Level::WARN => tracing::warn!(
+            node.id = %node_id, node.type_name = node_type, msg.id = %msg_id,
+            "{}", text
+        ),
+        Level::DEBUG => tracing::debug!(
+            node.id = %node_id, node.type_name = node_type, msg.id = %msg_id,
+            "{}", text
+        ),
+        _ => tracing::info!(
+            node.id = %node_id, node.type_name = node_type, msg.id = %msg_id,
+            "{}", text
+        ),
+    }
+}
+
+/// 提取 JS 错误信息,抛出异常时返回异常的消息
+pub(crate) fn js_error(ctx: &Ctx<'_>, error: rquickjs::Error) -> String {
+    match error {
+        rquickjs::Error::Exception => {
+            let exception = ctx.catch();
+            match exception.as_exception().and_then(Exception::message) {
+                Some(message) => message,
+                None => format!("{:?}", exception),
+            }
+        }
+        other => other.to_string(),
+    }
+}
+
+/// 脚本默认执行超时(毫秒)
+pub(crate) const DEFAULT_SCRIPT_TIMEOUT_MS: u64 = 1000;
+
+/// JS 运行时内存上限
+const MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+pub(crate) fn default_script_timeout_ms() -> u64 {
+    DEFAULT_SCRIPT_TIMEOUT_MS
+}
+
+/// 限制执行时间和内存的 JS 运行时
+///
+/// 中断回调在超过截止时间后中止脚本,避免死循环一直占用工作线程
+pub(crate) struct JsRuntime {
+    runtime: Runtime,
+    timeout: Duration,
+    /// 当前执行的截止时间,未执行脚本时为 None
+    deadline: Arc<Mutex<Option<Instant>>>,
+    /// 本次执行是否因超时被中断
+    interrupted: Arc<AtomicBool>,
+}
+
+impl JsRuntime {
+    pub(crate) fn new(timeout_ms: u64) -> Result<Self, RuleError> {
+        let runtime = Runtime::new()
+            .map_err(|e| RuleError::ConfigError(format!("创建JS运行时失败: {}", e)))?;
+        runtime.set_memory_limit(MEMORY_LIMIT);
+
+        let deadline = Arc::new(Mutex::new(None::<Instant>));
+        let interrupted = Arc::new(AtomicBool::new(false));
+        let (handler_deadline, handler_interrupted) = (deadline.clone(), interrupted.clone());
+        runtime.set_interrupt_handler(Some(Box::new(move || {
+            let expired = handler_deadline
+                .lock()
+                .unwrap()
+                .is_some_and(|deadline| Instant::now() >= deadline);
+            if expired {
+                handler_interrupted.store(true, Ordering::SeqCst);
+            }
+            expired
+        })));
+
+        Ok(Self {
+            runtime,
+            timeout: Duration::from_millis(timeout_ms),
+            deadline,
+            interrupted,
+        })
+    }
+
+    pub(crate) fn context(&self) -> Result<Context, RuleError> {
+        Context::full(&self.runtime)
+            .map_err(|e| RuleError::ConfigError(format!("创建JS上下文失败: {}", e)))
+    }
+
+    /// 在时限内执行脚本,超时或内存不足时返回 `RuleError::ScriptError`
+    ///
+    /// 需要在 `Context::with` 中调用,同一运行时的执行依次进行,不会互相覆盖截止时间
+    pub(crate) fn run<T>(
+        &self,
+        phase: ScriptPhase,
+        f: impl FnOnce() -> Result<T, RuleError>,
+    ) -> Result<T, RuleError> {
+        self.interrupted.store(false, Ordering::SeqCst);
+        *self.deadline.lock().unwrap() = Some(Instant::now() + self.timeout);
+        let result = f();
+        *self.deadline.lock().unwrap() = None;
+
+        result.map_err(|e| {
+            if self.interrupted.load(Ordering::SeqCst) {
+                RuleError::ScriptError {
+                    phase,
+                    message: format!("执行超过 {}ms", self.timeout.as_millis()),
+                }
+            } else if e.to_string().contains("out of memory") {
+                RuleError::ScriptError {
+                    phase,
+                    message: format!("内存超过上限 {}MB", MEMORY_LIMIT / 1024 / 1024),
+                }
+            } else {
+                e
+            }
+        })
+    }
+}
@@ -1,17 +1,27 @@
+use super::js_common::{default_script_timeout_ms, install_console, js_error, JsRuntime};
 use crate::engine::NodeHandler;
-use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError, ScriptPhase};
 use async_trait::async_trait;
-use rquickjs::Context;
+use rquickjs::{Context, Function};
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::fmt;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct JsFunctionConfig {
     pub functions: HashMap<String, String>, // 函数名 -> 函数定义
     pub main: String,                       // 主函数名
+    /// 函数库代码,在创建节点时执行一次,其中定义的辅助函数和变量对所有函数可见,并在消息间保留
+    #[serde(default)]
+    pub library: Option<String>,
+    /// 脚本执行超时(毫秒),超时后中止脚本并返回错误
+    #[serde(default = "default_script_timeout_ms")]
+    pub script_timeout_ms: u64,
+    /// 已不再使用,每个节点拥有独立的 JS 运行时,无需区分同名函数;保留以兼容已有配置
     #[serde(default)]
     pub chain_id: String,
+    /// 已不再使用,保留以兼容已有配置
     #[serde(default)]
     pub node_id: String,
 }
@@ -19,91 +29,163 @@ pub struct JsFunctionConfig {
 impl Default for JsFunctionConfig {
     fn default() -> Self {
         Self {
-            functions: HashMap::new(),
+            functions: HashMap::from([("main".to_string(), "return msg.data;".to_string())]),
             main: "main".to_string(),
+            library: None,
+            script_timeout_ms: default_script_timeout_ms(),
             chain_id: String::new(),
             node_id: String::new(),
         }
     }
 }
 
-#[derive(Debug)]
+/// 全局对象上保存节点入口函数的名称
+const ENTRY_NAME: &str = "__rule_js_function_entry";
+
+/// JS函数节点
+///
+/// 创建节点时编译函数库和所有函数,之后每条消息只调用主函数,函数库中的变量在消息间保留。
+/// 所有函数定义在同一个闭包作用域中,可以直接按名称互相调用,不需要改写函数名。
+/// 同一节点的消息在同一个 JS 运行时中依次执行
 pub struct JsFunctionNode {
     config: JsFunctionConfig,
+    runtime: JsRuntime,
+    /// 已加载函数库的 JS 上下文
+    context: Context,
 }
 
-impl JsFunctionNode {
-    pub fn new(config: JsFunctionConfig) -> Self {
-        Self { config }
+impl fmt::Debug for JsFunctionNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsFunctionNode")
+            .field("config", &self.config)
+            .finish()
     }
+}
 
-    fn replace_function_names(&self, code: &str, config: &JsFunctionConfig) -> String {
-        let mut modified_code = code.to_string();
-        // 遍历所有已定义的函数名
+impl JsFunctionNode {
+    pub fn new(config: JsFunctionConfig) -> Result<Self, RuleError> {
         for name in config.functions.keys() {
-            // 检查是否包含 "name("
-            let search_pattern = format!("{}(", name);
-            if code.contains(&search_pattern) {
-                // 构造完整的函数名
-                let full_name = format!("{}_{}_{}", name, config.chain_id, config.node_id);
-                // 直接替换函数调用
-                modified_code = modified_code.replace(&search_pattern, &format!("{}(", full_name));
+            if !is_identifier(name) {
+                return Err(RuleError::ConfigError(format!("无效的函数名: {}", name)));
             }
         }
-        modified_code
-    }
-
-    fn register_functions<'js>(&self, ctx: &rquickjs::Ctx<'js>) -> Result<(), RuleError> {
-        // 注册所有函数
-        let config = self.config.functions.clone();
-
-        // 先处理所有函数的代码
-        let mut processed_functions: HashMap<String, String> = HashMap::new();
-        for (name, code) in &config {
-            let func_name = format!("{}_{}_{}", name, self.config.chain_id, self.config.node_id);
-            // 替换函数体中的其他函数调用
-            let modified_code = self.replace_function_names(code, &self.config);
-            processed_functions.insert(func_name, modified_code);
+        if !config.functions.contains_key(&config.main) {
+            return Err(RuleError::ConfigError(format!(
+                "主函数 {} 未定义",
+                config.main
+            )));
         }
 
-        // 然后注册处理后的函数
-        for (func_name, modified_code) in processed_functions {
-            let js_code = format!(
-                r#"
-                function {}(msg) {{
-                    {}
-                }}
-                "#,
-                func_name, modified_code
-            );
-            // println!("js_code: {}", js_code); // 添加调试日志
-            ctx.eval::<(), _>(js_code)
-                .map_err(|e| RuleError::NodeExecutionError(format!("函数注册失败: {}", e)))?;
-        }
-        Ok(())
+        let runtime = JsRuntime::new(config.script_timeout_ms)?;
+        let context = runtime.context()?;
+        context.with(|ctx| {
+            runtime.run(ScriptPhase::Load, || {
+                ctx.eval::<(), _>(Self::entry_code(&config)).map_err(|e| {
+                    RuleError::ConfigError(format!("函数注册失败: {}", js_error(&ctx, e)))
+                })
+            })
+        })?;
+
+        Ok(Self {
+            config,
+            runtime,
+            context,
+        })
+    }
+
+    /// 生成定义入口函数的代码
+    ///
+    /// 函数库和所有函数放在同一个闭包中,入口函数接收消息 JSON,调用主函数并返回结果 JSON
+    fn entry_code(config: &JsFunctionConfig) -> String {
+        let functions = config
+            .functions
+            .iter()
+            .map(|(name, code)| format!("function {}(msg) {{\n{}\n}}", name, code))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            r#"
+            globalThis.{entry} = (function () {{
+                {library}
+                {functions}
+                return function (input) {{
+                    const result = {main}(JSON.parse(input));
+                    return JSON.stringify(result === undefined ? null : result);
+                }};
+            }})();
+            "#,
+            entry = ENTRY_NAME,
+            library = config.library.as_deref().unwrap_or_default(),
+            functions = functions,
+            main = config.main,
+        )
     }
 
-    fn execute_js<'js>(&self, ctx: &rquickjs::Ctx<'js>, msg: &Message) -> Result<Value, RuleError> {
-        // 注册函数
-        self.register_functions(ctx)?;
-
-        // 注入消息对象
-        let main_name = format!(
-            "{}_{}_{}",
-            self.config.main, self.config.chain_id, self.config.node_id
-        );
-        let msg_json = serde_json::to_string(&msg).unwrap();
-        ctx.eval::<(), _>(format!("const msg = {};", msg_json))
+    fn execute_js(&self, node_ctx: &NodeContext, msg: &Message) -> Result<Value, RuleError> {
+        let msg_json = serde_json::to_string(&msg)
             .map_err(|e| RuleError::NodeExecutionError(e.to_string()))?;
 
-        // 调用主函数并获取结果
-        let result = ctx
-            .eval::<String, _>(format!("JSON.stringify({}(msg));", main_name))
-            .map_err(|e| RuleError::NodeExecutionError(format!("函数执行失败: {}", e)))?;
+        // 只传入本次的消息,函数库和函数定义保留在上下文中
+        let result = self.context.with(|ctx| {
+            install_console(&ctx, node_ctx.node, msg)
+                .map_err(|e| RuleError::NodeExecutionError(js_error(&ctx, e)))?;
+            let entry: Function = ctx
+                .globals()
+                .get(ENTRY_NAME)
+                .map_err(|e| RuleError::NodeExecutionError(js_error(&ctx, e)))?;
+            self.runtime.run(ScriptPhase::Runtime, || {
+                entry.call::<_, String>((msg_json,)).map_err(|e| {
+                    RuleError::NodeExecutionError(format!("函数执行失败: {}", js_error(&ctx, e)))
+                })
+            })
+        })?;
+
         // 解析JSON结果
         serde_json::from_str(&result)
             .map_err(|e| RuleError::NodeExecutionError(format!("结果解析失败: {}", e)))
     }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "js_function".to_string(),
+            name: "JS函数节点".to_string(),
+            description: "执行自定义JS函数".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["functions", "main"],
+                "properties": {
+                    "functions": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "函数名到函数定义的映射"
+                    },
+                    "main": { "type": "string", "description": "主函数名" },
+                    "script_timeout_ms": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "default": 1000,
+                        "description": "脚本执行超时(毫秒),超时后中止脚本"
+                    },
+                    "library": {
+                        "type": "string",
+                        "description": "函数库代码,创建节点时执行一次,定义的辅助函数和变量对所有函数可见"
+                    },
+                    "chain_id": { "type": "string", "description": "已不再使用,保留以兼容已有配置" },
+                    "node_id": { "type": "string", "description": "已不再使用,保留以兼容已有配置" }
+                }
+            })),
+        }
+    }
+}
+
+/// 是否为合法的 JS 标识符
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
 }
 
 #[async_trait]
@@ -113,10 +195,7 @@ impl NodeHandler for JsFunctionNode {
         ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
-        // 创建新的运行时
-        let runtime = rquickjs::Runtime::new().unwrap();
-        let ctx_js = Context::full(&runtime).unwrap();
-        let result = ctx_js.with(|ctx| self.execute_js(&ctx, &msg))?;
+        let result = self.execute_js(&ctx, &msg)?;
 
         // 构造返回消息
         let new_msg = Message {
@@ -124,21 +203,15 @@ impl NodeHandler for JsFunctionNode {
             msg_type: "js_function_result".to_string(),
             metadata: msg.metadata,
             data: result,
+            binary: msg.binary,
             timestamp: msg.timestamp,
+            ack: msg.ack,
         };
 
-        // 发送到下一个节点
-        ctx.send_next(new_msg.clone()).await?;
-
         Ok(new_msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "js_function".to_string(),
-            name: "JS函数节点".to_string(),
-            description: "执行自定义JS函数".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
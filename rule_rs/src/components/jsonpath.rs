@@ -0,0 +1,147 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::set_value_by_path;
+use async_trait::async_trait;
+use jsonpath_rust::parser::model::JpQuery;
+use jsonpath_rust::parser::parse_json_path;
+use jsonpath_rust::query::js_path_process;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonPathConfig {
+    /// JSONPath 表达式,以 `$` 开头;以 `/` 开头时按 JSON Pointer 解析
+    pub expr: String,
+    /// 写入结果的 msg.data 字段路径,以点分隔;未设置时用结果替换 msg.data
+    #[serde(default)]
+    pub output_field: Option<String>,
+    /// 只取第一个匹配值,默认输出所有匹配值组成的数组
+    #[serde(default)]
+    pub first: bool,
+}
+
+impl Default for JsonPathConfig {
+    fn default() -> Self {
+        Self {
+            expr: "$".to_string(),
+            output_field: None,
+            first: false,
+        }
+    }
+}
+
+/// 表达式的解析结果
+#[derive(Debug)]
+enum Expr {
+    Path(JpQuery),
+    Pointer(String),
+}
+
+/// JSONPath 提取节点
+///
+/// 对 msg.data 执行 JSONPath 表达式或 JSON Pointer,把匹配值写入 `output_field`,走 `success` 分支。
+/// 没有匹配值时走 `no_match` 分支;未配置 `no_match` 连接时继续走 `success`,
+/// 输出空数组(`first` 为 true 时输出 null)
+#[derive(Debug)]
+pub struct JsonPathNode {
+    config: JsonPathConfig,
+    expr: Expr,
+}
+
+impl JsonPathNode {
+    pub fn new(config: JsonPathConfig) -> Result<Self, RuleError> {
+        // 表达式只在创建节点时解析一次
+        let expr = if config.expr.is_empty() || config.expr.starts_with('/') {
+            Expr::Pointer(config.expr.clone())
+        } else {
+            let query = parse_json_path(&config.expr).map_err(|e| {
+                RuleError::ConfigError(format!("JSONPath 表达式 {} 无效: {}", config.expr, e))
+            })?;
+            Expr::Path(query)
+        };
+        Ok(Self { config, expr })
+    }
+
+    /// 按顺序返回所有匹配值
+    fn matches<'a>(&self, data: &'a Value) -> Result<Vec<&'a Value>, RuleError> {
+        match &self.expr {
+            Expr::Path(query) => js_path_process(query, data)
+                .map(|results| results.into_iter().map(|r| r.val).collect())
+                .map_err(|e| RuleError::NodeExecutionError(format!("JSONPath 执行失败: {}", e))),
+            Expr::Pointer(pointer) => Ok(data.pointer(pointer).into_iter().collect()),
+        }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "jsonpath".to_string(),
+            name: "JSONPath提取节点".to_string(),
+            description: "使用 JSONPath 或 JSON Pointer 从消息数据中提取字段".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["expr"],
+                "properties": {
+                    "expr": {
+                        "type": "string",
+                        "description": "JSONPath 表达式,以 $ 开头;以 / 开头时按 JSON Pointer 解析"
+                    },
+                    "output_field": {
+                        "type": "string",
+                        "description": "写入结果的 msg.data 字段路径,未设置时用结果替换 msg.data"
+                    },
+                    "first": {
+                        "type": "boolean",
+                        "description": "只取第一个匹配值,默认输出所有匹配值组成的数组",
+                        "default": false
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for JsonPathNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        let matches = self.matches(&msg.data)?;
+        let branch =
+            if matches.is_empty() && !ctx.get_next_connections("no_match").await?.is_empty() {
+                "no_match"
+            } else {
+                "success"
+            };
+
+        let result = if self.config.first {
+            matches
+                .first()
+                .map(|value| (*value).clone())
+                .unwrap_or(Value::Null)
+        } else {
+            Value::Array(matches.into_iter().cloned().collect())
+        };
+        match &self.config.output_field {
+            Some(field) => {
+                if !set_value_by_path(&mut msg.data, field, result) {
+                    return Err(RuleError::NodeExecutionError(format!(
+                        "无法写入字段 {}",
+                        field
+                    )));
+                }
+            }
+            None => msg.data = result,
+        }
+
+        msg.set_meta("branch_name", branch);
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -1,18 +1,53 @@
 use crate::engine::NodeHandler;
 use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::render_text;
 use async_trait::async_trait;
 use serde::Deserialize;
-use tracing::info;
+use serde_json::json;
+use tracing::{debug, error, info, trace, warn};
+use tracing_log::log;
+
+/// 日志级别
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<LogLevel> for log::Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => log::Level::Trace,
+            LogLevel::Debug => log::Level::Debug,
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Error => log::Level::Error,
+        }
+    }
+}
 
 #[derive(Debug, Deserialize)]
 pub struct LogConfig {
     pub template: String,
+    /// 日志级别,默认为 info
+    #[serde(default)]
+    pub level: LogLevel,
+    /// 日志目标,未配置时为 `rule_rs::components::log`
+    #[serde(default)]
+    pub target: Option<String>,
 }
 
 impl Default for LogConfig {
     fn default() -> Self {
         Self {
             template: String::new(),
+            level: LogLevel::Info,
+            target: None,
         }
     }
 }
@@ -27,48 +62,60 @@ impl LogNode {
         Self { config }
     }
 
+    /// 替换模板中的 ${...} 变量,无法解析的变量保持原样
     fn format_message(&self, msg: &Message) -> String {
-        let mut result = self.config.template.clone();
-        // 替换消息相关的变量
-        result = result.replace("${msg.id}", &msg.id.to_string());
-        result = result.replace("${msg.type}", &msg.msg_type);
-        // 替换数据中的变量
-        if let Some(obj) = msg.data.as_object() {
-            for (key, value) in obj {
-                let placeholder = format!("${{msg.data.{}}}", key);
-                let placeholder_without_markers = format!("msg.data.{}", key);
-                let result_without_markers = result.replace("${", "").replace("}", ""); // 去掉结果中的 ${}
+        render_text(&self.config.template, msg)
+    }
 
-                if result_without_markers.contains(&placeholder_without_markers) {
-                    let value_str = if value.is_object() || value.is_array() {
-                        // 检查是否有更深层的路径
-                        let parts: Vec<&str> = key.split('.').collect();
-                        if parts.len() > 1 {
-                            // 递归查找嵌套值
-                            let mut current_value = value;
-                            for &part in &parts[1..] {
-                                if let Some(obj) = current_value.as_object() {
-                                    current_value = obj.get(part).unwrap_or(value);
-                                }
-                            }
-                            current_value.to_string()
-                        } else {
-                            value.to_string()
-                        }
-                    } else {
-                        value.to_string()
-                    };
-                    result = result.replace(&placeholder, &value_str);
-                }
+    /// 按配置的级别和目标输出日志
+    ///
+    /// `tracing` 宏的目标只能是常量,配置了目标时通过 `tracing_log` 构造事件,
+    /// 订阅者按配置的目标过滤和显示
+    fn emit(&self, log_message: &str) {
+        let level = self.config.level;
+        let Some(target) = &self.config.target else {
+            match level {
+                LogLevel::Trace => trace!("log组件输出: {}", log_message),
+                LogLevel::Debug => debug!("log组件输出: {}", log_message),
+                LogLevel::Info => info!("log组件输出: {}", log_message),
+                LogLevel::Warn => warn!("log组件输出: {}", log_message),
+                LogLevel::Error => error!("log组件输出: {}", log_message),
             }
-        } else if let Some(value) = msg.data.as_str() {
-            result = result.replace("${msg.data}", value);
-        } else if let Some(value) = msg.data.as_f64() {
-            result = result.replace("${msg.data}", &value.to_string());
-        } else {
-            result = result.replace("${msg.data}", &msg.data.to_string());
+            return;
+        };
+        let _ = tracing_log::format_trace(
+            &log::Record::builder()
+                .args(format_args!("log组件输出: {}", log_message))
+                .level(level.into())
+                .target(target)
+                .module_path(Some(module_path!()))
+                .file(Some(file!()))
+                .line(Some(line!()))
+                .build(),
+        );
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "log".to_string(),
+            name: "日志节点".to_string(),
+            description: "输出日志消息".to_string(),
+            node_type: NodeType::Tail,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["template"],
+                "properties": {
+                    "template": { "type": "string", "description": "日志模板,支持模板变量" },
+                    "level": {
+                        "type": "string",
+                        "enum": ["trace", "debug", "info", "warn", "error"],
+                        "default": "info",
+                        "description": "日志级别"
+                    },
+                    "target": { "type": "string", "description": "日志目标,用于按目标过滤日志" }
+                }
+            })),
         }
-        result
     }
 }
 
@@ -79,19 +126,14 @@ impl NodeHandler for LogNode {
         _ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
-        // 格式化并输出日志
+        // 格式化并按配置的级别输出日志
         let log_message = self.format_message(&msg);
-        info!("log组件输出: {}", log_message);
+        self.emit(&log_message);
         // 返回原始消息
         Ok(msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "log".to_string(),
-            name: "日志节点".to_string(),
-            description: "输出日志消息".to_string(),
-            node_type: NodeType::Tail,
-        }
+        Self::descriptor()
     }
 }
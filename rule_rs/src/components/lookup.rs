@@ -0,0 +1,287 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::{get_value_by_path, set_value_by_path};
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, OnceCell};
+
+type Table = Arc<Map<String, Value>>;
+
+/// 查找表来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LookupSource {
+    /// 配置中的静态表
+    #[default]
+    Inline,
+    /// JSON 文件,内容为 键 -> 记录 的对象
+    File,
+    /// Redis Hash,field 为键,值为 JSON 记录
+    Redis,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LookupConfig {
+    #[serde(default)]
+    pub source: LookupSource,
+    /// 查找键在 msg.data 中的路径,以点分隔,数组使用数字下标
+    pub key_path: String,
+    /// inline 时为 键 -> 记录 的对象,file 时为文件路径,redis 时为 Hash 键名
+    pub table: Value,
+    /// Redis 连接地址,source 为 redis 时必填
+    #[serde(default)]
+    pub url: Option<String>,
+    /// file/redis 查找表的缓存时间(毫秒),过期后在下一条消息时重新加载
+    #[serde(default = "default_cache_ttl_ms")]
+    pub cache_ttl_ms: u64,
+    /// 记录写入 msg.data 的路径,未设置时把记录的字段合并到 msg.data 顶层
+    #[serde(default)]
+    pub target: Option<String>,
+}
+
+fn default_cache_ttl_ms() -> u64 {
+    60000
+}
+
+impl Default for LookupConfig {
+    fn default() -> Self {
+        Self {
+            source: LookupSource::Inline,
+            key_path: "id".to_string(),
+            table: json!({}),
+            url: None,
+            cache_ttl_ms: default_cache_ttl_ms(),
+            target: None,
+        }
+    }
+}
+
+/// 查找节点
+///
+/// 从 msg.data 的 `key_path` 取出键,在查找表中找到记录后合并到 msg.data,
+/// 例如按 device_id 补充设备信息。`inline` 表在创建节点时解析;`file` 和 `redis` 表在首次使用时整体加载,
+/// 缓存 `cache_ttl_ms` 毫秒后重新加载,加载失败时返回 `RuleError::ComponentError`。
+/// 找到记录时走 `success` 分支;键不存在或没有对应记录时,如果配置了 `not_found` 连接则走 `not_found` 分支,
+/// 否则消息不变继续走 `success` 分支
+pub struct LookupNode {
+    config: LookupConfig,
+    /// 已加载的查找表及加载时间
+    cache: Mutex<Option<(Instant, Table)>>,
+    redis: OnceCell<ConnectionManager>,
+}
+
+impl fmt::Debug for LookupNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LookupNode")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl LookupNode {
+    pub fn new(config: LookupConfig) -> Result<Self, RuleError> {
+        if config.key_path.is_empty() {
+            return Err(RuleError::ConfigError("key_path 不能为空".to_string()));
+        }
+        let cache = match (config.source, &config.table) {
+            (LookupSource::Inline, Value::Object(table)) => {
+                Some((Instant::now(), Arc::new(table.clone())))
+            }
+            (LookupSource::Inline, _) => {
+                return Err(RuleError::ConfigError(
+                    "inline 查找表必须是对象".to_string(),
+                ))
+            }
+            (_, Value::String(name)) if !name.is_empty() => None,
+            _ => {
+                return Err(RuleError::ConfigError(
+                    "file/redis 查找表必须是文件路径或 Hash 键名".to_string(),
+                ))
+            }
+        };
+        if config.source == LookupSource::Redis && config.url.is_none() {
+            return Err(RuleError::ConfigError(
+                "source 为 redis 时必须设置 url".to_string(),
+            ));
+        }
+        Ok(Self {
+            config,
+            cache: Mutex::new(cache),
+            redis: OnceCell::new(),
+        })
+    }
+
+    /// 从消息数据中解析查找键
+    fn resolve_key(&self, data: &Value) -> Option<String> {
+        match get_value_by_path(data, &self.config.key_path)? {
+            Value::String(s) => Some(s.clone()),
+            Value::Null => None,
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// 返回缓存的查找表,缓存过期时重新加载
+    async fn table(&self) -> Result<Table, RuleError> {
+        let mut cache = self.cache.lock().await;
+        let ttl = Duration::from_millis(self.config.cache_ttl_ms);
+        if let Some((loaded_at, table)) = cache.as_ref() {
+            if self.config.source == LookupSource::Inline || loaded_at.elapsed() < ttl {
+                return Ok(table.clone());
+            }
+        }
+
+        let table = Arc::new(match self.config.source {
+            LookupSource::File => self.load_file().await?,
+            _ => self.load_redis().await?,
+        });
+        *cache = Some((Instant::now(), table.clone()));
+        Ok(table)
+    }
+
+    fn table_name(&self) -> &str {
+        self.config.table.as_str().unwrap_or_default()
+    }
+
+    async fn load_file(&self) -> Result<Map<String, Value>, RuleError> {
+        let path = self.table_name();
+        let content = tokio::fs::read(path)
+            .await
+            .map_err(|e| RuleError::ComponentError(format!("读取查找表 {} 失败: {}", path, e)))?;
+        match serde_json::from_slice(&content) {
+            Ok(Value::Object(table)) => Ok(table),
+            Ok(_) => Err(RuleError::ComponentError(format!(
+                "查找表 {} 必须是JSON对象",
+                path
+            ))),
+            Err(e) => Err(RuleError::ComponentError(format!(
+                "解析查找表 {} 失败: {}",
+                path, e
+            ))),
+        }
+    }
+
+    async fn load_redis(&self) -> Result<Map<String, Value>, RuleError> {
+        let url = self.config.url.as_deref().unwrap_or_default();
+        let conn = self
+            .redis
+            .get_or_try_init(|| async {
+                let client = redis::Client::open(url).map_err(|e| {
+                    RuleError::ComponentError(format!("无效的 Redis 地址 {}: {}", url, e))
+                })?;
+                client.get_connection_manager().await.map_err(|e| {
+                    RuleError::ComponentError(format!("连接 Redis {} 失败: {}", url, e))
+                })
+            })
+            .await?;
+
+        let key = self.table_name();
+        let entries: HashMap<String, String> =
+            conn.clone().hgetall(key).await.map_err(|e| {
+                RuleError::ComponentError(format!("读取查找表 {} 失败: {}", key, e))
+            })?;
+        // 值不是JSON时作为字符串
+        Ok(entries
+            .into_iter()
+            .map(|(k, v)| {
+                let record = serde_json::from_str(&v).unwrap_or(Value::String(v));
+                (k, record)
+            })
+            .collect())
+    }
+
+    /// 把记录写入消息数据
+    fn merge(&self, data: &mut Value, record: Value) -> Result<(), RuleError> {
+        if let Some(target) = &self.config.target {
+            if !set_value_by_path(data, target, record) {
+                return Err(RuleError::NodeExecutionError(format!(
+                    "无法写入字段 {}",
+                    target
+                )));
+            }
+            return Ok(());
+        }
+        match (data, record) {
+            (Value::Object(data), Value::Object(record)) => {
+                data.extend(record);
+                Ok(())
+            }
+            _ => Err(RuleError::NodeExecutionError(
+                "合并记录时消息数据和记录都必须是对象,否则请设置 target".to_string(),
+            )),
+        }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "lookup".to_string(),
+            name: "查找节点".to_string(),
+            description: "按消息中的键从查找表取出记录并合并到消息数据".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["key_path", "table"],
+                "properties": {
+                    "source": {
+                        "type": "string",
+                        "enum": ["inline", "file", "redis"],
+                        "default": "inline"
+                    },
+                    "key_path": { "type": "string", "description": "查找键在 msg.data 中的路径" },
+                    "table": {
+                        "type": ["object", "string"],
+                        "description": "inline 时为 键 -> 记录 的对象,file 时为文件路径,redis 时为 Hash 键名"
+                    },
+                    "url": { "type": "string", "description": "Redis 连接地址" },
+                    "cache_ttl_ms": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "file/redis 查找表的缓存时间(毫秒)",
+                        "default": 60000
+                    },
+                    "target": {
+                        "type": "string",
+                        "description": "记录写入的路径,未设置时合并到 msg.data 顶层"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for LookupNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        let record = match self.resolve_key(&msg.data) {
+            Some(key) => self.table().await?.get(&key).cloned(),
+            None => None,
+        };
+
+        match record {
+            Some(record) => {
+                self.merge(&mut msg.data, record)?;
+                msg.set_meta("branch_name", "success");
+            }
+            None if !ctx.get_next_connections("not_found").await?.is_empty() => {
+                msg.set_meta("branch_name", "not_found");
+            }
+            None => msg.set_meta("branch_name", "success"),
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
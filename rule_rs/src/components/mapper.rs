@@ -0,0 +1,159 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::{get_value_by_path, remove_value_by_path, set_value_by_path};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MapperConfig {
+    /// 重命名字段,源路径 -> 目标路径
+    #[serde(default)]
+    pub rename: BTreeMap<String, String>,
+    /// 删除的字段路径
+    #[serde(default)]
+    pub remove: Vec<String>,
+    /// 复制字段,源路径 -> 目标路径
+    #[serde(default)]
+    pub copy: BTreeMap<String, String>,
+    /// 写入固定值,目标路径 -> 值
+    #[serde(default)]
+    pub set: BTreeMap<String, Value>,
+}
+
+/// 字段映射节点
+///
+/// 对 msg.data 依次执行 `rename`、`remove`、`copy`、`set`,路径以点分隔,数组使用数字下标。
+/// `rename` 和 `copy` 先读取所有源字段再写入目标字段,因此可以互换两个字段;
+/// 目标字段已存在时被覆盖。源字段或待删除的字段不存在时跳过
+#[derive(Debug)]
+pub struct MapperNode {
+    config: MapperConfig,
+}
+
+impl MapperNode {
+    pub fn new(config: MapperConfig) -> Result<Self, RuleError> {
+        let paths = config
+            .rename
+            .iter()
+            .chain(&config.copy)
+            .flat_map(|(from, to)| [from, to])
+            .chain(&config.remove)
+            .chain(config.set.keys());
+        for path in paths {
+            if path.is_empty() || path.split('.').any(str::is_empty) {
+                return Err(RuleError::ConfigError(format!("字段路径 {:?} 无效", path)));
+            }
+        }
+        Self::check_targets("rename", &config.rename)?;
+        Self::check_targets("copy", &config.copy)?;
+        Ok(Self { config })
+    }
+
+    /// 同一操作中多个源字段写入同一个目标字段时结果不确定,视为配置错误
+    fn check_targets(op: &str, mapping: &BTreeMap<String, String>) -> Result<(), RuleError> {
+        let mut targets = HashSet::new();
+        for to in mapping.values() {
+            if !targets.insert(to) {
+                return Err(RuleError::ConfigError(format!(
+                    "{} 中有多个字段写入 {}",
+                    op, to
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn write(data: &mut Value, path: &str, value: Value) -> Result<(), RuleError> {
+        if set_value_by_path(data, path, value) {
+            Ok(())
+        } else {
+            Err(RuleError::NodeExecutionError(format!(
+                "无法写入字段 {}",
+                path
+            )))
+        }
+    }
+
+    fn apply(&self, data: &mut Value) -> Result<(), RuleError> {
+        let renamed: Vec<_> = self
+            .config
+            .rename
+            .iter()
+            .filter_map(|(from, to)| Some((to, remove_value_by_path(data, from)?)))
+            .collect();
+        for (to, value) in renamed {
+            Self::write(data, to, value)?;
+        }
+
+        for path in &self.config.remove {
+            remove_value_by_path(data, path);
+        }
+
+        let copied: Vec<_> = self
+            .config
+            .copy
+            .iter()
+            .filter_map(|(from, to)| Some((to, get_value_by_path(data, from)?.clone())))
+            .collect();
+        for (to, value) in copied {
+            Self::write(data, to, value)?;
+        }
+
+        for (path, value) in &self.config.set {
+            Self::write(data, path, value.clone())?;
+        }
+        Ok(())
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "mapper".to_string(),
+            name: "字段映射节点".to_string(),
+            description: "按配置重命名、删除、复制和写入消息数据中的字段".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "rename": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "重命名字段,源路径 -> 目标路径"
+                    },
+                    "remove": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "删除的字段路径"
+                    },
+                    "copy": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "复制字段,源路径 -> 目标路径"
+                    },
+                    "set": {
+                        "type": "object",
+                        "description": "写入固定值,目标路径 -> 值"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for MapperNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        self.apply(&mut msg.data)?;
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
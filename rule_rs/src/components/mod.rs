@@ -1,29 +1,91 @@
+mod calc;
+mod context;
+mod crypto;
+mod csv;
+mod datetime;
+mod debounce;
+mod dedup;
 mod delay;
+mod email;
+mod emit;
+mod file;
 mod filter;
 mod fork;
+mod graphql;
+mod grpc_client;
 mod join;
+mod js_common;
 mod js_function;
+mod jsonpath;
 mod log;
+mod lookup;
+mod mapper;
+mod nats;
+mod postgres;
+mod rate_limit;
 mod rest_client;
+mod s3;
 mod schedule;
 mod script;
+mod select;
+mod split;
+mod split_array;
 mod start;
 mod subchain;
 mod switch;
+mod template;
 mod transform;
 mod transform_js;
+mod validate;
+mod websocket;
+mod window;
 
+pub use calc::{CalcConfig, CalcNode};
+pub use context::{ContextConfig, ContextNode, ContextOperation};
+pub use crypto::{CryptoAlgorithm, CryptoConfig, CryptoNode, CryptoOperation};
+pub use csv::{CsvConfig, CsvDirection, CsvNode};
+pub use datetime::{DateTimeConfig, DateTimeNode, DateTimeOperation};
+pub use debounce::{DebounceConfig, DebounceNode};
+pub use dedup::{DedupConfig, DedupNode};
 pub use delay::{DelayConfig, DelayNode};
+pub use email::{EmailConfig, EmailNode, SmtpTls};
+pub use emit::{EmitConfig, EmitNode};
+pub use file::{FileConfig, FileFormat, FileMode, FileNode};
 pub use filter::{FilterConfig, FilterNode};
 pub use fork::ForkNode;
+pub use graphql::{GraphqlConfig, GraphqlNode};
+pub use grpc_client::{GrpcClientConfig, GrpcClientNode};
 pub use join::{JoinConfig, JoinNode};
 pub use js_function::{JsFunctionConfig, JsFunctionNode};
-pub use log::{LogConfig, LogNode};
-pub use rest_client::{RestClientConfig, RestClientNode};
+pub use jsonpath::{JsonPathConfig, JsonPathNode};
+pub use log::{LogConfig, LogLevel, LogNode};
+pub use lookup::{LookupConfig, LookupNode, LookupSource};
+pub use mapper::{MapperConfig, MapperNode};
+pub use nats::{NatsConfig, NatsNode, NatsOperation};
+pub use postgres::{PostgresConfig, PostgresNode, PostgresOperation};
+pub use rate_limit::{RateLimitConfig, RateLimitMode, RateLimitNode};
+pub use rest_client::{ParseResponse, RestClientConfig, RestClientNode, RetryConfig};
+pub use s3::{S3Config, S3Node, S3Operation};
 pub use schedule::{ScheduleConfig, ScheduleNode};
 pub use script::{ScriptConfig, ScriptNode};
+pub use select::{SelectConfig, SelectNode};
+pub use split::{SplitBranch, SplitConfig, SplitNode};
+pub use split_array::{SplitArrayConfig, SplitArrayNode};
 pub use start::{StartConfig, StartNode};
 pub use subchain::{SubchainConfig, SubchainNode};
-pub use switch::{SwitchConfig, SwitchNode};
+pub use switch::{SwitchCase, SwitchConfig, SwitchMode, SwitchNode};
+pub use template::{TemplateConfig, TemplateEngine, TemplateNode};
 pub use transform::{TransformConfig, TransformNode};
 pub use transform_js::{TransformJsConfig, TransformJsNode};
+pub use validate::{OnInvalid, ValidateConfig, ValidateNode};
+pub use websocket::{WebSocketConfig, WebSocketNode};
+pub use window::{WindowAggregate, WindowConfig, WindowNode};
+
+/// 清理规则链下有状态节点保存的全局状态,在删除规则链时调用
+pub(crate) async fn remove_chain_state(chain_id: uuid::Uuid) {
+    debounce::remove_chain_state(chain_id).await;
+    dedup::remove_chain_state(chain_id).await;
+    rate_limit::remove_chain_state(chain_id).await;
+    select::remove_chain_state(chain_id).await;
+    window::remove_chain_state(chain_id).await;
+}
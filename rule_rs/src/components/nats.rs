@@ -0,0 +1,164 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_nats::Client;
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+lazy_static! {
+    // 节点处理器会被重复创建,连接按 url 保存在全局状态中以便复用
+    static ref GLOBAL_NATS_CLIENTS: Mutex<HashMap<String, Client>> = Mutex::new(HashMap::new());
+}
+
+/// NATS 操作类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NatsOperation {
+    /// 发布消息,不等待回复
+    #[default]
+    Publish,
+    /// 发送请求并等待回复
+    Request,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NatsConfig {
+    /// NATS 服务地址,例如 `nats://localhost:4222`
+    pub url: String,
+    pub subject: String,
+    #[serde(default)]
+    pub operation: NatsOperation,
+}
+
+impl Default for NatsConfig {
+    fn default() -> Self {
+        Self {
+            url: "nats://localhost:4222".to_string(),
+            subject: "rule_rs".to_string(),
+            operation: NatsOperation::Publish,
+        }
+    }
+}
+
+/// NATS 节点
+///
+/// 把 msg.data 序列化为 JSON 发布到 `subject`;请求模式下用回复替换 msg.data,
+/// 回复不是 JSON 时作为字符串。成功走 `success` 分支,失败时把错误写入 `error` 元数据并走 `error` 分支,
+/// 未配置 `error` 连接时返回 `RuleError::ComponentError`。
+/// 连接在首次执行时建立,相同 url 的节点共享同一个连接
+#[derive(Debug)]
+pub struct NatsNode {
+    config: NatsConfig,
+}
+
+impl NatsNode {
+    pub fn new(config: NatsConfig) -> Result<Self, RuleError> {
+        if config.subject.is_empty() {
+            return Err(RuleError::ConfigError("NATS subject 不能为空".to_string()));
+        }
+        Ok(Self { config })
+    }
+
+    /// 获取或建立连接
+    async fn client(&self) -> Result<Client, RuleError> {
+        let mut clients = GLOBAL_NATS_CLIENTS.lock().await;
+        if let Some(client) = clients.get(&self.config.url) {
+            return Ok(client.clone());
+        }
+
+        let client = async_nats::connect(&self.config.url).await.map_err(|e| {
+            RuleError::ComponentError(format!("NATS连接 {} 失败: {}", self.config.url, e))
+        })?;
+        clients.insert(self.config.url.clone(), client.clone());
+        Ok(client)
+    }
+
+    async fn run(&self, msg: &Message) -> Result<Option<Value>, RuleError> {
+        let client = self.client().await?;
+        let payload = serde_json::to_vec(&msg.data)
+            .map_err(|e| RuleError::ComponentError(format!("消息序列化失败: {}", e)))?;
+        let subject = self.config.subject.clone();
+
+        match self.config.operation {
+            NatsOperation::Publish => {
+                client
+                    .publish(subject, payload.into())
+                    .await
+                    .map_err(|e| RuleError::ComponentError(format!("NATS发布失败: {}", e)))?;
+                // 等待消息写入服务端,连接异常时在这里返回错误
+                client
+                    .flush()
+                    .await
+                    .map_err(|e| RuleError::ComponentError(format!("NATS发布失败: {}", e)))?;
+                Ok(None)
+            }
+            NatsOperation::Request => {
+                let reply = client
+                    .request(subject, payload.into())
+                    .await
+                    .map_err(|e| RuleError::ComponentError(format!("NATS请求失败: {}", e)))?;
+                let data = serde_json::from_slice(&reply.payload).unwrap_or_else(|_| {
+                    Value::String(String::from_utf8_lossy(&reply.payload).into_owned())
+                });
+                Ok(Some(data))
+            }
+        }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "nats".to_string(),
+            name: "NATS节点".to_string(),
+            description: "发布消息到 NATS 或发送请求并等待回复".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["url", "subject"],
+                "properties": {
+                    "url": { "type": "string", "description": "NATS 服务地址" },
+                    "subject": { "type": "string", "minLength": 1 },
+                    "operation": {
+                        "type": "string",
+                        "enum": ["publish", "request"],
+                        "description": "publish 发布消息,request 发送请求并用回复替换 msg.data",
+                        "default": "publish"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for NatsNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        match self.run(&msg).await {
+            Ok(reply) => {
+                if let Some(data) = reply {
+                    msg.data = data;
+                }
+                msg.set_meta("branch_name", "success");
+            }
+            Err(e) => {
+                if ctx.get_next_connections("error").await?.is_empty() {
+                    return Err(e);
+                }
+                msg.set_meta("error", e.to_string());
+                msg.set_meta("branch_name", "error");
+            }
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
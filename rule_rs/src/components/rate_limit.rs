@@ -0,0 +1,226 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::get_value_by_path;
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration, Instant};
+use uuid::Uuid;
+
+/// 令牌桶
+struct Bucket {
+    /// 当前令牌数,等待模式下预占令牌后可能为负
+    tokens: f64,
+    /// 上次补充令牌的时间
+    last_refill: Instant,
+}
+
+/// 单个限流节点的状态
+struct RateLimitState {
+    buckets: HashMap<String, Bucket>,
+    /// 上次清理空闲令牌桶的时间
+    last_eviction: Instant,
+}
+
+lazy_static! {
+    // 节点处理器会被重复创建,令牌桶按 (规则链ID, 节点ID) 保存在全局状态中
+    static ref GLOBAL_RATE_LIMIT_STATE: Mutex<HashMap<(Uuid, Uuid), RateLimitState>> =
+        Mutex::new(HashMap::new());
+}
+
+/// 清理规则链下所有限流节点的状态,在删除规则链时调用
+pub(crate) async fn remove_chain_state(chain_id: Uuid) {
+    GLOBAL_RATE_LIMIT_STATE
+        .lock()
+        .await
+        .retain(|(id, _), _| *id != chain_id);
+}
+
+/// 超出限流时的处理方式
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitMode {
+    /// 发送到 `throttled` 分支,未配置该连接时丢弃消息
+    #[default]
+    Throttle,
+    /// 等待直到有可用令牌后继续转发
+    Wait,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RateLimitConfig {
+    /// 每秒补充的令牌数
+    pub rate_per_sec: f64,
+    /// 令牌桶容量,即允许的突发消息数
+    pub burst: u32,
+    /// 限流键在 msg.data 中的路径,未配置时所有消息共用一个令牌桶
+    pub key_path: Option<String>,
+    #[serde(default)]
+    pub mode: RateLimitMode,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            rate_per_sec: 10.0,
+            burst: 10,
+            key_path: None,
+            mode: RateLimitMode::Throttle,
+        }
+    }
+}
+
+/// 限流节点
+///
+/// 按键维护令牌桶,有令牌的消息走 `success` 分支;
+/// 令牌不足时按 `mode` 走 `throttled` 分支或等待令牌
+#[derive(Debug)]
+pub struct RateLimitNode {
+    config: RateLimitConfig,
+}
+
+impl RateLimitNode {
+    pub fn new(config: RateLimitConfig) -> Result<Self, RuleError> {
+        if config.rate_per_sec <= 0.0 {
+            return Err(RuleError::ConfigError("rate_per_sec 必须大于0".to_string()));
+        }
+        if config.burst == 0 {
+            return Err(RuleError::ConfigError("burst 必须大于0".to_string()));
+        }
+        Ok(Self { config })
+    }
+
+    /// 从消息数据中解析限流键
+    fn resolve_key(&self, data: &Value) -> Result<String, RuleError> {
+        let Some(path) = &self.config.key_path else {
+            return Ok(String::new());
+        };
+        match get_value_by_path(data, path) {
+            Some(Value::String(s)) => Ok(s.clone()),
+            Some(Value::Null) | None => Err(RuleError::NodeExecutionError(format!(
+                "限流字段 {} 不存在",
+                path
+            ))),
+            Some(value) => Ok(value.to_string()),
+        }
+    }
+
+    /// 获取一个令牌
+    ///
+    /// 返回 None 表示令牌不足且不等待;等待模式下预占令牌并返回需要等待的时长
+    async fn acquire(&self, chain_id: Uuid, node_id: Uuid, key: String) -> Option<Duration> {
+        let rate = self.config.rate_per_sec;
+        let burst = self.config.burst as f64;
+        let now = Instant::now();
+
+        let mut states = GLOBAL_RATE_LIMIT_STATE.lock().await;
+        let state = states
+            .entry((chain_id, node_id))
+            .or_insert_with(|| RateLimitState {
+                buckets: HashMap::new(),
+                last_eviction: now,
+            });
+
+        // 每个补满周期清理一次空闲的令牌桶,已补满的桶与新建的桶等价
+        let refill_period = Duration::from_secs_f64(burst / rate);
+        if now.duration_since(state.last_eviction) >= refill_period {
+            state.buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.last_refill).as_secs_f64() * rate < burst
+            });
+            state.last_eviction = now;
+        }
+
+        let bucket = state.buckets.entry(key).or_insert_with(|| Bucket {
+            tokens: burst,
+            last_refill: now,
+        });
+
+        // 按流逝的时间补充令牌
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Some(Duration::ZERO);
+        }
+
+        match self.config.mode {
+            RateLimitMode::Throttle => None,
+            RateLimitMode::Wait => {
+                let wait = Duration::from_secs_f64((1.0 - bucket.tokens) / rate);
+                bucket.tokens -= 1.0;
+                Some(wait)
+            }
+        }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "rate_limit".to_string(),
+            name: "限流节点".to_string(),
+            description: "按键使用令牌桶限制消息速率".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["rate_per_sec", "burst"],
+                "properties": {
+                    "rate_per_sec": {
+                        "type": "number",
+                        "exclusiveMinimum": 0,
+                        "description": "每秒补充的令牌数"
+                    },
+                    "burst": { "type": "integer", "minimum": 1, "description": "令牌桶容量,即允许的突发消息数" },
+                    "key_path": {
+                        "type": "string",
+                        "description": "限流键在 msg.data 中的路径,未设置时所有消息共用一个令牌桶"
+                    },
+                    "mode": {
+                        "type": "string",
+                        "enum": ["throttle", "wait"],
+                        "description": "throttle 超出速率时走 throttled 分支,wait 等待令牌",
+                        "default": "throttle"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for RateLimitNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        let key = self.resolve_key(&msg.data)?;
+
+        match self.acquire(ctx.node.chain_id, ctx.node.id, key).await {
+            Some(wait) => {
+                if !wait.is_zero() {
+                    sleep(wait).await;
+                }
+                msg.set_meta("branch_name", "success");
+            }
+            None => {
+                // 被限流的消息只发送到 throttled 分支
+                if ctx.get_next_connections("throttled").await?.is_empty() {
+                    ctx.mark_routed();
+                    return Ok(msg);
+                }
+                msg.set_meta("branch_name", "throttled");
+            }
+        }
+
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -1,18 +1,110 @@
 use crate::engine::NodeHandler;
 use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::{render_template, render_text};
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::header::{HeaderMap, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::time::Duration;
+use tracing::warn;
+
+/// 响应体的解析方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseResponse {
+    /// 根据 Content-Type 判断,JSON 类型解析为JSON,其他保存为文本
+    #[default]
+    Auto,
+    /// 始终解析为JSON
+    Json,
+    /// 始终保存为文本
+    Text,
+}
+
+/// 失败重试配置,连接失败及 5xx/429 响应按指数退避重试,其他 4xx 不重试
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetryConfig {
+    /// 最多尝试次数,包含首次请求
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// 首次重试的等待时间(毫秒),之后每次翻倍
+    #[serde(default = "default_base_delay_ms")]
+    pub base_delay_ms: u64,
+    /// 单次等待时间上限(毫秒)
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+    /// 是否在 [等待时间/2, 等待时间] 范围内随机等待,避免大量请求同时重试
+    #[serde(default = "default_jitter")]
+    pub jitter: bool,
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_base_delay_ms() -> u64 {
+    100
+}
+
+fn default_max_delay_ms() -> u64 {
+    10000
+}
+
+fn default_jitter() -> bool {
+    true
+}
+
+impl RetryConfig {
+    /// 第 `retry` 次重试(从1开始)前的等待时间
+    fn backoff(&self, retry: u32) -> Duration {
+        let delay = self
+            .base_delay_ms
+            .saturating_mul(2u64.saturating_pow(retry - 1))
+            .min(self.max_delay_ms);
+        let delay = if self.jitter && delay > 0 {
+            rand::random_range(delay / 2..=delay)
+        } else {
+            delay
+        };
+        Duration::from_millis(delay)
+    }
+}
+
+/// 单次请求的失败信息
+struct AttemptError {
+    message: String,
+    /// 是否可以重试
+    retryable: bool,
+    /// 响应头 Retry-After 指定的等待时间
+    retry_after: Option<Duration>,
+}
+
+impl AttemptError {
+    fn fatal(message: String) -> Self {
+        Self {
+            message,
+            retryable: false,
+            retry_after: None,
+        }
+    }
+}
 
 #[derive(Debug, Deserialize)]
 pub struct RestClientConfig {
     pub url: String,
     pub method: String,
+    /// 请求头,值中可以使用 `${msg.data.x}`、`${msg.metadata.y}` 等模板变量
     pub headers: Option<HashMap<String, String>>,
+    /// POST/PUT 请求体模板,使用消息渲染后发送;未设置时发送 msg.data
+    pub body_template: Option<Value>,
+    /// 响应体的解析方式,空响应体始终为 null
+    #[serde(default)]
+    pub parse_response: ParseResponse,
     pub timeout_ms: Option<u64>,
+    /// 失败重试配置,未设置时不重试
+    pub retry: Option<RetryConfig>,
     pub success_branch: Option<String>, // 成功分支名称
     pub error_branch: Option<String>,   // 失败分支名称
 }
@@ -23,7 +115,10 @@ impl Default for RestClientConfig {
             url: "http://localhost".to_string(),
             method: "GET".to_string(),
             headers: None,
+            body_template: None,
+            parse_response: ParseResponse::Auto,
             timeout_ms: None,
+            retry: None,
             success_branch: None,
             error_branch: None,
         }
@@ -33,22 +128,88 @@ impl Default for RestClientConfig {
 #[derive(Debug)]
 pub struct RestClientNode {
     config: RestClientConfig,
+    method: Method,
     client: Client,
 }
 
 impl RestClientNode {
-    pub fn new(config: RestClientConfig) -> Self {
+    pub fn new(config: RestClientConfig) -> Result<Self, RuleError> {
+        let method = config
+            .method
+            .parse()
+            .map_err(|_| RuleError::ConfigError(format!("无效的HTTP方法: {}", config.method)))?;
         let client = Client::builder()
             .timeout(Duration::from_millis(config.timeout_ms.unwrap_or(5000)))
             .build()
-            .unwrap();
-        Self { config, client }
+            .map_err(|e| RuleError::ConfigError(format!("HTTP客户端创建失败: {}", e)))?;
+        Ok(Self {
+            config,
+            method,
+            client,
+        })
     }
 
+    /// 按配置解析响应体
+    fn parse_body(&self, text: String, is_json: bool) -> Result<Value, String> {
+        if text.is_empty() {
+            return Ok(Value::Null);
+        }
+
+        let parse_json = match self.config.parse_response {
+            ParseResponse::Auto => is_json,
+            ParseResponse::Json => true,
+            ParseResponse::Text => false,
+        };
+        if parse_json {
+            serde_json::from_str(&text).map_err(|e| format!("响应解析失败: {}", e))
+        } else {
+            Ok(Value::String(text))
+        }
+    }
+
+    /// 发送请求,按重试配置重试可恢复的失败,最终错误包含尝试次数
     async fn make_request(&self, msg: &Message) -> Result<Value, RuleError> {
-        // 替换 URL 中的变量
+        let request = self.build_request(msg);
+        let Some(retry) = &self.config.retry else {
+            return self
+                .send_once(request)
+                .await
+                .map_err(|e| RuleError::NodeExecutionError(e.message));
+        };
+
+        let max_attempts = retry.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            // 请求体为JSON,总是可以复制
+            let current = request.try_clone().expect("请求无法复制");
+            match self.send_once(current).await {
+                Ok(value) => return Ok(value),
+                Err(e) if e.retryable && attempt < max_attempts => {
+                    let delay = e.retry_after.unwrap_or_else(|| retry.backoff(attempt));
+                    warn!(
+                        "HTTP请求第 {} 次失败, {}ms 后重试: {}",
+                        attempt,
+                        delay.as_millis(),
+                        e.message
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    return Err(RuleError::NodeExecutionError(format!(
+                        "{} (共尝试 {} 次)",
+                        e.message, attempt
+                    )));
+                }
+            }
+        }
+    }
+
+    /// 使用消息渲染 URL、请求头和请求体
+    fn build_request(&self, msg: &Message) -> RequestBuilder {
+        // 替换 URL 中的变量,`${key}` 简写取 msg.data 的顶层字段
         let url = if self.config.url.contains("${") {
-            let mut url = self.config.url.clone();
+            let mut url = render_text(&self.config.url, msg);
             if let Some(obj) = msg.data.as_object() {
                 for (key, value) in obj {
                     let placeholder = format!("${{{}}}", key);
@@ -62,34 +223,60 @@ impl RestClientNode {
             self.config.url.clone()
         };
 
-        let mut request = self
-            .client
-            .request(self.config.method.parse().unwrap(), &url);
+        let mut request = self.client.request(self.method.clone(), &url);
 
         // 添加请求头
         if let Some(headers) = &self.config.headers {
             for (key, value) in headers {
-                request = request.header(key, value);
+                request = request.header(key, render_text(value, msg));
             }
         }
 
-        // 对于 POST/PUT 请求，添加消息数据作为请求体
+        // 对于 POST/PUT 请求，添加请求体
         if ["POST", "PUT"].contains(&self.config.method.to_uppercase().as_str()) {
-            request = request.json(&msg.data);
+            match &self.config.body_template {
+                Some(template) => request = request.json(&render_template(template, msg)),
+                None => request = request.json(&msg.data),
+            }
         }
 
-        // 发送请求
-        let response = request
-            .send()
-            .await
-            .map_err(|e| RuleError::NodeExecutionError(format!("HTTP请求失败: {}", e)))?;
+        request
+    }
+
+    /// 发送一次请求
+    async fn send_once(&self, request: RequestBuilder) -> Result<Value, AttemptError> {
+        let response = request.send().await.map_err(|e| AttemptError {
+            retryable: e.is_connect(),
+            retry_after: None,
+            message: format!("HTTP请求失败: {}", e),
+        })?;
 
         // 解析响应
         let status = response.status();
-        let body = response
-            .json::<Value>()
-            .await
-            .map_err(|e| RuleError::NodeExecutionError(format!("响应解析失败: {}", e)))?;
+        let retryable = status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS;
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+        let is_json = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("json"));
+        let headers = headers_to_json(response.headers());
+        let text = response.text().await.map_err(|e| AttemptError {
+            retryable,
+            retry_after,
+            message: format!("响应读取失败: {}", e),
+        })?;
+        let body = match self.parse_body(text, is_json) {
+            Ok(body) => body,
+            // 可重试的状态码不依赖响应体判断
+            Err(_) if retryable => Value::Null,
+            Err(e) => return Err(AttemptError::fatal(e)),
+        };
 
         // 检查状态码和错误响应
         if !status.is_success() || body.get("error").is_some() {
@@ -101,22 +288,113 @@ impl RestClientNode {
             } else {
                 format!("HTTP请求返回错误状态码: {}", status)
             };
-            return Err(RuleError::NodeExecutionError(error_msg));
+            return Err(AttemptError {
+                message: error_msg,
+                retryable,
+                retry_after,
+            });
         }
 
         // 构造响应数据
         Ok(serde_json::json!({
             "status": status.as_u16(),
+            "headers": headers,
             "body": body,
         }))
     }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "rest_client".to_string(),
+            name: "HTTP客户端".to_string(),
+            description: "发送HTTP请求,支持成功/失败分支路由".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["url", "method"],
+                "properties": {
+                    "url": { "type": "string", "description": "请求地址,支持模板变量" },
+                    "method": {
+                        "type": "string",
+                        "enum": ["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS"],
+                        "description": "HTTP 方法"
+                    },
+                    "headers": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "请求头,值支持模板变量"
+                    },
+                    "body_template": { "description": "请求体模板,字符串值支持模板变量" },
+                    "parse_response": {
+                        "type": "string",
+                        "enum": ["auto", "json", "text"],
+                        "description": "响应解析方式",
+                        "default": "auto"
+                    },
+                    "timeout_ms": { "type": "integer", "minimum": 0, "description": "超时时间(毫秒)" },
+                    "retry": {
+                        "type": "object",
+                        "properties": {
+                            "max_attempts": {
+                                "type": "integer",
+                                "minimum": 1,
+                                "description": "最大尝试次数",
+                                "default": 3
+                            },
+                            "base_delay_ms": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "description": "首次重试的延迟(毫秒)",
+                                "default": 100
+                            },
+                            "max_delay_ms": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "description": "重试延迟上限(毫秒)",
+                                "default": 10000
+                            },
+                            "jitter": {
+                                "type": "boolean",
+                                "description": "是否为延迟加入随机抖动",
+                                "default": true
+                            }
+                        }
+                    },
+                    "success_branch": { "type": "string", "description": "成功分支名称,未设置时按默认分支转发" },
+                    "error_branch": {
+                        "type": "string",
+                        "description": "失败分支名称,未设置时错误写入元数据后按默认分支转发"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+/// 将响应头转换为JSON对象,头名称为小写,同名多值的头转换为数组
+fn headers_to_json(headers: &HeaderMap) -> Value {
+    let mut result = serde_json::Map::new();
+    for name in headers.keys() {
+        let mut values: Vec<Value> = headers
+            .get_all(name)
+            .iter()
+            .map(|value| Value::String(String::from_utf8_lossy(value.as_bytes()).into_owned()))
+            .collect();
+        let value = if values.len() == 1 {
+            values.remove(0)
+        } else {
+            Value::Array(values)
+        };
+        result.insert(name.to_string(), value);
+    }
+    Value::Object(result)
 }
 
 #[async_trait]
 impl NodeHandler for RestClientNode {
     async fn handle<'a>(
         &'a self,
-        ctx: NodeContext<'a>,
+        _ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
         let mut msg = msg;
@@ -131,36 +409,27 @@ impl NodeHandler for RestClientNode {
 
                 // 设置成功分支
                 if let Some(branch) = &self.config.success_branch {
-                    msg.metadata.insert("branch_name".into(), branch.clone());
+                    msg.set_meta("branch_name", branch.clone());
                 }
 
-                // 发送到成功分支的下一个节点
-                ctx.send_next(msg.clone()).await?;
                 Ok(msg)
             }
             Err(e) => {
                 println!("请求失败: {:?}", e);
                 // 请求失败
-                msg.metadata.insert("error".into(), e.to_string());
+                msg.set_meta("error", e.to_string());
 
                 // 设置失败分支
                 if let Some(branch) = &self.config.error_branch {
-                    msg.metadata.insert("branch_name".into(), branch.clone());
+                    msg.set_meta("branch_name", branch.clone());
                 }
 
-                // 发送到失败分支的下一个节点
-                ctx.send_next(msg.clone()).await?;
                 Ok(msg)
             }
         }
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "rest_client".to_string(),
-            name: "HTTP客户端".to_string(),
-            description: "发送HTTP请求,支持成功/失败分支路由".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
@@ -0,0 +1,295 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::render_text;
+use async_trait::async_trait;
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_s3::config::Credentials;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bytes::Bytes;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::OnceCell;
+
+/// 对象存储操作类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum S3Operation {
+    /// 下载对象到 msg.data 或 msg.binary
+    #[default]
+    Get,
+    /// 上传 msg.binary 或 msg.data
+    Put,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct S3Config {
+    /// 自定义服务地址,例如 MinIO;设置后使用路径风格访问存储桶
+    pub endpoint: Option<String>,
+    pub bucket: String,
+    /// 对象键模板,支持 `${msg.data.x}`、`${msg.metadata.y}` 等模板变量
+    pub key_template: String,
+    #[serde(default)]
+    pub operation: S3Operation,
+    pub region: Option<String>,
+    /// 访问密钥,未设置时使用默认的凭证链(环境变量、配置文件、实例角色等)
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    /// 下载时把对象原样写入 msg.binary,msg.data 保持不变
+    #[serde(default)]
+    pub binary: bool,
+    /// 成功分支名称
+    #[serde(default = "default_success_branch")]
+    pub success_branch: String,
+    /// 失败分支名称
+    #[serde(default = "default_error_branch")]
+    pub error_branch: String,
+}
+
+fn default_success_branch() -> String {
+    "success".to_string()
+}
+
+fn default_error_branch() -> String {
+    "error".to_string()
+}
+
+impl Default for S3Config {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            bucket: String::new(),
+            key_template: String::new(),
+            operation: S3Operation::Get,
+            region: None,
+            access_key_id: None,
+            secret_access_key: None,
+            binary: false,
+            success_branch: default_success_branch(),
+            error_branch: default_error_branch(),
+        }
+    }
+}
+
+/// 对象存储节点,兼容 S3 协议的服务均可使用
+///
+/// 上传时优先使用 msg.binary 中的原始字节;没有二进制载荷时,字符串类型的 msg.data 按 base64
+/// 解码为原始字节,无法解码时与其他类型一样序列化为JSON。
+/// 下载时如果配置了 `binary`,对象原样写入 msg.binary;否则JSON对象解析后写入 msg.data,
+/// 其他内容为UTF-8文本时写入字符串,否则写入base64字符串。
+/// 对象键写入 msg.metadata.s3_key,失败时错误写入 msg.metadata.error 并走失败分支。
+/// 客户端在处理第一条消息时创建,之后的消息共用
+#[derive(Debug)]
+pub struct S3Node {
+    config: S3Config,
+    client: OnceCell<Client>,
+}
+
+impl S3Node {
+    pub fn new(config: S3Config) -> Result<Self, RuleError> {
+        if config.access_key_id.is_some() != config.secret_access_key.is_some() {
+            return Err(RuleError::ConfigError(
+                "access_key_id 和 secret_access_key 必须同时设置".to_string(),
+            ));
+        }
+        Ok(Self {
+            config,
+            client: OnceCell::new(),
+        })
+    }
+
+    /// 获取共享的客户端,首次调用时加载凭证和区域配置
+    async fn client(&self) -> &Client {
+        self.client
+            .get_or_init(|| async {
+                let mut loader = aws_config::defaults(BehaviorVersion::latest());
+                if let Some(region) = &self.config.region {
+                    loader = loader.region(Region::new(region.clone()));
+                }
+                if let (Some(key), Some(secret)) =
+                    (&self.config.access_key_id, &self.config.secret_access_key)
+                {
+                    loader = loader.credentials_provider(Credentials::new(
+                        key.clone(),
+                        secret.clone(),
+                        None,
+                        None,
+                        "rule-rs",
+                    ));
+                }
+                let sdk_config = loader.load().await;
+
+                let mut builder = aws_sdk_s3::config::Builder::from(&sdk_config);
+                if let Some(endpoint) = &self.config.endpoint {
+                    builder = builder.endpoint_url(endpoint).force_path_style(true);
+                }
+                Client::from_conf(builder.build())
+            })
+            .await
+    }
+
+    async fn get(&self, key: &str) -> Result<Bytes, RuleError> {
+        let output = self
+            .client()
+            .await
+            .get_object()
+            .bucket(&self.config.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| {
+                RuleError::ComponentError(format!(
+                    "下载对象 {} 失败: {}",
+                    key,
+                    e.into_service_error()
+                ))
+            })?;
+        let bytes = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| RuleError::ComponentError(format!("读取对象 {} 失败: {}", key, e)))?
+            .into_bytes();
+        Ok(bytes)
+    }
+
+    /// 把下载的对象转换为 msg.data
+    fn decode_data(bytes: &[u8]) -> Value {
+        match serde_json::from_slice(bytes) {
+            Ok(value) => value,
+            Err(_) => match String::from_utf8(bytes.to_vec()) {
+                Ok(text) => Value::String(text),
+                Err(_) => Value::String(BASE64.encode(bytes)),
+            },
+        }
+    }
+
+    async fn put(&self, key: &str, msg: &Message) -> Result<(), RuleError> {
+        let decoded = match &msg.binary {
+            Some(bytes) => Some(bytes.clone()),
+            None => msg
+                .data
+                .as_str()
+                .and_then(|s| BASE64.decode(s).ok())
+                .map(Bytes::from),
+        };
+        let (body, content_type) = match decoded {
+            Some(bytes) => (bytes, "application/octet-stream"),
+            None => (
+                serde_json::to_vec(&msg.data)
+                    .map_err(|e| RuleError::ComponentError(format!("消息数据序列化失败: {}", e)))?
+                    .into(),
+                "application/json",
+            ),
+        };
+
+        self.client()
+            .await
+            .put_object()
+            .bucket(&self.config.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| {
+                RuleError::ComponentError(format!(
+                    "上传对象 {} 失败: {}",
+                    key,
+                    e.into_service_error()
+                ))
+            })?;
+        Ok(())
+    }
+
+    async fn execute(&self, msg: &mut Message) -> Result<(), RuleError> {
+        let key = render_text(&self.config.key_template, msg);
+        if key.is_empty() {
+            return Err(RuleError::ComponentError("对象键为空".to_string()));
+        }
+        msg.set_meta("s3_key", key.clone());
+
+        match self.config.operation {
+            S3Operation::Get => {
+                let bytes = self.get(&key).await?;
+                if self.config.binary {
+                    msg.binary = Some(bytes);
+                } else {
+                    msg.data = Self::decode_data(&bytes);
+                }
+            }
+            S3Operation::Put => self.put(&key, msg).await?,
+        }
+        Ok(())
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "s3".to_string(),
+            name: "对象存储".to_string(),
+            description: "上传或下载S3兼容对象存储中的对象".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["bucket", "key_template"],
+                "properties": {
+                    "endpoint": { "type": "string", "description": "自定义服务地址,例如 MinIO" },
+                    "bucket": { "type": "string", "description": "存储桶" },
+                    "key_template": { "type": "string", "description": "对象键模板,支持模板变量" },
+                    "operation": {
+                        "type": "string",
+                        "enum": ["get", "put"],
+                        "description": "get 下载对象,put 上传 msg.binary 或 msg.data",
+                        "default": "get"
+                    },
+                    "region": { "type": "string", "description": "区域" },
+                    "access_key_id": {
+                        "type": "string",
+                        "description": "访问密钥ID,与 secret_access_key 同时设置"
+                    },
+                    "secret_access_key": { "type": "string", "description": "访问密钥" },
+                    "binary": {
+                        "type": "boolean",
+                        "description": "下载时把对象原样写入 msg.binary",
+                        "default": false
+                    },
+                    "success_branch": {
+                        "type": "string",
+                        "description": "成功分支名称",
+                        "default": "success"
+                    },
+                    "error_branch": {
+                        "type": "string",
+                        "description": "失败分支名称",
+                        "default": "error"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for S3Node {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        match self.execute(&mut msg).await {
+            Ok(()) => msg.set_meta("branch_name", self.config.success_branch.clone()),
+            Err(e) => {
+                msg.set_meta("error", e.to_string());
+                msg.set_meta("branch_name", self.config.error_branch.clone());
+            }
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -1,18 +1,25 @@
 use crate::engine::NodeHandler;
-use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::types::{ExecutionContext, Message, NodeContext, NodeDescriptor, NodeType, RuleError};
 use async_trait::async_trait;
-use chrono::{DateTime, Local, Utc};
+use chrono::{FixedOffset, Utc};
 use cron::Schedule;
 use serde::Deserialize;
+use serde_json::json;
 use std::str::FromStr;
 use tokio::time::sleep;
+use tracing::error;
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 pub struct ScheduleConfig {
     /// Cron表达式
     pub cron: String,
     /// 时区偏移(小时)
+    #[serde(default)]
     pub timezone_offset: i32,
+    /// 最大执行次数,为空表示不限制
+    #[serde(default)]
+    pub max_runs: Option<u32>,
 }
 
 impl Default for ScheduleConfig {
@@ -20,29 +27,60 @@ impl Default for ScheduleConfig {
         Self {
             cron: "*/1 * * * * *".to_string(), // 默认每秒执行
             timezone_offset: 0,
+            max_runs: None,
         }
     }
 }
 
+/// 定时节点
+///
+/// 节点被触发时启动一个后台任务,按Cron表达式在每个触发时间向下游发送一条新消息,
+/// 随后立即返回。后台任务登记在引擎中: 同一节点再次触发会替换旧任务,
+/// 删除规则链时会取消该规则链的所有定时任务;达到 `max_runs`
+/// 或所属规则链、节点不存在时任务自动结束
 #[derive(Debug)]
 pub struct ScheduleNode {
-    #[allow(dead_code)]
     config: ScheduleConfig,
     schedule: Schedule,
 }
 
 impl ScheduleNode {
-    pub fn new(config: ScheduleConfig) -> Self {
-        let schedule = Schedule::from_str(&config.cron).unwrap();
-        Self { config, schedule }
+    pub fn new(config: ScheduleConfig) -> Result<Self, RuleError> {
+        let schedule = Schedule::from_str(&config.cron)
+            .map_err(|e| RuleError::ConfigError(format!("无效的Cron表达式: {}", e)))?;
+        Ok(Self { config, schedule })
     }
 
-    fn next_schedule_time(&self) -> Option<DateTime<Local>> {
-        let now = Utc::now();
-        self.schedule
-            .after(&now)
-            .next()
-            .map(|utc| utc.with_timezone(&Local))
+    fn timezone(&self) -> Result<FixedOffset, RuleError> {
+        FixedOffset::east_opt(self.config.timezone_offset * 3600).ok_or_else(|| {
+            RuleError::ConfigError(format!("无效的时区偏移: {}", self.config.timezone_offset))
+        })
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "schedule".to_string(),
+            name: "定时节点".to_string(),
+            description: "按Cron表达式定时执行".to_string(),
+            node_type: NodeType::Head,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["cron"],
+                "properties": {
+                    "cron": { "type": "string", "description": "Cron 表达式" },
+                    "timezone_offset": {
+                        "type": "integer",
+                        "description": "时区偏移(小时)",
+                        "default": 0
+                    },
+                    "max_runs": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "最大执行次数,未设置时不限制"
+                    }
+                }
+            })),
+        }
     }
 }
 
@@ -53,30 +91,54 @@ impl NodeHandler for ScheduleNode {
         ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
-        loop {
-            if let Some(next_time) = self.next_schedule_time() {
-                let now = Local::now();
-                let delay = next_time.signed_duration_since(now);
+        let timezone = self.timezone()?;
+        let schedule = self.schedule.clone();
+        let max_runs = self.config.max_runs;
+        let engine = ctx.engine.clone();
+        let chain_id = ctx.node.chain_id;
+        let node_id = ctx.node.id;
+        let trigger_msg = msg.clone();
 
-                if delay.num_milliseconds() > 0 {
-                    sleep(std::time::Duration::from_millis(
-                        delay.num_milliseconds() as u64
-                    ))
-                    .await;
+        let handle = tokio::spawn(async move {
+            let runs = max_runs.map_or(usize::MAX, |max| max as usize);
+            for next_time in schedule.upcoming(timezone).take(runs) {
+                if let Ok(delay) = next_time.signed_duration_since(Utc::now()).to_std() {
+                    sleep(delay).await;
                 }
 
+                // 每次执行时重新获取规则链,以便使用最新的定义
+                let Some(chain) = engine.get_chain(chain_id).await else {
+                    break;
+                };
+                let Some(node) = chain.nodes.iter().find(|n| n.id == node_id) else {
+                    break;
+                };
+
+                let mut tick_msg = trigger_msg.clone();
+                tick_msg.id = Uuid::new_v4();
+                tick_msg.timestamp = Utc::now().timestamp_millis();
+
                 // 发送到下一个节点
-                ctx.send_next(msg.clone()).await?;
+                let ctx = NodeContext::new(
+                    node,
+                    &ExecutionContext::new(tick_msg.clone()),
+                    engine.clone(),
+                );
+                if let Err(e) = ctx.send_next(tick_msg).await {
+                    error!("定时节点 {} 执行失败: {}", node_id, e);
+                }
             }
-        }
+        });
+
+        ctx.engine
+            .register_schedule(chain_id, node_id, handle)
+            .await;
+        // 由定时任务向下游发送
+        ctx.mark_routed();
+        Ok(msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "schedule".to_string(),
-            name: "定时节点".to_string(),
-            description: "按Cron表达式定时执行".to_string(),
-            node_type: NodeType::Head,
-        }
+        Self::descriptor()
     }
 }
@@ -1,9 +1,9 @@
+use super::js_common::{default_script_timeout_ms, install_console, js_error, JsRuntime};
 use crate::engine::NodeHandler;
-use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError, ScriptPhase};
 use async_trait::async_trait;
-use rquickjs::{Context, Function, Runtime};
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 #[derive(Debug)]
 pub struct ScriptNode {
@@ -14,6 +14,9 @@ pub struct ScriptNode {
 pub struct ScriptConfig {
     pub script: String,
     pub output_type: Option<String>,
+    /// 脚本执行超时(毫秒),超时后中止脚本并返回错误
+    #[serde(default = "default_script_timeout_ms")]
+    pub script_timeout_ms: u64,
 }
 
 impl Default for ScriptConfig {
@@ -21,6 +24,7 @@ impl Default for ScriptConfig {
         Self {
             script: "return msg;".to_string(),
             output_type: None,
+            script_timeout_ms: default_script_timeout_ms(),
         }
     }
 }
@@ -31,35 +35,25 @@ impl ScriptNode {
     }
 
     fn execute_script(&self, node_ctx: &NodeContext, msg: &Message) -> Result<Value, RuleError> {
-        let rt = Runtime::new().unwrap();
-        let js_ctx = Context::full(&rt).unwrap();
+        let rt = JsRuntime::new(self.config.script_timeout_ms)?;
+        let js_ctx = rt.context()?;
 
-        js_ctx
-            .with(|ctx| {
-                // 注入上下文变量
-                let msg_json = serde_json::to_string(&msg).unwrap();
-                // 创建简化的上下文对象
-                let ctx_obj = serde_json::json!({
-                    "node_id": node_ctx.node.id.to_string(),
-                    "node_type": node_ctx.node.type_name,
-                    "metadata": node_ctx.metadata
-                });
+        js_ctx.with(|ctx| {
+            // 注入上下文变量
+            let msg_json = serde_json::to_string(&msg).unwrap();
+            // 创建简化的上下文对象
+            let ctx_obj = serde_json::json!({
+                "node_id": node_ctx.node.id.to_string(),
+                "node_type": node_ctx.node.type_name,
+                "metadata": node_ctx.metadata
+            });
 
-                // 添加 console.log 支持
-                {
-                    let console = rquickjs::Object::new(ctx.clone()).unwrap();
-                    let log_fn =
-                        Function::new(ctx.clone(), |s: String| println!("JS log: {}", s)).unwrap();
-                    let error_fn =
-                        Function::new(ctx.clone(), |s: String| eprintln!("JS error: {}", s))
-                            .unwrap();
-                    console.set("log", log_fn).unwrap();
-                    console.set("error", error_fn).unwrap();
-                    ctx.globals().set("console", console).unwrap();
-                }
+            // 添加 console 支持
+            install_console(&ctx, node_ctx.node, msg)
+                .map_err(|e| RuleError::NodeExecutionError(format!("JavaScript执行错误: {}", e)))?;
 
-                let js_code = format!(
-                    r#"
+            let js_code = format!(
+                r#"
                 const msg = {};
                 const ctx = {};
                 const execute = () => {{
@@ -67,15 +61,42 @@ impl ScriptNode {
                 }};
                 JSON.stringify(execute());
                 "#,
-                    msg_json, ctx_obj, self.config.script
-                );
-                let result: String = ctx.eval(js_code).map_err(|e| {
-                    RuleError::NodeExecutionError(format!("JavaScript执行错误: {}", e))
-                })?;
-                serde_json::from_str(&result)
-                    .map_err(|e| RuleError::NodeExecutionError(format!("JSON解析错误: {}", e)))
-            })
-            .map_err(|e| RuleError::NodeExecutionError(format!("节点执行失败: {}", e)))
+                msg_json, ctx_obj, self.config.script
+            );
+            let result: String = rt.run(ScriptPhase::Runtime, || {
+                ctx.eval(js_code).map_err(|e| {
+                    RuleError::NodeExecutionError(format!(
+                        "JavaScript执行错误: {}",
+                        js_error(&ctx, e)
+                    ))
+                })
+            })?;
+            serde_json::from_str(&result)
+                .map_err(|e| RuleError::NodeExecutionError(format!("JSON解析错误: {}", e)))
+        })
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "script".to_string(),
+            name: "脚本节点".to_string(),
+            description: "执行自定义脚本".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["script"],
+                "properties": {
+                    "script": { "type": "string", "description": "JS脚本,返回值作为新的消息数据" },
+                    "output_type": { "type": "string", "description": "输出消息类型,未设置时保持原类型" },
+                    "script_timeout_ms": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "default": 1000,
+                        "description": "脚本执行超时(毫秒),超时后中止脚本"
+                    }
+                }
+            })),
+        }
     }
 }
 
@@ -92,21 +113,15 @@ impl NodeHandler for ScriptNode {
             msg_type: self.config.output_type.clone().unwrap_or(msg.msg_type),
             metadata: msg.metadata,
             data: new_data,
+            binary: msg.binary,
             timestamp: msg.timestamp,
+            ack: msg.ack,
         };
 
-        // 发送到下一个节点
-        ctx.send_next(new_msg.clone()).await?;
-
         Ok(new_msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "script".to_string(),
-            name: "脚本节点".to_string(),
-            description: "执行自定义脚本".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
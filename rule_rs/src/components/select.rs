@@ -0,0 +1,211 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::debug;
+use uuid::Uuid;
+
+/// 已选出结果的消息保留时长,超过后即使仍有分支未到达也清理
+const DECIDED_TTL: Duration = Duration::from_secs(60);
+
+/// 单条消息的竞争状态
+struct Decided {
+    /// 已到达的分支数量
+    arrived: usize,
+    /// 选出结果的时间
+    decided_at: Instant,
+}
+
+/// 单个竞争节点的状态
+struct SelectState {
+    /// 已选出结果的消息,key为消息ID
+    decided: HashMap<Uuid, Decided>,
+    /// 上次清理过期状态的时间
+    last_eviction: Instant,
+}
+
+lazy_static! {
+    // 节点处理器会被重复创建,竞争状态按 (规则链ID, 节点ID) 保存在全局状态中
+    static ref GLOBAL_SELECT_STATE: Mutex<HashMap<(Uuid, Uuid), SelectState>> =
+        Mutex::new(HashMap::new());
+}
+
+/// 清理规则链下所有竞争节点的状态,在删除规则链时调用
+pub(crate) async fn remove_chain_state(chain_id: Uuid) {
+    GLOBAL_SELECT_STATE
+        .lock()
+        .await
+        .retain(|(id, _), _| *id != chain_id);
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SelectConfig {
+    /// 从消息创建起等待分支到达的最长时间(毫秒),不设置则不限时
+    pub timeout_ms: Option<u64>,
+    /// 超时分支名称
+    #[serde(default = "default_error_branch")]
+    pub error_branch: String,
+}
+
+fn default_error_branch() -> String {
+    "error".to_string()
+}
+
+impl Default for SelectConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: None,
+            error_branch: default_error_branch(),
+        }
+    }
+}
+
+/// 竞争节点
+///
+/// 与 `join` 相反,同一消息ID第一个到达的分支走 `success` 分支继续转发,
+/// 之后到达的分支被丢弃。第一个分支到达时已超过 `timeout_ms` 则设置元数据 `error`
+/// 并走 `error_branch`,未配置该连接时消息被丢弃。
+///
+/// 上游 `fork` 节点仍会等待所有分支执行完毕,较慢的分支需要自行设置超时
+#[derive(Debug)]
+pub struct SelectNode {
+    config: SelectConfig,
+}
+
+impl SelectNode {
+    pub fn new(config: SelectConfig) -> Self {
+        Self { config }
+    }
+
+    /// 记录分支到达,返回是否为该消息第一个到达的分支
+    async fn arrive(&self, chain_id: Uuid, node_id: Uuid, msg_id: Uuid, expected: usize) -> bool {
+        let now = Instant::now();
+
+        let mut states = GLOBAL_SELECT_STATE.lock().await;
+        let state = states
+            .entry((chain_id, node_id))
+            .or_insert_with(|| SelectState {
+                decided: HashMap::new(),
+                last_eviction: now,
+            });
+
+        // 分支执行失败时不会到达,定期清理长时间未到齐的消息
+        if now.duration_since(state.last_eviction) >= DECIDED_TTL {
+            state
+                .decided
+                .retain(|_, decided| now.duration_since(decided.decided_at) < DECIDED_TTL);
+            state.last_eviction = now;
+        }
+
+        let first = match state.decided.get_mut(&msg_id) {
+            Some(decided) => {
+                decided.arrived += 1;
+                false
+            }
+            None => {
+                state.decided.insert(
+                    msg_id,
+                    Decided {
+                        arrived: 1,
+                        decided_at: now,
+                    },
+                );
+                true
+            }
+        };
+
+        // 所有分支都已到达,不再需要保留状态
+        if state.decided[&msg_id].arrived >= expected {
+            state.decided.remove(&msg_id);
+        }
+        first
+    }
+
+    /// 消息创建后是否已超过等待时间
+    fn is_timed_out(&self, msg: &Message) -> bool {
+        match self.config.timeout_ms {
+            Some(timeout_ms) => {
+                let elapsed = chrono::Utc::now().timestamp_millis() - msg.timestamp;
+                elapsed > timeout_ms as i64
+            }
+            None => false,
+        }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "select".to_string(),
+            name: "竞争节点".to_string(),
+            description: "转发最先到达的分支,丢弃其余分支".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "timeout_ms": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "从消息创建起等待分支到达的最长时间(毫秒)"
+                    },
+                    "error_branch": {
+                        "type": "string",
+                        "description": "超时分支名称",
+                        "default": "error"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for SelectNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let expected_branches = ctx.incoming_connections().await?.len();
+
+        let first = self
+            .arrive(ctx.node.chain_id, ctx.node.id, msg.id, expected_branches)
+            .await;
+        if !first {
+            debug!("Select节点 {} 丢弃消息 {} 的后续分支", ctx.node.id, msg.id);
+            ctx.mark_routed();
+            return Ok(msg);
+        }
+
+        let mut msg = msg;
+        if self.is_timed_out(&msg) {
+            if ctx
+                .get_next_connections(&self.config.error_branch)
+                .await?
+                .is_empty()
+            {
+                ctx.mark_routed();
+                return Ok(msg);
+            }
+            msg.set_meta(
+                "error",
+                format!(
+                    "等待分支超时: {}ms",
+                    self.config.timeout_ms.unwrap_or_default()
+                ),
+            );
+            msg.set_meta("branch_name", self.config.error_branch.clone());
+        } else {
+            msg.set_meta("branch_name", "success");
+        }
+
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
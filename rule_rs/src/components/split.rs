@@ -0,0 +1,166 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// 按权重分流的分支
+#[derive(Debug, Clone, Deserialize)]
+pub struct SplitBranch {
+    /// 分支名称,即连接的 type_name
+    pub name: String,
+    /// 权重,按所有分支权重之和归一化,不要求总和为 100
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SplitConfig {
+    pub branches: Vec<SplitBranch>,
+    /// 随机数种子,设置后分流结果可复现
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self {
+            branches: vec![
+                SplitBranch {
+                    name: "a".to_string(),
+                    weight: 50.0,
+                },
+                SplitBranch {
+                    name: "b".to_string(),
+                    weight: 50.0,
+                },
+            ],
+            seed: None,
+        }
+    }
+}
+
+/// 按权重分流节点
+///
+/// 每条消息按权重随机选择一个分支并设置 `branch_name`,可用于 A/B 测试或把部分流量导向新的子规则链。
+/// 权重为 0 的分支不会被选中
+#[derive(Debug)]
+pub struct SplitNode {
+    config: SplitConfig,
+    /// 所有分支的权重之和
+    total: f64,
+    rng: Mutex<StdRng>,
+}
+
+impl SplitNode {
+    pub fn new(config: SplitConfig) -> Result<Self, RuleError> {
+        if config.branches.is_empty() {
+            return Err(RuleError::ConfigError(
+                "分流节点至少需要一个分支".to_string(),
+            ));
+        }
+        let mut names = HashSet::new();
+        for branch in &config.branches {
+            if branch.name.is_empty() {
+                return Err(RuleError::ConfigError("分支名称不能为空".to_string()));
+            }
+            if !names.insert(branch.name.as_str()) {
+                return Err(RuleError::ConfigError(format!(
+                    "分支名称重复: {}",
+                    branch.name
+                )));
+            }
+            if !branch.weight.is_finite() || branch.weight < 0.0 {
+                return Err(RuleError::ConfigError(format!(
+                    "分支 {} 的权重必须是非负数",
+                    branch.name
+                )));
+            }
+        }
+        let total: f64 = config.branches.iter().map(|branch| branch.weight).sum();
+        if total <= 0.0 {
+            return Err(RuleError::ConfigError("分支权重之和必须大于 0".to_string()));
+        }
+
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        Ok(Self {
+            config,
+            total,
+            rng: Mutex::new(rng),
+        })
+    }
+
+    /// 按权重随机选择分支
+    fn pick(&self) -> &str {
+        let mut point = self.rng.lock().unwrap().random_range(0.0..self.total);
+        for branch in &self.config.branches {
+            if point < branch.weight {
+                return &branch.name;
+            }
+            point -= branch.weight;
+        }
+        // 浮点误差落在末尾时选择最后一个权重不为 0 的分支
+        self.config
+            .branches
+            .iter()
+            .rev()
+            .find(|branch| branch.weight > 0.0)
+            .map(|branch| branch.name.as_str())
+            .unwrap_or_default()
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "split".to_string(),
+            name: "分流节点".to_string(),
+            description: "按权重把消息随机分配到不同分支".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["branches"],
+                "properties": {
+                    "branches": {
+                        "type": "array",
+                        "minItems": 1,
+                        "items": {
+                            "type": "object",
+                            "required": ["name", "weight"],
+                            "properties": {
+                                "name": { "type": "string", "description": "分支名称" },
+                                "weight": {
+                                    "type": "number",
+                                    "minimum": 0,
+                                    "description": "权重,按所有分支权重之和归一化"
+                                }
+                            }
+                        }
+                    },
+                    "seed": { "type": "integer", "minimum": 0, "description": "随机数种子,设置后分流结果可复现" }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for SplitNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.set_meta("branch_name", self.pick());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -0,0 +1,105 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::get_value_by_path;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SplitArrayConfig {
+    /// 数组在 msg.data 中的路径,以点分隔,未设置时 msg.data 本身必须是数组
+    #[serde(default)]
+    pub field: Option<String>,
+    /// 是否并发执行各元素的下游,默认按顺序执行
+    #[serde(default)]
+    pub concurrent: bool,
+}
+
+/// 数组拆分节点
+///
+/// 把数组中的每个元素作为新消息的 `msg.data` 发送到下一个节点,元数据继承输入消息,
+/// 并设置 `index`(从 0 开始的序号)和 `total`(数组长度)。默认按顺序发送,
+/// 前一个元素的下游执行完成后才发送下一个;`concurrent` 为 true 时各元素的下游并发执行。
+/// 空数组不产生任何消息,部分元素失败时其余元素照常发送,最后返回 `RuleError::SendAllFailed`
+#[derive(Debug)]
+pub struct SplitArrayNode {
+    config: SplitArrayConfig,
+}
+
+impl SplitArrayNode {
+    pub fn new(config: SplitArrayConfig) -> Self {
+        Self { config }
+    }
+
+    /// 读取要拆分的数组
+    fn items<'m>(&self, data: &'m Value) -> Result<&'m Vec<Value>, RuleError> {
+        let (name, value) = match self.config.field.as_deref() {
+            Some(field) => (
+                field,
+                get_value_by_path(data, field)
+                    .ok_or_else(|| RuleError::ComponentError(format!("字段 {} 不存在", field)))?,
+            ),
+            None => ("msg.data", data),
+        };
+        value
+            .as_array()
+            .ok_or_else(|| RuleError::ComponentError(format!("{} 不是数组: {}", name, value)))
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "split_array".to_string(),
+            name: "数组拆分节点".to_string(),
+            description: "把数组拆分为多条消息,每个元素一条".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "field": {
+                        "type": "string",
+                        "description": "数组在 msg.data 中的路径,未设置时拆分 msg.data 本身"
+                    },
+                    "concurrent": {
+                        "type": "boolean",
+                        "description": "是否并发执行各元素的下游",
+                        "default": false
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for SplitArrayNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let items = self.items(&msg.data)?;
+        let total = items.len();
+        let msgs = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let mut element = Message::new(&msg.msg_type, item.clone());
+                element.metadata = msg.metadata.clone();
+                element.set_meta("index", index);
+                element.set_meta("total", total);
+                element
+            })
+            .collect();
+
+        if self.config.concurrent {
+            ctx.send_all_concurrent(msgs).await?;
+        } else {
+            ctx.send_all(msgs).await?;
+        }
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -2,6 +2,7 @@ use crate::engine::NodeHandler;
 use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
 use async_trait::async_trait;
 use serde::Deserialize;
+use serde_json::json;
 
 #[derive(Debug, Deserialize)]
 pub struct StartConfig {}
@@ -22,26 +23,30 @@ impl StartNode {
     pub fn new(config: StartConfig) -> Self {
         Self { config }
     }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "start".to_string(),
+            name: "开始节点".to_string(),
+            description: "规则链的起始节点".to_string(),
+            node_type: NodeType::Head,
+            config_schema: Some(json!({ "type": "object" })),
+        }
+    }
 }
 
 #[async_trait]
 impl NodeHandler for StartNode {
     async fn handle<'a>(
         &'a self,
-        ctx: NodeContext<'a>,
+        _ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
         // 开始节点只做消息转发
-        ctx.send_next(msg.clone()).await?;
         Ok(msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "start".to_string(),
-            name: "开始节点".to_string(),
-            description: "规则链的起始节点".to_string(),
-            node_type: NodeType::Head,
-        }
+        Self::descriptor()
     }
 }
@@ -1,7 +1,8 @@
 use crate::engine::NodeHandler;
-use crate::types::{ExecutionContext, Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
 use async_trait::async_trait;
 use serde::Deserialize;
+use serde_json::json;
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
@@ -26,6 +27,22 @@ impl SubchainNode {
     pub fn new(config: SubchainConfig) -> Self {
         Self { config }
     }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "subchain".to_string(),
+            name: "子规则链节点".to_string(),
+            description: "执行另一个规则链".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["chain_id"],
+                "properties": {
+                    "chain_id": { "type": "string", "format": "uuid", "description": "子规则链ID" }
+                }
+            })),
+        }
+    }
 }
 
 #[async_trait]
@@ -35,30 +52,28 @@ impl NodeHandler for SubchainNode {
         ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
+        if self.config.chain_id.is_nil() {
+            return Err(RuleError::ChainNotFound(self.config.chain_id));
+        }
+
         let subchain = ctx
             .engine
             .get_chain(self.config.chain_id)
             .await
-            .ok_or_else(|| {
-                RuleError::ConfigError(format!("子规则链未找到: {}", self.config.chain_id))
-            })?;
-
-        // 创建子规则链上下文
-        let mut sub_ctx = ExecutionContext::new(msg.clone());
-        let result = ctx.engine.execute_chain(&subchain, &mut sub_ctx).await?;
+            .ok_or(RuleError::ChainNotFound(self.config.chain_id))?;
 
-        // 发送到下一个节点
-        ctx.send_next(result.clone()).await?;
+        // 创建子规则链上下文,执行子规则链并获取其最终输出
+        let mut sub_ctx = ctx.create_subchain_context();
+        sub_ctx.msg = msg;
+        let result = ctx
+            .engine
+            .execute_chain(&subchain, None, &mut sub_ctx)
+            .await?;
 
         Ok(result)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "subchain".to_string(),
-            name: "子规则链节点".to_string(),
-            description: "执行另一个规则链".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
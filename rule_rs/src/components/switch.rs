@@ -1,21 +1,54 @@
+use super::js_common::{default_script_timeout_ms, install_console, js_error, JsRuntime};
 use crate::engine::NodeHandler;
-use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::types::{Message, Node, NodeContext, NodeDescriptor, NodeType, RuleError, ScriptPhase};
+use crate::utils::get_value_by_path;
 use async_trait::async_trait;
-use rquickjs::{Context, Runtime};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
 // 分支条件配置
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct SwitchCase {
-    pub name: String,        // 分支名称
-    pub condition: String,   // JS条件表达式
+    pub name: String, // 分支名称
+    #[serde(default)]
+    pub condition: Option<String>, // JS条件表达式
+    #[serde(default)]
     pub description: String, // 分支描述
+    /// 结构化匹配的 msg.data 字段路径,以点分隔,数组使用数字下标
+    #[serde(default)]
+    pub field: Option<String>,
+    /// 字段值等于该值时匹配
+    #[serde(default)]
+    pub equals: Option<Value>,
+    /// 字段值匹配该正则表达式时匹配,非字符串的值按 JSON 文本匹配
+    #[serde(default)]
+    pub matches_regex: Option<String>,
+    /// 优先级,数值大的分支先判断,相同时按配置顺序
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// 分支匹配模式
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SwitchMode {
+    /// 按顺序匹配,只发送到第一个条件成立的分支
+    #[default]
+    FirstMatch,
+    /// 发送到所有条件成立的分支
+    AllMatches,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SwitchConfig {
     pub cases: Vec<SwitchCase>,
     pub default_next: Option<String>,
+    #[serde(default)]
+    pub mode: SwitchMode,
+    /// 条件表达式执行超时(毫秒),超时后中止执行并返回错误
+    #[serde(default = "default_script_timeout_ms")]
+    pub script_timeout_ms: u64,
 }
 
 impl Default for SwitchConfig {
@@ -23,47 +56,181 @@ impl Default for SwitchConfig {
         Self {
             cases: Vec::new(),
             default_next: None,
+            mode: SwitchMode::FirstMatch,
+            script_timeout_ms: default_script_timeout_ms(),
         }
     }
 }
 
+/// 条件分支节点
+///
+/// 分支可以使用 `field` 配合 `equals` 或 `matches_regex` 进行结构化匹配,也可以使用 JS 条件表达式
+/// `condition`,表达式中可以使用 `msg` 和 `data`(即 `msg.data`)。两者都配置时需要同时满足,
+/// 先进行结构化匹配,不匹配时不再执行 JS。分支按 `priority` 从高到低判断,相同时按配置顺序。
+/// `first_match` 模式发送到第一个条件成立的分支;`all_matches` 模式把消息分别发送到每个条件成立的分支。
+/// 没有条件成立时发送到 `default_next` 分支,未配置则不发送并原样返回消息
 #[derive(Debug)]
 pub struct SwitchNode {
     config: SwitchConfig,
+    /// 与 `config.cases` 一一对应的预编译正则表达式
+    regexes: Vec<Option<Regex>>,
 }
 
 impl SwitchNode {
-    pub fn new(config: SwitchConfig) -> Self {
-        Self { config }
+    pub fn new(mut config: SwitchConfig) -> Result<Self, RuleError> {
+        // 稳定排序,优先级相同的分支保持配置顺序
+        config
+            .cases
+            .sort_by_key(|case| std::cmp::Reverse(case.priority));
+
+        let mut regexes = Vec::with_capacity(config.cases.len());
+        for case in &config.cases {
+            let error = |reason: &str| {
+                RuleError::ConfigError(format!("分支 {} 配置无效: {}", case.name, reason))
+            };
+            match (&case.field, &case.equals, &case.matches_regex) {
+                (None, None, None) if case.condition.is_none() => {
+                    return Err(error("需要配置 field 或 condition"));
+                }
+                (Some(_), None, None) => {
+                    return Err(error("field 需要配合 equals 或 matches_regex 使用"));
+                }
+                (_, Some(_), Some(_)) => {
+                    return Err(error("equals 和 matches_regex 只能配置一个"));
+                }
+                (None, Some(_), _) | (None, _, Some(_)) => {
+                    return Err(error("equals 和 matches_regex 需要配合 field 使用"));
+                }
+                _ => {}
+            }
+            let regex = match &case.matches_regex {
+                Some(pattern) => Some(Regex::new(pattern).map_err(|e| error(&e.to_string()))?),
+                None => None,
+            };
+            regexes.push(regex);
+        }
+
+        Ok(Self { config, regexes })
+    }
+
+    /// 判断分支是否匹配,先进行结构化匹配,再执行 JS 条件表达式
+    fn matches(&self, index: usize, node: &Node, msg: &Message) -> Result<bool, RuleError> {
+        let case = &self.config.cases[index];
+        if let Some(field) = &case.field {
+            let matched = match get_value_by_path(&msg.data, field) {
+                Some(value) => match (&case.equals, &self.regexes[index]) {
+                    (Some(expected), _) => value == expected,
+                    (None, Some(regex)) => match value {
+                        Value::String(text) => regex.is_match(text),
+                        other => regex.is_match(&other.to_string()),
+                    },
+                    (None, None) => false,
+                },
+                None => false,
+            };
+            if !matched {
+                return Ok(false);
+            }
+        }
+
+        match &case.condition {
+            Some(condition) => self.evaluate_condition(condition, node, msg),
+            None => Ok(true),
+        }
     }
 
     // 执行条件表达式
-    fn evaluate_condition(&self, case: &SwitchCase, msg: &Message) -> Result<bool, RuleError> {
-        let rt = Runtime::new().unwrap();
-        let ctx = Context::full(&rt).unwrap();
+    fn evaluate_condition(
+        &self,
+        condition: &str,
+        node: &Node,
+        msg: &Message,
+    ) -> Result<bool, RuleError> {
+        let rt = JsRuntime::new(self.config.script_timeout_ms)?;
+        let ctx = rt.context()?;
 
         ctx.with(|ctx| {
+            install_console(&ctx, node, msg)
+                .map_err(|e| RuleError::NodeExecutionError(e.to_string()))?;
+
             // 注入消息数据
             let msg_json = serde_json::to_string(&msg).unwrap();
             let js_code = format!(
                 r#"
                 const msg = {};
+                const data = msg.data;
                 const condition = () => {{ 
                     return {}; 
                 }};
                 condition();
                 "#,
-                msg_json, case.condition
+                msg_json, condition
             );
 
             // 执行条件表达式
-            let result: bool = ctx
-                .eval(js_code)
-                .map_err(|e: rquickjs::Error| RuleError::NodeExecutionError(e.to_string()))?;
+            let result: bool = rt.run(ScriptPhase::Runtime, || {
+                ctx.eval(js_code)
+                    .map_err(|e| RuleError::NodeExecutionError(js_error(&ctx, e)))
+            })?;
 
             Ok(result)
         })
     }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "switch".to_string(),
+            name: "条件分支节点".to_string(),
+            description: "根据条件选择不同的处理分支".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["cases"],
+                "properties": {
+                    "cases": {
+                        "type": "array",
+                        "description": "分支条件列表",
+                        "items": {
+                            "type": "object",
+                            "required": ["name"],
+                            "properties": {
+                                "name": { "type": "string", "description": "分支名称" },
+                                "condition": { "type": "string", "description": "JS条件表达式" },
+                                "description": { "type": "string", "description": "分支描述" },
+                                "field": {
+                                    "type": "string",
+                                    "description": "结构化匹配的 msg.data 字段路径,例如 order.status"
+                                },
+                                "equals": { "description": "字段值等于该值时匹配" },
+                                "matches_regex": {
+                                    "type": "string",
+                                    "description": "字段值匹配该正则表达式时匹配"
+                                },
+                                "priority": {
+                                    "type": "integer",
+                                    "description": "优先级,数值大的分支先判断",
+                                    "default": 0
+                                }
+                            }
+                        }
+                    },
+                    "default_next": { "type": "string", "description": "没有匹配条件时的默认分支" },
+                    "mode": {
+                        "type": "string",
+                        "enum": ["first_match", "all_matches"],
+                        "description": "first_match 只走第一个匹配的分支,all_matches 走所有匹配的分支",
+                        "default": "first_match"
+                    },
+                    "script_timeout_ms": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "default": 1000,
+                        "description": "条件表达式执行超时(毫秒),超时后中止执行"
+                    }
+                }
+            })),
+        }
+    }
 }
 
 #[async_trait]
@@ -73,31 +240,43 @@ impl NodeHandler for SwitchNode {
         ctx: NodeContext<'a>,
         mut msg: Message,
     ) -> Result<Message, RuleError> {
-        // 遍历所有分支条件
-        for case in &self.config.cases {
-            if self.evaluate_condition(case, &msg)? {
-                msg.metadata.insert("branch_name".into(), case.name.clone());
-                // 发送到对应分支的下一个节点
-                ctx.send_next(msg.clone()).await?;
-                return Ok(msg);
+        match self.config.mode {
+            SwitchMode::FirstMatch => {
+                // 遍历所有分支条件
+                for (index, case) in self.config.cases.iter().enumerate() {
+                    if self.matches(index, ctx.node, &msg)? {
+                        // 由引擎发送到对应分支的下一个节点
+                        msg.set_meta("branch_name", case.name.clone());
+                        return Ok(msg);
+                    }
+                }
+            }
+            SwitchMode::AllMatches => {
+                let mut matched = false;
+                for (index, case) in self.config.cases.iter().enumerate() {
+                    if self.matches(index, ctx.node, &msg)? {
+                        matched = true;
+                        let mut branch_msg = msg.clone();
+                        branch_msg.set_meta("branch_name", case.name.clone());
+                        ctx.send_next(branch_msg).await?;
+                    }
+                }
+                if matched {
+                    return Ok(msg);
+                }
             }
         }
 
-        // 没有匹配的条件,使用默认分支
-        if let Some(default) = &self.config.default_next {
-            msg.metadata.insert("branch_name".into(), default.clone());
-            ctx.send_next(msg.clone()).await?;
+        // 没有匹配的条件,使用默认分支;没有默认分支时不再向下游发送
+        match &self.config.default_next {
+            Some(default) => msg.set_meta("branch_name", default.clone()),
+            None => ctx.mark_routed(),
         }
 
         Ok(msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "switch".to_string(),
-            name: "条件分支节点".to_string(),
-            description: "根据条件选择不同的处理分支".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
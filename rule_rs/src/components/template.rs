@@ -0,0 +1,163 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use handlebars::Handlebars;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tera::Tera;
+
+/// 模板名称,每个节点只注册一个模板
+const TEMPLATE_NAME: &str = "template";
+
+/// 模板引擎类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateEngine {
+    #[default]
+    Handlebars,
+    Tera,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateConfig {
+    #[serde(default)]
+    pub engine: TemplateEngine,
+    /// 模板内容,通过 `msg.data`、`msg.metadata`、`msg.type`、`msg.id` 访问消息
+    pub template: String,
+    /// 渲染结果写入 msg.data 的字段,默认为 `output`
+    #[serde(default = "default_output_field")]
+    pub output_field: String,
+}
+
+fn default_output_field() -> String {
+    "output".to_string()
+}
+
+impl Default for TemplateConfig {
+    fn default() -> Self {
+        Self {
+            engine: TemplateEngine::Handlebars,
+            template: String::new(),
+            output_field: default_output_field(),
+        }
+    }
+}
+
+/// 编译后的模板
+#[derive(Debug)]
+enum Renderer {
+    Handlebars(Box<Handlebars<'static>>),
+    Tera(Box<Tera>),
+}
+
+/// 模板渲染节点
+///
+/// 使用 Handlebars 或 Tera 渲染完整模板,支持循环、条件等语法,
+/// 渲染结果以字符串写入 msg.data 的 `output_field` 字段。
+/// 模板在创建节点时编译一次,输出不做 HTML 转义
+#[derive(Debug)]
+pub struct TemplateNode {
+    config: TemplateConfig,
+    renderer: Renderer,
+}
+
+impl TemplateNode {
+    pub fn new(config: TemplateConfig) -> Result<Self, RuleError> {
+        let renderer = match config.engine {
+            TemplateEngine::Handlebars => {
+                let mut handlebars = Handlebars::new();
+                handlebars.register_escape_fn(handlebars::no_escape);
+                handlebars
+                    .register_template_string(TEMPLATE_NAME, &config.template)
+                    .map_err(|e| RuleError::ConfigError(format!("模板编译失败: {}", e)))?;
+                Renderer::Handlebars(Box::new(handlebars))
+            }
+            TemplateEngine::Tera => {
+                let mut tera = Tera::default();
+                tera.autoescape_on(vec![]);
+                tera.add_raw_template(TEMPLATE_NAME, &config.template)
+                    .map_err(|e| RuleError::ConfigError(format!("模板编译失败: {}", e)))?;
+                Renderer::Tera(Box::new(tera))
+            }
+        };
+        Ok(Self { config, renderer })
+    }
+
+    /// 使用消息渲染模板
+    fn render(&self, msg: &Message) -> Result<String, RuleError> {
+        let context = json!({
+            "msg": {
+                "id": msg.id,
+                "type": msg.msg_type,
+                "data": msg.data,
+                "metadata": msg.metadata,
+                "timestamp": msg.timestamp,
+            }
+        });
+
+        match &self.renderer {
+            Renderer::Handlebars(handlebars) => handlebars
+                .render(TEMPLATE_NAME, &context)
+                .map_err(|e| RuleError::ComponentError(format!("模板渲染失败: {}", e))),
+            Renderer::Tera(tera) => {
+                let context = tera::Context::from_value(context)
+                    .map_err(|e| RuleError::ComponentError(format!("模板渲染失败: {}", e)))?;
+                tera.render(TEMPLATE_NAME, &context)
+                    .map_err(|e| RuleError::ComponentError(format!("模板渲染失败: {}", e)))
+            }
+        }
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "template".to_string(),
+            name: "模板渲染".to_string(),
+            description: "使用Handlebars或Tera渲染模板".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["template"],
+                "properties": {
+                    "engine": {
+                        "type": "string",
+                        "enum": ["handlebars", "tera"],
+                        "description": "模板引擎",
+                        "default": "handlebars"
+                    },
+                    "template": { "type": "string", "description": "模板内容,上下文为 msg" },
+                    "output_field": {
+                        "type": "string",
+                        "description": "渲染结果写入 msg.data 的字段",
+                        "default": "output"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for TemplateNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let output = self.render(&msg)?;
+
+        let mut msg = msg;
+        if msg.data.is_null() {
+            msg.data = json!({});
+        }
+        let data = msg.data.as_object_mut().ok_or_else(|| {
+            RuleError::ComponentError("模板渲染结果只能写入对象类型的 msg.data".to_string())
+        })?;
+        data.insert(self.config.output_field.clone(), Value::String(output));
+
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
@@ -1,5 +1,7 @@
+use super::calc::to_json;
 use crate::engine::NodeHandler;
 use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::utils::resolve_variable;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::json;
@@ -13,12 +15,16 @@ pub struct TransformNode {
 #[derive(Debug, Deserialize)]
 pub struct TransformConfig {
     pub template: Value,
+    /// 替换变量后把包含模板变量的字符串作为算术表达式计算,例如 `"${msg.data.value} * 2"`
+    #[serde(default)]
+    pub evaluate: bool,
 }
 
 impl Default for TransformConfig {
     fn default() -> Self {
         Self {
             template: json!({}),
+            evaluate: false,
         }
     }
 }
@@ -47,11 +53,22 @@ impl TransformNode {
         }
     }
 
+    /// 解析 msg.data 及其字段,字符串直接输出,其他值输出JSON
+    fn resolve_data_variable(var_path: &str, msg: &Message) -> Option<String> {
+        if var_path != "msg.data" && !var_path.starts_with("msg.data.") {
+            return None;
+        }
+        match resolve_variable(var_path, msg)? {
+            Value::String(s) => Some(s),
+            value => Some(value.to_string()),
+        }
+    }
+
     fn apply_template(&self, msg: &Message) -> Result<Value, RuleError> {
         let mut result = self.config.template.clone();
 
         if let Value::Object(obj) = &mut result {
-            for (_key, value) in obj.iter_mut() {
+            for (key, value) in obj.iter_mut() {
                 if let Value::String(template) = value {
                     let mut new_value = template.clone();
 
@@ -60,8 +77,10 @@ impl TransformNode {
                         if let Some(end) = new_value[start..].find('}') {
                             let var_path = &new_value[start + 2..start + end];
                             let replacement = if var_path.starts_with("msg.") {
-                                // 处理消息数据中的变量
-                                Self::get_value_by_path(&msg.data, &var_path[4..])
+                                // msg.data.路径 与其他节点的模板变量含义相同,
+                                // 找不到时按 msg.路径 读取消息数据中的字段
+                                Self::resolve_data_variable(var_path, msg)
+                                    .or_else(|| Self::get_value_by_path(&msg.data, &var_path[4..]))
                                     .unwrap_or_else(|| "".to_string())
                             } else {
                                 // 处理其他类型的变量
@@ -78,20 +97,55 @@ impl TransformNode {
                         }
                     }
 
-                    *value = Value::String(new_value);
+                    *value = if self.config.evaluate && template.contains("${") {
+                        Self::evaluate(key, &new_value)?
+                    } else {
+                        Value::String(new_value)
+                    };
                 }
             }
         }
 
         Ok(result)
     }
+
+    /// 计算替换变量后的算术表达式
+    fn evaluate(key: &str, expr: &str) -> Result<Value, RuleError> {
+        let result = evalexpr::eval(expr).map_err(|e| {
+            RuleError::ComponentError(format!("字段 {} 的表达式 {} 计算失败: {}", key, expr, e))
+        })?;
+        to_json(result).ok_or_else(|| {
+            RuleError::ComponentError(format!("字段 {} 的计算结果不是有限数值", key))
+        })
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "transform".to_string(),
+            name: "消息转换器".to_string(),
+            description: "转换消息格式".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["template"],
+                "properties": {
+                    "template": { "description": "输出数据模板,字符串值支持模板变量" },
+                    "evaluate": {
+                        "type": "boolean",
+                        "description": "替换变量后把包含模板变量的字符串作为算术表达式计算",
+                        "default": false
+                    }
+                }
+            })),
+        }
+    }
 }
 
 #[async_trait]
 impl NodeHandler for TransformNode {
     async fn handle<'a>(
         &'a self,
-        ctx: NodeContext<'a>,
+        _ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
         // 执行转换
@@ -101,21 +155,15 @@ impl NodeHandler for TransformNode {
             msg_type: msg.msg_type,
             metadata: msg.metadata,
             data: new_data,
+            binary: msg.binary,
             timestamp: msg.timestamp,
+            ack: msg.ack,
         };
 
-        // 发送到下一个节点
-        ctx.send_next(transformed_msg.clone()).await?;
-
         Ok(transformed_msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "transform".to_string(),
-            name: "消息转换器".to_string(),
-            description: "转换消息格式".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
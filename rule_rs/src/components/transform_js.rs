@@ -1,9 +1,9 @@
+use super::js_common::{default_script_timeout_ms, install_console, js_error, JsRuntime};
 use crate::engine::NodeHandler;
-use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError, ScriptPhase};
 use async_trait::async_trait;
-use rquickjs::{Context, Runtime};
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 #[derive(Debug)]
 pub struct TransformJsNode {
@@ -13,12 +13,16 @@ pub struct TransformJsNode {
 #[derive(Debug, Deserialize)]
 pub struct TransformJsConfig {
     pub script: String,
+    /// 脚本执行超时(毫秒),超时后中止脚本并返回错误
+    #[serde(default = "default_script_timeout_ms")]
+    pub script_timeout_ms: u64,
 }
 
 impl Default for TransformJsConfig {
     fn default() -> Self {
         Self {
             script: "return msg;".to_string(),
+            script_timeout_ms: default_script_timeout_ms(),
         }
     }
 }
@@ -28,13 +32,16 @@ impl TransformJsNode {
         Self { config }
     }
 
-    fn execute_js(&self, msg: &Message) -> Result<Value, RuleError> {
+    fn execute_js(&self, node_ctx: &NodeContext, msg: &Message) -> Result<Value, RuleError> {
         // 创建 JS 运行时和上下文
-        let rt = Runtime::new().unwrap();
-        let ctx = Context::full(&rt).unwrap();
+        let rt = JsRuntime::new(self.config.script_timeout_ms)?;
+        let ctx = rt.context()?;
 
         // 在 JS 上下文中执行代码
         ctx.with(|ctx| {
+            install_console(&ctx, node_ctx.node, msg)
+                .map_err(|e| RuleError::NodeExecutionError(e.to_string()))?;
+
             // 将消息数据注入到 JS 上下文
             let msg_data = serde_json::to_string(&msg.data).unwrap();
             let js_code = format!(
@@ -48,13 +55,35 @@ impl TransformJsNode {
                 msg_data, self.config.script
             );
             // 执行转换脚本
-            let result: String = ctx
-                .eval(js_code)
-                .map_err(|e| RuleError::NodeExecutionError(e.to_string()))?;
+            let result: String = rt.run(ScriptPhase::Runtime, || {
+                ctx.eval(js_code)
+                    .map_err(|e| RuleError::NodeExecutionError(js_error(&ctx, e)))
+            })?;
             // 解析结果
             serde_json::from_str(&result).map_err(|e| RuleError::NodeExecutionError(e.to_string()))
         })
-        .map_err(|e| RuleError::NodeExecutionError(e.to_string()))
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "transform_js".to_string(),
+            name: "JS转换器".to_string(),
+            description: "使用JavaScript转换消息".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["script"],
+                "properties": {
+                    "script": { "type": "string", "description": "JS脚本,返回值作为新的消息数据" },
+                    "script_timeout_ms": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "default": 1000,
+                        "description": "脚本执行超时(毫秒),超时后中止脚本"
+                    }
+                }
+            })),
+        }
     }
 }
 
@@ -65,27 +94,21 @@ impl NodeHandler for TransformJsNode {
         ctx: NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
-        let new_data = self.execute_js(&msg)?;
+        let new_data = self.execute_js(&ctx, &msg)?;
         let transformed_msg = Message {
             id: msg.id,
             msg_type: msg.msg_type,
             metadata: msg.metadata,
             data: new_data,
+            binary: msg.binary,
             timestamp: msg.timestamp,
+            ack: msg.ack,
         };
 
-        // 发送到下一个节点
-        ctx.send_next(transformed_msg.clone()).await?;
-
         Ok(transformed_msg)
     }
 
     fn get_descriptor(&self) -> NodeDescriptor {
-        NodeDescriptor {
-            type_name: "transform_js".to_string(),
-            name: "JS转换器".to_string(),
-            description: "使用JavaScript转换消息".to_string(),
-            node_type: NodeType::Middle,
-        }
+        Self::descriptor()
     }
 }
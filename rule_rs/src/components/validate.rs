@@ -0,0 +1,129 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use jsonschema::Validator;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// 校验失败时的处理方式
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OnInvalid {
+    /// 返回 `RuleError::ValidationError`
+    #[default]
+    Error,
+    /// 发送到 `invalid` 分支,错误写入 msg.metadata.validation_errors
+    Branch,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ValidateConfig {
+    /// 校验 msg.data 的 JSON Schema
+    pub schema: Value,
+    #[serde(default)]
+    pub on_invalid: OnInvalid,
+}
+
+impl Default for ValidateConfig {
+    fn default() -> Self {
+        Self {
+            schema: json!({}),
+            on_invalid: OnInvalid::Error,
+        }
+    }
+}
+
+/// JSON Schema 校验节点
+///
+/// 校验通过的消息原样走 `success` 分支;校验失败时按 `on_invalid` 返回错误或走 `invalid` 分支
+#[derive(Debug)]
+pub struct ValidateNode {
+    config: ValidateConfig,
+    validator: Validator,
+}
+
+impl ValidateNode {
+    pub fn new(config: ValidateConfig) -> Result<Self, RuleError> {
+        // Schema 只在创建节点时编译一次
+        let validator = jsonschema::validator_for(&config.schema)
+            .map_err(|e| RuleError::ConfigError(format!("JSON Schema无效: {}", e)))?;
+        Ok(Self { config, validator })
+    }
+
+    /// 校验消息数据,返回所有校验错误的 (路径, 描述)
+    fn validate(&self, data: &Value) -> Vec<(String, String)> {
+        self.validator
+            .iter_errors(data)
+            .map(|e| (e.instance_path.to_string(), e.to_string()))
+            .collect()
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "validate".to_string(),
+            name: "Schema校验节点".to_string(),
+            description: "使用JSON Schema校验消息数据".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["schema"],
+                "properties": {
+                    "schema": { "type": "object", "description": "校验 msg.data 的 JSON Schema" },
+                    "on_invalid": {
+                        "type": "string",
+                        "enum": ["error", "branch"],
+                        "description": "error 校验失败时返回错误,branch 走 invalid 分支",
+                        "default": "error"
+                    }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for ValidateNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        let errors = self.validate(&msg.data);
+
+        if errors.is_empty() {
+            msg.set_meta("branch_name", "success");
+        } else {
+            match self.config.on_invalid {
+                OnInvalid::Error => {
+                    let details = errors
+                        .iter()
+                        .map(|(path, message)| {
+                            if path.is_empty() {
+                                message.clone()
+                            } else {
+                                format!("{}: {}", path, message)
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join("; ");
+                    return Err(RuleError::ValidationError(details));
+                }
+                OnInvalid::Branch => {
+                    let errors: Vec<Value> = errors
+                        .into_iter()
+                        .map(|(path, message)| json!({ "path": path, "message": message }))
+                        .collect();
+                    msg.set_meta("validation_errors", errors);
+                    msg.set_meta("branch_name", "invalid");
+                }
+            }
+        }
+
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
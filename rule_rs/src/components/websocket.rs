@@ -0,0 +1,210 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use futures::SinkExt;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// 单个 WebSocket 端点的持久连接状态
+#[derive(Default)]
+struct WsConnection {
+    stream: Option<WsStream>,
+    /// 连接是否曾经断开
+    dropped: bool,
+}
+
+lazy_static! {
+    // 节点处理器会被重复创建,连接按 url 保存在全局状态中以便复用
+    static ref GLOBAL_WS_CONNECTIONS: Mutex<HashMap<String, Arc<Mutex<WsConnection>>>> =
+        Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebSocketConfig {
+    pub url: String,
+    #[serde(default = "default_reconnect")]
+    pub reconnect: bool,
+    pub headers: Option<HashMap<String, String>>,
+    pub success_branch: Option<String>, // 成功分支名称
+    pub error_branch: Option<String>,   // 失败分支名称
+}
+
+fn default_reconnect() -> bool {
+    true
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            url: "ws://localhost".to_string(),
+            reconnect: true,
+            headers: None,
+            success_branch: None,
+            error_branch: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketNode {
+    config: WebSocketConfig,
+}
+
+impl WebSocketNode {
+    pub fn new(config: WebSocketConfig) -> Self {
+        Self { config }
+    }
+
+    async fn get_connection(&self) -> Arc<Mutex<WsConnection>> {
+        GLOBAL_WS_CONNECTIONS
+            .lock()
+            .await
+            .entry(self.config.url.clone())
+            .or_default()
+            .clone()
+    }
+
+    async fn connect(&self) -> Result<WsStream, RuleError> {
+        let mut request = self
+            .config
+            .url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| RuleError::ConfigError(format!("WebSocket地址无效: {}", e)))?;
+
+        // 添加请求头
+        if let Some(headers) = &self.config.headers {
+            for (key, value) in headers {
+                let name = HeaderName::from_bytes(key.as_bytes())
+                    .map_err(|e| RuleError::ConfigError(format!("请求头无效: {}", e)))?;
+                let value = HeaderValue::from_str(value)
+                    .map_err(|e| RuleError::ConfigError(format!("请求头无效: {}", e)))?;
+                request.headers_mut().insert(name, value);
+            }
+        }
+
+        let (stream, _) = connect_async(request)
+            .await
+            .map_err(|e| RuleError::ComponentError(format!("WebSocket连接失败: {}", e)))?;
+        Ok(stream)
+    }
+
+    async fn send(&self, msg: &Message) -> Result<(), RuleError> {
+        let text = serde_json::to_string(&msg.data)
+            .map_err(|e| RuleError::ComponentError(format!("消息序列化失败: {}", e)))?;
+
+        let connection = self.get_connection().await;
+        let mut connection = connection.lock().await;
+
+        // 最多尝试两次: 首次发送失败且允许重连时重新建立连接
+        let attempts = if self.config.reconnect { 2 } else { 1 };
+        let mut last_error = None;
+        for _ in 0..attempts {
+            if connection.stream.is_none() {
+                if connection.dropped && !self.config.reconnect {
+                    return Err(RuleError::ComponentError(format!(
+                        "WebSocket连接已断开: {}",
+                        self.config.url
+                    )));
+                }
+                connection.stream = Some(self.connect().await?);
+            }
+
+            let stream = connection.stream.as_mut().unwrap();
+            // 先写入缓冲区再刷新
+            let result = match stream.feed(WsMessage::text(text.clone())).await {
+                Ok(()) => stream.flush().await,
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    connection.stream = None;
+                    connection.dropped = true;
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(RuleError::ComponentError(format!(
+            "WebSocket发送失败: {}",
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        )))
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "websocket".to_string(),
+            name: "WebSocket节点".to_string(),
+            description: "通过WebSocket持久连接推送消息,支持成功/失败分支路由".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "required": ["url"],
+                "properties": {
+                    "url": { "type": "string", "description": "WebSocket 服务地址" },
+                    "reconnect": {
+                        "type": "boolean",
+                        "description": "连接断开后是否重连",
+                        "default": true
+                    },
+                    "headers": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "请求头,值支持模板变量"
+                    },
+                    "success_branch": { "type": "string", "description": "成功分支名称,未设置时按默认分支转发" },
+                    "error_branch": { "type": "string", "description": "失败分支名称,未设置时返回错误" }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for WebSocketNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut msg = msg;
+
+        match self.send(&msg).await {
+            Ok(()) => {
+                // 设置成功分支
+                if let Some(branch) = &self.config.success_branch {
+                    msg.set_meta("branch_name", branch.clone());
+                }
+
+                Ok(msg)
+            }
+            Err(e) => {
+                // 未配置失败分支时直接返回错误
+                let Some(branch) = &self.config.error_branch else {
+                    return Err(e);
+                };
+                msg.set_meta("error", e.to_string());
+                msg.set_meta("branch_name", branch.clone());
+
+                Ok(msg)
+            }
+        }
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
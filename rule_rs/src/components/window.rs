@@ -0,0 +1,281 @@
+use crate::engine::rule::DynRuleEngine;
+use crate::engine::NodeHandler;
+use crate::types::{ExecutionContext, Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::error;
+use uuid::Uuid;
+
+/// 单个窗口节点的缓冲状态
+#[derive(Default)]
+struct WindowState {
+    /// 当前窗口内缓冲的消息
+    buffer: Vec<Message>,
+    /// 当前窗口的超时任务
+    timer: Option<JoinHandle<()>>,
+    /// 窗口序号,每次输出后递增,用于让过期的超时任务失效
+    generation: u64,
+}
+
+impl WindowState {
+    /// 取出当前窗口的消息并开始新窗口
+    fn take(&mut self) -> Vec<Message> {
+        if let Some(timer) = self.timer.take() {
+            timer.abort();
+        }
+        self.generation += 1;
+        std::mem::take(&mut self.buffer)
+    }
+}
+
+lazy_static! {
+    // 节点处理器会被重复创建,缓冲按 (规则链ID, 节点ID) 保存在全局状态中
+    static ref GLOBAL_WINDOW_STATE: Mutex<HashMap<(Uuid, Uuid), WindowState>> =
+        Mutex::new(HashMap::new());
+}
+
+/// 丢弃规则链下所有窗口节点的缓冲并停止超时任务,在删除规则链时调用
+pub(crate) async fn remove_chain_state(chain_id: Uuid) {
+    GLOBAL_WINDOW_STATE.lock().await.retain(|(id, _), state| {
+        if *id == chain_id {
+            state.take();
+        }
+        *id != chain_id
+    });
+}
+
+/// 窗口聚合方式
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowAggregate {
+    /// 输出窗口内所有消息数据组成的数组
+    Collect,
+    /// 对字段求和
+    Sum,
+    /// 对字段求平均值
+    Avg,
+    /// 统计消息数量
+    Count,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WindowConfig {
+    /// 窗口消息数量阈值,0表示不按数量触发
+    #[serde(default)]
+    pub size: usize,
+    /// 窗口超时时间(毫秒),从窗口内第一条消息开始计时,0表示不按时间触发
+    #[serde(default)]
+    pub timeout_ms: u64,
+    #[serde(default = "default_aggregate")]
+    pub aggregate: WindowAggregate,
+    /// sum/avg 聚合的字段在 msg.data 中的路径,以点分隔
+    pub field: Option<String>,
+}
+
+fn default_aggregate() -> WindowAggregate {
+    WindowAggregate::Collect
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            size: 10,
+            timeout_ms: 1000,
+            aggregate: default_aggregate(),
+            field: None,
+        }
+    }
+}
+
+/// 窗口聚合节点
+///
+/// 缓冲连续到达的消息,窗口内消息数量达到 `size` 或距第一条消息超过 `timeout_ms`
+/// 时输出一条聚合消息,未触发输出的消息不会向下游转发。
+/// `collect` 输出数据数组,`sum`/`avg`/`count` 输出 `{"value": 聚合值, "count": 消息数}`
+#[derive(Debug)]
+pub struct WindowNode {
+    config: WindowConfig,
+}
+
+impl WindowNode {
+    pub fn new(config: WindowConfig) -> Self {
+        Self { config }
+    }
+
+    /// 按路径获取字段的数值,非数值字段不参与聚合
+    fn field_value(&self, data: &Value) -> Option<f64> {
+        let mut current = data;
+        for part in self.config.field.as_deref()?.split('.') {
+            current = match current {
+                Value::Array(items) => items.get(part.parse::<usize>().ok()?)?,
+                _ => current.get(part)?,
+            };
+        }
+        current.as_f64()
+    }
+
+    /// 将窗口内的消息聚合为一条消息,继承最后一条消息的元数据
+    fn aggregate(&self, messages: Vec<Message>) -> Message {
+        let count = messages.len();
+        let metadata = messages
+            .last()
+            .map(|msg| msg.metadata.clone())
+            .unwrap_or_default();
+
+        let data = match self.config.aggregate {
+            WindowAggregate::Collect => {
+                Value::Array(messages.into_iter().map(|msg| msg.data).collect())
+            }
+            WindowAggregate::Count => json!({ "value": count, "count": count }),
+            WindowAggregate::Sum | WindowAggregate::Avg => {
+                let values: Vec<f64> = messages
+                    .iter()
+                    .filter_map(|msg| self.field_value(&msg.data))
+                    .collect();
+                let sum: f64 = values.iter().sum();
+                let value = if self.config.aggregate == WindowAggregate::Sum {
+                    json!(sum)
+                } else if values.is_empty() {
+                    Value::Null
+                } else {
+                    json!(sum / values.len() as f64)
+                };
+                json!({ "value": value, "count": count })
+            }
+        };
+
+        let mut msg = Message::new("window", data);
+        msg.metadata = metadata;
+        msg
+    }
+
+    /// 启动窗口超时任务,超时后输出窗口内的消息
+    fn spawn_timer(
+        &self,
+        engine: DynRuleEngine,
+        chain_id: Uuid,
+        node_id: Uuid,
+        generation: u64,
+    ) -> JoinHandle<()> {
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let node = WindowNode::new(self.config.clone());
+
+        tokio::spawn(async move {
+            sleep(timeout).await;
+
+            let messages = {
+                let mut states = GLOBAL_WINDOW_STATE.lock().await;
+                match states.get_mut(&(chain_id, node_id)) {
+                    // 窗口已经按数量输出过,本次超时失效
+                    Some(state) if state.generation == generation => {
+                        state.timer = None;
+                        state.take()
+                    }
+                    _ => return,
+                }
+            };
+            if messages.is_empty() {
+                return;
+            }
+
+            let Some(chain) = engine.get_chain(chain_id).await else {
+                return;
+            };
+            let Some(chain_node) = chain.nodes.iter().find(|n| n.id == node_id) else {
+                return;
+            };
+
+            let msg = node.aggregate(messages);
+            let ctx = NodeContext::new(
+                chain_node,
+                &ExecutionContext::new(msg.clone()),
+                engine.clone(),
+            );
+            if let Err(e) = ctx.send_next(msg).await {
+                error!("窗口节点 {} 超时输出失败: {}", node_id, e);
+            }
+        })
+    }
+
+    pub fn descriptor() -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: "window".to_string(),
+            name: "窗口聚合节点".to_string(),
+            description: "按数量或时间窗口批量聚合消息".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "size": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "窗口消息数量阈值,0表示不按数量触发",
+                        "default": 0
+                    },
+                    "timeout_ms": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "窗口超时时间(毫秒),0表示不按时间触发",
+                        "default": 0
+                    },
+                    "aggregate": {
+                        "type": "string",
+                        "enum": ["collect", "sum", "avg", "count"],
+                        "description": "聚合方式",
+                        "default": "collect"
+                    },
+                    "field": { "type": "string", "description": "sum/avg 聚合的字段在 msg.data 中的路径" }
+                }
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for WindowNode {
+    async fn handle<'a>(
+        &'a self,
+        ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let chain_id = ctx.node.chain_id;
+        let node_id = ctx.node.id;
+
+        let messages = {
+            let mut states = GLOBAL_WINDOW_STATE.lock().await;
+            let state = states.entry((chain_id, node_id)).or_default();
+            state.buffer.push(msg.clone());
+
+            if self.config.size > 0 && state.buffer.len() >= self.config.size {
+                state.take()
+            } else {
+                // 窗口内第一条消息开始计时
+                if state.buffer.len() == 1 && self.config.timeout_ms > 0 {
+                    state.timer = Some(self.spawn_timer(
+                        ctx.engine.clone(),
+                        chain_id,
+                        node_id,
+                        state.generation,
+                    ));
+                }
+                // 窗口未满时不向下游发送
+                ctx.mark_routed();
+                return Ok(msg);
+            }
+        };
+
+        let result = self.aggregate(messages);
+        Ok(result)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        Self::descriptor()
+    }
+}
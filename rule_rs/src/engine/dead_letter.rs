@@ -0,0 +1,47 @@
+use crate::types::{Message, RuleError};
+use std::fmt;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 处理失败且未被错误分支处理的消息
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    /// 处理消息的根规则链ID
+    pub chain_id: Uuid,
+    /// 执行失败的节点ID,可能位于子规则链中;未执行到任何节点时为 None
+    pub node_id: Option<Uuid>,
+    /// 传入 `process_msg` 的原始消息,可直接用于重放
+    pub msg: Message,
+    /// 导致处理失败的错误
+    pub error: RuleError,
+}
+
+/// 死信处理函数,通过 `RuleEngine::with_dead_letter_handler` 注册
+///
+/// 在消息处理的调用方收到错误之前同步调用,耗时操作应转发到通道或后台任务中执行
+pub type DeadLetterHandler = Arc<dyn Fn(DeadLetter) + Send + Sync>;
+
+/// 死信处理函数的包装器,供引擎保存
+#[derive(Clone)]
+pub(crate) struct DeadLetterSink {
+    handler: DeadLetterHandler,
+}
+
+impl fmt::Debug for DeadLetterSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeadLetterSink")
+            .field("handler", &"<dead letter handler>")
+            .finish()
+    }
+}
+
+impl DeadLetterSink {
+    pub(crate) fn new(handler: DeadLetterHandler) -> Self {
+        Self { handler }
+    }
+
+    /// 将失败的消息交给处理函数
+    pub(crate) fn send(&self, letter: DeadLetter) {
+        (self.handler)(letter)
+    }
+}
@@ -0,0 +1,91 @@
+use crate::types::ExecutionProgress;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use uuid::Uuid;
+
+/// 正在处理的一条消息
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecutionInfo {
+    /// 处理消息的根规则链ID
+    pub chain_id: Uuid,
+    /// 消息ID
+    pub msg_id: Uuid,
+    /// 正在执行的节点ID,可能属于子规则链;节点之间切换时为 None
+    pub node_id: Option<Uuid>,
+    /// 已处理的时间(毫秒)
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug)]
+struct RunningExecution {
+    chain_id: Uuid,
+    msg_id: Uuid,
+    started: Instant,
+    progress: ExecutionProgress,
+}
+
+/// 正在处理的消息登记表
+///
+/// 每条消息只在开始和结束时登记一次,节点的进度记录在消息自身的执行上下文中,
+/// 查询时才读取,不查询时不产生额外开销
+#[derive(Debug, Default)]
+pub(crate) struct ExecutionRegistry {
+    next_id: AtomicU64,
+    running: Mutex<HashMap<u64, RunningExecution>>,
+}
+
+impl ExecutionRegistry {
+    /// 登记开始处理的消息,返回的标记丢弃时移除登记
+    pub(crate) fn register(
+        self: &Arc<Self>,
+        chain_id: Uuid,
+        msg_id: Uuid,
+        progress: ExecutionProgress,
+    ) -> RegisteredExecution {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.running.lock().unwrap().insert(
+            id,
+            RunningExecution {
+                chain_id,
+                msg_id,
+                started: Instant::now(),
+                progress,
+            },
+        );
+        RegisteredExecution {
+            registry: self.clone(),
+            id,
+        }
+    }
+
+    /// 所有正在处理的消息,按开始时间排序
+    pub(crate) fn list(&self) -> Vec<ExecutionInfo> {
+        let running = self.running.lock().unwrap();
+        let mut executions: Vec<_> = running.values().collect();
+        executions.sort_by_key(|execution| execution.started);
+        executions
+            .into_iter()
+            .map(|execution| ExecutionInfo {
+                chain_id: execution.chain_id,
+                msg_id: execution.msg_id,
+                node_id: execution.progress.current_node(),
+                elapsed_ms: execution.started.elapsed().as_millis() as u64,
+            })
+            .collect()
+    }
+}
+
+/// 消息的登记标记,处理结束或被中止时移除登记
+pub(crate) struct RegisteredExecution {
+    registry: Arc<ExecutionRegistry>,
+    id: u64,
+}
+
+impl Drop for RegisteredExecution {
+    fn drop(&mut self) {
+        self.registry.running.lock().unwrap().remove(&self.id);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::fmt;
+use std::sync::Arc;
+
+/// 同步函数节点的处理函数,通过 `register_fn` 注册
+pub type NodeFn = Arc<dyn Fn(Message) -> Result<Message, RuleError> + Send + Sync>;
+
+/// 异步函数节点的处理函数,通过 `register_async_fn` 注册
+pub type AsyncNodeFn =
+    Arc<dyn Fn(Message) -> BoxFuture<'static, Result<Message, RuleError>> + Send + Sync>;
+
+#[derive(Clone)]
+enum Function {
+    Sync(NodeFn),
+    Async(AsyncNodeFn),
+}
+
+/// 调用注册的 Rust 函数处理消息的节点,返回的消息按 `branch_name` 转发
+#[derive(Clone)]
+pub(crate) struct FnNode {
+    type_name: String,
+    function: Function,
+}
+
+impl fmt::Debug for FnNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnNode")
+            .field("type_name", &self.type_name)
+            .field("function", &"<node function>")
+            .finish()
+    }
+}
+
+impl FnNode {
+    pub(crate) fn new(type_name: &str, function: NodeFn) -> Self {
+        Self {
+            type_name: type_name.to_string(),
+            function: Function::Sync(function),
+        }
+    }
+
+    pub(crate) fn new_async(type_name: &str, function: AsyncNodeFn) -> Self {
+        Self {
+            type_name: type_name.to_string(),
+            function: Function::Async(function),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeHandler for FnNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        match &self.function {
+            Function::Sync(function) => function(msg),
+            Function::Async(function) => function(msg).await,
+        }
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: self.type_name.clone(),
+            name: self.type_name.clone(),
+            description: "调用注册的 Rust 函数处理消息".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::types::RuleError;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 未配置时统计错误率的时间窗口(毫秒)
+pub const DEFAULT_GUARD_WINDOW_MS: u64 = 60_000;
+
+/// 未配置时窗口内至少执行的次数,达到后才计算错误率
+pub const DEFAULT_GUARD_MIN_EXECUTIONS: usize = 10;
+
+/// 规则链自动禁用策略
+///
+/// 最近 `window` 内的执行次数不少于 `min_executions` 且失败比例超过 `max_error_rate` 时禁用规则链,
+/// 之后 `process_msg` 返回 `RuleError::ChainDisabled`,直到调用 `enable_chain`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainGuardPolicy {
+    /// 允许的最大错误率,取值 0 到 1
+    pub max_error_rate: f64,
+    /// 统计错误率的时间窗口
+    pub window: Duration,
+    /// 窗口内至少执行的次数,避免少量执行就触发禁用
+    pub min_executions: usize,
+}
+
+impl ChainGuardPolicy {
+    /// 创建禁用策略,窗口内至少执行 `DEFAULT_GUARD_MIN_EXECUTIONS` 次后才计算错误率
+    ///
+    /// # Arguments
+    /// * `max_error_rate` - 允许的最大错误率,取值 0 到 1
+    /// * `window` - 统计错误率的时间窗口
+    pub fn new(max_error_rate: f64, window: Duration) -> Self {
+        Self {
+            max_error_rate,
+            window,
+            min_executions: DEFAULT_GUARD_MIN_EXECUTIONS,
+        }
+    }
+
+    /// 设置窗口内至少执行的次数
+    pub fn with_min_executions(mut self, min_executions: usize) -> Self {
+        self.min_executions = min_executions;
+        self
+    }
+
+    /// 检查策略取值
+    pub(crate) fn validate(&self) -> Result<(), RuleError> {
+        if !(0.0..=1.0).contains(&self.max_error_rate) {
+            return Err(RuleError::ConfigError(format!(
+                "max_error_rate 必须在 0 到 1 之间: {}",
+                self.max_error_rate
+            )));
+        }
+        if self.window.is_zero() {
+            return Err(RuleError::ConfigError("window_ms 必须大于 0".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 单个规则链的禁用状态
+#[derive(Debug, Default)]
+pub(crate) struct ChainGuard {
+    /// 通过 `set_chain_guard` 设置的策略,优先于规则链元数据中的配置
+    pub(crate) policy: Option<ChainGuardPolicy>,
+    /// 窗口内每次执行的结束时间和是否成功
+    outcomes: VecDeque<(Instant, bool)>,
+    pub(crate) disabled: bool,
+}
+
+impl ChainGuard {
+    /// 记录一次执行,错误率超过上限时禁用规则链,返回本次是否触发禁用
+    ///
+    /// # Arguments
+    /// * `policy` - 生效的禁用策略
+    /// * `success` - 是否执行成功
+    pub(crate) fn record(&mut self, policy: &ChainGuardPolicy, success: bool) -> bool {
+        let now = Instant::now();
+        self.outcomes.push_back((now, success));
+        while let Some((at, _)) = self.outcomes.front() {
+            if now.duration_since(*at) <= policy.window {
+                break;
+            }
+            self.outcomes.pop_front();
+        }
+
+        if self.disabled || self.outcomes.len() < policy.min_executions.max(1) {
+            return false;
+        }
+        if self.error_rate() > policy.max_error_rate {
+            self.disabled = true;
+            return true;
+        }
+        false
+    }
+
+    /// 窗口内的错误率
+    pub(crate) fn error_rate(&self) -> f64 {
+        let failures = self.outcomes.iter().filter(|(_, success)| !success).count();
+        failures as f64 / self.outcomes.len().max(1) as f64
+    }
+
+    /// 重新启用规则链并清空窗口
+    pub(crate) fn reset(&mut self) {
+        self.outcomes.clear();
+        self.disabled = false;
+    }
+}
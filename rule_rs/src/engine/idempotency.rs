@@ -0,0 +1,115 @@
+use crate::types::{Message, RuleError};
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// 消息的处理状态
+#[derive(Debug)]
+enum ProcessState {
+    /// 正在处理
+    Processing,
+    /// 处理成功,保存处理结果
+    Done(Message),
+}
+
+/// 幂等缓存,记录在有效期内处理过的消息ID
+///
+/// 按 (规则链ID, 消息ID) 记录,同一消息发送到不同规则链互不影响;
+/// 处理失败的消息不会被记录,允许上游重试
+#[derive(Debug)]
+pub struct IdempotencyCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(Uuid, Uuid), (ProcessState, Instant)>>,
+}
+
+impl IdempotencyCache {
+    /// 创建幂等缓存,并启动后台任务定期清理过期记录
+    ///
+    /// # Arguments
+    /// * `ttl` - 记录的有效期
+    pub fn new(ttl: Duration) -> Arc<Self> {
+        let cache = Arc::new(Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        });
+
+        // 后台任务只持有弱引用,缓存释放后自动退出
+        let weak = Arc::downgrade(&cache);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(ttl).await;
+                let Some(cache) = Weak::upgrade(&weak) else {
+                    break;
+                };
+                cache.evict_expired().await;
+            }
+        });
+
+        cache
+    }
+
+    /// 清理过期记录
+    ///
+    /// 正在处理的记录同样会过期,避免处理被取消后消息永远无法重试
+    async fn evict_expired(&self) {
+        let now = Instant::now();
+        self.entries
+            .lock()
+            .await
+            .retain(|_, (_, recorded_at)| now.duration_since(*recorded_at) < self.ttl);
+    }
+
+    /// 开始处理消息
+    ///
+    /// # Returns
+    /// * `Ok(None)` - 消息未处理过,已标记为正在处理
+    /// * `Ok(Some(msg))` - 消息已处理过,返回缓存的处理结果
+    /// * `Err(RuleError::DuplicateMessage)` - 消息正在处理中
+    pub async fn begin(&self, chain_id: Uuid, msg_id: Uuid) -> Result<Option<Message>, RuleError> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().await;
+
+        if let Some((state, recorded_at)) = entries.get(&(chain_id, msg_id)) {
+            if now.duration_since(*recorded_at) < self.ttl {
+                return match state {
+                    ProcessState::Processing => Err(RuleError::DuplicateMessage(msg_id)),
+                    ProcessState::Done(result) => Ok(Some(result.clone())),
+                };
+            }
+        }
+
+        entries.insert((chain_id, msg_id), (ProcessState::Processing, now));
+        Ok(None)
+    }
+
+    /// 结束处理消息,成功时缓存处理结果,失败时删除记录
+    pub async fn complete(
+        &self,
+        chain_id: Uuid,
+        msg_id: Uuid,
+        result: &Result<Message, RuleError>,
+    ) {
+        let mut entries = self.entries.lock().await;
+        match result {
+            Ok(msg) => {
+                entries.insert(
+                    (chain_id, msg_id),
+                    (ProcessState::Done(msg.clone()), Instant::now()),
+                );
+            }
+            Err(_) => {
+                entries.remove(&(chain_id, msg_id));
+            }
+        }
+    }
+
+    /// 清理规则链的所有记录,在删除规则链时调用
+    pub async fn remove_chain(&self, chain_id: Uuid) {
+        self.entries
+            .lock()
+            .await
+            .retain(|(id, _), _| *id != chain_id);
+    }
+}
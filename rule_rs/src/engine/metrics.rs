@@ -0,0 +1,55 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// 计算平均耗时使用的最近执行次数
+const LATENCY_WINDOW: usize = 100;
+
+/// 规则链的执行统计,包含作为子规则链的执行
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ChainMetrics {
+    /// 执行总次数
+    pub executions: u64,
+    /// 执行成功次数
+    pub successes: u64,
+    /// 执行失败次数,包括被取消的执行
+    pub failures: u64,
+    /// 最近 100 次执行的平均耗时(毫秒)
+    pub avg_latency_ms: f64,
+}
+
+/// 规则链执行统计的累计状态
+#[derive(Debug, Default)]
+pub(crate) struct ChainStats {
+    metrics: ChainMetrics,
+    /// 最近执行的耗时(毫秒)
+    latencies: VecDeque<f64>,
+}
+
+impl ChainStats {
+    /// 记录一次执行
+    ///
+    /// # Arguments
+    /// * `success` - 是否执行成功
+    /// * `latency` - 执行耗时
+    pub(crate) fn record(&mut self, success: bool, latency: Duration) {
+        self.metrics.executions += 1;
+        if success {
+            self.metrics.successes += 1;
+        } else {
+            self.metrics.failures += 1;
+        }
+
+        self.latencies.push_back(latency.as_secs_f64() * 1000.0);
+        if self.latencies.len() > LATENCY_WINDOW {
+            self.latencies.pop_front();
+        }
+        self.metrics.avg_latency_ms =
+            self.latencies.iter().sum::<f64>() / self.latencies.len() as f64;
+    }
+
+    /// 当前的统计结果
+    pub(crate) fn metrics(&self) -> ChainMetrics {
+        self.metrics.clone()
+    }
+}
@@ -1,7 +1,32 @@
+mod dead_letter;
+mod executions;
+mod function;
+mod guard;
+mod idempotency;
+mod metrics;
 mod node;
 pub mod rule;
+mod secret;
+mod source;
+mod store;
 mod version;
+mod watch;
 
+pub(crate) use dead_letter::DeadLetterSink;
+pub use dead_letter::{DeadLetter, DeadLetterHandler};
+pub use executions::ExecutionInfo;
+pub(crate) use executions::ExecutionRegistry;
+pub(crate) use function::FnNode;
+pub use function::{AsyncNodeFn, NodeFn};
+pub(crate) use guard::ChainGuard;
+pub use guard::{ChainGuardPolicy, DEFAULT_GUARD_MIN_EXECUTIONS, DEFAULT_GUARD_WINDOW_MS};
+pub use idempotency::IdempotencyCache;
+pub use metrics::ChainMetrics;
+pub(crate) use metrics::ChainStats;
 pub use node::*;
 pub use rule::{DynRuleEngine, RuleEngine};
+pub use secret::{EnvResolver, SecretResolver};
+pub use source::ChannelSource;
+pub use store::{ChainStore, FileChainStore, RedisChainStore};
 pub use version::*;
+pub use watch::ChainWatcher;
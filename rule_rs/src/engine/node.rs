@@ -1,4 +1,4 @@
-use crate::types::{Message, NodeContext, NodeDescriptor, RuleError};
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::error::Error;
@@ -11,6 +11,9 @@ use tokio::sync::RwLock;
 pub trait NodeHandler: Send + Sync + std::fmt::Debug {
     /// 处理消息
     ///
+    /// 返回 `Ok(msg)` 后由引擎按 `msg` 的分支转发到下一个节点;
+    /// 节点已通过 `ctx.send_next` 等自行路由,或调用了 `ctx.mark_routed()` 时不再转发
+    ///
     /// # Arguments
     /// * `ctx` - 节点执行上下文
     /// * `msg` - 输入消息
@@ -97,31 +100,55 @@ impl NodeRegistry {
         }
     }
 
+    /// 使用给定的描述符注册新的节点类型,不构造节点实例
+    ///
+    /// 适用于没有合理默认配置、无法用空配置 `{}` 构造的节点,
+    /// 描述符的 `type_name` 以注册的类型名称为准
+    ///
+    /// # Arguments
+    /// * `type_name` - 节点类型名称
+    /// * `descriptor` - 节点描述符
+    /// * `factory` - 节点工厂函数
+    pub async fn register_with_descriptor(
+        &self,
+        type_name: &str,
+        descriptor: NodeDescriptor,
+        factory: NodeFactory,
+    ) {
+        let mut factories = self.factories.write().await;
+        let mut descriptors = self.descriptors.write().await;
+        descriptors.insert(
+            type_name.to_string(),
+            NodeDescriptor {
+                type_name: type_name.to_string(),
+                ..descriptor
+            },
+        );
+        factories.insert(type_name.to_string(), factory);
+    }
+
     /// 获取所有已注册节点的描述符
     pub async fn get_descriptors(&self) -> Vec<NodeDescriptor> {
         let descriptors = self.descriptors.read().await;
         descriptors.values().cloned().collect()
     }
 
+    /// 获取所有已注册节点的类型名称到节点类型(头/中间/尾)的映射
+    pub async fn get_node_types(&self) -> HashMap<String, NodeType> {
+        let descriptors = self.descriptors.read().await;
+        descriptors
+            .iter()
+            .map(|(type_name, descriptor)| (type_name.clone(), descriptor.node_type.clone()))
+            .collect()
+    }
+
     /// 获取指定节点类型的描述符
     pub async fn get_descriptor(&self, type_name: &str) -> Option<NodeDescriptor> {
-        let factories = self.factories.read().await;
-        if let Some(factory) = factories.get(type_name) {
-            let empty_config = serde_json::Value::Object(serde_json::Map::new());
-            if let Ok(node) = factory(empty_config) {
-                let descriptor = node.get_descriptor();
-                Some(NodeDescriptor {
-                    type_name: type_name.to_string(),
-                    name: descriptor.name,
-                    description: descriptor.description,
-                    node_type: descriptor.node_type,
-                })
-            } else {
-                None
-            }
-        } else {
-            None
-        }
+        let descriptors = self.descriptors.read().await;
+        descriptors.get(type_name).map(|descriptor| NodeDescriptor {
+            type_name: type_name.to_string(),
+            ..descriptor.clone()
+        })
     }
 
     /// 根据节点类型和配置创建节点处理器实例
@@ -131,25 +158,32 @@ impl NodeRegistry {
     /// * `config` - 节点配置
     ///
     /// # Returns
-    /// * `Option<Arc<dyn NodeHandler>>` - 节点处理器实例或None
+    /// * `Result<Arc<dyn NodeHandler>, RuleError>` - 节点处理器实例;
+    ///   类型未注册时返回 `HandlerNotFound`,配置无效时返回 `ConfigError`
     pub async fn create_handler(
         &self,
         type_name: &str,
         config: serde_json::Value,
-    ) -> Option<Arc<dyn NodeHandler>> {
+    ) -> Result<Arc<dyn NodeHandler>, RuleError> {
         let factories = self.factories.read().await;
-        if let Some(factory) = factories.get(type_name) {
-            match factory(config) {
-                Ok(handler) => Some(handler),
-                Err(e) => {
-                    tracing::error!("Failed to create handler for {}: {}", type_name, e);
-                    None
-                }
-            }
-        } else {
-            tracing::error!("No factory found for node type: {}", type_name);
-            None
-        }
+        let factory = factories
+            .get(type_name)
+            .ok_or_else(|| RuleError::HandlerNotFound(type_name.to_string()))?;
+        factory(config)
+            .map_err(|e| RuleError::ConfigError(format!("创建节点 {} 失败: {}", type_name, e)))
+    }
+
+    /// 校验节点配置,使用工厂函数构造一次节点处理器
+    ///
+    /// # Arguments
+    /// * `type_name` - 节点类型名称
+    /// * `config` - 节点配置
+    pub async fn validate_config(
+        &self,
+        type_name: &str,
+        config: serde_json::Value,
+    ) -> Result<(), RuleError> {
+        self.create_handler(type_name, config).await.map(|_| ())
     }
 
     /// 获取指定节点类型的工厂函数
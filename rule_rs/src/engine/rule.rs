@@ -3,37 +3,119 @@ use crate::aop::{
     NodeInterceptor,
 };
 use crate::components::{
-    DelayConfig, DelayNode, FilterConfig, FilterNode, ForkNode, JoinConfig, JoinNode,
-    JsFunctionConfig, JsFunctionNode, LogConfig, LogNode, RestClientConfig, RestClientNode,
-    ScheduleConfig, ScheduleNode, ScriptConfig, ScriptNode, StartConfig, StartNode, SubchainConfig,
-    SubchainNode, SwitchConfig, SwitchNode, TransformConfig, TransformJsConfig, TransformJsNode,
-    TransformNode,
+    remove_chain_state, CalcConfig, CalcNode, ContextConfig, ContextNode, CryptoConfig, CryptoNode,
+    CsvConfig, CsvNode, DateTimeConfig, DateTimeNode, DebounceConfig, DebounceNode, DedupConfig,
+    DedupNode, DelayConfig, DelayNode, EmailConfig, EmailNode, EmitConfig, EmitNode, FileConfig,
+    FileNode, FilterConfig, FilterNode, ForkNode, GraphqlConfig, GraphqlNode, GrpcClientConfig,
+    GrpcClientNode, JoinConfig, JoinNode, JsFunctionConfig, JsFunctionNode, JsonPathConfig,
+    JsonPathNode, LogConfig, LogNode, LookupConfig, LookupNode, MapperConfig, MapperNode,
+    NatsConfig, NatsNode, ParseResponse, PostgresConfig, PostgresNode, RateLimitConfig,
+    RateLimitNode, RestClientConfig, RestClientNode, S3Config, S3Node, ScheduleConfig,
+    ScheduleNode, ScriptConfig, ScriptNode, SelectConfig, SelectNode, SplitArrayConfig,
+    SplitArrayNode, SplitConfig, SplitNode, StartConfig, StartNode, SubchainConfig, SubchainNode,
+    SwitchConfig, SwitchNode, TemplateConfig, TemplateNode, TransformConfig, TransformJsConfig,
+    TransformJsNode, TransformNode, ValidateConfig, ValidateNode, WebSocketConfig, WebSocketNode,
+    WindowConfig, WindowNode,
+};
+use crate::engine::secret::interpolate_config;
+use crate::engine::{
+    AsyncNodeFn, ChainGuard, ChainGuardPolicy, ChainMetrics, ChainStats, ChainStore, DeadLetter,
+    DeadLetterHandler, DeadLetterSink, EnvResolver, ExecutionInfo, ExecutionRegistry, FnNode,
+    IdempotencyCache, NodeFactory, NodeFn, NodeHandler, NodeRegistry, SecretResolver,
+    VersionManager, DEFAULT_GUARD_MIN_EXECUTIONS, DEFAULT_GUARD_WINDOW_MS,
 };
-use crate::engine::{NodeFactory, NodeHandler, NodeRegistry, VersionManager};
 use crate::types::{
-    ExecutionContext, Message, Node, NodeContext, NodeDescriptor, NodeType, RuleChain, RuleError,
+    run_node, AckHandle, CancellationToken, ChainAnalysis, CommonConfig, Connection,
+    ExecutionContext, ExecutionTrace, Message, Node, NodeContext, NodeDescriptor, NodeType,
+    RuleChain, RuleError,
 };
 use async_trait::async_trait;
-use serde_json::json;
-use std::collections::{HashMap, HashSet};
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex, RwLock, Semaphore};
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 pub type DynRuleEngine = Arc<dyn RuleEngineTrait + Send + Sync>;
 
+/// 单个规则链的定时任务,key为节点ID
+type ScheduleHandles = HashMap<Uuid, JoinHandle<()>>;
+
+/// 节点处理器缓存,key为 (规则链ID, 节点ID)
+type HandlerCache = HashMap<(Uuid, Uuid), CachedHandler>;
+
+/// 节点并发限制,key为 (规则链ID, 节点ID),value为 (并发上限, 信号量)
+type NodeLimiters = HashMap<(Uuid, Uuid), (usize, Arc<Semaphore>)>;
+
+/// 加载规则链时构造的节点处理器,记录构造时使用的节点定义
+#[derive(Debug)]
+struct CachedHandler {
+    type_name: String,
+    config: Value,
+    handler: Arc<dyn NodeHandler>,
+}
+
+/// 每个广播主题缓存的消息数量
+const TOPIC_CAPACITY: usize = 1024;
+
+/// 单条消息处理中默认最多执行的节点次数
+const DEFAULT_MAX_HOPS: usize = 10_000;
+
+/// 批量处理消息时默认同时处理的消息数
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 16;
+
 /// 规则引擎特征,定义了规则引擎的核心功能接口
 #[async_trait]
 pub trait RuleEngineTrait: Debug + Send + Sync {
     async fn check_circular_dependency(&self, chain: &RuleChain) -> Result<(), RuleError>;
     async fn load_chain(&self, content: &str) -> Result<Uuid, RuleError>;
+    async fn validate_chain(&self, content: &str) -> Result<(), RuleError>;
+    async fn load_chain_value(&self, value: Value) -> Result<Uuid, RuleError>;
+    async fn load_rule_chain(&self, chain: RuleChain) -> Result<Uuid, RuleError>;
+    async fn update_chain(&self, id: Uuid, content: &str) -> Result<u64, RuleError>;
+    async fn update_chain_value(&self, id: Uuid, value: Value) -> Result<u64, RuleError>;
     async fn add_node_interceptor(&self, interceptor: Arc<dyn NodeInterceptor>);
     async fn add_msg_interceptor(&self, interceptor: Arc<dyn MessageInterceptor>);
     async fn process_msg(&self, chain_id: Uuid, msg: Message) -> Result<Message, RuleError>;
+    async fn process_msg_with_token(
+        &self,
+        chain_id: Uuid,
+        msg: Message,
+        token: CancellationToken,
+    ) -> Result<Message, RuleError>;
+    async fn process_msg_from(
+        &self,
+        chain_id: Uuid,
+        entry_id: Uuid,
+        msg: Message,
+    ) -> Result<Message, RuleError>;
+    async fn process_msg_traced(
+        &self,
+        chain_id: Uuid,
+        msg: Message,
+    ) -> Result<(Message, ExecutionTrace), RuleError>;
+    async fn replay_msg(&self, chain_id: Uuid, msg: Message) -> Result<Message, RuleError>;
+    async fn process_batch(
+        &self,
+        chain_id: Uuid,
+        msgs: Vec<Message>,
+    ) -> Vec<Result<Message, RuleError>>;
+    async fn process_batch_with_concurrency(
+        &self,
+        chain_id: Uuid,
+        msgs: Vec<Message>,
+        concurrency: usize,
+    ) -> Vec<Result<Message, RuleError>>;
     async fn execute_chain(
         &self,
         chain: &RuleChain,
+        entry_id: Option<Uuid>,
         ctx: &mut ExecutionContext,
     ) -> Result<Message, RuleError>;
     async fn execute_node<'a>(
@@ -43,12 +125,42 @@ pub trait RuleEngineTrait: Debug + Send + Sync {
         msg: Message,
     ) -> Result<Message, RuleError>;
     async fn get_current_version(&self) -> u64;
+    async fn get_chain_versions(&self, id: Uuid) -> Vec<(u64, i64)>;
+    async fn chain_metrics(&self, id: Uuid) -> Option<ChainMetrics>;
+    async fn set_chain_guard(
+        &self,
+        id: Uuid,
+        policy: Option<ChainGuardPolicy>,
+    ) -> Result<(), RuleError>;
+    async fn enable_chain(&self, id: Uuid) -> Result<(), RuleError>;
+    async fn is_chain_disabled(&self, id: Uuid) -> bool;
+    async fn rollback_chain(&self, id: Uuid, version: u64) -> Result<(), RuleError>;
     async fn get_registered_components(&self) -> Vec<NodeDescriptor>;
+    async fn component_types(&self) -> HashMap<String, NodeType>;
     async fn get_loaded_chains(&self) -> Vec<Arc<RuleChain>>;
     async fn get_chain(&self, id: Uuid) -> Option<Arc<RuleChain>>;
+    async fn export_chain_dot(&self, id: Uuid) -> Option<String>;
+    async fn export_chain_mermaid(&self, id: Uuid) -> Option<String>;
     async fn remove_chain(&self, id: Uuid) -> Result<(), RuleError>;
+    async fn register_schedule(&self, chain_id: Uuid, node_id: Uuid, handle: JoinHandle<()>);
+    async fn cancel_schedules(&self, chain_id: Uuid);
     async fn register_node_type(&self, type_name: &str, factory: NodeFactory);
+    async fn register_node_type_with_descriptor(
+        &self,
+        type_name: &str,
+        descriptor: NodeDescriptor,
+        factory: NodeFactory,
+    );
+    async fn register_fn(&self, type_name: &str, function: NodeFn);
+    async fn register_async_fn(&self, type_name: &str, function: AsyncNodeFn);
     async fn get_component_descriptor(&self, type_name: &str) -> Option<NodeDescriptor>;
+    async fn shutdown(&self, timeout: Duration) -> Result<(), RuleError>;
+    fn is_shutting_down(&self) -> bool;
+    fn running_executions(&self) -> Vec<ExecutionInfo>;
+    async fn subscribe(&self, topic: &str) -> broadcast::Receiver<Message>;
+    async fn publish(&self, topic: &str, msg: Message) -> usize;
+    fn analyze_chain(&self, chain: &RuleChain) -> ChainAnalysis;
+    async fn flatten_chain(&self, id: Uuid) -> Result<RuleChain, RuleError>;
 }
 
 /// 规则引擎的具体实现
@@ -56,6 +168,10 @@ pub trait RuleEngineTrait: Debug + Send + Sync {
 pub struct RuleEngine {
     /// 存储所有已加载的规则链,key为规则链ID
     pub(crate) chains: Arc<RwLock<HashMap<Uuid, Arc<RuleChain>>>>,
+    /// 节点处理器缓存,在加载、更新规则链时构造,删除规则链时清理
+    handlers: Arc<RwLock<HandlerCache>>,
+    /// 配置了 `max_concurrency` 的节点的信号量,首次执行时创建
+    limiters: Arc<std::sync::Mutex<NodeLimiters>>,
     /// 节点注册表,用于管理所有可用的节点类型
     node_registry: Arc<NodeRegistry>,
     /// 版本管理器,用于管理规则链的版本
@@ -64,6 +180,30 @@ pub struct RuleEngine {
     interceptor_manager: Arc<RwLock<InterceptorManager>>,
     /// 执行计数器,记录每个规则链当前正在执行的实例数
     execution_counters: Arc<RwLock<HashMap<Uuid, Arc<Mutex<usize>>>>>,
+    /// 执行统计,记录每个规则链的执行次数、成功失败次数和平均耗时
+    metrics: Arc<std::sync::Mutex<HashMap<Uuid, ChainStats>>>,
+    /// 规则链的自动禁用状态,配置了禁用策略的规则链首次执行或调用 `set_chain_guard` 时创建
+    guards: Arc<std::sync::Mutex<HashMap<Uuid, ChainGuard>>>,
+    /// 正在处理的消息,用于查询每条消息正在执行的节点
+    executions: Arc<ExecutionRegistry>,
+    /// 定时任务句柄,key为规则链ID,value为节点ID到后台任务的映射
+    schedules: Arc<Mutex<HashMap<Uuid, ScheduleHandles>>>,
+    /// 幂等缓存,通过 `with_idempotency` 启用
+    idempotency: Option<Arc<IdempotencyCache>>,
+    /// 是否正在关闭,关闭后不再接收新消息
+    draining: Arc<AtomicBool>,
+    /// 广播主题,key为主题名称,由 emit 节点发布消息
+    topics: Arc<RwLock<HashMap<String, broadcast::Sender<Message>>>>,
+    /// 加载时是否拒绝包含不可达节点的规则链,默认只记录警告
+    reject_unreachable: bool,
+    /// 节点配置占位符的解析器,key为占位符前缀,默认注册 `env`
+    secret_resolvers: HashMap<String, Arc<dyn SecretResolver>>,
+    /// 单条消息处理中最多执行的节点次数,防止运行时形成的循环无限执行
+    max_hops: usize,
+    /// 规则链持久化存储,通过 `with_store` 启用
+    store: Option<Arc<dyn ChainStore>>,
+    /// 死信处理函数,通过 `with_dead_letter_handler` 启用
+    dead_letter: Option<DeadLetterSink>,
 }
 
 impl RuleEngine {
@@ -73,14 +213,13 @@ impl RuleEngine {
         let registry = node_registry.clone();
 
         // 注册内置组件
-        let factories: Vec<(&str, NodeFactory)> = vec![
+        let factories: Vec<(&str, NodeDescriptor, NodeFactory)> = vec![
             (
                 "log",
+                LogNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
-                        Ok(Arc::new(LogNode::new(LogConfig {
-                            template: "".to_string(),
-                        })) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(LogNode::new(LogConfig::default())) as Arc<dyn NodeHandler>)
                     } else {
                         let config: LogConfig = serde_json::from_value(config)?;
                         Ok(Arc::new(LogNode::new(config)) as Arc<dyn NodeHandler>)
@@ -89,6 +228,7 @@ impl RuleEngine {
             ),
             (
                 "start",
+                StartNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
                         Ok(Arc::new(StartNode::new(StartConfig::default()))
@@ -99,8 +239,21 @@ impl RuleEngine {
                     }
                 }),
             ),
+            (
+                "emit",
+                EmitNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(EmitNode::new(EmitConfig::default())) as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: EmitConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(EmitNode::new(config)) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
             (
                 "delay",
+                DelayNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
                         Ok(Arc::new(DelayNode::new(DelayConfig::default()))
@@ -113,24 +266,75 @@ impl RuleEngine {
             ),
             (
                 "schedule",
+                ScheduleNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
-                        Ok(Arc::new(ScheduleNode::new(ScheduleConfig::default()))
+                        Ok(Arc::new(ScheduleNode::new(ScheduleConfig::default())?)
                             as Arc<dyn NodeHandler>)
                     } else {
                         let config: ScheduleConfig = serde_json::from_value(config)?;
-                        Ok(Arc::new(ScheduleNode::new(config)) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(ScheduleNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "dedup",
+                DedupNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(DedupNode::new(DedupConfig::default()))
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: DedupConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(DedupNode::new(config)) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "debounce",
+                DebounceNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(DebounceNode::new(DebounceConfig::default()))
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: DebounceConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(DebounceNode::new(config)) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "rate_limit",
+                RateLimitNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(RateLimitNode::new(RateLimitConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: RateLimitConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(RateLimitNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "file",
+                FileNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(FileNode::new(FileConfig::default())) as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: FileConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(FileNode::new(config)) as Arc<dyn NodeHandler>)
                     }
                 }),
             ),
             (
                 "filter",
+                FilterNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
-                        Ok(Arc::new(FilterNode::new(FilterConfig {
-                            condition: "true".to_string(),
-                            js_script: None,
-                        })) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(FilterNode::new(FilterConfig::default()))
+                            as Arc<dyn NodeHandler>)
                     } else {
                         let config: FilterConfig = serde_json::from_value(config)?;
                         Ok(Arc::new(FilterNode::new(config)) as Arc<dyn NodeHandler>)
@@ -139,11 +343,11 @@ impl RuleEngine {
             ),
             (
                 "transform",
+                TransformNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
-                        Ok(Arc::new(TransformNode::new(TransformConfig {
-                            template: json!({}),
-                        })) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(TransformNode::new(TransformConfig::default()))
+                            as Arc<dyn NodeHandler>)
                     } else {
                         let config: TransformConfig = serde_json::from_value(config)?;
                         Ok(Arc::new(TransformNode::new(config)) as Arc<dyn NodeHandler>)
@@ -152,11 +356,11 @@ impl RuleEngine {
             ),
             (
                 "transform_js",
+                TransformJsNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
-                        Ok(Arc::new(TransformJsNode::new(TransformJsConfig {
-                            script: "return msg;".to_string(),
-                        })) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(TransformJsNode::new(TransformJsConfig::default()))
+                            as Arc<dyn NodeHandler>)
                     } else {
                         let config: TransformJsConfig = serde_json::from_value(config)?;
                         Ok(Arc::new(TransformJsNode::new(config)) as Arc<dyn NodeHandler>)
@@ -165,12 +369,11 @@ impl RuleEngine {
             ),
             (
                 "script",
+                ScriptNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
-                        Ok(Arc::new(ScriptNode::new(ScriptConfig {
-                            script: "return msg;".to_string(),
-                            output_type: None,
-                        })) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(ScriptNode::new(ScriptConfig::default()))
+                            as Arc<dyn NodeHandler>)
                     } else {
                         let config: ScriptConfig = serde_json::from_value(config)?;
                         Ok(Arc::new(ScriptNode::new(config)) as Arc<dyn NodeHandler>)
@@ -179,38 +382,298 @@ impl RuleEngine {
             ),
             (
                 "switch",
+                SwitchNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
-                        Ok(Arc::new(SwitchNode::new(SwitchConfig {
-                            cases: Vec::new(),
-                            default_next: None,
-                        })) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(SwitchNode::new(SwitchConfig::default())?)
+                            as Arc<dyn NodeHandler>)
                     } else {
                         let config: SwitchConfig = serde_json::from_value(config)?;
-                        Ok(Arc::new(SwitchNode::new(config)) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(SwitchNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "split",
+                SplitNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(SplitNode::new(SplitConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: SplitConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(SplitNode::new(config)?) as Arc<dyn NodeHandler>)
                     }
                 }),
             ),
             (
                 "rest_client",
+                RestClientNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
                         Ok(Arc::new(RestClientNode::new(RestClientConfig {
                             url: "http://localhost".to_string(),
                             method: "GET".to_string(),
                             headers: None,
+                            body_template: None,
+                            parse_response: ParseResponse::Auto,
                             timeout_ms: None,
+                            retry: None,
                             success_branch: None,
                             error_branch: None,
-                        })) as Arc<dyn NodeHandler>)
+                        })?) as Arc<dyn NodeHandler>)
                     } else {
                         let config: RestClientConfig = serde_json::from_value(config)?;
-                        Ok(Arc::new(RestClientNode::new(config)) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(RestClientNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "graphql",
+                GraphqlNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(GraphqlNode::new(GraphqlConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: GraphqlConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(GraphqlNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "grpc_client",
+                GrpcClientNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(GrpcClientNode::new(GrpcClientConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: GrpcClientConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(GrpcClientNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "postgres",
+                PostgresNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(PostgresNode::new(PostgresConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: PostgresConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(PostgresNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "nats",
+                NatsNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(NatsNode::new(NatsConfig::default())?) as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: NatsConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(NatsNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "crypto",
+                CryptoNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(CryptoNode::new(CryptoConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: CryptoConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(CryptoNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "lookup",
+                LookupNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(LookupNode::new(LookupConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: LookupConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(LookupNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "websocket",
+                WebSocketNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(WebSocketNode::new(WebSocketConfig::default()))
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: WebSocketConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(WebSocketNode::new(config)) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "csv",
+                CsvNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(CsvNode::new(CsvConfig::default())?) as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: CsvConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(CsvNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "template",
+                TemplateNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(TemplateNode::new(TemplateConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: TemplateConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(TemplateNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "email",
+                EmailNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(EmailNode::new(EmailConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: EmailConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(EmailNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "s3",
+                S3Node::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(S3Node::new(S3Config::default())?) as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: S3Config = serde_json::from_value(config)?;
+                        Ok(Arc::new(S3Node::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "context",
+                ContextNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(ContextNode::new(ContextConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: ContextConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(ContextNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "validate",
+                ValidateNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(ValidateNode::new(ValidateConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: ValidateConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(ValidateNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "jsonpath",
+                JsonPathNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(JsonPathNode::new(JsonPathConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: JsonPathConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(JsonPathNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "mapper",
+                MapperNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(MapperNode::new(MapperConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: MapperConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(MapperNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "calc",
+                CalcNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(CalcNode::new(CalcConfig::default())?) as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: CalcConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(CalcNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "datetime",
+                DateTimeNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(DateTimeNode::new(DateTimeConfig::default())?)
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: DateTimeConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(DateTimeNode::new(config)?) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "window",
+                WindowNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(WindowNode::new(WindowConfig::default()))
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: WindowConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(WindowNode::new(config)) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
+            (
+                "split_array",
+                SplitArrayNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(SplitArrayNode::new(SplitArrayConfig::default()))
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: SplitArrayConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(SplitArrayNode::new(config)) as Arc<dyn NodeHandler>)
                     }
                 }),
             ),
             (
                 "subchain",
+                SubchainNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
                         Ok(Arc::new(SubchainNode::new(SubchainConfig {
@@ -224,26 +687,25 @@ impl RuleEngine {
             ),
             (
                 "js_function",
+                JsFunctionNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
-                        Ok(Arc::new(JsFunctionNode::new(JsFunctionConfig {
-                            functions: HashMap::new(),
-                            main: "main".to_string(),
-                            chain_id: String::new(),
-                            node_id: String::new(),
-                        })) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(JsFunctionNode::new(JsFunctionConfig::default())?)
+                            as Arc<dyn NodeHandler>)
                     } else {
                         let config: JsFunctionConfig = serde_json::from_value(config)?;
-                        Ok(Arc::new(JsFunctionNode::new(config)) as Arc<dyn NodeHandler>)
+                        Ok(Arc::new(JsFunctionNode::new(config)?) as Arc<dyn NodeHandler>)
                     }
                 }),
             ),
             (
                 "fork",
+                ForkNode::descriptor(),
                 Arc::new(|_config| Ok(Arc::new(ForkNode::new()) as Arc<dyn NodeHandler>)),
             ),
             (
                 "join",
+                JoinNode::descriptor(),
                 Arc::new(|config| {
                     if config.is_object() && config.as_object().unwrap().is_empty() {
                         Ok(Arc::new(JoinNode::new(JoinConfig {})) as Arc<dyn NodeHandler>)
@@ -253,26 +715,58 @@ impl RuleEngine {
                     }
                 }),
             ),
+            (
+                "select",
+                SelectNode::descriptor(),
+                Arc::new(|config| {
+                    if config.is_object() && config.as_object().unwrap().is_empty() {
+                        Ok(Arc::new(SelectNode::new(SelectConfig::default()))
+                            as Arc<dyn NodeHandler>)
+                    } else {
+                        let config: SelectConfig = serde_json::from_value(config)?;
+                        Ok(Arc::new(SelectNode::new(config)) as Arc<dyn NodeHandler>)
+                    }
+                }),
+            ),
         ];
 
-        // 直接注册所有工厂
-        for (type_name, factory) in factories {
-            registry.register(type_name, factory).await;
+        // 内置组件直接提供描述符,注册时不构造节点,避免创建客户端等副作用
+        for (type_name, descriptor, factory) in factories {
+            registry
+                .register_with_descriptor(type_name, descriptor, factory)
+                .await;
         }
 
         let engine = Self {
             chains: Arc::new(RwLock::new(HashMap::new())),
+            handlers: Arc::new(RwLock::new(HashMap::new())),
+            limiters: Arc::default(),
             node_registry,
             version_manager: Arc::new(VersionManager::new()),
             interceptor_manager: Arc::new(RwLock::new(InterceptorManager::new())),
             execution_counters: Arc::new(RwLock::new(HashMap::new())),
+            metrics: Arc::default(),
+            guards: Arc::default(),
+            executions: Arc::default(),
+            schedules: Arc::new(Mutex::new(HashMap::new())),
+            idempotency: None,
+            draining: Arc::new(AtomicBool::new(false)),
+            topics: Arc::new(RwLock::new(HashMap::new())),
+            reject_unreachable: false,
+            secret_resolvers: HashMap::from([(
+                "env".to_string(),
+                Arc::new(EnvResolver) as Arc<dyn SecretResolver>,
+            )]),
+            max_hops: DEFAULT_MAX_HOPS,
+            store: None,
+            dead_letter: None,
         };
 
         // 注册默认拦截器
         {
             let mut manager = engine.interceptor_manager.write().await;
             // 注册节点日志拦截器
-            manager.register_node_interceptor(Arc::new(LoggingInterceptor));
+            manager.set_logging_interceptor(Arc::new(LoggingInterceptor::new()));
             // 注册消息日志拦截器
             manager.register_msg_interceptor(Arc::new(MessageLoggingInterceptor));
         }
@@ -280,6 +774,448 @@ impl RuleEngine {
         engine
     }
 
+    /// 启用消息幂等检查
+    ///
+    /// 在有效期内重复处理同一规则链的同一消息ID时,返回首次处理的结果;
+    /// 首次处理尚未完成时返回 `RuleError::DuplicateMessage`。
+    /// 规则链可以通过 `"idempotent": false` 关闭检查
+    ///
+    /// # Arguments
+    /// * `ttl` - 已处理消息ID的保留时长
+    pub fn with_idempotency(mut self, ttl: Duration) -> Self {
+        self.idempotency = Some(IdempotencyCache::new(ttl));
+        self
+    }
+
+    /// 替换默认的节点日志拦截器
+    ///
+    /// 默认的日志拦截器记录每条消息经过的每个节点,生产环境中可以用
+    /// [`LoggingInterceptor::builder`] 设置采样率或节点类型过滤后替换
+    ///
+    /// # Arguments
+    /// * `interceptor` - 新的日志拦截器
+    pub async fn with_logging_interceptor(self, interceptor: LoggingInterceptor) -> Self {
+        self.interceptor_manager
+            .write()
+            .await
+            .set_logging_interceptor(Arc::new(interceptor));
+        self
+    }
+
+    /// 设置加载时是否拒绝包含不可达节点的规则链
+    ///
+    /// 默认只记录警告,设置为 `true` 后返回配置错误
+    pub fn with_reject_unreachable(mut self, reject: bool) -> Self {
+        self.reject_unreachable = reject;
+        self
+    }
+
+    /// 设置单条消息处理中最多执行的节点次数
+    ///
+    /// 计数包含并行分支和子规则链中执行的节点,超出时返回 `RuleError::MaxHopsExceeded`,
+    /// 默认为 10000
+    pub fn with_max_hops(mut self, max_hops: usize) -> Self {
+        self.max_hops = max_hops;
+        self
+    }
+
+    /// 注册节点配置占位符的解析器
+    ///
+    /// 加载规则链时节点配置中的 `${<scheme>:<name>}` 由对应前缀的解析器替换,
+    /// 已注册的前缀会被覆盖
+    ///
+    /// # Arguments
+    /// * `scheme` - 占位符前缀,例如 `vault`
+    /// * `resolver` - 解析器实例
+    pub fn with_secret_resolver(mut self, scheme: &str, resolver: Arc<dyn SecretResolver>) -> Self {
+        self.secret_resolvers.insert(scheme.to_string(), resolver);
+        self
+    }
+
+    /// 启用规则链持久化存储,并加载存储中已保存的规则链
+    ///
+    /// 启用后加载、更新、回滚和删除规则链时同步写入存储,写入失败时操作返回错误且不生效。
+    /// 存储中无法加载的规则链会记录错误并跳过
+    ///
+    /// # Arguments
+    /// * `store` - 存储实例,例如 `FileChainStore` 或 `RedisChainStore`
+    pub async fn with_store(mut self, store: Arc<dyn ChainStore>) -> Result<Self, RuleError> {
+        // 先加载已保存的规则链再启用写入,避免重复写回存储
+        for chain in store.load_all().await? {
+            let id = chain.id;
+            if let Err(e) = self.load_rule_chain(chain).await {
+                tracing::error!("从存储加载规则链 {} 失败: {}", id, e);
+            }
+        }
+        self.store = Some(store);
+        Ok(self)
+    }
+
+    /// 设置死信处理函数
+    ///
+    /// 规则链执行失败且错误未被 `error_branch` 处理时,处理函数收到原始消息、错误以及失败的节点,
+    /// 可用于记录和重放失败的消息。执行被取消,以及规则链开始执行前的错误(例如规则链不存在、
+    /// 引擎正在关闭)不会产生死信
+    ///
+    /// # Arguments
+    /// * `handler` - 死信处理函数
+    pub fn with_dead_letter_handler(mut self, handler: DeadLetterHandler) -> Self {
+        self.dead_letter = Some(DeadLetterSink::new(handler));
+        self
+    }
+
+    /// 注册函数节点,所有节点实例共享同一个函数
+    async fn register_function_node(&self, node: FnNode) {
+        let descriptor = node.get_descriptor();
+        let type_name = descriptor.type_name.clone();
+        self.node_registry
+            .register_with_descriptor(
+                &type_name,
+                descriptor,
+                Arc::new(move |_| Ok(Arc::new(node.clone()) as Arc<dyn NodeHandler>)),
+            )
+            .await;
+    }
+
+    /// 从入口节点开始处理消息,`replay` 为 true 时跳过幂等检查
+    ///
+    /// 消息附带确认句柄时,处理结束后通过句柄回报结果
+    async fn process(
+        &self,
+        chain_id: Uuid,
+        entry_id: Option<Uuid>,
+        mut ctx: ExecutionContext,
+        replay: bool,
+    ) -> Result<Message, RuleError> {
+        let ack = ctx.msg.ack.take();
+        let result = self.process_root(chain_id, entry_id, ctx, replay).await;
+        settle_ack(ack, result)
+    }
+
+    /// 在根规则链中处理消息
+    async fn process_root(
+        &self,
+        chain_id: Uuid,
+        entry_id: Option<Uuid>,
+        mut ctx: ExecutionContext,
+        replay: bool,
+    ) -> Result<Message, RuleError> {
+        // 关闭中的引擎不再接收新消息
+        if self.is_shutting_down() {
+            return Err(RuleError::ShuttingDown);
+        }
+
+        let manager = self.interceptor_manager.read().await;
+
+        // 消息处理前拦截
+        manager.before_process(&ctx.msg).await?;
+
+        // 查找指定的规则链
+        let chain = self
+            .get_chain(chain_id)
+            .await
+            .ok_or(RuleError::ChainNotFound(chain_id))?;
+
+        // 检查是否为根规则链
+        chain.check_root()?;
+
+        self.process_in_chain(&manager, &chain, entry_id, &mut ctx, replay)
+            .await
+    }
+
+    /// 在已找到的规则链中处理上下文中的消息,消息已通过处理前拦截
+    async fn process_in_chain(
+        &self,
+        manager: &InterceptorManager,
+        chain: &RuleChain,
+        entry_id: Option<Uuid>,
+        ctx: &mut ExecutionContext,
+        replay: bool,
+    ) -> Result<Message, RuleError> {
+        let chain_id = chain.id;
+        let msg = ctx.msg.clone();
+        chain.check_input_type(&msg)?;
+
+        // 幂等检查,已处理过的消息直接返回缓存的结果
+        let idempotency = self
+            .idempotency
+            .as_ref()
+            .filter(|_| chain.idempotent && !replay);
+        if let Some(cache) = idempotency {
+            if let Some(result) = cache.begin(chain_id, msg.id).await? {
+                return Ok(result);
+            }
+        }
+
+        // 执行规则链,执行期间登记为正在处理的消息
+        let running = self.executions.register(chain_id, msg.id, ctx.progress());
+        let result = self.execute_chain(chain, entry_id, ctx).await;
+        drop(running);
+        if let Some(cache) = idempotency {
+            cache.complete(chain_id, msg.id, &result).await;
+        }
+        if let (Err(e), Some(sink)) = (&result, &self.dead_letter) {
+            if !matches!(e, RuleError::Cancelled) {
+                sink.send(DeadLetter {
+                    chain_id,
+                    node_id: ctx.failed_node(),
+                    msg: msg.clone(),
+                    error: e.clone(),
+                });
+            }
+        }
+        let result = result?;
+        if let Some(output_type) = &chain.metadata.output_type {
+            if *output_type != result.msg_type {
+                tracing::warn!(
+                    "规则链 {} 声明输出 {} 类型的消息, 实际输出 {}",
+                    chain_id,
+                    output_type,
+                    result.msg_type
+                );
+            }
+        }
+
+        // 消息处理后拦截
+        manager.after_process(&msg).await?;
+
+        Ok(result)
+    }
+
+    /// 将规则链定义写入持久化存储,未启用存储时忽略
+    async fn persist(&self, definition: &RuleChain) -> Result<(), RuleError> {
+        match &self.store {
+            Some(store) => store.save(definition).await,
+            None => Ok(()),
+        }
+    }
+
+    /// 为规则链的所有节点构造处理器
+    async fn build_handlers(&self, chain: &RuleChain) -> Result<HandlerCache, RuleError> {
+        let mut handlers = HashMap::new();
+        for node in &chain.nodes {
+            let handler = self
+                .node_registry
+                .create_handler(&node.type_name, node.config.clone())
+                .await?;
+            handlers.insert(
+                (chain.id, node.id),
+                CachedHandler {
+                    type_name: node.type_name.clone(),
+                    config: node.config.clone(),
+                    handler,
+                },
+            );
+        }
+        Ok(handlers)
+    }
+
+    /// 用新构造的处理器替换规则链原有的缓存
+    async fn store_handlers(&self, chain_id: Uuid, handlers: HandlerCache) {
+        // 保留仍存在的节点的信号量,更新前启动的执行与新的执行共用并发上限
+        self.limiters
+            .lock()
+            .unwrap()
+            .retain(|key, _| key.0 != chain_id || handlers.contains_key(key));
+
+        let mut cache = self.handlers.write().await;
+        cache.retain(|(id, _), _| *id != chain_id);
+        cache.extend(handlers);
+    }
+
+    /// 获取节点的并发限制信号量,并发上限变化时重新创建
+    ///
+    /// # Arguments
+    /// * `node` - 节点
+    /// * `limit` - 并发上限
+    fn node_limiter(&self, node: &Node, limit: usize) -> Arc<Semaphore> {
+        let mut limiters = self.limiters.lock().unwrap();
+        let entry = limiters
+            .entry((node.chain_id, node.id))
+            .or_insert_with(|| (limit, Arc::new(Semaphore::new(limit))));
+        if entry.0 != limit {
+            *entry = (limit, Arc::new(Semaphore::new(limit)));
+        }
+        entry.1.clone()
+    }
+
+    /// 获取节点处理器,优先使用加载时缓存的实例
+    ///
+    /// 缓存与节点定义不一致时(例如更新前启动的实例仍按旧定义执行)重新构造
+    async fn get_handler(&self, node: &Node) -> Result<Arc<dyn NodeHandler>, RuleError> {
+        if let Some(cached) = self.handlers.read().await.get(&(node.chain_id, node.id)) {
+            if cached.type_name == node.type_name && cached.config == node.config {
+                return Ok(cached.handler.clone());
+            }
+        }
+        self.node_registry
+            .create_handler(&node.type_name, node.config.clone())
+            .await
+    }
+
+    /// 节点执行失败时按配置的 `error_branch` 路由,错误信息写入 metadata.error
+    ///
+    /// 未配置错误分支、没有对应连接、执行已取消或节点已自行路由时返回原错误
+    async fn route_error(
+        &self,
+        node: &Node,
+        ctx: &NodeContext<'_>,
+        common: Option<&CommonConfig>,
+        mut msg: Message,
+        error: RuleError,
+    ) -> Result<Message, RuleError> {
+        if matches!(error, RuleError::Cancelled) || ctx.is_routed() {
+            return Err(error);
+        }
+        let branch = match common.and_then(|config| config.error_branch.clone()) {
+            Some(branch) => branch,
+            None => return Err(error),
+        };
+        if ctx.get_next_connections(&branch).await?.is_empty() {
+            return Err(error);
+        }
+
+        tracing::warn!(
+            "节点 {} 执行失败, 路由到错误分支 {}: {}",
+            node.id,
+            branch,
+            error
+        );
+        msg.set_meta("error", error.to_string());
+        msg.set_meta("branch_name", branch);
+        Ok(msg)
+    }
+
+    /// 获取规则链中各节点的类型,用于导出图形;无法创建处理器的节点不包含在结果中
+    async fn node_types(&self, chain: &RuleChain) -> HashMap<Uuid, NodeType> {
+        let mut node_types = HashMap::new();
+        for node in &chain.nodes {
+            if let Ok(handler) = self.get_handler(node).await {
+                node_types.insert(node.id, handler.get_descriptor().node_type);
+            }
+        }
+        node_types
+    }
+
+    /// 替换所有节点配置中的占位符,在节点处理器创建之前执行
+    ///
+    /// 加载后的规则链保存替换后的配置
+    async fn resolve_secrets(&self, chain: &mut RuleChain) -> Result<(), RuleError> {
+        for node in &mut chain.nodes {
+            interpolate_config(node, &self.secret_resolvers).await?;
+        }
+        Ok(())
+    }
+
+    /// 校验规则链: 起始节点、节点类型约束及循环依赖
+    ///
+    /// 起始节点可以是头节点,也可以是没有入向连接的任意已注册节点,
+    /// 便于自定义的消息源组件作为规则链入口
+    async fn check_chain(&self, chain: &RuleChain) -> Result<(), RuleError> {
+        let start_node = chain
+            .get_start_node(None)?
+            .ok_or_else(|| RuleError::ConfigError("规则链没有起始节点".to_string()))?;
+
+        let node_type = RuleChain::get_node_type(self, start_node).await?;
+        let has_incoming = chain
+            .connections
+            .iter()
+            .any(|conn| conn.to_id == start_node.id);
+        if node_type != NodeType::Head && has_incoming {
+            return Err(RuleError::ConfigError(format!(
+                "起始节点 {} 不能被其他节点指向",
+                start_node.type_name
+            )));
+        }
+        chain.validate(self).await?;
+        self.check_reachability(chain)?;
+
+        // 启用循环依赖检查
+        self.check_circular_dependency(chain).await
+    }
+
+    /// 检查规则链中不可达的节点,根据配置记录警告或返回错误
+    fn check_reachability(&self, chain: &RuleChain) -> Result<(), RuleError> {
+        let analysis = chain.analyze();
+        if analysis.unreachable_nodes.is_empty() {
+            return Ok(());
+        }
+
+        let ids = analysis
+            .unreachable_nodes
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.reject_unreachable {
+            return Err(RuleError::ConfigError(format!(
+                "规则链存在不可达的节点: {}",
+                ids
+            )));
+        }
+        tracing::warn!("规则链 {} 存在不可达的节点: {}", chain.id, ids);
+        Ok(())
+    }
+
+    /// 替换已加载的规则链,返回新的版本号
+    async fn replace_chain(&self, id: Uuid, chain: RuleChain) -> Result<u64, RuleError> {
+        let mut definition = chain;
+        definition.assign_node_chain_ids();
+        if definition.id != id {
+            return Err(RuleError::ConfigError(format!(
+                "规则链ID不匹配: 期望 {}, 实际 {}",
+                id, definition.id
+            )));
+        }
+
+        let current = self
+            .get_chain(id)
+            .await
+            .ok_or(RuleError::ChainNotFound(id))?;
+
+        let mut chain = definition.clone();
+        self.resolve_secrets(&mut chain).await?;
+        self.check_chain(&chain).await?;
+
+        let version = self.version_manager.create_version(&definition);
+        chain.metadata.version = version.version;
+        chain.metadata.updated_at = version.timestamp;
+        let handlers = self.build_handlers(&chain).await?;
+
+        definition.metadata.version = version.version;
+        definition.metadata.updated_at = version.timestamp;
+        definition.metadata.created_at = current.metadata.created_at;
+        self.persist(&definition).await?;
+
+        // 持有写锁完成检查和替换,保证更新的原子性
+        let mut chains = self.chains.write().await;
+        let old = chains.get(&id).ok_or(RuleError::ChainNotFound(id))?;
+        chain.metadata.created_at = old.metadata.created_at;
+        chains.insert(id, Arc::new(chain));
+        self.store_handlers(id, handlers).await;
+
+        Ok(version.version)
+    }
+
+    /// 按禁用策略记录一次执行结果,未配置策略时忽略
+    fn record_guard(&self, chain: &RuleChain, success: bool) {
+        let mut guards = self.guards.lock().unwrap();
+        let explicit = guards.get(&chain.id).and_then(|guard| guard.policy.clone());
+        let Some(policy) = explicit.or_else(|| chain.guard_policy()) else {
+            return;
+        };
+        let guard = guards.entry(chain.id).or_default();
+        if guard.record(&policy, success) {
+            tracing::warn!(
+                "规则链 {} 在 {}ms 内的错误率 {:.2} 超过上限 {}, 已自动禁用",
+                chain.id,
+                policy.window.as_millis(),
+                guard.error_rate(),
+                policy.max_error_rate
+            );
+        }
+    }
+
     /// 增加规则链的执行计数
     async fn increment_counter(&self, chain_id: Uuid) {
         let counter = {
@@ -300,6 +1236,140 @@ impl RuleEngine {
             *count = count.saturating_sub(1);
         }
     }
+
+    /// 已加载规则链的定义,节点配置中的占位符未替换
+    async fn definition(&self, id: Uuid) -> Option<RuleChain> {
+        let chain = self.get_chain(id).await?;
+        Some(
+            self.version_manager
+                .get_version(id, chain.metadata.version)
+                .map(|version| version.chain)
+                .unwrap_or_else(|| (*chain).clone()),
+        )
+    }
+
+    /// 展开规则链中的子规则链节点,返回展开后的节点和连接
+    ///
+    /// `stack` 为正在展开的规则链,子规则链已在其中时返回循环依赖错误。
+    /// 子规则链的节点使用新的ID;父规则链中指向子规则链节点的连接改为指向子规则链的入口节点,
+    /// 从子规则链节点出发的连接改为从子规则链的每个末端节点出发
+    async fn flatten_nodes(
+        &self,
+        chain: &RuleChain,
+        stack: &mut Vec<Uuid>,
+    ) -> Result<(Vec<Node>, Vec<Connection>), RuleError> {
+        // 子规则链节点ID -> (入口节点ID, 末端节点ID)
+        let mut spliced: HashMap<Uuid, (Uuid, Vec<Uuid>)> = HashMap::new();
+        let mut nodes = Vec::new();
+        let mut connections = Vec::new();
+
+        for node in &chain.nodes {
+            if node.type_name != "subchain" {
+                nodes.push(node.clone());
+                continue;
+            }
+
+            let config: SubchainConfig = serde_json::from_value(node.config.clone())
+                .map_err(|e| RuleError::ConfigError(format!("节点 {} 配置无效: {}", node.id, e)))?;
+            if stack.contains(&config.chain_id) {
+                let chain_names = stack
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                return Err(RuleError::CircularDependency(format!(
+                    "检测到规则链循环依赖: {} -> {}",
+                    chain_names, config.chain_id
+                )));
+            }
+            let subchain = self
+                .definition(config.chain_id)
+                .await
+                .ok_or(RuleError::ChainNotFound(config.chain_id))?;
+
+            stack.push(subchain.id);
+            let (mut sub_nodes, mut sub_conns) =
+                Box::pin(self.flatten_nodes(&subchain, stack)).await?;
+            stack.pop();
+
+            let ids: HashMap<Uuid, Uuid> = sub_nodes
+                .iter()
+                .map(|sub_node| (sub_node.id, Uuid::new_v4()))
+                .collect();
+            for sub_node in &mut sub_nodes {
+                sub_node.id = ids[&sub_node.id];
+                sub_node.layout.x += node.layout.x;
+                sub_node.layout.y += node.layout.y;
+            }
+            for conn in &mut sub_conns {
+                conn.from_id = ids[&conn.from_id];
+                conn.to_id = ids[&conn.to_id];
+            }
+
+            // 头节点不能被指向: 子规则链节点有入向连接时去掉只做转发的开始节点,
+            // 未指定分支的消息走开始节点的第一个连接,因此入口为该连接的目标节点
+            let entry = sub_nodes
+                .first()
+                .ok_or_else(|| RuleError::ConfigError("Empty rule chain".to_string()))?;
+            let has_incoming = chain.connections.iter().any(|conn| conn.to_id == node.id);
+            let entry_type = self
+                .node_registry
+                .get_descriptor(&entry.type_name)
+                .await
+                .map(|descriptor| descriptor.node_type);
+            if has_incoming && entry_type == Some(NodeType::Head) {
+                if entry.type_name != "start" {
+                    return Err(RuleError::ConfigError(format!(
+                        "子规则链 {} 的起始节点 {} 是头节点,无法展开",
+                        subchain.id, entry.type_name
+                    )));
+                }
+                let start_id = entry.id;
+                let next_id = sub_conns
+                    .iter()
+                    .find(|conn| conn.from_id == start_id)
+                    .map(|conn| conn.to_id)
+                    .ok_or_else(|| {
+                        RuleError::ConfigError(format!(
+                            "子规则链 {} 只有开始节点,无法展开",
+                            subchain.id
+                        ))
+                    })?;
+                sub_nodes.retain(|sub_node| sub_node.id != start_id);
+                sub_conns.retain(|conn| conn.from_id != start_id);
+                // 入口节点放在最前面,嵌套展开时外层按第一个节点确定入口
+                let index = sub_nodes.iter().position(|n| n.id == next_id).unwrap();
+                let next = sub_nodes.remove(index);
+                sub_nodes.insert(0, next);
+            }
+
+            let entry = sub_nodes[0].id;
+            let terminals = sub_nodes
+                .iter()
+                .filter(|sub_node| !sub_conns.iter().any(|conn| conn.from_id == sub_node.id))
+                .map(|sub_node| sub_node.id)
+                .collect();
+            spliced.insert(node.id, (entry, terminals));
+            nodes.extend(sub_nodes);
+            connections.extend(sub_conns);
+        }
+
+        for conn in &chain.connections {
+            let to_id = spliced
+                .get(&conn.to_id)
+                .map_or(conn.to_id, |(entry, _)| *entry);
+            let from_ids = match spliced.get(&conn.from_id) {
+                Some((_, terminals)) => terminals.clone(),
+                None => vec![conn.from_id],
+            };
+            connections.extend(from_ids.into_iter().map(|from_id| Connection {
+                from_id,
+                to_id,
+                type_name: conn.type_name.clone(),
+            }));
+        }
+        Ok((nodes, connections))
+    }
 }
 
 #[async_trait]
@@ -310,6 +1380,12 @@ impl RuleEngineTrait for RuleEngine {
     async fn get_registered_components(&self) -> Vec<NodeDescriptor> {
         self.node_registry.get_descriptors().await
     }
+    /// 获取所有已注册节点的类型名称到节点类型(头/中间/尾)的映射,用于离线检查规则链
+    ///
+    /// 节点类型取自空配置构造的描述符,随配置变化的节点(例如 `file` 读模式为头节点)返回默认配置下的类型
+    async fn component_types(&self) -> HashMap<String, NodeType> {
+        self.node_registry.get_node_types().await
+    }
     /// 检查规则链是否存在循环依赖(优化版本)
     async fn check_circular_dependency(&self, chain: &RuleChain) -> Result<(), RuleError> {
         let mut visited = HashSet::new();
@@ -422,42 +1498,78 @@ impl RuleEngineTrait for RuleEngine {
         let chain: RuleChain =
             serde_json::from_str(content).map_err(|e| RuleError::ConfigError(e.to_string()))?;
 
-        let start_node = chain
-            .get_start_node()?
-            .ok_or_else(|| RuleError::ConfigError("规则链没有起始节点".to_string()))?;
+        self.load_rule_chain(chain).await
+    }
 
-        let descriptor = self
-            .get_component_descriptor(&start_node.type_name)
-            .await
-            .ok_or_else(|| {
-                RuleError::ConfigError(format!("未找到节点类型: {}", start_node.type_name))
-            })?;
+    /// 只校验规则链定义而不加载
+    ///
+    /// 执行与 `load_chain` 相同的检查: 解析定义、替换配置占位符、头尾节点和连接规则、
+    /// 不可达节点、循环依赖以及每个节点的配置,但不保存规则链也不创建新版本
+    async fn validate_chain(&self, content: &str) -> Result<(), RuleError> {
+        let mut chain: RuleChain =
+            serde_json::from_str(content).map_err(|e| RuleError::ConfigError(e.to_string()))?;
+        chain.assign_node_chain_ids();
+        self.resolve_secrets(&mut chain).await?;
+        self.check_chain(&chain).await?;
+        self.build_handlers(&chain).await?;
+        Ok(())
+    }
 
-        let node_type = descriptor.node_type;
-        if node_type != NodeType::Head {
-            return Err(RuleError::ConfigError(
-                "规则链必须以header节点开始".to_string(),
-            ));
-        }
-        chain.validate(self).await?;
+    /// 从JSON值加载规则链,调用方已持有 `Value` 时无需再序列化为字符串
+    async fn load_chain_value(&self, value: Value) -> Result<Uuid, RuleError> {
+        let chain: RuleChain =
+            serde_json::from_value(value).map_err(|e| RuleError::ConfigError(e.to_string()))?;
 
-        // 启用循环依赖检查
-        self.check_circular_dependency(&chain).await?;
+        self.load_rule_chain(chain).await
+    }
+
+    /// 直接加载规则链定义,例如由 `RuleChainBuilder` 构造的规则链
+    async fn load_rule_chain(&self, chain: RuleChain) -> Result<Uuid, RuleError> {
+        // 版本快照和持久化存储保存占位符替换前的定义,不包含密钥
+        let mut definition = chain;
+        definition.assign_node_chain_ids();
+        let mut chain = definition.clone();
+        self.resolve_secrets(&mut chain).await?;
+        self.check_chain(&chain).await?;
 
         // 创建新版本
-        let version = self.version_manager.create_version(&chain);
+        let version = self.version_manager.create_version(&definition);
 
         // 更新规则链元数据
-        let mut chain = chain;
         chain.metadata.version = version.version;
         chain.metadata.updated_at = version.timestamp;
 
         let id = chain.id;
-        self.chains.write().await.insert(id, Arc::new(chain));
+        let handlers = self.build_handlers(&chain).await?;
+
+        definition.metadata.version = version.version;
+        definition.metadata.updated_at = version.timestamp;
+        self.persist(&definition).await?;
+        let mut chains = self.chains.write().await;
+        chains.insert(id, Arc::new(chain));
+        self.store_handlers(id, handlers).await;
 
         Ok(id)
     }
 
+    /// 原地更新已加载的规则链,返回新的版本号
+    ///
+    /// 正在执行的实例持有旧规则链的 `Arc`,会继续按旧定义执行完毕
+    async fn update_chain(&self, id: Uuid, content: &str) -> Result<u64, RuleError> {
+        let chain: RuleChain =
+            serde_json::from_str(content).map_err(|e| RuleError::ConfigError(e.to_string()))?;
+
+        self.replace_chain(id, chain).await
+    }
+
+    /// 从JSON值原地更新已加载的规则链,返回新的版本号
+    async fn update_chain_value(&self, id: Uuid, value: Value) -> Result<u64, RuleError> {
+        let chain: RuleChain =
+            serde_json::from_value(value).map_err(|e| RuleError::ConfigError(e.to_string()))?;
+
+        self.replace_chain(id, chain).await
+    }
+
     /// 添加节点拦截器
     async fn add_node_interceptor(&self, interceptor: Arc<dyn NodeInterceptor>) {
         self.interceptor_manager
@@ -476,93 +1588,239 @@ impl RuleEngineTrait for RuleEngine {
 
     /// 处理消息,执行指定的规则链
     async fn process_msg(&self, chain_id: Uuid, msg: Message) -> Result<Message, RuleError> {
-        let manager = self.interceptor_manager.read().await;
+        self.process_msg_with_token(chain_id, msg, CancellationToken::new())
+            .await
+    }
 
-        // 消息处理前拦截
-        manager.before_process(&msg).await?;
+    /// 处理消息,令牌被取消时中止本次执行并返回 `RuleError::Cancelled`
+    ///
+    /// 每个节点执行前检查令牌,正在执行的节点被中止;只影响本次消息处理,
+    /// 同一规则链的其他消息不受影响
+    async fn process_msg_with_token(
+        &self,
+        chain_id: Uuid,
+        msg: Message,
+        token: CancellationToken,
+    ) -> Result<Message, RuleError> {
+        let ctx = ExecutionContext::new(msg).with_cancel_token(token);
+        self.process(chain_id, None, ctx, false).await
+    }
 
-        // 查找指定的规则链
-        let chain = self
-            .get_chain(chain_id)
+    /// 从指定的入口节点开始处理消息,用于包含多个头节点的规则链
+    ///
+    /// 入口节点必须是规则链的第一个节点,或没有入向连接但有出向连接的节点,否则返回 `RuleError::ConfigError`
+    async fn process_msg_from(
+        &self,
+        chain_id: Uuid,
+        entry_id: Uuid,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        self.process(chain_id, Some(entry_id), ExecutionContext::new(msg), false)
             .await
-            .ok_or(RuleError::ChainNotFound(chain_id))?;
+    }
 
-        // 检查是否为根规则链
-        if !chain.root {
-            return Err(RuleError::ConfigError(format!(
-                "Chain {} is not a root chain",
-                chain_id
-            )));
-        }
+    /// 处理消息并返回执行轨迹,记录经过的每个节点的开始和结束时间、输出分支和错误
+    ///
+    /// 消息命中幂等缓存时直接返回缓存的结果,执行轨迹为空
+    async fn process_msg_traced(
+        &self,
+        chain_id: Uuid,
+        msg: Message,
+    ) -> Result<(Message, ExecutionTrace), RuleError> {
+        let ctx = ExecutionContext::new(msg).with_trace();
+        let trace = ctx.clone();
+        let result = self.process(chain_id, None, ctx, false).await?;
+        Ok((result, trace.take_trace().unwrap_or_default()))
+    }
 
-        // 创建执行上下文并执行规则链
-        let mut ctx = ExecutionContext::new(msg.clone());
-        let result = self.execute_chain(&chain, &mut ctx).await?;
+    /// 重放之前记录的消息,例如死信中的原始消息
+    ///
+    /// 与 `process_msg` 相同,但跳过幂等检查,并在消息元数据中设置 `replay = true`,
+    /// 拦截器和节点可以通过 `Message::is_replay` 跳过有副作用的操作
+    async fn replay_msg(&self, chain_id: Uuid, msg: Message) -> Result<Message, RuleError> {
+        let mut msg = msg;
+        msg.set_meta("replay", true);
+        self.process(chain_id, None, ExecutionContext::new(msg), true)
+            .await
+    }
 
-        // 消息处理后拦截
-        manager.after_process(&msg).await?;
+    /// 批量处理消息,以默认并发数 `DEFAULT_BATCH_CONCURRENCY` 执行
+    async fn process_batch(
+        &self,
+        chain_id: Uuid,
+        msgs: Vec<Message>,
+    ) -> Vec<Result<Message, RuleError>> {
+        self.process_batch_with_concurrency(chain_id, msgs, DEFAULT_BATCH_CONCURRENCY)
+            .await
+    }
 
-        Ok(result)
+    /// 批量处理消息,最多同时处理 `concurrency` 条,为 0 时按 1 处理
+    ///
+    /// 规则链只查找一次,结果与输入消息一一对应并保持顺序,单条消息失败不影响其他消息。
+    /// 规则链不存在或不是根规则链时每条消息都返回该错误
+    async fn process_batch_with_concurrency(
+        &self,
+        chain_id: Uuid,
+        msgs: Vec<Message>,
+        concurrency: usize,
+    ) -> Vec<Result<Message, RuleError>> {
+        let chain = match self.get_chain(chain_id).await {
+            Some(chain) => chain,
+            None => {
+                return msgs
+                    .into_iter()
+                    .map(|msg| settle_ack(msg.ack, Err(RuleError::ChainNotFound(chain_id))))
+                    .collect()
+            }
+        };
+        if let Err(e) = chain.check_root() {
+            return msgs
+                .into_iter()
+                .map(|msg| settle_ack(msg.ack, Err(e.clone())))
+                .collect();
+        }
+
+        let manager = self.interceptor_manager.read().await;
+        let (manager, chain) = (&*manager, &*chain);
+        stream::iter(msgs)
+            .map(|mut msg| async move {
+                let ack = msg.ack.take();
+                let result = async {
+                    // 关闭中的引擎不再处理尚未开始的消息
+                    if self.is_shutting_down() {
+                        return Err(RuleError::ShuttingDown);
+                    }
+                    manager.before_process(&msg).await?;
+                    let mut ctx = ExecutionContext::new(msg);
+                    self.process_in_chain(manager, chain, None, &mut ctx, false)
+                        .await
+                }
+                .await;
+                settle_ack(ack, result)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
     }
 
-    /// 执行规则链
+    /// 执行规则链,从 `entry_id` 指定的入口节点开始,未指定时从第一个节点开始
     async fn execute_chain(
         &self,
         chain: &RuleChain,
+        entry_id: Option<Uuid>,
         ctx: &mut ExecutionContext,
     ) -> Result<Message, RuleError> {
+        // 被自动禁用的规则链不再执行
+        if self.is_chain_disabled(chain.id).await {
+            return Err(RuleError::ChainDisabled(chain.id));
+        }
+
         // 增加计数
         self.increment_counter(chain.id).await;
+        let started = std::time::Instant::now();
 
         // 使用 defer 模式确保计数器一定会减少
         let result = async {
             let start_node = chain
-                .get_start_node()?
+                .get_start_node(entry_id)?
                 .ok_or_else(|| RuleError::ConfigError("规则链没有起始节点".to_string()))?;
 
-            let node_ctx = NodeContext::new(start_node, ctx, Arc::new(self.clone()));
-            self.execute_node(start_node, &node_ctx, ctx.msg.clone())
-                .await
+            let result = run_node(Arc::new(self.clone()), start_node, ctx.clone()).await?;
+
+            // 返回末端节点的输出,没有后继节点时为起始节点的输出
+            Ok(ctx.take_output().await.unwrap_or(result))
         }
         .await;
 
         // 减少计数
         self.decrement_counter(chain.id).await;
+        self.metrics
+            .lock()
+            .unwrap()
+            .entry(chain.id)
+            .or_default()
+            .record(result.is_ok(), started.elapsed());
+        // 取消不代表规则链出错,不计入错误率
+        if !matches!(result, Err(RuleError::Cancelled)) {
+            self.record_guard(chain, result.is_ok());
+        }
 
         result
     }
 
-    /// 执行单个节点
+    /// 执行单个节点,不转发节点的输出
     async fn execute_node<'a>(
         &self,
         node: &'a Node,
         ctx: &NodeContext<'a>,
         msg: Message,
     ) -> Result<Message, RuleError> {
-        let manager = self.interceptor_manager.read().await;
-        // 获取节点处理器
-        let handler = self
-            .node_registry
-            .create_handler(&node.type_name, node.config.clone())
-            .await
-            .ok_or_else(|| RuleError::HandlerNotFound(node.type_name.clone()))?;
-
-        // 节点执行前拦截
-        manager.before_node(ctx, &msg).await?;
+        if ctx.is_cancelled() {
+            return Err(RuleError::Cancelled);
+        }
+        let hop = ctx.record_hop(self.max_hops)?;
+        let _active = ctx.enter_node();
+        let span = ctx.trace_start();
+        // 被错误分支处理的失败,仅在启用追踪时记录
+        let mut handled = None;
 
-        // 执行节点
-        let result = match handler.handle(ctx.clone(), msg.clone()).await {
-            Ok(result) => {
-                // 节点执行后拦截
-                manager.after_node(ctx, &result).await?;
-                Ok(result)
+        // 未被错误分支处理的失败记录到上下文,用于死信中的失败节点
+        let result = async {
+            let manager = self.interceptor_manager.read().await;
+            // 获取节点处理器
+            let handler = self.get_handler(node).await?;
+            let common = CommonConfig::deserialize(&node.config).ok();
+            let limiter = common
+                .as_ref()
+                .and_then(|config| config.max_concurrency)
+                .map(|limit| self.node_limiter(node, limit));
+
+            // 节点执行前拦截
+            manager.before_node(ctx, &msg).await?;
+
+            // 拦截器修改消息
+            let msg = manager.transform_node(ctx, msg).await?;
+
+            // 执行节点,配置了并发上限时先获取许可,等待许可和执行过程中被取消时中止节点
+            let result = tokio::select! {
+                biased;
+                _ = ctx.cancel_token().cancelled() => Err(RuleError::Cancelled),
+                result = async {
+                    let _permit = match &limiter {
+                        Some(limiter) => Some(limiter.acquire().await.map_err(|e| {
+                            RuleError::NodeExecutionError(e.to_string())
+                        })?),
+                        None => None,
+                    };
+                    handler.handle(ctx.clone(), msg.clone()).await
+                } => result,
+            };
+            match result {
+                Ok(result) => {
+                    // 节点执行后拦截
+                    manager.after_node(ctx, &result).await?;
+                    Ok(result)
+                }
+                Err(e) => {
+                    // 节点错误拦截
+                    manager.node_error(ctx, &e).await?;
+                    if span.is_some() {
+                        handled = Some(e.to_string());
+                    }
+                    self.route_error(node, ctx, common.as_ref(), msg, e).await
+                }
             }
-            Err(e) => {
-                // 节点错误拦截
-                manager.node_error(ctx, &e).await?;
-                Err(e)
+        }
+        .await;
+        if result.is_err() {
+            ctx.record_failure(hop);
+        }
+        if let Some(span) = span {
+            match &result {
+                Ok(msg) => span.finish(msg.get_meta_str("branch_name").map(String::from), handled),
+                Err(e) => span.finish(None, Some(e.to_string())),
             }
-        };
+        }
 
         result
     }
@@ -572,6 +1830,98 @@ impl RuleEngineTrait for RuleEngine {
         self.version_manager.get_current_version()
     }
 
+    /// 获取规则链的历史版本列表 (版本号, 时间戳)
+    async fn get_chain_versions(&self, id: Uuid) -> Vec<(u64, i64)> {
+        self.version_manager.get_versions(id)
+    }
+
+    /// 获取规则链的执行统计,规则链不存在时返回 None
+    ///
+    /// 统计在更新和回滚后保留,删除规则链时清除
+    async fn chain_metrics(&self, id: Uuid) -> Option<ChainMetrics> {
+        self.get_chain(id).await?;
+        let metrics = self.metrics.lock().unwrap();
+        Some(
+            metrics
+                .get(&id)
+                .map(ChainStats::metrics)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// 设置规则链的自动禁用策略,优先于规则链元数据中的 `max_error_rate`/`window_ms`
+    ///
+    /// 传入 None 时恢复使用元数据中的配置。策略变化不影响规则链当前是否被禁用
+    async fn set_chain_guard(
+        &self,
+        id: Uuid,
+        policy: Option<ChainGuardPolicy>,
+    ) -> Result<(), RuleError> {
+        self.get_chain(id)
+            .await
+            .ok_or(RuleError::ChainNotFound(id))?;
+        if let Some(policy) = &policy {
+            policy.validate()?;
+        }
+        self.guards.lock().unwrap().entry(id).or_default().policy = policy;
+        Ok(())
+    }
+
+    /// 重新启用被自动禁用的规则链,并清空错误率统计窗口
+    async fn enable_chain(&self, id: Uuid) -> Result<(), RuleError> {
+        self.get_chain(id)
+            .await
+            .ok_or(RuleError::ChainNotFound(id))?;
+        if let Some(guard) = self.guards.lock().unwrap().get_mut(&id) {
+            guard.reset();
+        }
+        Ok(())
+    }
+
+    /// 规则链是否已被自动禁用
+    async fn is_chain_disabled(&self, id: Uuid) -> bool {
+        self.guards
+            .lock()
+            .unwrap()
+            .get(&id)
+            .is_some_and(|guard| guard.disabled)
+    }
+
+    /// 将规则链回滚到指定的历史版本
+    async fn rollback_chain(&self, id: Uuid, version: u64) -> Result<(), RuleError> {
+        let current = self
+            .get_chain(id)
+            .await
+            .ok_or(RuleError::ChainNotFound(id))?;
+
+        let snapshot = self
+            .version_manager
+            .get_version(id, version)
+            .ok_or_else(|| {
+                RuleError::ConfigError(format!("规则链 {} 不存在版本 {} 的记录", id, version))
+            })?;
+
+        // 快照保存的是占位符替换前的定义,重新解析以使用当前的密钥
+        let mut definition = snapshot.chain;
+        let mut chain = definition.clone();
+        self.resolve_secrets(&mut chain).await?;
+
+        // 历史版本依赖的子规则链可能已变化,需要重新校验
+        self.check_chain(&chain).await?;
+        let handlers = self.build_handlers(&chain).await?;
+
+        definition.metadata.created_at = current.metadata.created_at;
+        self.persist(&definition).await?;
+
+        let mut chains = self.chains.write().await;
+        let old = chains.get(&id).ok_or(RuleError::ChainNotFound(id))?;
+        chain.metadata.created_at = old.metadata.created_at;
+        chains.insert(id, Arc::new(chain));
+        self.store_handlers(id, handlers).await;
+
+        Ok(())
+    }
+
     /// 获取所有已加载的规则链
     async fn get_loaded_chains(&self) -> Vec<Arc<RuleChain>> {
         self.chains.read().await.values().cloned().collect()
@@ -582,6 +1932,26 @@ impl RuleEngineTrait for RuleEngine {
         self.chains.read().await.get(&id).cloned()
     }
 
+    /// 将已加载的规则链导出为 Graphviz DOT 格式,规则链不存在时返回 None
+    ///
+    /// 节点以 `type_name` 标注,连接以分支名称标注;头节点为椭圆,中间节点为方框,
+    /// 尾节点为双八边形,无法确定类型的节点按中间节点绘制
+    async fn export_chain_dot(&self, id: Uuid) -> Option<String> {
+        let chain = self.get_chain(id).await?;
+        let node_types = self.node_types(&chain).await;
+        Some(chain.to_dot(&node_types))
+    }
+
+    /// 将已加载的规则链导出为 Mermaid `flowchart LR` 图,规则链不存在时返回 None
+    ///
+    /// 节点以 `type_name` 标注,连接以分支名称标注;头节点为圆角矩形,中间节点为方框,
+    /// 尾节点为双圆,便于嵌入 Markdown 文档
+    async fn export_chain_mermaid(&self, id: Uuid) -> Option<String> {
+        let chain = self.get_chain(id).await?;
+        let node_types = self.node_types(&chain).await;
+        Some(chain.to_mermaid(&node_types))
+    }
+
     /// 删除规则链,会等待当前执行的实例完成
     async fn remove_chain(&self, id: Uuid) -> Result<(), RuleError> {
         // 先用读锁检查规则链是否存在
@@ -608,6 +1978,9 @@ impl RuleEngineTrait for RuleEngine {
             }
         }
 
+        // 停止该规则链的定时任务
+        self.cancel_schedules(id).await;
+
         // 等待执行完成，最多等待 5 秒
         let start_time = std::time::Instant::now();
         let timeout = std::time::Duration::from_secs(5);
@@ -648,37 +2021,326 @@ impl RuleEngineTrait for RuleEngine {
             )));
         }
 
+        // 先从存储中删除,失败时保留已加载的规则链
+        if let Some(store) = &self.store {
+            store.delete(id).await?;
+        }
+
         // 获取写锁并删除
         {
             let mut chains = self.chains.write().await;
             chains.remove(&id);
         }
+        self.handlers
+            .write()
+            .await
+            .retain(|(chain_id, _), _| *chain_id != id);
+        self.limiters
+            .lock()
+            .unwrap()
+            .retain(|(chain_id, _), _| *chain_id != id);
 
         // 清理计数器
         {
             let mut counters = self.execution_counters.write().await;
             counters.remove(&id);
         }
+        self.metrics.lock().unwrap().remove(&id);
+        self.guards.lock().unwrap().remove(&id);
+
+        // 清理历史版本
+        self.version_manager.remove_history(id);
+
+        // 清理节点状态
+        remove_chain_state(id).await;
+
+        // 清理幂等记录
+        if let Some(cache) = &self.idempotency {
+            cache.remove_chain(id).await;
+        }
 
         Ok(())
     }
 
+    /// 登记定时节点的后台任务,同一节点已有的任务会被取消
+    async fn register_schedule(&self, chain_id: Uuid, node_id: Uuid, handle: JoinHandle<()>) {
+        let mut schedules = self.schedules.lock().await;
+        let handles = schedules.entry(chain_id).or_default();
+        handles.retain(|_, handle| !handle.is_finished());
+        if let Some(old) = handles.insert(node_id, handle) {
+            old.abort();
+        }
+    }
+
+    /// 取消规则链的所有定时任务
+    async fn cancel_schedules(&self, chain_id: Uuid) {
+        if let Some(handles) = self.schedules.lock().await.remove(&chain_id) {
+            for handle in handles.into_values() {
+                handle.abort();
+            }
+        }
+    }
+
     /// 注册自定义节点类型
     async fn register_node_type(&self, type_name: &str, factory: NodeFactory) {
         self.node_registry.register(type_name, factory).await;
     }
+
+    /// 使用给定的描述符注册自定义节点类型
+    ///
+    /// 不会用空配置构造节点来获取描述符,节点没有合理的默认配置时使用
+    async fn register_node_type_with_descriptor(
+        &self,
+        type_name: &str,
+        descriptor: NodeDescriptor,
+        factory: NodeFactory,
+    ) {
+        self.node_registry
+            .register_with_descriptor(type_name, descriptor, factory)
+            .await;
+    }
+
+    /// 注册调用 Rust 函数的节点类型
+    ///
+    /// 节点忽略配置,把消息交给函数处理,函数返回的消息按 `branch_name` 转发,
+    /// 返回错误时节点执行失败
+    async fn register_fn(&self, type_name: &str, function: NodeFn) {
+        self.register_function_node(FnNode::new(type_name, function))
+            .await;
+    }
+
+    /// 注册调用异步 Rust 函数的节点类型,函数返回的 future 在节点执行时等待
+    async fn register_async_fn(&self, type_name: &str, function: AsyncNodeFn) {
+        self.register_function_node(FnNode::new_async(type_name, function))
+            .await;
+    }
+
+    /// 关闭规则引擎
+    ///
+    /// 停止接收新消息并取消所有定时任务,然后等待正在执行的规则链完成,
+    /// 超时后仍有执行中的实例时返回错误
+    async fn shutdown(&self, timeout: Duration) -> Result<(), RuleError> {
+        self.draining.store(true, Ordering::SeqCst);
+
+        // 停止所有定时任务
+        let chain_ids: Vec<Uuid> = self.schedules.lock().await.keys().copied().collect();
+        for chain_id in chain_ids {
+            self.cancel_schedules(chain_id).await;
+        }
+
+        // 等待执行完成
+        let start_time = tokio::time::Instant::now();
+        loop {
+            let running = {
+                let counters = self.execution_counters.read().await;
+                let mut running = 0;
+                for counter in counters.values() {
+                    running += *counter.lock().await;
+                }
+                running
+            };
+
+            if running == 0 {
+                return Ok(());
+            }
+
+            if start_time.elapsed() >= timeout {
+                return Err(RuleError::ConfigError(format!(
+                    "关闭规则引擎超时, 仍有 {} 个规则链实例正在执行",
+                    running
+                )));
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    /// 是否正在关闭
+    fn is_shutting_down(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// 正在处理的消息及其正在执行的节点,按开始处理的时间排序
+    ///
+    /// 只包含通过 `process_msg` 等入口处理的消息,子规则链的执行计入调用它的消息
+    fn running_executions(&self) -> Vec<ExecutionInfo> {
+        self.executions.list()
+    }
+
+    /// 订阅广播主题,接收 emit 节点发布到该主题的消息
+    ///
+    /// 每个主题最多缓存 `TOPIC_CAPACITY` 条消息,发布不会等待订阅者。
+    /// 订阅者消费过慢时最旧的消息会被覆盖,下一次 `recv` 返回
+    /// `RecvError::Lagged(n)` 表示跳过了 n 条消息,之后从最早仍保留的消息继续接收
+    async fn subscribe(&self, topic: &str) -> broadcast::Receiver<Message> {
+        if let Some(sender) = self.topics.read().await.get(topic) {
+            return sender.subscribe();
+        }
+        self.topics
+            .write()
+            .await
+            .entry(topic.to_string())
+            .or_insert_with(|| broadcast::channel(TOPIC_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// 发布消息到广播主题,返回接收到消息的订阅者数量,没有订阅者时消息被丢弃
+    async fn publish(&self, topic: &str, msg: Message) -> usize {
+        match self.topics.read().await.get(topic) {
+            Some(sender) => sender.send(msg).unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    /// 分析规则链结构,不需要加载规则链,可用于保存前提示不可达的节点
+    fn analyze_chain(&self, chain: &RuleChain) -> ChainAnalysis {
+        chain.analyze()
+    }
+
+    /// 展开规则链中的所有子规则链节点,返回不含跨规则链调用的扁平规则链,不会加载结果
+    ///
+    /// 子规则链(包括嵌套的子规则链)的节点和连接被拼接到父规则链中,节点使用新的ID,
+    /// 从子规则链节点出发的连接改为从子规则链的每个末端节点出发,路由结果与原规则链一致。
+    /// 结果保留原规则链的ID、名称和元数据,加载后替换原规则链;节点配置中的密钥占位符保持不变。
+    /// 子规则链相互引用形成循环时返回 `RuleError::CircularDependency`。
+    /// 子规则链节点自身的 `error_branch` 不会保留;子规则链中包含 fork 等自行转发的节点时,
+    /// 每个末端节点的输出都会继续执行父规则链的后继节点
+    async fn flatten_chain(&self, id: Uuid) -> Result<RuleChain, RuleError> {
+        let mut chain = self
+            .definition(id)
+            .await
+            .ok_or(RuleError::ChainNotFound(id))?;
+        let (nodes, connections) = self.flatten_nodes(&chain, &mut vec![id]).await?;
+        chain.nodes = nodes;
+        chain.connections = connections;
+        chain.assign_node_chain_ids();
+        Ok(chain)
+    }
 }
 
 impl RuleChain {
-    /// 获取规则链的起始节点
-    pub fn get_start_node(&self) -> Result<Option<&Node>, RuleError> {
+    /// 元数据中配置的自动禁用策略,未设置 `max_error_rate` 时为 None
+    pub fn guard_policy(&self) -> Option<ChainGuardPolicy> {
+        let max_error_rate = self.metadata.max_error_rate?;
+        let window_ms = self.metadata.window_ms.unwrap_or(DEFAULT_GUARD_WINDOW_MS);
+        Some(
+            ChainGuardPolicy::new(max_error_rate, Duration::from_millis(window_ms))
+                .with_min_executions(
+                    self.metadata
+                        .min_executions
+                        .unwrap_or(DEFAULT_GUARD_MIN_EXECUTIONS),
+                ),
+        )
+    }
+
+    /// 将所有节点的 `chain_id` 设置为规则链ID,定义中省略或填写错误时以所属规则链为准
+    fn assign_node_chain_ids(&mut self) {
+        for node in &mut self.nodes {
+            node.chain_id = self.id;
+        }
+    }
+
+    /// 检查是否为根规则链,只有根规则链可以被直接触发执行
+    pub fn check_root(&self) -> Result<(), RuleError> {
+        if !self.root {
+            return Err(RuleError::ConfigError(format!(
+                "Chain {} is not a root chain",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+
+    /// 检查消息类型是否符合规则链声明的 `input_type`,未声明时不检查
+    pub fn check_input_type(&self, msg: &Message) -> Result<(), RuleError> {
+        match &self.metadata.input_type {
+            Some(input_type) if *input_type != msg.msg_type => {
+                Err(RuleError::ConfigError(format!(
+                    "规则链 {} 只接收 {} 类型的消息, 收到 {}",
+                    self.id, input_type, msg.msg_type
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 获取规则链的入口节点: 第一个节点,以及其他没有入向连接但有出向连接的节点
+    ///
+    /// 规则链可以包含多个头节点,例如定时节点和其他消息源各自作为入口汇入同一处理流程
+    pub fn entry_nodes(&self) -> Vec<&Node> {
         self.nodes
-            .first()
-            .ok_or(RuleError::ConfigError("Empty rule chain".to_string()))
+            .iter()
+            .enumerate()
+            .filter(|(index, node)| {
+                *index == 0
+                    || (!self.connections.iter().any(|conn| conn.to_id == node.id)
+                        && self.connections.iter().any(|conn| conn.from_id == node.id))
+            })
+            .map(|(_, node)| node)
+            .collect()
+    }
+
+    /// 获取规则链的起始节点
+    ///
+    /// 未指定 `entry_id` 时为第一个节点;指定时必须是 `entry_nodes` 中的节点,否则返回错误
+    ///
+    /// # Arguments
+    /// * `entry_id` - 入口节点ID
+    pub fn get_start_node(&self, entry_id: Option<Uuid>) -> Result<Option<&Node>, RuleError> {
+        let Some(entry_id) = entry_id else {
+            return self
+                .nodes
+                .first()
+                .ok_or(RuleError::ConfigError("Empty rule chain".to_string()))
+                .map(Some);
+        };
+        self.entry_nodes()
+            .into_iter()
+            .find(|node| node.id == entry_id)
+            .ok_or_else(|| {
+                RuleError::ConfigError(format!(
+                    "节点 {} 不是规则链 {} 的入口节点",
+                    entry_id, self.id
+                ))
+            })
             .map(Some)
     }
 
+    /// 分析规则链结构: 从所有入口节点沿连接广度优先遍历,
+    /// 找出无法到达的节点以及没有出向连接的终止节点
+    pub fn analyze(&self) -> ChainAnalysis {
+        let mut reachable = HashSet::new();
+        let mut queue: VecDeque<Uuid> = self.entry_nodes().iter().map(|node| node.id).collect();
+        while let Some(id) = queue.pop_front() {
+            if !reachable.insert(id) {
+                continue;
+            }
+            queue.extend(
+                self.connections
+                    .iter()
+                    .filter(|conn| conn.from_id == id)
+                    .map(|conn| conn.to_id),
+            );
+        }
+
+        let mut analysis = ChainAnalysis::default();
+        for node in &self.nodes {
+            if !reachable.contains(&node.id) && !analysis.unreachable_nodes.contains(&node.id) {
+                analysis.unreachable_nodes.push(node.id);
+            }
+            let is_terminal = !self.connections.iter().any(|conn| conn.from_id == node.id);
+            if is_terminal && !analysis.terminal_nodes.contains(&node.id) {
+                analysis.terminal_nodes.push(node.id);
+            }
+        }
+        analysis
+    }
+
     /// 获取当前节点的下一个节点
+    ///
+    /// 消息元数据中设置了 `branch_name` 时,选择类型名称与之相同的连接,
+    /// 没有匹配的连接则使用 `default` 连接,两者都不存在时返回错误;
+    /// 未设置 `branch_name` 时使用第一个连接
     pub fn get_next_node(
         &self,
         current_id: &Uuid,
@@ -696,24 +2358,22 @@ impl RuleChain {
             return Ok(None);
         }
 
-        // 检查消息元数据中的分支名称
-        if let Some(branch) = ctx.msg.metadata.get("branch_name") {
-            // 查找匹配分支名称的连接
-            if let Some(conn) = next_conns
+        let conn = match ctx.msg.get_meta_str("branch_name") {
+            // 查找匹配分支名称的连接,没有则使用默认连接
+            Some(branch) => next_conns
                 .iter()
-                .find(|conn| conn.type_name == branch.to_string())
-            {
-                return self
-                    .nodes
-                    .iter()
-                    .find(|node| node.id == conn.to_id)
-                    .ok_or_else(|| RuleError::ConfigError("Invalid connection".to_string()))
-                    .map(Some);
-            }
-        }
+                .find(|conn| conn.type_name == branch)
+                .or_else(|| next_conns.iter().find(|conn| conn.type_name == "default"))
+                .ok_or_else(|| {
+                    RuleError::ConfigError(format!(
+                        "节点 {} 没有与分支 {} 匹配的连接",
+                        current_id, branch
+                    ))
+                })?,
+            // 未指定分支时使用第一个连接
+            None => &next_conns[0],
+        };
 
-        // 如果没有匹配的分支，使用第一个连接
-        let conn = &next_conns[0];
         self.nodes
             .iter()
             .find(|node| node.id == conn.to_id)
@@ -723,8 +2383,14 @@ impl RuleChain {
 
     /// 验证规则链配置的合法性
     pub async fn validate(&self, engine: &RuleEngine) -> Result<(), RuleError> {
+        self.validate_structure()?;
+        if let Some(policy) = self.guard_policy() {
+            policy.validate()?;
+        }
+
         for node in &self.nodes {
             let node_type = Self::get_node_type(engine, node).await?;
+
             // 检查头节点不能被指向
             if node_type == NodeType::Head {
                 let has_incoming = self.connections.iter().any(|conn| conn.to_id == node.id);
@@ -736,6 +2402,18 @@ impl RuleChain {
                 }
             }
 
+            // 检查并发上限
+            if let Ok(CommonConfig {
+                max_concurrency: Some(0),
+                ..
+            }) = CommonConfig::deserialize(&node.config)
+            {
+                return Err(RuleError::ConfigError(format!(
+                    "节点 {} 的 max_concurrency 必须大于 0",
+                    node.id
+                )));
+            }
+
             // 检查尾节点不能指向其他节点
             if node_type == NodeType::Tail {
                 let has_outgoing = self.connections.iter().any(|conn| conn.from_id == node.id);
@@ -750,12 +2428,150 @@ impl RuleChain {
         Ok(())
     }
 
+    /// 检查节点ID唯一且所有连接的端点都存在,错误信息列出全部有问题的ID
+    fn validate_structure(&self) -> Result<(), RuleError> {
+        let mut node_ids = HashSet::new();
+        let mut duplicate_ids = Vec::new();
+        for node in &self.nodes {
+            if !node_ids.insert(node.id) && !duplicate_ids.contains(&node.id) {
+                duplicate_ids.push(node.id);
+            }
+        }
+
+        let mut dangling_ids = Vec::new();
+        for conn in &self.connections {
+            for id in [conn.from_id, conn.to_id] {
+                if !node_ids.contains(&id) && !dangling_ids.contains(&id) {
+                    dangling_ids.push(id);
+                }
+            }
+        }
+
+        let join = |ids: &[Uuid]| {
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut errors = Vec::new();
+        if !duplicate_ids.is_empty() {
+            errors.push(format!("重复的节点ID: {}", join(&duplicate_ids)));
+        }
+        if !dangling_ids.is_empty() {
+            errors.push(format!("连接引用了不存在的节点: {}", join(&dangling_ids)));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(RuleError::ConfigError(errors.join("; ")))
+        }
+    }
+
+    /// 渲染为 Graphviz DOT 格式,节点形状按节点类型区分
+    fn to_dot(&self, node_types: &HashMap<Uuid, NodeType>) -> String {
+        let mut dot = format!("digraph \"{}\" {{\n", self.id);
+        dot.push_str(&format!("    label=\"{}\";\n", escape_dot(&self.name)));
+        dot.push_str("    rankdir=LR;\n");
+        for node in &self.nodes {
+            let shape = match node_types.get(&node.id) {
+                Some(NodeType::Head) => "ellipse",
+                Some(NodeType::Tail) => "doubleoctagon",
+                _ => "box",
+            };
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\", shape={}];\n",
+                node.id,
+                escape_dot(&node.type_name),
+                shape
+            ));
+        }
+        for conn in &self.connections {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                conn.from_id,
+                conn.to_id,
+                escape_dot(&conn.type_name)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// 渲染为 Mermaid 流程图,节点按在规则链中的顺序编号,形状按节点类型区分
+    fn to_mermaid(&self, node_types: &HashMap<Uuid, NodeType>) -> String {
+        // JSON 字符串同时是合法的 YAML 双引号字符串
+        let mut mermaid = format!(
+            "---\ntitle: {}\n---\nflowchart LR\n",
+            Value::String(self.name.clone())
+        );
+        let mut ids = HashMap::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            let id = format!("n{}", index);
+            let label = escape_mermaid(&node.type_name);
+            let shape = match node_types.get(&node.id) {
+                Some(NodeType::Head) => format!("([\"{}\"])", label),
+                Some(NodeType::Tail) => format!("(((\"{}\")))", label),
+                _ => format!("[\"{}\"]", label),
+            };
+            mermaid.push_str(&format!("    {}{}\n", id, shape));
+            ids.insert(node.id, id);
+        }
+        for conn in &self.connections {
+            let (Some(from), Some(to)) = (ids.get(&conn.from_id), ids.get(&conn.to_id)) else {
+                continue;
+            };
+            mermaid.push_str(&format!(
+                "    {} -->|\"{}\"| {}\n",
+                from,
+                escape_mermaid(&conn.type_name),
+                to
+            ));
+        }
+        mermaid
+    }
+
     /// 获取节点的类型
+    ///
+    /// 使用节点配置构造一次节点处理器,确保配置错误在加载时暴露,
+    /// 节点类型也以该配置下的描述符为准
     async fn get_node_type(engine: &RuleEngine, node: &Node) -> Result<NodeType, RuleError> {
-        let descriptor = engine
-            .get_component_descriptor(&node.type_name)
+        let handler = engine
+            .node_registry
+            .create_handler(&node.type_name, node.config.clone())
             .await
-            .ok_or_else(|| RuleError::ConfigError(format!("未找到节点类型: {}", node.type_name)))?;
-        Ok(descriptor.node_type)
+            .map_err(|e| match e {
+                RuleError::HandlerNotFound(type_name) => {
+                    RuleError::ConfigError(format!("未找到节点类型: {}", type_name))
+                }
+                RuleError::ConfigError(e) => {
+                    RuleError::ConfigError(format!("节点 {} 配置无效: {}", node.id, e))
+                }
+                e => e,
+            })?;
+        Ok(handler.get_descriptor().node_type)
+    }
+}
+
+/// 转义 DOT 字符串中的反斜杠、双引号和换行
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// 转义 Mermaid 标签中的双引号和换行
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;").replace('\n', "<br/>")
+}
+
+/// 消息处理结束后通过确认句柄回报结果
+fn settle_ack(
+    ack: Option<AckHandle>,
+    result: Result<Message, RuleError>,
+) -> Result<Message, RuleError> {
+    if let Some(ack) = ack {
+        ack.resolve_result(&result);
     }
+    result
 }
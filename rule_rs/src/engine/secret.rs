@@ -0,0 +1,94 @@
+use crate::types::{Node, RuleError};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 密钥解析器,为节点配置中的 `${<scheme>:<name>}` 占位符提供取值
+///
+/// 通过 `RuleEngine::with_secret_resolver` 按前缀注册,
+/// 可以接入 Vault、AWS Secrets Manager 等外部密钥服务
+#[async_trait]
+pub trait SecretResolver: Debug + Send + Sync {
+    /// 解析名称对应的值,不存在时返回 `None`
+    async fn resolve(&self, name: &str) -> Result<Option<String>, RuleError>;
+}
+
+/// 从进程环境变量读取,对应 `${env:VAR_NAME}` 占位符
+#[derive(Debug, Default)]
+pub struct EnvResolver;
+
+#[async_trait]
+impl SecretResolver for EnvResolver {
+    async fn resolve(&self, name: &str) -> Result<Option<String>, RuleError> {
+        Ok(std::env::var(name).ok())
+    }
+}
+
+/// 替换节点配置中所有已注册前缀的占位符,其他 `${...}` 模板变量保持不变
+pub(crate) async fn interpolate_config(
+    node: &mut Node,
+    resolvers: &HashMap<String, Arc<dyn SecretResolver>>,
+) -> Result<(), RuleError> {
+    let node_id = node.id;
+    let mut pending = vec![&mut node.config];
+    while let Some(value) = pending.pop() {
+        match value {
+            Value::String(s) => {
+                if let Some(resolved) = interpolate_str(s, resolvers, node_id).await? {
+                    *s = resolved;
+                }
+            }
+            Value::Array(items) => pending.extend(items.iter_mut()),
+            Value::Object(obj) => pending.extend(obj.values_mut()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// 替换字符串中的占位符,没有占位符时返回 `None`
+async fn interpolate_str(
+    s: &str,
+    resolvers: &HashMap<String, Arc<dyn SecretResolver>>,
+    node_id: Uuid,
+) -> Result<Option<String>, RuleError> {
+    let mut result = String::new();
+    let mut rest = s;
+    let mut replaced = false;
+
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let placeholder = &rest[start + 2..start + end];
+        let resolver = placeholder
+            .split_once(':')
+            .and_then(|(scheme, name)| resolvers.get(scheme).map(|r| (scheme, name, r)));
+
+        result.push_str(&rest[..start]);
+        match resolver {
+            Some((scheme, name, resolver)) => {
+                let value = resolver.resolve(name).await?.ok_or_else(|| {
+                    RuleError::ConfigError(format!(
+                        "节点 {} 配置引用的 {}:{} 不存在",
+                        node_id, scheme, name
+                    ))
+                })?;
+                result.push_str(&value);
+                replaced = true;
+            }
+            // 未注册的前缀按普通模板变量原样保留
+            None => result.push_str(&rest[start..start + end + 1]),
+        }
+        rest = &rest[start + end + 1..];
+    }
+
+    if !replaced {
+        return Ok(None);
+    }
+    result.push_str(rest);
+    Ok(Some(result))
+}
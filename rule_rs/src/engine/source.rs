@@ -0,0 +1,128 @@
+use crate::engine::rule::RuleEngineTrait;
+use crate::engine::RuleEngine;
+use crate::types::{CancellationToken, Message};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::warn;
+use uuid::Uuid;
+
+/// 通道消息源句柄,调用 `stop` 或丢弃句柄后停止从通道读取消息
+///
+/// 停止后已开始处理的消息继续执行完成,通道中尚未读取的消息保留在通道中
+#[derive(Debug)]
+pub struct ChannelSource {
+    task: Option<JoinHandle<mpsc::Receiver<Message>>>,
+    stop: CancellationToken,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl ChannelSource {
+    /// 正在处理的消息数
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// 停止读取消息,等待正在处理的消息完成后返回通道的接收端
+    ///
+    /// # Returns
+    /// * `mpsc::Receiver<Message>` - 接收端,其中保留尚未读取的消息
+    pub async fn stop(mut self) -> mpsc::Receiver<Message> {
+        self.stop.cancel();
+        self.wait().await
+    }
+
+    /// 等待消息源结束,即通道关闭且消息全部处理完成,或引擎关闭后读取到消息
+    ///
+    /// # Returns
+    /// * `mpsc::Receiver<Message>` - 接收端,引擎关闭时其中保留尚未读取的消息
+    pub async fn join(mut self) -> mpsc::Receiver<Message> {
+        self.wait().await
+    }
+
+    async fn wait(&mut self) -> mpsc::Receiver<Message> {
+        let task = self.task.take().expect("消息源任务只等待一次");
+        // 任务从不中止,处理消息时的 panic 由 JoinSet 捕获
+        task.await.expect("消息源任务异常退出")
+    }
+}
+
+impl Drop for ChannelSource {
+    fn drop(&mut self) {
+        self.stop.cancel();
+    }
+}
+
+impl RuleEngine {
+    /// 从 `tokio::mpsc` 通道读取消息并交给规则链处理
+    ///
+    /// 后台任务依次读取消息,每条消息通过 `process_msg` 在独立的任务中处理,
+    /// 最多同时处理 `max_in_flight` 条(为 0 时按 1 处理)。达到上限时暂停读取,
+    /// 通道写满后发送方的 `send` 随之等待,从而把背压传递给上游。
+    /// 处理结果通过消息的确认句柄回报,失败只记录警告。通道关闭或引擎关闭后读取到下一条消息时停止读取
+    ///
+    /// # Arguments
+    /// * `chain_id` - 处理消息的根规则链ID
+    /// * `receiver` - 消息通道的接收端
+    /// * `max_in_flight` - 同时处理的最大消息数
+    ///
+    /// # Returns
+    /// * `ChannelSource` - 消息源句柄,停止或丢弃后不再读取消息
+    pub fn attach_source(
+        &self,
+        chain_id: Uuid,
+        mut receiver: mpsc::Receiver<Message>,
+        max_in_flight: usize,
+    ) -> ChannelSource {
+        let limiter = Arc::new(Semaphore::new(max_in_flight.max(1)));
+        let stop = CancellationToken::new();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+
+        let engine = self.clone();
+        let cancelled = stop.clone();
+        let counter = in_flight.clone();
+        let task = tokio::spawn(async move {
+            let mut running = JoinSet::new();
+            loop {
+                // 先取得处理名额再读取消息,达到上限时消息留在通道中
+                let permit = tokio::select! {
+                    _ = cancelled.cancelled() => break,
+                    permit = limiter.clone().acquire_owned() => permit.expect("信号量不会关闭"),
+                };
+                let msg = tokio::select! {
+                    _ = cancelled.cancelled() => break,
+                    msg = receiver.recv() => match msg {
+                        Some(msg) => msg,
+                        None => break,
+                    },
+                };
+                if engine.is_shutting_down() {
+                    // 已读取的消息交给 process_msg 回报 ShuttingDown,之后停止读取
+                    cancelled.cancel();
+                }
+
+                let engine = engine.clone();
+                let counter = counter.clone();
+                counter.fetch_add(1, Ordering::SeqCst);
+                running.spawn(async move {
+                    if let Err(e) = engine.process_msg(chain_id, msg).await {
+                        warn!("消息源处理消息失败, 规则链 {}: {}", chain_id, e);
+                    }
+                    counter.fetch_sub(1, Ordering::SeqCst);
+                    drop(permit);
+                });
+                // 回收已完成的任务
+                while running.try_join_next().is_some() {}
+            }
+            while running.join_next().await.is_some() {}
+            receiver
+        });
+
+        ChannelSource {
+            task: Some(task),
+            stop,
+            in_flight,
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::types::{RuleChain, RuleError};
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use uuid::Uuid;
+
+/// 规则链持久化存储,通过 `RuleEngine::with_store` 启用
+///
+/// 引擎启动时从存储加载规则链,加载、更新、回滚和删除规则链时同步写入存储。
+/// 保存的是配置占位符替换前的规则链定义,密钥不会写入存储
+#[async_trait]
+pub trait ChainStore: Debug + Send + Sync {
+    /// 保存规则链,已存在时覆盖
+    async fn save(&self, chain: &RuleChain) -> Result<(), RuleError>;
+
+    /// 读取所有已保存的规则链
+    async fn load_all(&self) -> Result<Vec<RuleChain>, RuleError>;
+
+    /// 删除规则链,不存在时忽略
+    async fn delete(&self, id: Uuid) -> Result<(), RuleError>;
+}
+
+/// 文件系统存储,每个规则链保存为目录下的 `<规则链ID>.json`
+#[derive(Debug, Clone)]
+pub struct FileChainStore {
+    dir: PathBuf,
+}
+
+impl FileChainStore {
+    /// 创建文件系统存储,目录不存在时自动创建
+    pub fn new(dir: impl AsRef<Path>) -> Result<Self, RuleError> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir).map_err(|e| {
+            RuleError::ConfigError(format!("创建规则链存储目录 {} 失败: {}", dir.display(), e))
+        })?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, id: Uuid) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+}
+
+#[async_trait]
+impl ChainStore for FileChainStore {
+    async fn save(&self, chain: &RuleChain) -> Result<(), RuleError> {
+        let content = serde_json::to_string_pretty(chain)
+            .map_err(|e| RuleError::ConfigError(e.to_string()))?;
+
+        // 先写临时文件再重命名,避免进程中断时留下不完整的文件
+        let path = self.path(chain.id);
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, content)
+            .await
+            .map_err(|e| RuleError::ConfigError(format!("写入 {} 失败: {}", tmp.display(), e)))?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(|e| RuleError::ConfigError(format!("写入 {} 失败: {}", path.display(), e)))
+    }
+
+    async fn load_all(&self) -> Result<Vec<RuleChain>, RuleError> {
+        let mut entries = tokio::fs::read_dir(&self.dir).await.map_err(|e| {
+            RuleError::ConfigError(format!("读取目录 {} 失败: {}", self.dir.display(), e))
+        })?;
+
+        let mut paths = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| RuleError::ConfigError(e.to_string()))?
+        {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut chains = Vec::new();
+        for path in paths {
+            let content = tokio::fs::read_to_string(&path).await.map_err(|e| {
+                RuleError::ConfigError(format!("读取 {} 失败: {}", path.display(), e))
+            })?;
+            match serde_json::from_str(&content) {
+                Ok(chain) => chains.push(chain),
+                Err(e) => warn!("跳过无法解析的规则链文件 {}: {}", path.display(), e),
+            }
+        }
+        Ok(chains)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), RuleError> {
+        let path = self.path(id);
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(RuleError::ConfigError(format!(
+                "删除 {} 失败: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+}
+
+/// Redis 存储,所有规则链保存在同一个 Hash 中,field 为规则链ID
+#[derive(Clone)]
+pub struct RedisChainStore {
+    conn: ConnectionManager,
+    key: String,
+}
+
+impl Debug for RedisChainStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisChainStore")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl RedisChainStore {
+    /// 连接 Redis 并创建存储
+    ///
+    /// # Arguments
+    /// * `url` - Redis 连接地址,例如 `redis://127.0.0.1:6379`
+    /// * `key` - 保存规则链的 Hash 键名
+    pub async fn new(url: &str, key: &str) -> Result<Self, RuleError> {
+        let client = redis::Client::open(url)
+            .map_err(|e| RuleError::ConfigError(format!("无效的 Redis 地址 {}: {}", url, e)))?;
+        let conn = client
+            .get_connection_manager()
+            .await
+            .map_err(|e| RuleError::ConfigError(format!("连接 Redis {} 失败: {}", url, e)))?;
+        Ok(Self {
+            conn,
+            key: key.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl ChainStore for RedisChainStore {
+    async fn save(&self, chain: &RuleChain) -> Result<(), RuleError> {
+        let content =
+            serde_json::to_string(chain).map_err(|e| RuleError::ConfigError(e.to_string()))?;
+        let mut conn = self.conn.clone();
+        conn.hset::<_, _, _, ()>(&self.key, chain.id.to_string(), content)
+            .await
+            .map_err(|e| RuleError::ConfigError(format!("保存规则链 {} 失败: {}", chain.id, e)))
+    }
+
+    async fn load_all(&self) -> Result<Vec<RuleChain>, RuleError> {
+        let mut conn = self.conn.clone();
+        let values: Vec<String> = conn
+            .hvals(&self.key)
+            .await
+            .map_err(|e| RuleError::ConfigError(format!("读取规则链失败: {}", e)))?;
+
+        let mut chains = Vec::new();
+        for content in values {
+            match serde_json::from_str(&content) {
+                Ok(chain) => chains.push(chain),
+                Err(e) => warn!("跳过无法解析的规则链 {}: {}", self.key, e),
+            }
+        }
+        Ok(chains)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), RuleError> {
+        let mut conn = self.conn.clone();
+        conn.hdel::<_, _, ()>(&self.key, id.to_string())
+            .await
+            .map_err(|e| RuleError::ConfigError(format!("删除规则链 {} 失败: {}", id, e)))
+    }
+}
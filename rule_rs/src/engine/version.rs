@@ -1,9 +1,14 @@
 use crate::types::RuleChain;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use uuid::Uuid;
 
 #[derive(Debug)]
 pub struct VersionManager {
     current_version: AtomicU64,
+    /// 规则链历史版本,key为规则链ID,按版本号递增排列
+    history: RwLock<HashMap<Uuid, Vec<ChainVersion>>>,
 }
 
 pub struct Version {
@@ -11,22 +16,69 @@ pub struct Version {
     pub timestamp: i64,
 }
 
+/// 规则链的一个历史版本快照
+#[derive(Debug, Clone)]
+pub struct ChainVersion {
+    pub version: u64,
+    pub timestamp: i64,
+    pub chain: RuleChain,
+}
+
 impl VersionManager {
     pub fn new() -> Self {
         Self {
             current_version: AtomicU64::new(1),
+            history: RwLock::new(HashMap::new()),
         }
     }
 
-    pub fn create_version(&self, _chain: &RuleChain) -> Version {
+    pub fn create_version(&self, chain: &RuleChain) -> Version {
         let version = self.current_version.fetch_add(1, Ordering::SeqCst);
-        Version {
-            version,
-            timestamp: chrono::Utc::now().timestamp_millis(),
-        }
+        let timestamp = chrono::Utc::now().timestamp_millis();
+
+        // 保存规则链快照,用于历史查询和回滚
+        let mut snapshot = chain.clone();
+        snapshot.metadata.version = version;
+        snapshot.metadata.updated_at = timestamp;
+        self.history
+            .write()
+            .unwrap()
+            .entry(chain.id)
+            .or_default()
+            .push(ChainVersion {
+                version,
+                timestamp,
+                chain: snapshot,
+            });
+
+        Version { version, timestamp }
     }
 
     pub fn get_current_version(&self) -> u64 {
         self.current_version.load(Ordering::SeqCst)
     }
+
+    /// 获取规则链的所有历史版本 (版本号, 时间戳)
+    pub fn get_versions(&self, chain_id: Uuid) -> Vec<(u64, i64)> {
+        self.history
+            .read()
+            .unwrap()
+            .get(&chain_id)
+            .map(|versions| versions.iter().map(|v| (v.version, v.timestamp)).collect())
+            .unwrap_or_default()
+    }
+
+    /// 获取规则链指定版本的快照
+    pub fn get_version(&self, chain_id: Uuid, version: u64) -> Option<ChainVersion> {
+        self.history
+            .read()
+            .unwrap()
+            .get(&chain_id)
+            .and_then(|versions| versions.iter().find(|v| v.version == version).cloned())
+    }
+
+    /// 清除规则链的所有历史版本
+    pub fn remove_history(&self, chain_id: Uuid) {
+        self.history.write().unwrap().remove(&chain_id);
+    }
 }
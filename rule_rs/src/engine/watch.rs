@@ -0,0 +1,212 @@
+use crate::engine::rule::RuleEngineTrait;
+use crate::engine::RuleEngine;
+use crate::types::RuleError;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// 目录监听句柄,调用 `stop` 或丢弃句柄后停止监听,已加载的规则链保持不变
+#[derive(Debug)]
+pub struct ChainWatcher {
+    watcher: Option<RecommendedWatcher>,
+    task: JoinHandle<()>,
+}
+
+impl ChainWatcher {
+    /// 停止监听目录
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        // 先释放监听器,事件通道随之关闭
+        self.watcher.take();
+        self.task.abort();
+    }
+}
+
+impl Drop for ChainWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// 监听目录中已同步到引擎的规则链文件
+#[derive(Default)]
+struct WatchedFiles {
+    /// 文件路径到规则链ID及最近一次加载内容的映射
+    files: HashMap<PathBuf, (Uuid, String)>,
+    /// 最近一次同步失败的文件内容,同一内容触发的多次事件只报告一次
+    rejected: HashMap<PathBuf, String>,
+}
+
+impl WatchedFiles {
+    /// 加载新文件或更新已修改的文件,内容未变化时跳过,失败时保留之前的版本
+    async fn sync(&mut self, engine: &RuleEngine, path: &Path) {
+        let content = match tokio::fs::read_to_string(path).await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("读取规则链文件 {} 失败: {}", path.display(), e);
+                return;
+            }
+        };
+        if matches!(self.files.get(path), Some((_, last)) if *last == content)
+            || self.rejected.get(path) == Some(&content)
+        {
+            return;
+        }
+
+        let id = match chain_id(&content) {
+            Ok(id) => id,
+            Err(e) => {
+                error!(
+                    "解析规则链文件 {} 失败, 保留之前的版本: {}",
+                    path.display(),
+                    e
+                );
+                self.rejected.insert(path.to_path_buf(), content);
+                return;
+            }
+        };
+
+        let result = if engine.get_chain(id).await.is_some() {
+            engine.update_chain(id, &content).await.map(|version| {
+                info!(
+                    "规则链 {} 已从 {} 更新到版本 {}",
+                    id,
+                    path.display(),
+                    version
+                );
+            })
+        } else {
+            engine.load_chain(&content).await.map(|_| {
+                info!("规则链 {} 已从 {} 加载", id, path.display());
+            })
+        };
+        if let Err(e) = result {
+            error!(
+                "同步规则链文件 {} 失败, 保留之前的版本: {}",
+                path.display(),
+                e
+            );
+            self.rejected.insert(path.to_path_buf(), content);
+            return;
+        }
+        self.rejected.remove(path);
+
+        // 文件中的规则链ID发生变化时删除旧的规则链
+        if let Some((old_id, _)) = self.files.insert(path.to_path_buf(), (id, content)) {
+            if old_id != id {
+                self.remove_chain(engine, old_id).await;
+            }
+        }
+    }
+
+    /// 文件被删除或移出目录时删除对应的规则链
+    async fn remove(&mut self, engine: &RuleEngine, path: &Path) {
+        self.rejected.remove(path);
+        if let Some((id, _)) = self.files.remove(path) {
+            self.remove_chain(engine, id).await;
+        }
+    }
+
+    async fn remove_chain(&self, engine: &RuleEngine, id: Uuid) {
+        // 其他文件仍在使用该规则链ID时保留
+        if self.files.values().any(|(other, _)| *other == id) {
+            return;
+        }
+        match engine.remove_chain(id).await {
+            Ok(()) => info!("规则链 {} 已随文件删除", id),
+            Err(e) => error!("删除规则链 {} 失败: {}", id, e),
+        }
+    }
+}
+
+/// 读取规则链文件中的ID
+fn chain_id(content: &str) -> Result<Uuid, String> {
+    let value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    value
+        .get("id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "缺少规则链ID".to_string())?
+        .parse()
+        .map_err(|e| format!("无效的规则链ID: {}", e))
+}
+
+fn is_chain_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+impl RuleEngine {
+    /// 监听目录中的规则链文件(`*.json`),按文件中的规则链ID同步到引擎
+    ///
+    /// 启动时加载目录中已有的文件;之后新增的文件被加载,修改的文件通过 `update_chain`
+    /// 原地更新,删除的文件通过 `remove_chain` 删除对应的规则链。
+    /// 文件解析或校验失败时记录错误并保留之前的版本
+    ///
+    /// # Arguments
+    /// * `path` - 监听的目录,不包含子目录
+    ///
+    /// # Returns
+    /// * `Result<ChainWatcher, RuleError>` - 监听句柄,停止或丢弃后不再同步
+    pub async fn watch_directory(&self, path: impl AsRef<Path>) -> Result<ChainWatcher, RuleError> {
+        let dir = path.as_ref().to_path_buf();
+        let (tx, mut rx) = mpsc::unbounded_channel::<PathBuf>();
+
+        // 先开始监听再扫描已有文件,避免遗漏扫描期间发生的变化
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+                Ok(event) => {
+                    for path in event.paths {
+                        let _ = tx.send(path);
+                    }
+                }
+                Err(e) => error!("监听规则链目录出错: {}", e),
+            })
+            .map_err(|e| RuleError::ConfigError(format!("创建目录监听失败: {}", e)))?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| {
+                RuleError::ConfigError(format!("监听目录 {} 失败: {}", dir.display(), e))
+            })?;
+
+        let mut existing = Vec::new();
+        let mut entries = tokio::fs::read_dir(&dir).await.map_err(|e| {
+            RuleError::ConfigError(format!("读取目录 {} 失败: {}", dir.display(), e))
+        })?;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            existing.push(entry.path());
+        }
+        existing.sort();
+
+        let mut files = WatchedFiles::default();
+        for path in existing.iter().filter(|p| is_chain_file(p)) {
+            files.sync(self, path).await;
+        }
+
+        let engine = self.clone();
+        let task = tokio::spawn(async move {
+            while let Some(path) = rx.recv().await {
+                if !is_chain_file(&path) {
+                    continue;
+                }
+                // 事件类型因平台和编辑器而异,以文件当前是否存在为准
+                if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+                    files.sync(&engine, &path).await;
+                } else {
+                    files.remove(&engine, &path).await;
+                }
+            }
+        });
+
+        Ok(ChainWatcher {
+            watcher: Some(watcher),
+            task,
+        })
+    }
+}
@@ -1,6 +1,9 @@
 pub mod aop;
 pub mod components;
 pub mod engine;
+/// 测试辅助工具,需要启用 `testing` 特性
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod types;
 pub mod utils;
 
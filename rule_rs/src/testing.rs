@@ -0,0 +1,113 @@
+use crate::engine::rule::RuleEngineTrait;
+use crate::engine::NodeHandler;
+use crate::types::{Message, NodeContext, NodeDescriptor, NodeType, RuleError};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// 记录收到的所有消息并原样返回的测试节点
+///
+/// 通过 `register_mock` 注册后可以在规则链中替代外部服务,用 `assert_received` 验证消息的路由和转换结果。
+/// 克隆的实例共享同一份记录,同一类型的所有节点写入同一份记录
+#[derive(Debug, Clone)]
+pub struct MockNode {
+    type_name: String,
+    received: Arc<Mutex<Vec<Message>>>,
+}
+
+impl MockNode {
+    /// 创建测试节点
+    ///
+    /// # Arguments
+    /// * `type_name` - 节点类型名称
+    pub fn new(type_name: &str) -> Self {
+        Self {
+            type_name: type_name.to_string(),
+            received: Arc::default(),
+        }
+    }
+
+    /// 按接收顺序返回收到的所有消息
+    pub fn received(&self) -> Vec<Message> {
+        self.received.lock().unwrap().clone()
+    }
+
+    /// 收到的消息数量
+    pub fn count(&self) -> usize {
+        self.received.lock().unwrap().len()
+    }
+
+    /// 清空已记录的消息
+    pub fn clear(&self) {
+        self.received.lock().unwrap().clear();
+    }
+}
+
+#[async_trait]
+impl NodeHandler for MockNode {
+    async fn handle<'a>(
+        &'a self,
+        _ctx: NodeContext<'a>,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        self.received.lock().unwrap().push(msg.clone());
+        Ok(msg)
+    }
+
+    fn get_descriptor(&self) -> NodeDescriptor {
+        NodeDescriptor {
+            type_name: self.type_name.clone(),
+            name: "测试节点".to_string(),
+            description: "记录收到的所有消息".to_string(),
+            node_type: NodeType::Middle,
+            config_schema: None,
+        }
+    }
+}
+
+/// 注册测试节点类型,返回记录该类型所有节点收到的消息的 `MockNode`
+///
+/// # Arguments
+/// * `engine` - 规则引擎实例
+/// * `type_name` - 节点类型名称,需要区分的节点应使用不同的类型名称
+pub async fn register_mock(engine: &impl RuleEngineTrait, type_name: &str) -> MockNode {
+    let mock = MockNode::new(type_name);
+    let handler = mock.clone();
+    engine
+        .register_node_type(
+            type_name,
+            Arc::new(move |_| Ok(Arc::new(handler.clone()) as Arc<dyn NodeHandler>)),
+        )
+        .await;
+    mock
+}
+
+/// 断言测试节点收到过满足条件的消息,否则 panic 并列出收到的所有消息
+///
+/// # Arguments
+/// * `mock` - 测试节点
+/// * `predicate` - 消息需要满足的条件
+#[track_caller]
+pub fn assert_received(mock: &MockNode, predicate: impl Fn(&Message) -> bool) {
+    let received = mock.received();
+    assert!(
+        received.iter().any(predicate),
+        "测试节点 {} 没有收到满足条件的消息, 收到的消息: {:#?}",
+        mock.type_name,
+        received
+    );
+}
+
+/// 断言测试节点没有收到任何消息,否则 panic 并列出收到的所有消息
+///
+/// # Arguments
+/// * `mock` - 测试节点
+#[track_caller]
+pub fn assert_not_received(mock: &MockNode) {
+    let received = mock.received();
+    assert!(
+        received.is_empty(),
+        "测试节点 {} 不应收到消息, 收到的消息: {:#?}",
+        mock.type_name,
+        received
+    );
+}
@@ -0,0 +1,102 @@
+use crate::types::{Message, RuleError};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// 消息处理结果,由引擎在消息处理结束时回报给消息源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AckOutcome {
+    /// 规则链处理成功,消息源可以向消息队列确认消息
+    Ack,
+    /// 规则链处理失败,消息源可以要求消息队列重新投递,附带错误信息
+    Nack(String),
+}
+
+impl AckOutcome {
+    /// 是否处理成功
+    pub fn is_ack(&self) -> bool {
+        matches!(self, AckOutcome::Ack)
+    }
+}
+
+type AckCallback = Box<dyn FnOnce(AckOutcome) + Send>;
+
+/// 消息确认句柄,消息源通过 `Message::with_ack` 附加到消息上
+///
+/// `process_msg` 等处理入口在规则链执行结束后以 `AckOutcome` 调用句柄: 成功时为 `Ack`,
+/// 失败时(包括规则链不存在、被取消)为 `Nack`。句柄只在处理入口生效,不随消息向下游节点、
+/// 子规则链或处理结果传递。克隆的句柄共享同一个回调,只有第一次确认生效
+#[derive(Clone)]
+pub struct AckHandle {
+    callback: Arc<Mutex<Option<AckCallback>>>,
+}
+
+impl AckHandle {
+    /// 使用回调函数创建确认句柄
+    ///
+    /// # Arguments
+    /// * `callback` - 收到处理结果时调用,最多调用一次
+    pub fn new(callback: impl FnOnce(AckOutcome) + Send + 'static) -> Self {
+        Self {
+            callback: Arc::new(Mutex::new(Some(Box::new(callback)))),
+        }
+    }
+
+    /// 创建确认句柄以及接收处理结果的 `oneshot` 通道
+    ///
+    /// 句柄未确认就被丢弃时(例如处理消息的任务被中止),接收端返回 `RecvError`,
+    /// 消息源应按处理失败对待
+    pub fn channel() -> (Self, oneshot::Receiver<AckOutcome>) {
+        let (tx, rx) = oneshot::channel();
+        let handle = Self::new(move |outcome| {
+            let _ = tx.send(outcome);
+        });
+        (handle, rx)
+    }
+
+    /// 确认消息处理成功,已确认过时返回 false
+    pub fn ack(&self) -> bool {
+        self.resolve(AckOutcome::Ack)
+    }
+
+    /// 报告消息处理失败,已确认过时返回 false
+    ///
+    /// # Arguments
+    /// * `reason` - 失败原因
+    pub fn nack(&self, reason: impl Into<String>) -> bool {
+        self.resolve(AckOutcome::Nack(reason.into()))
+    }
+
+    /// 回报处理结果,已确认过时返回 false
+    pub fn resolve(&self, outcome: AckOutcome) -> bool {
+        let callback = self.callback.lock().unwrap().take();
+        match callback {
+            Some(callback) => {
+                callback(outcome);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 是否已经回报过处理结果
+    pub fn is_resolved(&self) -> bool {
+        self.callback.lock().unwrap().is_none()
+    }
+
+    /// 按消息处理结果回报
+    pub(crate) fn resolve_result(&self, result: &Result<Message, RuleError>) {
+        match result {
+            Ok(_) => self.ack(),
+            Err(e) => self.nack(e.to_string()),
+        };
+    }
+}
+
+impl fmt::Debug for AckHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AckHandle")
+            .field("resolved", &self.is_resolved())
+            .finish()
+    }
+}
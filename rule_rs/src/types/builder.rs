@@ -0,0 +1,138 @@
+use crate::engine::ChainGuardPolicy;
+use crate::types::{Connection, Metadata, Node, Position, RuleChain};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// 规则链构建器,在代码中构造规则链而无需编写JSON
+///
+/// 自动生成节点ID并设置节点所属的规则链ID,第一个添加的节点为起始节点
+#[derive(Debug, Clone)]
+pub struct RuleChainBuilder {
+    id: Uuid,
+    name: String,
+    root: bool,
+    idempotent: bool,
+    input_type: Option<String>,
+    output_type: Option<String>,
+    guard: Option<ChainGuardPolicy>,
+    nodes: Vec<Node>,
+    connections: Vec<Connection>,
+}
+
+impl RuleChainBuilder {
+    /// 创建构建器,生成新的规则链ID,默认不是根规则链
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            root: false,
+            idempotent: true,
+            input_type: None,
+            output_type: None,
+            guard: None,
+            nodes: Vec::new(),
+            connections: Vec::new(),
+        }
+    }
+
+    /// 使用指定的规则链ID,已添加节点的规则链ID同步更新
+    pub fn id(&mut self, id: Uuid) -> &mut Self {
+        self.id = id;
+        for node in &mut self.nodes {
+            node.chain_id = id;
+        }
+        self
+    }
+
+    /// 设置是否为根规则链
+    pub fn root(&mut self, root: bool) -> &mut Self {
+        self.root = root;
+        self
+    }
+
+    /// 设置引擎启用幂等检查时是否对该规则链生效
+    pub fn idempotent(&mut self, idempotent: bool) -> &mut Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    /// 声明规则链接收的消息类型
+    pub fn input_type(&mut self, msg_type: impl Into<String>) -> &mut Self {
+        self.input_type = Some(msg_type.into());
+        self
+    }
+
+    /// 声明规则链输出的消息类型
+    pub fn output_type(&mut self, msg_type: impl Into<String>) -> &mut Self {
+        self.output_type = Some(msg_type.into());
+        self
+    }
+
+    /// 设置错误率超过上限时自动禁用规则链的策略
+    pub fn error_guard(&mut self, policy: ChainGuardPolicy) -> &mut Self {
+        self.guard = Some(policy);
+        self
+    }
+
+    /// 添加节点,返回生成的节点ID
+    ///
+    /// # Arguments
+    /// * `type_name` - 节点类型
+    /// * `config` - 节点配置
+    pub fn add_node(&mut self, type_name: &str, config: Value) -> Uuid {
+        let id = Uuid::new_v4();
+        self.nodes.push(Node {
+            id,
+            type_name: type_name.to_string(),
+            config,
+            // 按添加顺序从左到右排列
+            layout: Position {
+                x: 150.0 * self.nodes.len() as f32 + 50.0,
+                y: 100.0,
+            },
+            chain_id: self.id,
+        });
+        id
+    }
+
+    /// 连接两个节点
+    ///
+    /// # Arguments
+    /// * `from` - 起始节点ID
+    /// * `to` - 目标节点ID
+    /// * `branch` - 连接类型名称,用于条件路由
+    pub fn connect(&mut self, from: Uuid, to: Uuid, branch: &str) -> &mut Self {
+        self.connections.push(Connection {
+            from_id: from,
+            to_id: to,
+            type_name: branch.to_string(),
+        });
+        self
+    }
+
+    /// 生成规则链,结构由加载时的校验检查
+    pub fn build(&self) -> RuleChain {
+        let now = chrono::Utc::now().timestamp_millis();
+        RuleChain {
+            id: self.id,
+            name: self.name.clone(),
+            root: self.root,
+            idempotent: self.idempotent,
+            nodes: self.nodes.clone(),
+            connections: self.connections.clone(),
+            metadata: Metadata {
+                version: 1,
+                created_at: now,
+                updated_at: now,
+                input_type: self.input_type.clone(),
+                output_type: self.output_type.clone(),
+                max_error_rate: self.guard.as_ref().map(|guard| guard.max_error_rate),
+                window_ms: self
+                    .guard
+                    .as_ref()
+                    .map(|guard| guard.window.as_millis() as u64),
+                min_executions: self.guard.as_ref().map(|guard| guard.min_executions),
+            },
+        }
+    }
+}
@@ -1,10 +1,61 @@
 use crate::engine::DynRuleEngine;
-use crate::types::{Connection, Message, Node, RuleError};
-use std::collections::HashMap;
+use crate::types::{
+    Connection, ExecutionTrace, Message, Node, RuleChain, RuleError, TraceLog, TraceSpan,
+};
+use futures::future::join_all;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+pub use tokio_util::sync::CancellationToken;
+
+/// 超出执行上限时错误信息中保留的最近节点数
+const RECENT_HOPS: usize = 5;
+
+/// 后继节点递归执行,每隔该深度在新任务中执行一次,避免长链路耗尽线程栈
+const STACK_RESET_DEPTH: usize = 32;
+
+/// 一次消息处理中已执行的节点计数,在并行分支和子规则链间共享
+#[derive(Debug, Default)]
+struct HopCounter {
+    count: AtomicUsize,
+    /// 最近执行的节点ID
+    recent: std::sync::Mutex<VecDeque<Uuid>>,
+    /// 最近一次执行失败的节点 (执行序号, 节点ID)
+    failed: std::sync::Mutex<Option<(usize, Uuid)>>,
+    /// 正在执行的节点ID,按开始执行的顺序排列
+    active: std::sync::Mutex<Vec<Uuid>>,
+}
+
+/// 一次消息处理的执行进度,用于查询正在执行的节点
+#[derive(Debug, Clone)]
+pub(crate) struct ExecutionProgress(Arc<HopCounter>);
+
+impl ExecutionProgress {
+    /// 最近开始且仍在执行的节点,fork 的分支并行执行时为其中最后开始的一个
+    pub(crate) fn current_node(&self) -> Option<Uuid> {
+        self.0.active.lock().unwrap().last().copied()
+    }
+}
+
+/// 节点正在执行的标记,丢弃时从执行进度中移除
+pub(crate) struct ActiveNode {
+    hops: Arc<HopCounter>,
+    node_id: Uuid,
+}
+
+impl Drop for ActiveNode {
+    fn drop(&mut self) {
+        let mut active = self.hops.active.lock().unwrap();
+        if let Some(index) = active.iter().rposition(|id| *id == self.node_id) {
+            active.remove(index);
+        }
+    }
+}
+
 /// 节点执行上下文,包含节点执行所需的所有信息
 #[derive(Debug, Clone)]
 pub struct NodeContext<'a> {
@@ -18,6 +69,22 @@ pub struct NodeContext<'a> {
     pub msg: Message,
     /// 分支执行结果,用于存储并行分支的执行结果
     branch_results: Arc<Mutex<HashMap<String, Message>>>,
+    /// 规则链最终输出,与所属执行上下文共享
+    output: Arc<Mutex<Option<Message>>>,
+    /// 调用当前规则链的上层规则链,用于检测运行时的循环调用
+    chain_stack: Vec<Uuid>,
+    /// 节点是否已自行路由,为 false 时引擎在节点返回后自动转发其输出
+    routed: Arc<AtomicBool>,
+    /// 本次消息处理已执行的节点计数
+    hops: Arc<HopCounter>,
+    /// 本次消息处理的取消令牌
+    cancel: CancellationToken,
+    /// 本次消息处理中所有节点共享的数据
+    vars: Arc<Mutex<HashMap<String, Value>>>,
+    /// 当前节点在调用链路上的深度
+    depth: usize,
+    /// 本次消息处理的执行轨迹,未启用追踪时为 None
+    trace: Option<TraceLog>,
 }
 
 /// 规则链执行上下文,包含规则链执行过程中的状态信息
@@ -27,6 +94,20 @@ pub struct ExecutionContext {
     pub msg: Message,
     /// 上下文元数据,用于在规则链执行过程中传递信息
     pub metadata: HashMap<String, String>,
+    /// 规则链最终输出,由最先执行完成的末端节点写入
+    output: Arc<Mutex<Option<Message>>>,
+    /// 调用当前规则链的上层规则链,用于检测运行时的循环调用
+    chain_stack: Vec<Uuid>,
+    /// 本次消息处理已执行的节点计数
+    hops: Arc<HopCounter>,
+    /// 本次消息处理的取消令牌,在并行分支和子规则链间共享
+    cancel: CancellationToken,
+    /// 本次消息处理中所有节点共享的数据,在并行分支和子规则链间共享
+    vars: Arc<Mutex<HashMap<String, Value>>>,
+    /// 调用链路深度
+    depth: usize,
+    /// 本次消息处理的执行轨迹,未启用追踪时为 None
+    trace: Option<TraceLog>,
 }
 
 impl ExecutionContext {
@@ -38,8 +119,52 @@ impl ExecutionContext {
         Self {
             msg,
             metadata: HashMap::new(),
+            output: Arc::new(Mutex::new(None)),
+            chain_stack: Vec::new(),
+            hops: Arc::default(),
+            cancel: CancellationToken::new(),
+            vars: Arc::default(),
+            depth: 0,
+            trace: None,
         }
     }
+
+    /// 使用指定的取消令牌,令牌被取消后不再执行新的节点,正在执行的节点被中止
+    ///
+    /// # Arguments
+    /// * `token` - 取消令牌
+    pub fn with_cancel_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// 记录本次消息处理经过的每个节点,通过 `take_trace` 取出
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(TraceLog::default());
+        self
+    }
+
+    /// 取出已记录的执行轨迹,未启用追踪时返回 None
+    pub fn take_trace(&self) -> Option<ExecutionTrace> {
+        let log = self.trace.as_ref()?;
+        let entries = std::mem::take(&mut *log.lock().unwrap());
+        Some(ExecutionTrace { entries })
+    }
+
+    /// 获取规则链的最终输出消息
+    pub async fn take_output(&self) -> Option<Message> {
+        self.output.lock().await.take()
+    }
+
+    /// 本次消息处理的执行进度,与并行分支和子规则链共享
+    pub(crate) fn progress(&self) -> ExecutionProgress {
+        ExecutionProgress(self.hops.clone())
+    }
+
+    /// 本次消息处理中导致失败的节点,没有节点执行失败时返回 None
+    pub(crate) fn failed_node(&self) -> Option<Uuid> {
+        self.hops.failed.lock().unwrap().map(|(_, node_id)| node_id)
+    }
 }
 
 impl<'a> NodeContext<'a> {
@@ -56,14 +181,153 @@ impl<'a> NodeContext<'a> {
             engine,
             msg: ctx.msg.clone(),
             branch_results: Arc::new(Mutex::new(HashMap::new())),
+            output: ctx.output.clone(),
+            chain_stack: ctx.chain_stack.clone(),
+            routed: Arc::new(AtomicBool::new(false)),
+            hops: ctx.hops.clone(),
+            cancel: ctx.cancel.clone(),
+            vars: ctx.vars.clone(),
+            depth: ctx.depth,
+            trace: ctx.trace.clone(),
         }
     }
 
-    /// 创建子规则链的执行上下文
+    /// 本次消息处理的取消令牌
+    ///
+    /// 节点启动的后台任务或长时间 IO 可以监听该令牌,在执行被取消时及时退出
+    pub fn cancel_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    /// 本次消息处理是否已被取消
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// 写入本次消息处理的共享数据,已存在的键被覆盖
+    ///
+    /// 共享数据在一次 `process_msg` 中对所有节点可见,用于在前面的节点记录、后面的节点读取,
+    /// 无需放入 `msg.data`。fork 的各个分支和子规则链共享同一份数据,并发写入同一个键时以最后写入的为准;
+    /// 不同消息的处理互不影响
+    ///
+    /// # Arguments
+    /// * `key` - 键名
+    /// * `value` - 写入的值
+    pub async fn ctx_set(&self, key: &str, value: Value) {
+        self.vars.lock().await.insert(key.to_string(), value);
+    }
+
+    /// 读取本次消息处理的共享数据,键不存在时返回 None
+    ///
+    /// # Arguments
+    /// * `key` - 键名
+    pub async fn ctx_get(&self, key: &str) -> Option<Value> {
+        self.vars.lock().await.get(key).cloned()
+    }
+
+    /// 记录当前节点的一次执行,返回本次执行的序号;执行次数超过上限时返回错误
+    ///
+    /// # Arguments
+    /// * `max_hops` - 执行次数上限
+    pub(crate) fn record_hop(&self, max_hops: usize) -> Result<usize, RuleError> {
+        let count = self.hops.count.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut recent = self.hops.recent.lock().unwrap();
+        recent.push_back(self.node.id);
+        if recent.len() > RECENT_HOPS {
+            recent.pop_front();
+        }
+
+        if count > max_hops {
+            return Err(RuleError::MaxHopsExceeded {
+                max: max_hops,
+                recent: recent.iter().copied().collect(),
+            });
+        }
+        Ok(count)
+    }
+
+    /// 标记当前节点开始执行,返回的标记丢弃时节点执行结束
+    pub(crate) fn enter_node(&self) -> ActiveNode {
+        self.hops.active.lock().unwrap().push(self.node.id);
+        ActiveNode {
+            hops: self.hops.clone(),
+            node_id: self.node.id,
+        }
+    }
+
+    /// 在执行轨迹中记录当前节点开始执行,未启用追踪时返回 None
+    pub(crate) fn trace_start(&self) -> Option<TraceSpan> {
+        self.trace
+            .as_ref()
+            .map(|log| TraceSpan::start(log, self.node))
+    }
+
+    /// 记录当前节点执行失败
+    ///
+    /// 错误沿子规则链、fork 等上层节点向外传播时,已记录的内层节点不会被覆盖;
+    /// 当前节点开始执行前记录的失败已被处理,直接覆盖
+    ///
+    /// # Arguments
+    /// * `hop` - 当前节点的执行序号
+    pub(crate) fn record_failure(&self, hop: usize) {
+        let mut failed = self.hops.failed.lock().unwrap();
+        if !matches!(*failed, Some((failed_hop, _)) if failed_hop > hop) {
+            *failed = Some((hop, self.node.id));
+        }
+    }
+
+    /// 标记当前节点已自行路由,引擎不再自动转发节点返回的消息
+    ///
+    /// `send_next`、`send_all` 和 `send_to_node` 会自动标记;节点有意丢弃消息或稍后异步发送时需手动调用
+    pub fn mark_routed(&self) {
+        self.routed.store(true, Ordering::SeqCst);
+    }
+
+    /// 当前节点是否已自行路由
+    pub fn is_routed(&self) -> bool {
+        self.routed.load(Ordering::SeqCst)
+    }
+
+    /// 创建子规则链的执行上下文,子规则链拥有独立的最终输出
     pub fn create_subchain_context(&self) -> ExecutionContext {
+        let mut chain_stack = self.chain_stack.clone();
+        chain_stack.push(self.node.chain_id);
         ExecutionContext {
             msg: self.msg.clone(),
             metadata: self.metadata.clone(),
+            output: Arc::new(Mutex::new(None)),
+            chain_stack,
+            hops: self.hops.clone(),
+            cancel: self.cancel.clone(),
+            vars: self.vars.clone(),
+            depth: self.depth + 1,
+            trace: self.trace.clone(),
+        }
+    }
+
+    /// 创建后继节点的执行上下文,与当前节点共享规则链最终输出
+    ///
+    /// # Arguments
+    /// * `msg` - 发送给后继节点的消息
+    pub fn create_next_context(&self, msg: Message) -> ExecutionContext {
+        ExecutionContext {
+            msg,
+            metadata: self.metadata.clone(),
+            output: self.output.clone(),
+            chain_stack: self.chain_stack.clone(),
+            hops: self.hops.clone(),
+            cancel: self.cancel.clone(),
+            vars: self.vars.clone(),
+            depth: self.depth + 1,
+            trace: self.trace.clone(),
+        }
+    }
+
+    /// 记录规则链最终输出,仅保留第一个写入的结果
+    async fn set_output(&self, msg: &Message) {
+        let mut output = self.output.lock().await;
+        if output.is_none() {
+            *output = Some(msg.clone());
         }
     }
 
@@ -72,29 +336,63 @@ impl<'a> NodeContext<'a> {
     /// # Arguments
     /// * `msg` - 要发送的消息
     pub async fn send_next(&self, msg: Message) -> Result<(), RuleError> {
+        self.mark_routed();
+
         // 获取当前节点的规则链
-        let chain = self
-            .engine
-            .get_chain(self.node.chain_id)
-            .await
-            .ok_or_else(|| RuleError::ChainNotFound(self.node.chain_id))?;
+        let chain = self.current_chain().await?;
 
         // 创建执行上下文
-        let mut exec_ctx = ExecutionContext::new(msg);
-        exec_ctx.metadata = self.metadata.clone();
+        let mut exec_ctx = self.create_next_context(msg);
 
         // 获取下一个节点
         let next_node = chain.get_next_node(&self.node.id, &exec_ctx)?;
 
-        // 如果有下一个节点，则执行
+        // 如果有下一个节点，则执行;否则当前消息即为规则链的输出
         if let Some(node) = next_node {
-            let ctx = NodeContext::new(node, &exec_ctx, self.engine.clone());
-            self.engine.execute_node(node, &ctx, exec_ctx.msg).await?;
+            // 分支名称只用于本次路由,不传递给下一个节点
+            exec_ctx.msg.metadata.remove("branch_name");
+            self.execute_next(chain.clone(), node, exec_ctx).await?;
+        } else {
+            self.set_output(&exec_ctx.msg).await;
         }
 
         Ok(())
     }
 
+    /// 按顺序发送多条消息到下一个节点,用于一条输入产生多条输出的节点
+    ///
+    /// 下游按顺序依次执行,前一条消息的下游执行完成后才发送下一条;
+    /// 某条消息失败时继续发送其余消息,全部发送后返回 `RuleError::SendAllFailed` 汇总失败的序号和错误。
+    /// 规则链最终输出为第一条到达末端节点的消息;消息列表为空时同样视为已路由,不再转发节点返回的消息
+    ///
+    /// # Arguments
+    /// * `msgs` - 要发送的消息列表
+    pub async fn send_all(&self, msgs: Vec<Message>) -> Result<(), RuleError> {
+        self.mark_routed();
+
+        let total = msgs.len();
+        let mut results = Vec::with_capacity(total);
+        for msg in msgs {
+            results.push(self.send_next(msg).await);
+        }
+        collect_send_errors(total, results)
+    }
+
+    /// 并发发送多条消息到下一个节点
+    ///
+    /// 各消息的下游在当前任务中交替执行,执行顺序和规则链最终输出来自哪条消息都不确定;
+    /// 等待全部完成后按消息序号汇总失败,错误处理与 `send_all` 相同
+    ///
+    /// # Arguments
+    /// * `msgs` - 要发送的消息列表
+    pub async fn send_all_concurrent(&self, msgs: Vec<Message>) -> Result<(), RuleError> {
+        self.mark_routed();
+
+        let total = msgs.len();
+        let results = join_all(msgs.into_iter().map(|msg| self.send_next(msg))).await;
+        collect_send_errors(total, results)
+    }
+
     /// 设置下一个要执行的分支名称
     ///
     /// # Arguments
@@ -115,16 +413,40 @@ impl<'a> NodeContext<'a> {
         &self,
         type_name: &str,
     ) -> Result<Vec<Connection>, RuleError> {
-        let chain = self
-            .engine
+        Ok(self
+            .outgoing_connections()
+            .await?
+            .into_iter()
+            .filter(|conn| conn.type_name == type_name)
+            .collect())
+    }
+
+    /// 获取当前节点所属的规则链
+    pub async fn current_chain(&self) -> Result<Arc<RuleChain>, RuleError> {
+        self.engine
             .get_chain(self.node.chain_id)
             .await
-            .ok_or_else(|| RuleError::ChainNotFound(self.node.chain_id))?;
+            .ok_or(RuleError::ChainNotFound(self.node.chain_id))
+    }
+
+    /// 获取指向当前节点的所有连接
+    pub async fn incoming_connections(&self) -> Result<Vec<Connection>, RuleError> {
+        let chain = self.current_chain().await?;
+        Ok(chain
+            .connections
+            .iter()
+            .filter(|conn| conn.to_id == self.node.id)
+            .cloned()
+            .collect())
+    }
 
+    /// 获取从当前节点出发的所有连接
+    pub async fn outgoing_connections(&self) -> Result<Vec<Connection>, RuleError> {
+        let chain = self.current_chain().await?;
         Ok(chain
             .connections
             .iter()
-            .filter(|conn| conn.from_id == self.node.id && conn.type_name == type_name)
+            .filter(|conn| conn.from_id == self.node.id)
             .cloned()
             .collect())
     }
@@ -138,11 +460,9 @@ impl<'a> NodeContext<'a> {
     /// # Returns
     /// * `Result<Message, RuleError>` - 节点处理后的消息或错误
     pub async fn send_to_node(&self, node_id: &Uuid, msg: Message) -> Result<Message, RuleError> {
-        let chain = self
-            .engine
-            .get_chain(self.node.chain_id)
-            .await
-            .ok_or_else(|| RuleError::ChainNotFound(self.node.chain_id))?;
+        self.mark_routed();
+
+        let chain = self.current_chain().await?;
 
         let target_node = chain
             .nodes
@@ -150,12 +470,70 @@ impl<'a> NodeContext<'a> {
             .find(|n| n.id == *node_id)
             .ok_or_else(|| RuleError::ConfigError(format!("节点 {} 不存在", node_id)))?;
 
-        let ctx = NodeContext::new(
-            target_node,
-            &ExecutionContext::new(msg.clone()),
-            self.engine.clone(),
-        );
-        self.engine.execute_node(target_node, &ctx, msg).await
+        self.execute_next(chain.clone(), target_node, self.create_next_context(msg))
+            .await
+    }
+
+    /// 执行后继节点,调用链路每加深 `STACK_RESET_DEPTH` 层时在新任务中执行
+    async fn execute_next(
+        &self,
+        chain: Arc<RuleChain>,
+        node: &Node,
+        exec_ctx: ExecutionContext,
+    ) -> Result<Message, RuleError> {
+        if !exec_ctx.depth.is_multiple_of(STACK_RESET_DEPTH) {
+            return run_node(self.engine.clone(), node, exec_ctx).await;
+        }
+
+        let engine = self.engine.clone();
+        let node_id = node.id;
+        tokio::spawn(async move {
+            let node = chain.nodes.iter().find(|n| n.id == node_id).unwrap();
+            run_node(engine, node, exec_ctx).await
+        })
+        .await
+        .map_err(|e| RuleError::NodeExecutionError(e.to_string()))?
+    }
+
+    /// 执行另一个根规则链并返回其最终输出
+    ///
+    /// 目标规则链在独立的执行上下文中运行,不影响当前规则链的路由和输出;
+    /// 目标规则链已在当前调用链路上时返回循环依赖错误
+    ///
+    /// # Arguments
+    /// * `chain_id` - 目标规则链ID
+    /// * `msg` - 发送给目标规则链的消息
+    ///
+    /// # Returns
+    /// * `Result<Message, RuleError>` - 目标规则链的输出或错误
+    pub async fn dispatch_to_chain(
+        &self,
+        chain_id: Uuid,
+        msg: Message,
+    ) -> Result<Message, RuleError> {
+        let mut sub_ctx = self.create_subchain_context();
+        if sub_ctx.chain_stack.contains(&chain_id) {
+            let chain_names = sub_ctx
+                .chain_stack
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(RuleError::CircularDependency(format!(
+                "检测到规则链循环调用: {} -> {}",
+                chain_names, chain_id
+            )));
+        }
+
+        let chain = self
+            .engine
+            .get_chain(chain_id)
+            .await
+            .ok_or(RuleError::ChainNotFound(chain_id))?;
+        chain.check_root()?;
+
+        sub_ctx.msg = msg;
+        self.engine.execute_chain(&chain, None, &mut sub_ctx).await
     }
 
     /// 获取所有分支的执行结果
@@ -193,3 +571,66 @@ impl<'a> NodeContext<'a> {
         results.insert(branch_id, msg);
     }
 }
+
+/// 汇总批量发送的结果,存在失败时返回带序号的错误列表
+fn collect_send_errors(total: usize, results: Vec<Result<(), RuleError>>) -> Result<(), RuleError> {
+    let errors: Vec<_> = results
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, result)| result.err().map(|e| (index, e)))
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(RuleError::SendAllFailed { total, errors })
+    }
+}
+
+/// 从指定节点开始执行,节点未自行路由时按输出消息的分支依次执行后继节点
+///
+/// 线性链路在循环中执行,只有节点主动调用 `send_next` 等方法时才会递归;
+/// 执行结束时记录最后一个节点的输出,返回起始节点的输出
+pub(crate) async fn run_node(
+    engine: DynRuleEngine,
+    node: &Node,
+    exec_ctx: ExecutionContext,
+) -> Result<Message, RuleError> {
+    let chain = engine
+        .get_chain(node.chain_id)
+        .await
+        .ok_or(RuleError::ChainNotFound(node.chain_id))?;
+
+    let mut node = node;
+    let mut exec_ctx = exec_ctx;
+    let mut first = None;
+    loop {
+        let ctx = NodeContext::new(node, &exec_ctx, engine.clone());
+        let result = engine
+            .execute_node(node, &ctx, exec_ctx.msg.clone())
+            .await?;
+        if first.is_none() {
+            first = Some(result.clone());
+        }
+
+        if ctx.is_routed() {
+            ctx.set_output(&result).await;
+            return Ok(first.unwrap());
+        }
+
+        // 后继节点在循环中执行,调用链路深度不变
+        let mut next_ctx = ctx.create_next_context(result);
+        next_ctx.depth = exec_ctx.depth;
+        match chain.get_next_node(&node.id, &next_ctx)? {
+            Some(next) => {
+                // 分支名称只用于本次路由,不传递给下一个节点
+                next_ctx.msg.metadata.remove("branch_name");
+                node = next;
+                exec_ctx = next_ctx;
+            }
+            None => {
+                ctx.set_output(&next_ctx.msg).await;
+                return Ok(first.unwrap());
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::types::NodeType;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NodeDescriptor {
@@ -7,4 +8,8 @@ pub struct NodeDescriptor {
     pub name: String,
     pub description: String,
     pub node_type: NodeType,
+    /// 节点配置的 JSON Schema,供可视化编辑器生成配置表单并在创建规则链前校验配置;
+    /// 内置节点均会提供,自定义节点可不提供
+    #[serde(default)]
+    pub config_schema: Option<Value>,
 }
@@ -1,7 +1,8 @@
+use std::fmt;
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum RuleError {
     #[error("找不到根规则链")]
     NoRootChain,
@@ -26,4 +27,58 @@ pub enum RuleError {
 
     #[error("规则链未找到: {0}")]
     ChainNotFound(Uuid),
+
+    #[error("数据校验失败: {0}")]
+    ValidationError(String),
+
+    #[error("重复的消息: {0}")]
+    DuplicateMessage(Uuid),
+
+    #[error("规则链已被禁用: {0}")]
+    ChainDisabled(Uuid),
+
+    #[error("规则引擎正在关闭")]
+    ShuttingDown,
+
+    #[error("消息处理已取消")]
+    Cancelled,
+
+    #[error("脚本{phase}失败: {message}")]
+    ScriptError { phase: ScriptPhase, message: String },
+
+    #[error("节点执行次数超过上限 {max}, 最近执行的节点: {recent:?}")]
+    MaxHopsExceeded { max: usize, recent: Vec<Uuid> },
+
+    #[error("批量发送的 {total} 条消息中有 {} 条失败: {}", .errors.len(), format_send_errors(.errors))]
+    SendAllFailed {
+        total: usize,
+        /// 失败消息在批次中的序号及其错误
+        errors: Vec<(usize, RuleError)>,
+    },
+}
+
+/// 脚本出错的阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptPhase {
+    /// 创建节点时加载脚本
+    Load,
+    /// 处理消息时执行脚本
+    Runtime,
+}
+
+impl fmt::Display for ScriptPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptPhase::Load => write!(f, "加载"),
+            ScriptPhase::Runtime => write!(f, "执行"),
+        }
+    }
+}
+
+fn format_send_errors(errors: &[(usize, RuleError)]) -> String {
+    errors
+        .iter()
+        .map(|(index, e)| format!("[{}] {}", index, e))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
@@ -1,14 +1,35 @@
+use crate::types::AckHandle;
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// 规则链中流转的消息
+///
+/// `data` 保存结构化数据,条件、模板、脚本等节点都只读写 `data`;
+/// `binary` 保存原始字节(文件、图片、protobuf 等),由 s3 等面向二进制的节点直接读写,
+/// 其他节点原样传递。两者互相独立,二进制节点可以在 `data` 中保留文件名、大小等描述信息。
+/// 序列化为JSON时 `binary` 编码为base64字符串,为空时省略。
+/// `ack` 是消息源的确认句柄,不参与序列化
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub id: Uuid,
     pub msg_type: String,
-    pub metadata: HashMap<String, String>,
+    /// 消息元数据,如分支名称、错误信息、关联ID、计数等
+    pub metadata: HashMap<String, Value>,
     pub data: serde_json::Value,
+    /// 原始字节载荷
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "base64_bytes"
+    )]
+    pub binary: Option<Bytes>,
     pub timestamp: i64,
+    /// 消息确认句柄,处理入口在处理结束时回报结果
+    #[serde(skip)]
+    pub ack: Option<AckHandle>,
 }
 
 impl Message {
@@ -18,7 +39,79 @@ impl Message {
             msg_type: msg_type.to_string(),
             metadata: HashMap::new(),
             data,
+            binary: None,
             timestamp: chrono::Utc::now().timestamp_millis(),
+            ack: None,
         }
     }
+
+    /// 设置原始字节载荷
+    pub fn with_binary(mut self, binary: impl Into<Bytes>) -> Self {
+        self.binary = Some(binary.into());
+        self
+    }
+
+    /// 附加确认句柄,`process_msg` 处理结束后通过句柄回报成功或失败
+    pub fn with_ack(mut self, ack: AckHandle) -> Self {
+        self.ack = Some(ack);
+        self
+    }
+
+    /// 设置元数据
+    ///
+    /// # Arguments
+    /// * `key` - 元数据键名
+    /// * `value` - 元数据值,可以是字符串、数值、布尔值或任意JSON值
+    pub fn set_meta(&mut self, key: impl Into<String>, value: impl Into<Value>) {
+        self.metadata.insert(key.into(), value.into());
+    }
+
+    /// 获取元数据
+    pub fn get_meta(&self, key: &str) -> Option<&Value> {
+        self.metadata.get(key)
+    }
+
+    /// 获取字符串类型的元数据,值不是字符串时返回 None
+    pub fn get_meta_str(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).and_then(Value::as_str)
+    }
+
+    /// 是否为通过 `replay_msg` 重放的消息,即元数据 `replay` 为 true
+    pub fn is_replay(&self) -> bool {
+        self.metadata
+            .get("replay")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+}
+
+/// `binary` 字段在JSON中以base64字符串表示
+mod base64_bytes {
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use bytes::Bytes;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        binary: &Option<Bytes>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match binary {
+            Some(bytes) => serializer.serialize_str(&BASE64.encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Bytes>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| {
+                BASE64
+                    .decode(s)
+                    .map(Bytes::from)
+                    .map_err(serde::de::Error::custom)
+            })
+            .transpose()
+    }
 }
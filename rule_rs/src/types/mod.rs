@@ -1,14 +1,21 @@
+mod ack;
+mod builder;
 mod context;
 mod descriptor;
 mod error;
 mod message;
 mod node;
+mod trace;
 
+pub use ack::{AckHandle, AckOutcome};
+pub use builder::*;
 pub use context::*;
 pub use descriptor::*;
 pub use error::*;
 pub use message::*;
 pub use node::*;
+pub use trace::{ExecutionTrace, TraceEntry};
+pub(crate) use trace::{TraceLog, TraceSpan};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -22,6 +29,9 @@ pub struct RuleChain {
     pub name: String,
     /// 是否为根规则链
     pub root: bool,
+    /// 引擎启用幂等检查时是否对该规则链生效,默认生效
+    #[serde(default = "default_idempotent")]
+    pub idempotent: bool,
     /// 规则链中的所有节点
     pub nodes: Vec<Node>,
     /// 节点之间的连接关系
@@ -30,6 +40,19 @@ pub struct RuleChain {
     pub metadata: Metadata,
 }
 
+fn default_idempotent() -> bool {
+    true
+}
+
+/// 规则链结构分析结果,不需要加载规则链即可得到
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChainAnalysis {
+    /// 从起始节点出发无法到达的节点,永远不会被执行
+    pub unreachable_nodes: Vec<Uuid>,
+    /// 没有出向连接的节点,规则链在这些节点处结束
+    pub terminal_nodes: Vec<Uuid>,
+}
+
 /// 节点之间的连接定义
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Connection {
@@ -50,6 +73,21 @@ pub struct Metadata {
     pub created_at: i64,
     /// 最后更新时间戳
     pub updated_at: i64,
+    /// 规则链接收的消息类型,设置后 `process_msg` 拒绝其它类型的消息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_type: Option<String>,
+    /// 规则链输出的消息类型,设置后输出类型不一致时记录警告
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_type: Option<String>,
+    /// 允许的最大错误率(0 到 1),设置后错误率超过上限时自动禁用规则链
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_error_rate: Option<f64>,
+    /// 统计错误率的时间窗口(毫秒),默认 60000
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_ms: Option<u64>,
+    /// 窗口内至少执行的次数,达到后才计算错误率,默认 10
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_executions: Option<usize>,
 }
 
 /// 节点类型枚举
@@ -72,6 +110,16 @@ pub struct CommonConfig {
     /// 节点类型,默认为中间节点
     #[serde(default = "default_node_type")]
     pub node_type: NodeType,
+    /// 节点执行失败时路由到的分支,错误信息写入 metadata.error
+    ///
+    /// 未配置或不存在该分支的连接时错误照常向上传播
+    #[serde(default)]
+    pub error_branch: Option<String>,
+    /// 节点在所有正在处理的消息中同时执行的最大数量,未配置时不限制
+    ///
+    /// 超出上限的执行等待其他执行完成;节点通过 `send_next` 等自行路由时,后继节点执行完成前不释放许可
+    #[serde(default)]
+    pub max_concurrency: Option<usize>,
 }
 
 /// 默认节点类型为中间节点
@@ -10,6 +10,8 @@ pub struct Node {
     pub type_name: String,
     pub config: Value,
     pub layout: Position,
+    /// 所属规则链ID,可以省略,加载时始终设置为所属规则链的ID
+    #[serde(default)]
     pub chain_id: Uuid,
 }
 
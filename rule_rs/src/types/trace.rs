@@ -0,0 +1,99 @@
+use crate::types::Node;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use uuid::Uuid;
+
+/// 一次消息处理中共享的执行记录,在并行分支和子规则链间共享
+pub(crate) type TraceLog = Arc<Mutex<Vec<TraceEntry>>>;
+
+/// 单个节点的一次执行记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// 节点所属的规则链
+    pub chain_id: Uuid,
+    /// 节点ID
+    pub node_id: Uuid,
+    /// 节点类型
+    pub type_name: String,
+    /// 开始执行的时间戳(毫秒)
+    pub started_at: i64,
+    /// 执行结束的时间戳(毫秒)
+    pub ended_at: i64,
+    /// 执行耗时(毫秒)
+    pub duration_ms: f64,
+    /// 节点输出的分支,节点未设置分支时为 None
+    pub branch: Option<String>,
+    /// 执行失败时的错误信息,包括被错误分支处理的失败
+    pub error: Option<String>,
+}
+
+impl TraceEntry {
+    /// 节点是否执行成功
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// 一次消息处理的执行轨迹,按节点开始执行的顺序排列
+///
+/// 子规则链中的节点紧跟在调用它的节点之后;fork 等并行执行的分支按实际开始时间交错排列
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    pub entries: Vec<TraceEntry>,
+}
+
+impl ExecutionTrace {
+    /// 按执行顺序返回经过的节点ID
+    pub fn path(&self) -> Vec<Uuid> {
+        self.entries.iter().map(|entry| entry.node_id).collect()
+    }
+
+    /// 第一个执行失败的节点记录
+    pub fn first_error(&self) -> Option<&TraceEntry> {
+        self.entries.iter().find(|entry| !entry.is_success())
+    }
+}
+
+/// 正在执行的节点在执行轨迹中的记录
+#[derive(Debug)]
+pub(crate) struct TraceSpan {
+    log: TraceLog,
+    index: usize,
+    started: Instant,
+}
+
+impl TraceSpan {
+    /// 记录节点开始执行
+    pub(crate) fn start(log: &TraceLog, node: &Node) -> Self {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut entries = log.lock().unwrap();
+        entries.push(TraceEntry {
+            chain_id: node.chain_id,
+            node_id: node.id,
+            type_name: node.type_name.clone(),
+            started_at: now,
+            ended_at: now,
+            duration_ms: 0.0,
+            branch: None,
+            error: None,
+        });
+        Self {
+            log: log.clone(),
+            index: entries.len() - 1,
+            started: Instant::now(),
+        }
+    }
+
+    /// 记录节点执行结束,执行轨迹已被取出时忽略
+    pub(crate) fn finish(self, branch: Option<String>, error: Option<String>) {
+        let mut entries = self.log.lock().unwrap();
+        let Some(entry) = entries.get_mut(self.index) else {
+            return;
+        };
+        entry.ended_at = chrono::Utc::now().timestamp_millis();
+        entry.duration_ms = self.started.elapsed().as_secs_f64() * 1000.0;
+        entry.branch = branch;
+        entry.error = error;
+    }
+}
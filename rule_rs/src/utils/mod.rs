@@ -1 +1,155 @@
-// 工具函数模块，暂时为空 
\ No newline at end of file
+// 工具函数模块
+use crate::types::Message;
+use serde_json::{Map, Value};
+
+/// 按路径获取JSON中的嵌套值
+///
+/// # Arguments
+/// * `data` - JSON数据
+/// * `path` - 以点分隔的路径,数组使用数字下标,如 `items.0.name`
+pub fn get_value_by_path<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = data;
+    for part in path.split('.') {
+        current = match current {
+            Value::Array(items) => items.get(part.parse::<usize>().ok()?)?,
+            _ => current.get(part)?,
+        };
+    }
+    Some(current)
+}
+
+/// 按路径写入JSON中的嵌套值,路径上不存在的对象字段会被创建
+///
+/// 路径经过字符串、数字等非容器值或数组下标越界时返回 false
+///
+/// # Arguments
+/// * `data` - JSON数据
+/// * `path` - 以点分隔的路径,数组使用数字下标,如 `items.0.name`
+/// * `value` - 写入的值
+pub fn set_value_by_path(data: &mut Value, path: &str, value: Value) -> bool {
+    let mut current = data;
+    for part in path.split('.') {
+        if current.is_null() {
+            *current = Value::Object(Map::new());
+        }
+        current = match current {
+            Value::Object(map) => map.entry(part).or_insert(Value::Null),
+            Value::Array(items) => {
+                match part.parse::<usize>().ok().and_then(|i| items.get_mut(i)) {
+                    Some(item) => item,
+                    None => return false,
+                }
+            }
+            _ => return false,
+        };
+    }
+    *current = value;
+    true
+}
+
+/// 按路径删除JSON中的嵌套值,返回被删除的值
+///
+/// 路径不存在时返回 None,数据不变;删除数组元素时后面的元素前移
+///
+/// # Arguments
+/// * `data` - JSON数据
+/// * `path` - 以点分隔的路径,数组使用数字下标,如 `items.0.name`
+pub fn remove_value_by_path(data: &mut Value, path: &str) -> Option<Value> {
+    let (parent, last) = match path.rsplit_once('.') {
+        Some((parent, last)) => (get_value_by_path_mut(data, parent)?, last),
+        None => (data, path),
+    };
+    match parent {
+        Value::Object(map) => map.remove(last),
+        Value::Array(items) => {
+            let index = last.parse::<usize>().ok().filter(|i| *i < items.len())?;
+            Some(items.remove(index))
+        }
+        _ => None,
+    }
+}
+
+fn get_value_by_path_mut<'a>(data: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    let mut current = data;
+    for part in path.split('.') {
+        current = match current {
+            Value::Array(items) => items.get_mut(part.parse::<usize>().ok()?)?,
+            _ => current.get_mut(part)?,
+        };
+    }
+    Some(current)
+}
+
+/// 解析模板变量: msg.id、msg.type、msg.data[.路径]、msg.metadata.键
+pub fn resolve_variable(var: &str, msg: &Message) -> Option<Value> {
+    match var {
+        "msg.id" => Some(Value::String(msg.id.to_string())),
+        "msg.type" => Some(Value::String(msg.msg_type.clone())),
+        "msg.data" => Some(msg.data.clone()),
+        _ => {
+            if let Some(path) = var.strip_prefix("msg.data.") {
+                get_value_by_path(&msg.data, path).cloned()
+            } else {
+                var.strip_prefix("msg.metadata.")
+                    .and_then(|key| msg.get_meta(key))
+                    .cloned()
+            }
+        }
+    }
+}
+
+/// 使用消息渲染JSON模板
+///
+/// 整个字符串为单个 `${变量}` 时替换为变量的JSON值,保留原始类型;
+/// 字符串中嵌入的变量替换为文本;无法解析的变量保持原样
+pub fn render_template(template: &Value, msg: &Message) -> Value {
+    match template {
+        Value::String(s) => {
+            if let Some(var) = s.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
+                if !var.contains("${") {
+                    if let Some(value) = resolve_variable(var, msg) {
+                        return value;
+                    }
+                }
+            }
+            Value::String(render_text(s, msg))
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| render_template(item, msg))
+                .collect(),
+        ),
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .map(|(key, value)| (key.clone(), render_template(value, msg)))
+                .collect(),
+        ),
+        _ => template.clone(),
+    }
+}
+
+/// 替换文本中的 `${变量}`,字符串直接输出,其他值输出紧凑的JSON
+pub fn render_text(template: &str, msg: &Message) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let var = &rest[start + 2..];
+        let Some(end) = var.find('}') else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+
+        match resolve_variable(&var[..end], msg) {
+            Some(Value::String(s)) => result.push_str(&s),
+            Some(value) => result.push_str(&value.to_string()),
+            None => result.push_str(&rest[start..start + end + 3]),
+        }
+        rest = &var[end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}